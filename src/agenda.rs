@@ -0,0 +1,306 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use crate::category::file_category;
+use crate::elements::{timestamp::units_between, Datetime, Element, Repeater, Timestamp, Title};
+use crate::{Headline, Org};
+
+/// Kind of agenda entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AgendaKind {
+    /// From a `DEADLINE:` planning timestamp
+    Deadline,
+    /// From a `SCHEDULED:` planning timestamp
+    Scheduled,
+    /// From an active timestamp in headline's title or section
+    Timestamp,
+}
+
+/// Entry of the agenda, shown on a single day
+#[derive(Debug)]
+pub struct AgendaEntry<'a, 'b> {
+    /// Day this entry is shown on
+    pub date: Datetime<'static>,
+    pub kind: AgendaKind,
+    /// Name of the file this entry comes from
+    pub file: &'b str,
+    /// Category from [`Org::category`], or the file name without extension
+    ///
+    /// [`Org::category`]: struct.Org.html#method.category
+    pub category: &'b str,
+    pub headline: Headline,
+    pub title: &'b Title<'a>,
+    /// Hour and minute of the timestamp, if any
+    pub time: Option<(u8, u8)>,
+    /// Number of days an unfinished deadline or scheduled item is overdue,
+    /// when it's carried over to today
+    pub overdue: Option<u32>,
+}
+
+/// Agenda builder collecting dated entries from one or more documents
+///
+/// ```rust
+/// # use orgize::{Agenda, AgendaKind, Org};
+/// # use orgize::elements::Datetime;
+/// #
+/// let work = Org::parse(
+///     "* TODO report\nDEADLINE: <2023-01-02 Mon>\n\
+///      * meeting <2023-01-05 Thu 10:00>\n",
+/// );
+/// let home = Org::parse("* TODO laundry\nSCHEDULED: <2023-01-05 Thu>\n");
+///
+/// let mut agenda = Agenda::default();
+/// agenda.add("work.org", &work).add("home.org", &home);
+///
+/// let date = |day| Datetime {
+///     year: 2023,
+///     month: 1,
+///     day,
+///     dayname: "".into(),
+///     hour: None,
+///     minute: None,
+/// };
+/// let entries = agenda.entries(&date(4), &date(10), &date(5));
+///
+/// let entries: Vec<_> = entries
+///     .iter()
+///     .map(|entry| (entry.date.day, entry.kind, entry.category, entry.overdue))
+///     .collect();
+/// assert_eq!(
+///     entries,
+///     vec![
+///         (5, AgendaKind::Timestamp, "work", None),
+///         (5, AgendaKind::Deadline, "work", Some(3)),
+///         (5, AgendaKind::Scheduled, "home", None),
+///     ]
+/// );
+/// ```
+pub struct Agenda<'a, 'b> {
+    files: Vec<(&'b str, &'b Org<'a>)>,
+    /// Done keywords, whose headlines are never carried over as overdue
+    pub done_keywords: Vec<String>,
+}
+
+impl Default for Agenda<'_, '_> {
+    fn default() -> Self {
+        Agenda {
+            files: Vec::new(),
+            done_keywords: vec![String::from("DONE")],
+        }
+    }
+}
+
+impl<'a, 'b> Agenda<'a, 'b> {
+    /// Adds a document with its file name to this agenda.
+    pub fn add(&mut self, file: &'b str, org: &'b Org<'a>) -> &mut Self {
+        self.files.push((file, org));
+        self
+    }
+
+    /// Returns the entries shown between `start` and `end` inclusively,
+    /// sorted like org-agenda's daily view: by day, then timed entries by
+    /// time, then by priority, and finally in file order.
+    ///
+    /// Timestamps with repeaters, e.g. `<2023-01-02 Mon +1w>`, are shown on
+    /// every repeat from their dates. Unfinished deadline and scheduled
+    /// items before `today` are also shown on `today`, if it's in the range.
+    /// Time of `start`, `end` and `today` is ignored.
+    pub fn entries(
+        &self,
+        start: &Datetime,
+        end: &Datetime,
+        today: &Datetime,
+    ) -> Vec<AgendaEntry<'a, 'b>> {
+        let (start, end) = match (start.days(), end.days()) {
+            (Some(start), Some(end)) => (start, end),
+            _ => return Vec::new(),
+        };
+        let today = today.days().filter(|today| (start..=end).contains(today));
+
+        let mut entries = Vec::new();
+        for (index, &(file, org)) in self.files.iter().enumerate() {
+            let file_category = org
+                .keyword_category()
+                .unwrap_or_else(|| file_category(file));
+
+            for (position, headline) in org.headlines().enumerate() {
+                if is_hidden(headline, org) {
+                    continue;
+                }
+
+                let title = headline.title(org);
+                let category = org
+                    .property_category(headline.headline_node())
+                    .unwrap_or(file_category);
+                let done = title
+                    .keyword
+                    .as_ref()
+                    .is_some_and(|k| self.done_keywords.iter().any(|d| d == k));
+
+                let mut push = |kind, date: i64, time, overdue| {
+                    let date = match Datetime::from_days(date) {
+                        Some(date) => date,
+                        None => return,
+                    };
+                    let entry = AgendaEntry {
+                        date,
+                        kind,
+                        file,
+                        category,
+                        headline,
+                        title,
+                        time,
+                        overdue,
+                    };
+                    entries.push((index, position, entry));
+                };
+
+                let planning = [
+                    (AgendaKind::Deadline, title.deadline()),
+                    (AgendaKind::Scheduled, title.scheduled()),
+                ];
+                for (kind, timestamp) in planning.iter() {
+                    let (datetime, repeater) = match timestamp {
+                        Some(timestamp) => match timestamp.start() {
+                            Some(datetime) => (datetime, timestamp.repeater()),
+                            None => continue,
+                        },
+                        None => continue,
+                    };
+                    let date = match datetime.days() {
+                        Some(date) => date,
+                        None => continue,
+                    };
+                    let overdue = today.filter(|today| !done && date < *today);
+                    for repeat in occurrences(datetime, repeater, start, end) {
+                        let repeat_date = repeat.days();
+                        // replaced by the overdue entry below
+                        if overdue.is_some() && repeat_date == overdue {
+                            continue;
+                        }
+                        if let Some(repeat_date) = repeat_date {
+                            push(*kind, repeat_date, time(&repeat), None);
+                        }
+                    }
+                    if let Some(today) = overdue {
+                        push(*kind, today, None, Some((today - date) as u32));
+                    }
+                }
+
+                let nodes = Some(headline.title_node())
+                    .into_iter()
+                    .chain(headline.section_node())
+                    .flat_map(|node| node.descendants(&org.arena));
+                for node in nodes {
+                    let timestamp = match &org[node] {
+                        Element::Timestamp(timestamp) => timestamp,
+                        _ => continue,
+                    };
+                    let (first, last) = match timestamp {
+                        Timestamp::Active { start, .. } => (start, start),
+                        Timestamp::ActiveRange { start, end, .. } => (start, end),
+                        _ => continue,
+                    };
+                    let (first_date, last_date) = match (first.days(), last.days()) {
+                        (Some(first), Some(last)) => (first, last),
+                        _ => continue,
+                    };
+                    // repeats of ranges starting before `start` may still cover it
+                    let from = start - (last_date - first_date);
+                    for repeat in occurrences(first, timestamp.repeater(), from, end) {
+                        let offset = match repeat.days() {
+                            Some(date) => date - first_date,
+                            None => continue,
+                        };
+                        for date in (first_date + offset).max(start)..=(last_date + offset).min(end)
+                        {
+                            let time = if date == first_date + offset {
+                                time(&repeat)
+                            } else {
+                                None
+                            };
+                            push(AgendaKind::Timestamp, date, time, None);
+                        }
+                    }
+                }
+            }
+        }
+
+        entries.sort_by_key(|(index, position, entry)| {
+            (
+                entry.date.days(),
+                entry.time.is_none(),
+                entry.time,
+                entry.title.priority.unwrap_or('B'),
+                *index,
+                entry.kind,
+                *position,
+            )
+        });
+
+        entries.into_iter().map(|(_, _, entry)| entry).collect()
+    }
+}
+
+/// Returns `true` if this headline or any of its ancestors is commented
+/// or archived.
+fn is_hidden(headline: Headline, org: &Org) -> bool {
+    let mut headline = Some(headline);
+    while let Some(hdl) = headline {
+        let title = hdl.title(org);
+        if title.is_commented() || title.is_archived() {
+            return true;
+        }
+        headline = hdl.parent(org);
+    }
+    false
+}
+
+/// Returns `datetime` and its repeats by `repeater` whose dates are between
+/// `from` and `to` inclusively.
+fn occurrences(
+    datetime: &Datetime,
+    repeater: Option<Repeater>,
+    from: i64,
+    to: i64,
+) -> Vec<Datetime<'static>> {
+    let value = repeater
+        .as_ref()
+        .and_then(|repeater| i64::try_from(repeater.value).ok())
+        .filter(|value| *value > 0);
+    let (repeater, value) = match (repeater, value) {
+        (Some(repeater), Some(value)) => (repeater, value),
+        _ => {
+            let date = datetime.days();
+            return match date {
+                Some(date) if (from..=to).contains(&date) => vec![datetime.clone().into_owned()],
+                _ => Vec::new(),
+            };
+        }
+    };
+
+    // skips the repeats before `from` at once
+    let mut n = Datetime::from_days(from)
+        .and_then(|from| units_between(datetime, &from, repeater.unit))
+        .map_or(0, |units| (units / value - 1).max(0));
+    let mut repeats = Vec::new();
+    while let Some(repeat) = value
+        .checked_mul(n)
+        .and_then(|units| datetime.shift(units, repeater.unit))
+    {
+        match repeat.days() {
+            Some(date) if date > to => break,
+            Some(date) if date >= from => repeats.push(repeat),
+            Some(_) => (),
+            None => break,
+        }
+        n += 1;
+    }
+    repeats
+}
+
+fn time(datetime: &Datetime) -> Option<(u8, u8)> {
+    Some((datetime.hour?, datetime.minute?))
+}
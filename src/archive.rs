@@ -0,0 +1,172 @@
+use alloc::string::{String, ToString};
+
+use crate::{Headline, Org};
+
+const DEFAULT_LOCATION: &str = "%s_archive::";
+
+/// Archive location, from the `#+ARCHIVE` keyword or the `ARCHIVE` property
+///
+/// ```rust
+/// # use orgize::ArchiveLocation;
+/// #
+/// let location = ArchiveLocation::parse("%s_archive::* Archived from %s");
+///
+/// assert_eq!(location.file, "%s_archive");
+/// assert_eq!(location.headline, Some((1, "Archived from %s".to_string())));
+///
+/// let location = location.resolve("notes/todo.org");
+///
+/// assert_eq!(location.file, "notes/todo.org_archive");
+/// assert_eq!(location.headline, Some((1, "Archived from todo.org".to_string())));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ArchiveLocation {
+    /// Target file pattern, empty means the current file
+    pub file: String,
+    /// Whether subtrees are archived into a date tree, i.e. the headline
+    /// part starts with `datetree/`
+    pub datetree: bool,
+    /// Level and title of the headline to archive under, or `None` for the
+    /// top level of the file
+    pub headline: Option<(usize, String)>,
+}
+
+impl ArchiveLocation {
+    /// Parses an archive location like `file::headline`. Location without
+    /// `::` is treated as a file.
+    pub fn parse(location: &str) -> ArchiveLocation {
+        let location = location.trim();
+        let (file, headline) = location.split_once("::").unwrap_or((location, ""));
+
+        let (datetree, headline) = match headline.trim().strip_prefix("datetree/") {
+            Some(headline) => (true, headline),
+            None => (false, headline.trim()),
+        };
+
+        let headline = if headline.is_empty() {
+            None
+        } else {
+            let level = headline.bytes().take_while(|&c| c == b'*').count();
+            Some((level.max(1), headline[level..].trim().to_string()))
+        };
+
+        ArchiveLocation {
+            file: file.trim().to_string(),
+            datetree,
+            headline,
+        }
+    }
+
+    /// Substitutes `%s` with the file name of the current document, like
+    /// org-mode: the whole path in the file part and the name without
+    /// directory in the headline. An empty file part becomes `file`.
+    pub fn resolve(&self, file: &str) -> ArchiveLocation {
+        let name = file.rsplit(['/', '\\']).next().unwrap_or(file);
+
+        ArchiveLocation {
+            file: if self.file.is_empty() {
+                file.to_string()
+            } else {
+                self.file.replace("%s", file)
+            },
+            datetree: self.datetree,
+            headline: self
+                .headline
+                .as_ref()
+                .map(|(level, title)| (*level, title.replace("%s", name))),
+        }
+    }
+}
+
+impl Org<'_> {
+    /// Returns the archive location of `headline`, or the document if it's
+    /// `None`.
+    ///
+    /// The location comes from the nearest `ARCHIVE` property, the
+    /// `#+ARCHIVE` keyword, or defaults to `%s_archive::`.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse(
+    ///     "#+ARCHIVE: archive.org::* From %s\n\
+    ///      * project\n:PROPERTIES:\n:ARCHIVE: ::* Done\n:END:\n** task\n\
+    ///      * other\n",
+    /// );
+    /// let task = org.headlines().nth(1).unwrap();
+    /// let other = org.headlines().nth(2).unwrap();
+    ///
+    /// let location = org.archive_location(Some(task)).resolve("todo.org");
+    /// assert_eq!(location.file, "todo.org");
+    /// assert_eq!(location.headline, Some((1, "Done".to_string())));
+    ///
+    /// let location = org.archive_location(Some(other)).resolve("todo.org");
+    /// assert_eq!(location.file, "archive.org");
+    /// assert_eq!(location.headline, Some((1, "From todo.org".to_string())));
+    /// ```
+    pub fn archive_location(&self, headline: Option<Headline>) -> ArchiveLocation {
+        let mut ancestor = headline;
+        while let Some(hdl) = ancestor {
+            let property = hdl
+                .title(self)
+                .properties
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case("ARCHIVE"));
+            if let Some((_, value)) = property {
+                return ArchiveLocation::parse(value);
+            }
+            ancestor = hdl.parent(self);
+        }
+
+        let keyword = self
+            .keywords()
+            .find(|keyword| keyword.key.eq_ignore_ascii_case("ARCHIVE"))
+            .map(|keyword| &*keyword.value);
+
+        ArchiveLocation::parse(keyword.unwrap_or(DEFAULT_LOCATION))
+    }
+}
+
+#[test]
+fn parse() {
+    assert_eq!(
+        ArchiveLocation::parse("%s_archive::"),
+        ArchiveLocation {
+            file: "%s_archive".into(),
+            datetree: false,
+            headline: None,
+        }
+    );
+    assert_eq!(
+        ArchiveLocation::parse("::*** Archived"),
+        ArchiveLocation {
+            file: "".into(),
+            datetree: false,
+            headline: Some((3, "Archived".into())),
+        }
+    );
+    assert_eq!(
+        ArchiveLocation::parse("~/archive.org::datetree/* Finished"),
+        ArchiveLocation {
+            file: "~/archive.org".into(),
+            datetree: true,
+            headline: Some((1, "Finished".into())),
+        }
+    );
+    assert_eq!(
+        ArchiveLocation::parse("archive.org"),
+        ArchiveLocation {
+            file: "archive.org".into(),
+            datetree: false,
+            headline: None,
+        }
+    );
+    assert_eq!(
+        ArchiveLocation::parse("::datetree/").resolve("a.org"),
+        ArchiveLocation {
+            file: "a.org".into(),
+            datetree: true,
+            headline: None,
+        }
+    );
+}
@@ -0,0 +1,73 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use indextree::NodeId;
+
+use crate::elements::Element;
+use crate::{Headline, Org};
+
+/// Base directory of attachments of headlines with an `ID` property, like
+/// `org-attach-id-dir`
+const ATTACH_ID_DIR: &str = "data";
+
+impl Org<'_> {
+    /// Returns the attachment directory of this headline, like `org-attach`.
+    ///
+    /// The directory is the `DIR` property of this headline or its nearest
+    /// ancestor, or comes from the `ID` property in the default layout,
+    /// `data/ID[0..2]/ID[2..]`.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse(
+    ///     "* trip\n:PROPERTIES:\n:ID: 9a3f21c0-8b2d\n:END:\n\
+    ///      * notes\n:PROPERTIES:\n:DIR: ~/notes/files\n:END:\n** draft\n",
+    /// );
+    /// let mut headlines = org.headlines();
+    ///
+    /// assert_eq!(
+    ///     org.attachment_dir(headlines.next().unwrap()).as_deref(),
+    ///     Some("data/9a/3f21c0-8b2d")
+    /// );
+    /// assert_eq!(
+    ///     org.attachment_dir(headlines.nth(1).unwrap()).as_deref(),
+    ///     Some("~/notes/files")
+    /// );
+    /// ```
+    pub fn attachment_dir(&self, headline: Headline) -> Option<String> {
+        let mut ancestor = Some(headline);
+        while let Some(hdl) = ancestor {
+            let properties = &hdl.title(self).properties;
+            let property = |name: &str| {
+                properties
+                    .iter()
+                    .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                    .map(|(_, value)| value.trim())
+                    .filter(|value| !value.is_empty())
+            };
+
+            if let Some(dir) = property("DIR") {
+                return Some(dir.trim_end_matches('/').to_string());
+            }
+            if let Some(id) = property("ID") {
+                return Some(match id.char_indices().nth(2) {
+                    Some((i, _)) => format!("{}/{}/{}", ATTACH_ID_DIR, &id[..i], &id[i..]),
+                    None => format!("{}/{}", ATTACH_ID_DIR, id),
+                });
+            }
+            ancestor = hdl.parent(self);
+        }
+        None
+    }
+
+    /// Returns the path of the `attachment:` link `node`, resolved against
+    /// the attachment directory of its headline.
+    pub(crate) fn attachment_path(&self, node: NodeId) -> Option<String> {
+        let path = match &self[node] {
+            Element::Link(link) => link.path.strip_prefix("attachment:")?,
+            _ => return None,
+        };
+        let dir = self.attachment_dir(self.enclosing_headline(node)?)?;
+        Some(format!("{}/{}", dir, path))
+    }
+}
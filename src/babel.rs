@@ -0,0 +1,358 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::Range;
+use indextree::NodeId;
+
+use crate::elements::{Element, Keyword, SourceBlock};
+use crate::{Headline, Org};
+
+/// Maximum depth of nested noweb references
+const MAX_NOWEB_DEPTH: usize = 32;
+
+/// Header arguments of a source block, e.g. `:tangle yes :noweb yes`
+///
+/// ```rust
+/// # use orgize::HeaderArgs;
+/// #
+/// let args = HeaderArgs::parse(":tangle ~/init.el :var x=1 :var y=2 :noweb yes");
+///
+/// assert_eq!(args.get("tangle"), Some("~/init.el"));
+/// assert_eq!(args.get_all("var").collect::<Vec<_>>(), vec!["x=1", "y=2"]);
+/// assert_eq!(args.get("results"), None);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HeaderArgs {
+    args: Vec<(String, String)>,
+}
+
+impl HeaderArgs {
+    /// Parses header arguments. Text before the first `:key` is ignored.
+    pub fn parse(input: &str) -> HeaderArgs {
+        let mut args = HeaderArgs::default();
+        args.extend(input);
+        args
+    }
+
+    /// Returns the value of the last argument named `key`, without leading
+    /// colon.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.args
+            .iter()
+            .rev()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns values of all arguments named `key`, without leading colon.
+    pub fn get_all<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a str> {
+        self.args
+            .iter()
+            .filter(move |(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns all arguments as key-value pairs, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.args.iter().map(|(key, value)| (&**key, &**value))
+    }
+
+    /// Appends arguments parsed from `input`, which override existing ones
+    /// of the same name.
+    pub fn extend(&mut self, input: &str) {
+        let mut key: Option<&str> = None;
+        let mut start = 0;
+        let mut prev = ' ';
+
+        for (i, c) in input.char_indices() {
+            if c == ':' && prev.is_whitespace() {
+                if let Some(key) = key {
+                    self.push(key, &input[start..i]);
+                }
+                let end = input[i..]
+                    .find(char::is_whitespace)
+                    .map_or(input.len(), |end| i + end);
+                key = Some(&input[i + 1..end]);
+                start = end;
+            }
+            prev = c;
+        }
+        if let Some(key) = key {
+            self.push(key, &input[start..]);
+        }
+    }
+
+    fn push(&mut self, key: &str, value: &str) {
+        if !key.is_empty() {
+            self.args.push((key.to_string(), value.trim().to_string()));
+        }
+    }
+}
+
+impl Org<'_> {
+    /// Returns the effective header arguments of the source block `node`.
+    ///
+    /// Arguments are collected from the `header-args` property, the language
+    /// specific one like `header-args:sh`, `#+HEADER` keywords and the block
+    /// itself, where later ones override earlier ones. Properties are looked
+    /// up with inheritance, see
+    /// [`Org::inherited_property`](#method.inherited_property).
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse(
+    ///     "#+PROPERTY: header-args :tangle yes :noweb yes\n\
+    ///      * config\n\
+    ///      :PROPERTIES:\n:header-args:sh: :tangle setup.sh\n:END:\n\
+    ///      #+BEGIN_SRC sh :noweb no\necho hi\n#+END_SRC\n",
+    /// );
+    /// let args = org.header_args(org.src_blocks()[0]);
+    ///
+    /// assert_eq!(args.get("tangle"), Some("setup.sh"));
+    /// assert_eq!(args.get("noweb"), Some("no"));
+    /// ```
+    pub fn header_args(&self, node: NodeId) -> HeaderArgs {
+        let mut args = HeaderArgs::default();
+        let block = match &self[node] {
+            Element::SourceBlock(block) => block,
+            _ => return args,
+        };
+        let language_key = format!("header-args:{}", block.language);
+        for key in ["header-args", &language_key] {
+            if let Some(value) = self.inherited_property(node, key) {
+                args.extend(&value);
+            }
+        }
+
+        for keyword in self.affiliated_keywords(node) {
+            if keyword.key.eq_ignore_ascii_case("HEADER") {
+                args.extend(&keyword.value);
+            }
+        }
+        args.extend(&block.arguments);
+
+        args
+    }
+
+    /// Returns the name of this element, from its `#+NAME` keyword.
+    pub fn element_name(&self, node: NodeId) -> Option<&str> {
+        self.affiliated_keywords(node)
+            .filter(|keyword| keyword.key.eq_ignore_ascii_case("NAME"))
+            .map(|keyword| keyword.value.trim())
+            .last()
+    }
+
+    /// Returns the body of the source block `node` with noweb references
+    /// like `<<name>>` expanded, or `None` if it isn't a source block.
+    ///
+    /// A reference is replaced by bodies of blocks whose `#+NAME` or
+    /// `:noweb-ref` header argument is `name`. Text before the reference, up
+    /// to the previous one on the same line, is repeated on every expanded
+    /// line. References to missing blocks, calls like `<<name()>>` and
+    /// circular references are kept as is.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse(
+    ///     "#+NAME: greet\n#+BEGIN_SRC sh\necho hi\necho bye\n#+END_SRC\n\n\
+    ///      #+BEGIN_SRC sh :noweb yes\nmain() {\n  <<greet>>\n}\n#+END_SRC\n",
+    /// );
+    /// let blocks = org.src_blocks();
+    ///
+    /// assert_eq!(
+    ///     org.expand_noweb(blocks[1]).unwrap(),
+    ///     "main() {\n  echo hi\n  echo bye\n}\n"
+    /// );
+    /// ```
+    pub fn expand_noweb(&self, node: NodeId) -> Option<String> {
+        let body = self.src_body(node)?;
+        // a block never expands references to itself
+        let mut stack: Vec<String> = self
+            .element_name(node)
+            .map(Into::into)
+            .into_iter()
+            .collect();
+        stack.extend(self.header_args(node).get("noweb-ref").map(Into::into));
+        Some(self.expand_noweb_inner(&body, &self.src_blocks(), &mut stack))
+    }
+
+    /// Returns all source blocks in document order.
+    pub fn src_blocks(&self) -> Vec<NodeId> {
+        self.root
+            .descendants(&self.arena)
+            .filter(|&node| matches!(self[node], Element::SourceBlock(_)))
+            .collect()
+    }
+
+    /// Returns contents of the source block `node`, with common indentation
+    /// removed unless its `-i` switch is set.
+    pub(crate) fn src_body(&self, node: NodeId) -> Option<String> {
+        match &self[node] {
+            Element::SourceBlock(block) => Some(remove_indentation(block)),
+            _ => None,
+        }
+    }
+
+    /// Returns the byte range of contents of the source block `node` in the
+    /// source text, and its common indentation.
+    pub(crate) fn src_contents(&self, node: NodeId) -> Option<(Range<usize>, usize)> {
+        let block = match &self[node] {
+            Element::SourceBlock(block) => block,
+            _ => return None,
+        };
+        let span = self.span(node)?;
+        let text = &self.text[span.clone()];
+
+        let start = text.find('\n')? + 1;
+        let mut end = None;
+        let mut offset = start;
+        for line in text[start..].split_inclusive('\n') {
+            let trimmed = line.trim_start();
+            if trimmed
+                .get(..9)
+                .is_some_and(|end| end.eq_ignore_ascii_case("#+END_SRC"))
+            {
+                end = Some(offset);
+            }
+            offset += line.len();
+        }
+
+        Some((span.start + start..span.start + end?, indentation(block)))
+    }
+
+    /// Returns the nearest headline containing `node`.
+    pub(crate) fn enclosing_headline(&self, node: NodeId) -> Option<Headline> {
+        node.ancestors(&self.arena)
+            .skip(1)
+            .find_map(|node| match self[node] {
+                Element::Headline { level } => Some(Headline::from_node(node, level, self)),
+                _ => None,
+            })
+    }
+
+    /// Returns keywords right before `node`, e.g. `#+NAME` and `#+HEADER`.
+    pub(crate) fn affiliated_keywords(&self, node: NodeId) -> impl Iterator<Item = &Keyword<'_>> {
+        node.preceding_siblings(&self.arena)
+            .skip(1)
+            .map_while(move |node| match &self[node] {
+                Element::Keyword(keyword) if keyword.post_blank == 0 => Some(keyword),
+                _ => None,
+            })
+    }
+
+    /// Expands references in `body`, keeping references to blocks in
+    /// `stack`, which are being expanded, as is.
+    fn expand_noweb_inner(&self, body: &str, blocks: &[NodeId], stack: &mut Vec<String>) -> String {
+        let mut output = String::with_capacity(body.len());
+
+        for line in body.split_inclusive('\n') {
+            let mut pos = 0;
+            while let Some((start, end)) = line[pos..].find("<<").and_then(|start| {
+                let start = pos + start;
+                Some((start, start + 2 + line[start + 2..].find(">>")?))
+            }) {
+                let name = &line[start + 2..end];
+                if name.is_empty()
+                    || name.contains('(')
+                    || stack.len() > MAX_NOWEB_DEPTH
+                    || stack.iter().any(|n| n == name)
+                {
+                    output.push_str(&line[pos..end + 2]);
+                    pos = end + 2;
+                    continue;
+                }
+
+                let bodies: Vec<_> = blocks
+                    .iter()
+                    .filter(|&&block| {
+                        self.element_name(block) == Some(name)
+                            || self.header_args(block).get("noweb-ref") == Some(name)
+                    })
+                    .filter_map(|&block| self.src_body(block))
+                    .map(|body| body.trim_end_matches('\n').to_string())
+                    .collect();
+                if bodies.is_empty() {
+                    output.push_str(&line[pos..end + 2]);
+                    pos = end + 2;
+                    continue;
+                }
+
+                stack.push(name.to_string());
+                let expanded = self.expand_noweb_inner(&bodies.join("\n"), blocks, stack);
+                stack.pop();
+
+                // like org-babel, the prefix starts after the previous reference
+                let prefix = &line[pos..start];
+                let expanded = expanded.strip_suffix('\n').unwrap_or(&expanded);
+                for (i, expanded_line) in expanded.split('\n').enumerate() {
+                    if i > 0 {
+                        output.push('\n');
+                    }
+                    output.push_str(prefix);
+                    output.push_str(expanded_line);
+                }
+                pos = end + 2;
+            }
+            output.push_str(&line[pos..]);
+        }
+
+        output
+    }
+}
+
+/// Returns the common indentation of block contents, or `0` if its `-i`
+/// switch is set.
+fn indentation(block: &SourceBlock) -> usize {
+    if block.preserve_indent() {
+        return 0;
+    }
+
+    block
+        .contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0)
+}
+
+fn remove_indentation(block: &SourceBlock) -> String {
+    let indent = indentation(block);
+
+    block
+        .contents
+        .split_inclusive('\n')
+        .map(|line| {
+            line.get(indent..)
+                .unwrap_or_else(|| line.trim_start_matches(' '))
+        })
+        .collect()
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn parse() {
+    let args = HeaderArgs::parse("-n :results output  silent :dir /tmp :var a=1 :var b=\"x y\"");
+    assert_eq!(
+        args.iter().collect::<Vec<_>>(),
+        vec![
+            ("results", "output  silent"),
+            ("dir", "/tmp"),
+            ("var", "a=1"),
+            ("var", "b=\"x y\"")
+        ]
+    );
+    assert_eq!(args.get("var"), Some("b=\"x y\""));
+    assert_eq!(HeaderArgs::parse(":a:b c").get("a:b"), Some("c"));
+    assert_eq!(HeaderArgs::parse(":flag").get("flag"), Some(""));
+
+    let org = Org::parse(
+        "#+NAME: a\n#+BEGIN_SRC sh\n<<b>>\n#+END_SRC\n\n\
+         #+NAME: b\n#+BEGIN_SRC sh\n<<a>> <<a()>>\n#+END_SRC\n",
+    );
+    let blocks = org.src_blocks();
+    assert_eq!(org.element_name(blocks[0]), Some("a"));
+    assert!(org.expand_noweb(blocks[0]).unwrap().ends_with("<<a()>>\n"));
+}
@@ -0,0 +1,171 @@
+use orgize::export::StandaloneConfig;
+use orgize::{Org, ParseConfig};
+use std::env::args;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::exit;
+
+const USAGE: &str = "\
+Usage: orgize [options] [<org-file>]
+
+Reads an org file, or stdin if it's omitted or `-`, and writes it in another
+format to stdout.
+
+Options:
+    -t, --to <format>       Output format: html, json or markdown [default: html]
+    -o, --output <file>     Write to <file> instead of stdout
+    -s, --standalone        Write a complete html page
+        --css <file>        Stylesheet of standalone html pages
+        --no-css            Don't embed any stylesheet in standalone html pages
+        --inline-images     Inline local images of standalone html pages
+        --todo <keywords>   Comma-separated todo keywords [default: TODO]
+        --done <keywords>   Comma-separated done keywords [default: DONE]
+        --pretty            Pretty-print json
+    -h, --help              Print this message";
+
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Html,
+    Json,
+    Markdown,
+}
+
+struct Options {
+    input: Option<PathBuf>,
+    output: Option<PathBuf>,
+    format: Format,
+    standalone: bool,
+    css: Option<String>,
+    inline_images: bool,
+    parse_config: ParseConfig,
+    pretty: bool,
+}
+
+fn main() {
+    let options = match parse_args(args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("orgize: {}\n\n{}", message, USAGE);
+            exit(2);
+        }
+    };
+
+    if let Err(err) = run(&options) {
+        eprintln!("orgize: {}", err);
+        exit(1);
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        input: None,
+        output: None,
+        format: Format::Html,
+        standalone: false,
+        css: None,
+        inline_images: false,
+        parse_config: ParseConfig::default(),
+        pretty: false,
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("missing value of `{}`", name))
+        };
+        match &*arg {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                exit(0);
+            }
+            "-t" | "--to" => {
+                options.format = match &*value(&arg)? {
+                    "html" => Format::Html,
+                    "json" => Format::Json,
+                    "markdown" | "md" => Format::Markdown,
+                    format => return Err(format!("unknown output format `{}`", format)),
+                }
+            }
+            "-o" | "--output" => options.output = Some(value(&arg)?.into()),
+            "-s" | "--standalone" => options.standalone = true,
+            "--css" => {
+                let path = value(&arg)?;
+                let css = fs::read_to_string(&path)
+                    .map_err(|err| format!("failed to read `{}`: {}", path, err))?;
+                options.css = Some(css);
+            }
+            "--no-css" => options.css = Some(String::new()),
+            "--inline-images" => options.inline_images = true,
+            "--todo" => options.parse_config.todo_keywords.0 = keywords(&value(&arg)?),
+            "--done" => options.parse_config.todo_keywords.1 = keywords(&value(&arg)?),
+            "--pretty" => options.pretty = true,
+            "-" => options.input = None,
+            _ if arg.starts_with('-') => return Err(format!("unknown option `{}`", arg)),
+            _ if options.input.is_some() => return Err("more than one input file".into()),
+            _ => options.input = Some(arg.into()),
+        }
+    }
+
+    if options.format != Format::Html
+        && (options.standalone || options.css.is_some() || options.inline_images)
+    {
+        return Err("standalone page options only apply to html".into());
+    }
+
+    Ok(options)
+}
+
+fn keywords(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|keyword| !keyword.is_empty())
+        .map(Into::into)
+        .collect()
+}
+
+fn run(options: &Options) -> io::Result<()> {
+    let mut contents = String::new();
+    match &options.input {
+        Some(path) => contents = fs::read_to_string(path)?,
+        None => {
+            io::stdin().read_to_string(&mut contents)?;
+        }
+    }
+    let org = Org::parse_custom(&contents, &options.parse_config);
+
+    let mut output = Vec::new();
+    match options.format {
+        Format::Html if options.standalone => {
+            let mut config = StandaloneConfig {
+                inline_images: options.inline_images,
+                ..Default::default()
+            };
+            if let Some(css) = &options.css {
+                config.css = css.clone();
+            }
+            if let Some(dir) = options.input.as_deref().and_then(Path::parent) {
+                config.base_dir = dir.to_path_buf();
+            }
+            org.write_html_standalone(&mut output, &config)?;
+        }
+        Format::Html => org.write_html(&mut output)?,
+        Format::Json if options.pretty => serde_json::to_writer_pretty(&mut output, &org)?,
+        Format::Json => serde_json::to_writer(&mut output, &org)?,
+        Format::Markdown => {
+            let mut markdown = String::new();
+            pulldown_cmark_to_cmark::cmark(org.cmark_events(), &mut markdown)
+                .map_err(io::Error::other)?;
+            output = markdown.into_bytes();
+        }
+    }
+    if !output.ends_with(b"\n") {
+        output.push(b'\n');
+    }
+
+    match &options.output {
+        Some(path) => fs::write(path, output),
+        None => io::stdout().write_all(&output),
+    }
+}
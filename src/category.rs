@@ -0,0 +1,76 @@
+use indextree::NodeId;
+
+use crate::elements::Element;
+use crate::{Headline, Org};
+
+impl Org<'_> {
+    /// Returns the category of `node`, used by agenda views for grouping.
+    ///
+    /// It's the `CATEGORY` property of the headline containing `node` or its
+    /// nearest ancestor, or the value of the last `#+CATEGORY` keyword in
+    /// the document, like org-mode does. Empty values are ignored. Returns
+    /// `None` if there's neither, in which case org-mode falls back to the
+    /// file name without extension.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse(
+    ///     "#+CATEGORY: notes\n\
+    ///      * work\n:PROPERTIES:\n:CATEGORY: job\n:END:\n** meeting\ntext\n\
+    ///      * home\n",
+    /// );
+    /// let headlines: Vec<_> = org.headlines().collect();
+    /// let text = headlines[1].section_node().unwrap();
+    ///
+    /// assert_eq!(org.category(headlines[0].headline_node()), Some("job"));
+    /// assert_eq!(org.category(text), Some("job"));
+    /// assert_eq!(org.category(headlines[2].headline_node()), Some("notes"));
+    ///
+    /// let org = Org::parse("#+CATEGORY:\n* home\n:PROPERTIES:\n:CATEGORY:\n:END:\n");
+    /// let home = org.headlines().next().unwrap();
+    /// assert_eq!(org.category(home.headline_node()), None);
+    /// ```
+    pub fn category(&self, node: NodeId) -> Option<&str> {
+        self.property_category(node)
+            .or_else(|| self.keyword_category())
+    }
+
+    /// Returns the `CATEGORY` property of the headline containing `node` or
+    /// its nearest ancestor. Only ancestors are walked, so it's cheap to
+    /// call for every headline.
+    pub(crate) fn property_category(&self, node: NodeId) -> Option<&str> {
+        node.ancestors(&self.arena)
+            .filter_map(|node| match self[node] {
+                Element::Headline { level } => Some(Headline::from_node(node, level, self)),
+                _ => None,
+            })
+            .find_map(|headline| {
+                headline
+                    .title(self)
+                    .properties
+                    .iter()
+                    .find(|(key, value)| key.eq_ignore_ascii_case("CATEGORY") && !value.is_empty())
+                    .map(|(_, value)| &**value)
+            })
+    }
+
+    /// Returns the value of the last non-empty `#+CATEGORY` keyword.
+    pub(crate) fn keyword_category(&self) -> Option<&str> {
+        self.keyword_values("CATEGORY")
+            .into_iter()
+            .rev()
+            .map(str::trim)
+            .find(|value| !value.is_empty())
+    }
+}
+
+/// Returns the category of a file from its name, i.e. the file name without
+/// directory and extension.
+pub(crate) fn file_category(file: &str) -> &str {
+    let name = file.rsplit(['/', '\\']).next().unwrap_or(file);
+    match name.rfind('.') {
+        Some(i) if i > 0 => &name[..i],
+        _ => name,
+    }
+}
@@ -0,0 +1,193 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use indextree::NodeId;
+
+use crate::compat::HashMap;
+use crate::elements::{list::parse_checkbox, Checkbox, Element};
+use crate::Org;
+
+impl Org<'_> {
+    /// Returns the checkbox of the list item `node`, or `None` if it isn't a
+    /// list item with a checkbox.
+    pub fn checkbox(&self, node: NodeId) -> Option<Checkbox> {
+        self.checkbox_span(node).map(|(_, checkbox)| checkbox)
+    }
+
+    /// Toggles the checkbox of the list item `node` in the source text, like
+    /// `org-toggle-checkbox`, and reparses it.
+    ///
+    /// Checked boxes become unchecked, and other boxes become checked,
+    /// including the boxes of sub-items. Then boxes of parent items become
+    /// `[-]` if their sub-items are partially checked, and statistics cookies
+    /// like `[/]` and `[%]` of the list items and the headline are updated,
+    /// counting direct sub-items only.
+    ///
+    /// Returns `false` if `node` isn't a list item with a checkbox.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let mut org = Org::parse("* shopping [/]\n- [ ] fruits [%]\n  - [ ] apple\n  - [ ] pear\n");
+    /// let apple = org
+    ///     .headlines()
+    ///     .next()
+    ///     .and_then(|h| h.section_node())
+    ///     .and_then(|section| {
+    ///         section
+    ///             .descendants(org.arena())
+    ///             .filter(|node| org.checkbox(*node).is_some())
+    ///             .nth(1)
+    ///     })
+    ///     .unwrap();
+    ///
+    /// assert!(org.toggle_checkbox(apple));
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "* shopping [0/1]\n- [-] fruits [50%]\n  - [X] apple\n  - [ ] pear\n"
+    /// );
+    /// ```
+    pub fn toggle_checkbox(&mut self, node: NodeId) -> bool {
+        match self.toggled_checkbox(node) {
+            Some(edits) => {
+                self.apply_edits(edits);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns edits of changed checkboxes and cookies after toggling the
+    /// list item `node`.
+    fn toggled_checkbox(&self, node: NodeId) -> Option<Vec<(Range<usize>, String)>> {
+        let (_, checkbox) = self.checkbox_span(node)?;
+        let checkbox = match checkbox {
+            Checkbox::On => Checkbox::Off,
+            Checkbox::Off | Checkbox::Trans => Checkbox::On,
+        };
+
+        let mut boxes = HashMap::new();
+        for item in node.descendants(&self.arena) {
+            if self.checkbox(item).is_some() {
+                boxes.insert(item, checkbox);
+            }
+        }
+        let boxes = |node: NodeId| boxes.get(&node).copied().or_else(|| self.checkbox(node));
+
+        // parent boxes are computed from the innermost one
+        let mut parent_boxes = HashMap::new();
+        for item in node.ancestors(&self.arena).skip(1) {
+            if !matches!(self[item], Element::ListItem(_)) || self.checkbox(item).is_none() {
+                continue;
+            }
+            let children: Vec<_> = self
+                .sub_items(item)
+                .filter_map(|child| parent_boxes.get(&child).copied().or_else(|| boxes(child)))
+                .collect();
+            let parent = if children.is_empty() {
+                continue;
+            } else if children.iter().all(|child| *child == Checkbox::On) {
+                Checkbox::On
+            } else if children.iter().all(|child| *child == Checkbox::Off) {
+                Checkbox::Off
+            } else {
+                Checkbox::Trans
+            };
+            parent_boxes.insert(item, parent);
+        }
+        let boxes = |node: NodeId| parent_boxes.get(&node).copied().or_else(|| boxes(node));
+
+        let mut edits = Vec::new();
+        let list = node
+            .ancestors(&self.arena)
+            .filter(|node| matches!(self[*node], Element::List(_)))
+            .last()?;
+        for item in list.descendants(&self.arena) {
+            if let Some((span, old)) = self.checkbox_span(item) {
+                let new = boxes(item).unwrap_or(old);
+                if new != old {
+                    edits.push((span, String::from(new.as_str())));
+                }
+            }
+
+            if let Some(paragraph) = self.arena[item]
+                .first_child()
+                .filter(|child| matches!(self[*child], Element::Paragraph { .. }))
+            {
+                let children: Vec<_> = self.sub_items(item).filter_map(&boxes).collect();
+                edits.extend(self.updated_cookies(paragraph, &children));
+            }
+        }
+
+        // cookies of the headline count top-level items of its lists
+        if let Some(headline) = self.enclosing_headline(node) {
+            let children: Vec<_> = headline
+                .section_node()
+                .into_iter()
+                .flat_map(|section| section.children(&self.arena))
+                .filter(|node| matches!(self[*node], Element::List(_)))
+                .flat_map(|list| list.children(&self.arena))
+                .filter_map(&boxes)
+                .collect();
+            edits.extend(self.updated_cookies(headline.title_node(), &children));
+        }
+
+        edits.sort_by_key(|(range, _)| range.start);
+        Some(edits)
+    }
+
+    /// Returns the range and the value of the checkbox of list item `node`.
+    fn checkbox_span(&self, node: NodeId) -> Option<(Range<usize>, Checkbox)> {
+        let item = match &self[node] {
+            Element::ListItem(item) => item,
+            _ => return None,
+        };
+        let start = self.span(node)?.start + item.indent + item.bullet.len();
+        let (offset, checkbox) = parse_checkbox(&self.text[start..])?;
+        Some((start + offset..start + offset + 3, checkbox))
+    }
+
+    /// Returns items of the lists right inside the list item `node`.
+    fn sub_items(&self, node: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        node.children(&self.arena)
+            .filter(move |child| matches!(self[*child], Element::List(_)))
+            .flat_map(move |list| list.children(&self.arena))
+    }
+
+    /// Returns edits of statistics cookies inside `node`, counting checked
+    /// boxes of `children`. Cookies are left unchanged if there're no boxes.
+    fn updated_cookies(&self, node: NodeId, children: &[Checkbox]) -> Vec<(Range<usize>, String)> {
+        if children.is_empty() {
+            return Vec::new();
+        }
+        let done = children
+            .iter()
+            .filter(|child| **child == Checkbox::On)
+            .count();
+        let total = children.len();
+
+        node.descendants(&self.arena)
+            .filter_map(|node| {
+                let cookie = match &self[node] {
+                    Element::Cookie(cookie) => cookie,
+                    _ => return None,
+                };
+                let value = if cookie.value.ends_with("%]") {
+                    format!("[{}%]", done * 100 / total)
+                } else {
+                    format!("[{}/{}]", done, total)
+                };
+                if value == cookie.value {
+                    return None;
+                }
+                let span = self.span(node)?;
+                Some((span.start..span.start + cookie.value.len(), value))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,158 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::ops::Range;
+
+use indextree::NodeId;
+
+use crate::elements::{Clock, Datetime, DayNames, Element};
+use crate::{Duration, Headline, Org};
+
+impl Org<'_> {
+    /// Starts a clock on `headline`, by inserting a running `CLOCK:` line at
+    /// the top of its `LOGBOOK` drawer. The drawer is created after the
+    /// planning line and the properties drawer if it doesn't exist.
+    ///
+    /// Returns `false` if a clock is already running on this headline, or it
+    /// isn't parsed from the source text. The document is reparsed after
+    /// clocking in, so previously returned headlines and nodes are invalid.
+    ///
+    /// ```rust
+    /// # use orgize::elements::Datetime;
+    /// # use orgize::Org;
+    /// #
+    /// let mut org = Org::parse("* task\nSCHEDULED: <2023-01-02 Mon>\n");
+    /// let now = Datetime {
+    ///     year: 2023,
+    ///     month: 1,
+    ///     day: 2,
+    ///     dayname: "Mon".into(),
+    ///     hour: Some(9),
+    ///     minute: Some(15),
+    /// };
+    ///
+    /// let task = org.headlines().next().unwrap();
+    /// assert!(org.clock_in(task, &now));
+    /// assert_eq!(org.running_clocks().len(), 1);
+    ///
+    /// let task = org.headlines().next().unwrap();
+    /// let later = Datetime { hour: Some(10), minute: Some(45), ..now };
+    /// assert_eq!(org.clock_out(task, &later).unwrap().minutes(), 90);
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "* task\nSCHEDULED: <2023-01-02 Mon>\n:LOGBOOK:\n\
+    ///      CLOCK: [2023-01-02 Mon 09:15]--[2023-01-02 Mon 10:45] => 1:30\n:END:\n"
+    /// );
+    /// ```
+    pub fn clock_in(&mut self, headline: Headline, now: &Datetime) -> bool {
+        if self.running_clock(headline).is_some() {
+            return false;
+        }
+        let clock = format!("CLOCK: [{}]\n", format_datetime(now));
+        match self.logbook_edit(headline, &clock) {
+            Some((range, text)) => self.edit(range, &text),
+            None => return false,
+        }
+        true
+    }
+
+    /// Stops the running clock on `headline` at `now`, and writes its
+    /// duration like `=>  1:30`.
+    ///
+    /// Returns the clocked duration, or `None` if no clock is running on
+    /// this headline, or it started after `now`. The document is reparsed
+    /// after clocking out, so previously returned headlines and nodes are
+    /// invalid.
+    pub fn clock_out(&mut self, headline: Headline, now: &Datetime) -> Option<Duration> {
+        let node = self.running_clock(headline)?;
+        let start = match &self[node] {
+            Element::Clock(Clock::Running { start, .. }) => start.minutes()?,
+            _ => return None,
+        };
+        let minutes = u64::try_from(now.minutes()? - start).ok()?;
+
+        let span = self.span(node)?;
+        let line = &self.text[span.clone()];
+        let end = span.start + line.find('\n').unwrap_or(line.len());
+        let end = span.start + self.text[span.start..end].trim_end().len();
+        let closed = format!(
+            "--[{}] => {:2}:{:02}",
+            format_datetime(now),
+            minutes / 60,
+            minutes % 60
+        );
+        self.edit(end..end, &closed);
+
+        Some(Duration::from_minutes(minutes))
+    }
+
+    /// Returns headlines with a running clock, in document order.
+    pub fn running_clocks(&self) -> Vec<Headline> {
+        self.headlines()
+            .filter(|headline| self.running_clock(*headline).is_some())
+            .collect()
+    }
+
+    /// Returns the first running clock in the section of `headline`.
+    fn running_clock(&self, headline: Headline) -> Option<NodeId> {
+        headline
+            .section_node()?
+            .descendants(&self.arena)
+            .find(|node| matches!(&self[*node], Element::Clock(Clock::Running { .. })))
+    }
+
+    /// Returns the `LOGBOOK` drawer in the section of `headline`.
+    fn logbook(&self, headline: Headline) -> Option<NodeId> {
+        headline.section_node()?.children(&self.arena).find(|node| {
+            matches!(&self[*node], Element::Drawer(drawer) if drawer.name.eq_ignore_ascii_case("LOGBOOK"))
+        })
+    }
+
+    /// Returns the edit inserting `lines` at the top of the `LOGBOOK` drawer
+    /// of `headline`, which is created after the planning line and the
+    /// properties drawer if it doesn't exist.
+    pub(crate) fn logbook_edit(
+        &self,
+        headline: Headline,
+        lines: &str,
+    ) -> Option<(Range<usize>, String)> {
+        match self.logbook(headline) {
+            Some(drawer) => {
+                let span = self.span(drawer)?;
+                let contents = self.contents_span(drawer)?;
+                let line = &self.text[span];
+                let indent = &line[..line.len() - line.trim_start().len()];
+                let lines: String = lines
+                    .split_inclusive('\n')
+                    .map(|line| format!("{}{}", indent, line))
+                    .collect();
+                Some((contents.start..contents.start, lines))
+            }
+            None => {
+                let end = self.span(headline.title_node())?.end;
+                let newline = if self.text[..end].ends_with('\n') {
+                    ""
+                } else {
+                    "\n"
+                };
+                let drawer = format!("{}:LOGBOOK:\n{}:END:\n", newline, lines);
+                Some((end..end, drawer))
+            }
+        }
+    }
+}
+
+/// Formats a datetime of clock lines with an English day name. Datetimes
+/// without time are written at midnight.
+pub(crate) fn format_datetime(datetime: &Datetime) -> String {
+    Datetime {
+        hour: Some(datetime.hour.unwrap_or(0)),
+        minute: Some(datetime.minute.unwrap_or(0)),
+        ..datetime.clone()
+    }
+    .format(&DayNames::ENGLISH)
+}
@@ -0,0 +1,276 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::ops::Range;
+use indextree::NodeId;
+
+use crate::elements::{table::format_table, Clock, Datetime, Element, Timestamp, Title};
+use crate::{Duration, Headline, Org};
+
+/// Clock report of a document, like the one in org-mode's clocktable
+#[derive(Debug)]
+pub struct ClockReport<'a, 'b> {
+    /// Total clocked minutes, including clocks outside of any headline
+    pub total: u64,
+    /// Headlines with clocked time, in document order
+    pub rows: Vec<ClockRow<'a, 'b>>,
+}
+
+/// Row of the clock report
+#[derive(Debug)]
+pub struct ClockRow<'a, 'b> {
+    pub headline: Headline,
+    pub title: &'b Title<'a>,
+    /// Level relative to the report, starting from `1`
+    pub level: usize,
+    /// Clocked minutes of this headline and its descendants
+    pub minutes: u64,
+    /// Clocked minutes of this headline only
+    pub own_minutes: u64,
+}
+
+impl<'a> Org<'a> {
+    /// Sums closed clocks per headline and subtree.
+    ///
+    /// Only the part of each clock between `start` (inclusive) and `end`
+    /// (exclusive) is counted, if they are given. Datetimes without time
+    /// mean midnight. Running clocks are ignored.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse(
+    ///     "* project\n\
+    ///      CLOCK: [2023-01-02 Mon 09:00]--[2023-01-02 Mon 10:00] =>  1:00\n\
+    ///      ** task\n\
+    ///      :LOGBOOK:\n\
+    ///      CLOCK: [2023-01-03 Tue 09:00]--[2023-01-03 Tue 11:30] =>  2:30\n\
+    ///      :END:\n",
+    /// );
+    /// let report = org.clock_report(None, None);
+    ///
+    /// assert_eq!(report.total, 210);
+    /// assert_eq!(report.rows[0].minutes, 210);
+    /// assert_eq!(report.rows[0].own_minutes, 60);
+    /// assert_eq!(report.rows[1].title.raw, "task");
+    /// assert_eq!(report.rows[1].minutes, 150);
+    ///
+    /// assert_eq!(
+    ///     report.to_table(None),
+    ///     "| Headline     | Time   |      |\n\
+    ///      |--------------+--------+------|\n\
+    ///      | *Total time* | *3:30* |      |\n\
+    ///      |--------------+--------+------|\n\
+    ///      | project      | 3:30   |      |\n\
+    ///      | \\_  task     |        | 2:30 |\n"
+    /// );
+    /// ```
+    pub fn clock_report<'b>(
+        &'b self,
+        start: Option<&Datetime>,
+        end: Option<&Datetime>,
+    ) -> ClockReport<'a, 'b> {
+        let range = clock_range(start, end);
+
+        let mut rows = Vec::new();
+        let mut total = self
+            .document()
+            .section_node()
+            .map_or(0, |section| self.clocked_minutes(section, &range));
+        for headline in self.document().children(self) {
+            total += self.clock_rows(headline, 1, &range, &mut rows);
+        }
+
+        ClockReport { total, rows }
+    }
+
+    /// Regenerates the contents of all `#+BEGIN: clocktable` dynamic blocks.
+    ///
+    /// `:maxlevel`, `:tstart`, `:tend` and `:scope` (`file` or `subtree`)
+    /// parameters are supported.
+    ///
+    /// ```rust
+    /// # use orgize::{Element, Event, Org};
+    /// #
+    /// let mut org = Org::parse(
+    ///     "#+BEGIN: clocktable :maxlevel 2\n#+END:\n\
+    ///      * project\n\
+    ///      CLOCK: [2023-01-02 Mon 09:00]--[2023-01-02 Mon 10:00] =>  1:00\n\
+    ///      ** task\n\
+    ///      CLOCK: [2023-01-03 Tue 09:00]--[2023-01-03 Tue 11:30] =>  2:30\n",
+    /// );
+    /// org.update_clocktables();
+    ///
+    /// let rows = org
+    ///     .iter()
+    ///     .filter(|event| matches!(event, Event::Start(Element::TableRow(_))))
+    ///     .count();
+    /// assert_eq!(rows, 6);
+    /// ```
+    pub fn update_clocktables(&mut self) {
+        let mut edits = Vec::new();
+
+        for node in self.root.descendants(&self.arena) {
+            let block = match &self[node] {
+                Element::DynBlock(block) if block.block_name.eq_ignore_ascii_case("clocktable") => {
+                    block
+                }
+                _ => continue,
+            };
+            let contents = match self.dyn_block_contents(node) {
+                Some(contents) => contents,
+                None => continue,
+            };
+
+            let timestamp = |key| {
+                let value = block.argument(key)?;
+                Timestamp::parse_active(value)
+                    .or_else(|| Timestamp::parse_inactive(value))
+                    .map(|(_, timestamp)| timestamp)
+            };
+            let (start, end) = (timestamp(":tstart"), timestamp(":tend"));
+            let (start, end) = (
+                start.as_ref().and_then(Timestamp::start),
+                end.as_ref().and_then(Timestamp::start),
+            );
+            let max_level = block
+                .argument(":maxlevel")
+                .and_then(|level| level.parse().ok());
+
+            let report = match block.argument(":scope") {
+                Some("subtree") | Some("tree") => {
+                    let headline = node
+                        .ancestors(&self.arena)
+                        .find_map(|node| match self[node] {
+                            Element::Headline { level } => {
+                                Some(Headline::from_node(node, level, self))
+                            }
+                            _ => None,
+                        });
+                    match headline {
+                        Some(headline) => {
+                            let range = clock_range(start, end);
+                            let mut rows = Vec::new();
+                            let total = self.clock_rows(headline, 1, &range, &mut rows);
+                            ClockReport { total, rows }
+                        }
+                        None => self.clock_report(start, end),
+                    }
+                }
+                _ => self.clock_report(start, end),
+            };
+
+            edits.push((contents, report.to_table(max_level)));
+        }
+
+        for (range, table) in edits.into_iter().rev() {
+            self.edit(range, &table);
+        }
+    }
+
+    /// Pushes rows of this headline and its descendants, and returns the
+    /// minutes clocked in this subtree.
+    fn clock_rows<'b>(
+        &'b self,
+        headline: Headline,
+        level: usize,
+        range: &Range<i64>,
+        rows: &mut Vec<ClockRow<'a, 'b>>,
+    ) -> u64 {
+        let own_minutes = headline
+            .section_node()
+            .map_or(0, |section| self.clocked_minutes(section, range));
+
+        let index = rows.len();
+        rows.push(ClockRow {
+            headline,
+            title: headline.title(self),
+            level,
+            minutes: 0,
+            own_minutes,
+        });
+
+        let mut minutes = own_minutes;
+        for child in headline.children(self) {
+            minutes += self.clock_rows(child, level + 1, range, rows);
+        }
+
+        if minutes == 0 {
+            rows.truncate(index);
+        } else {
+            rows[index].minutes = minutes;
+        }
+        minutes
+    }
+
+    /// Returns the minutes of closed clocks in this section.
+    pub(crate) fn clocked_minutes(&self, section: NodeId, range: &Range<i64>) -> u64 {
+        section
+            .descendants(&self.arena)
+            .filter_map(|node| match &self[node] {
+                Element::Clock(Clock::Closed { start, end, .. }) => {
+                    let start = start.minutes()?.max(range.start);
+                    let end = end.minutes()?.min(range.end);
+                    Some((end - start).max(0) as u64)
+                }
+                _ => None,
+            })
+            .sum()
+    }
+}
+
+impl ClockReport<'_, '_> {
+    /// Renders this report as an org-mode table, like the contents of a
+    /// clocktable dynamic block. Headlines deeper than `max_level` are
+    /// omitted, while their time is still counted in their ancestors.
+    ///
+    /// The returned string always ends with a newline.
+    pub fn to_table(&self, max_level: Option<usize>) -> String {
+        let rows: Vec<_> = self
+            .rows
+            .iter()
+            .filter(|row| max_level.map_or(true, |max| row.level <= max))
+            .collect();
+        let levels = rows.iter().map(|row| row.level).max().unwrap_or(1);
+
+        let mut table: Vec<Vec<String>> = Vec::new();
+        let mut header = vec![String::from("Headline"), String::from("Time")];
+        header.resize(levels + 1, String::new());
+        table.push(header);
+        let mut total = vec![
+            String::from("*Total time*"),
+            format!("*{}*", format_minutes(self.total)),
+        ];
+        total.resize(levels + 1, String::new());
+        table.push(total);
+        for row in rows {
+            let mut cells = vec![String::new(); levels + 1];
+            cells[0] = if row.level == 1 {
+                row.title.raw.to_string()
+            } else {
+                format!("\\_{}{}", " ".repeat(2 * (row.level - 1)), row.title.raw)
+            };
+            cells[row.level] = format_minutes(row.minutes);
+            table.push(cells);
+        }
+
+        format_table(&table, &[0, 1])
+    }
+}
+
+/// Formats minutes like org-mode's default duration format, e.g. `1:05`
+/// or `1d 2:30`.
+fn format_minutes(minutes: u64) -> String {
+    Duration::from_minutes(minutes).format_with_days()
+}
+
+fn clock_range(start: Option<&Datetime>, end: Option<&Datetime>) -> Range<i64> {
+    start.and_then(Datetime::minutes).unwrap_or(i64::MIN)
+        ..end.and_then(Datetime::minutes).unwrap_or(i64::MAX)
+}
+
+#[test]
+fn parse() {
+    assert_eq!(format_minutes(65), "1:05");
+    assert_eq!(format_minutes(1590), "1d 2:30");
+}
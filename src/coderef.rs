@@ -0,0 +1,47 @@
+use indextree::NodeId;
+
+use crate::elements::Element;
+use crate::Org;
+
+/// Line of a source or example block labeled by a coderef, e.g.
+/// `(ref:name)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Coderef {
+    /// Block containing the labeled line
+    pub block: NodeId,
+    /// Line number in the block, starting from one
+    pub line: usize,
+}
+
+impl Org<'_> {
+    /// Resolves the coderef `label`, which coderef links like `[[(name)]]`
+    /// point to, to its block and line. Labels are searched in document
+    /// order, honoring the `-l` switch of each block.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse(
+    ///     "#+BEGIN_SRC rust -n -r\nlet x = 1;\nx + 1 (ref:sum)\n#+END_SRC\n\
+    ///      Line [[(sum)]] sums up.\n",
+    /// );
+    /// let coderef = org.resolve_coderef("sum").unwrap();
+    ///
+    /// assert_eq!(coderef.line, 2);
+    /// assert_eq!(org.src_blocks(), vec![coderef.block]);
+    /// assert!(org.resolve_coderef("missing").is_none());
+    /// ```
+    pub fn resolve_coderef(&self, label: &str) -> Option<Coderef> {
+        self.root.descendants(&self.arena).find_map(|block| {
+            let coderefs = match &self[block] {
+                Element::SourceBlock(block) => block.coderefs(),
+                Element::ExampleBlock(block) => block.coderefs(),
+                _ => return None,
+            };
+            coderefs
+                .into_iter()
+                .find(|(_, l)| *l == label)
+                .map(|(line, _)| Coderef { block, line })
+        })
+    }
+}
@@ -0,0 +1,568 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+
+use crate::elements::{table::format_table, Element, Title};
+use crate::{Duration, Headline, Org};
+
+const DEFAULT_FORMAT: &str = "%25ITEM %TODO %3PRIORITY %TAGS";
+
+/// Summary type of a column, e.g. `{+}`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SummaryType {
+    /// `{+}`, sum of numbers
+    Sum,
+    /// `{:}`, sum of durations
+    Time,
+    /// `{X}`, checked if all children are checked
+    Checkbox,
+    /// `{X/}`, number of checked children, e.g. `[2/3]`
+    CheckboxCount,
+    /// `{X%}`, percentage of checked children, e.g. `[66%]`
+    CheckboxPercent,
+    /// `{min}`, smallest number
+    Min,
+    /// `{max}`, largest number
+    Max,
+    /// `{mean}`, arithmetic mean of numbers
+    Mean,
+    /// `{est+}`, sum of `low-high` estimates
+    Estimate,
+    /// Other summary types, which aren't computed
+    Other(String),
+}
+
+/// Column of the column view, e.g. `%25Effort(Estimate){:}`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Column {
+    /// Property name, or special properties like `ITEM` and `TODO`
+    pub property: String,
+    pub title: Option<String>,
+    pub width: Option<usize>,
+    pub summary: Option<SummaryType>,
+}
+
+impl Column {
+    /// Parses a column view format, e.g. `%25ITEM %TODO %Effort{:}`.
+    /// Invalid columns are skipped.
+    ///
+    /// ```rust
+    /// # use orgize::{Column, SummaryType};
+    /// #
+    /// let columns = Column::parse_format("%25ITEM %Effort(Estimate){:}");
+    ///
+    /// assert_eq!(columns[0].property, "ITEM");
+    /// assert_eq!(columns[0].width, Some(25));
+    /// assert_eq!(columns[1].title.as_deref(), Some("Estimate"));
+    /// assert_eq!(columns[1].summary, Some(SummaryType::Time));
+    /// ```
+    pub fn parse_format(format: &str) -> Vec<Column> {
+        let mut columns = Vec::new();
+        let mut start = None;
+        // `%` inside titles and summary types like `{X%}` doesn't start a
+        // new column
+        let mut closing = None;
+        for (i, c) in format.char_indices() {
+            match (closing, c) {
+                (Some(close), _) if c == close => closing = None,
+                (Some(_), _) => (),
+                (None, '(') => closing = Some(')'),
+                (None, '{') => closing = Some('}'),
+                (None, '%') => {
+                    if let Some(start) = start {
+                        columns.extend(Column::parse(format[start..i].trim()));
+                    }
+                    start = Some(i + 1);
+                }
+                _ => (),
+            }
+        }
+        if let Some(start) = start {
+            columns.extend(Column::parse(format[start..].trim()));
+        }
+        columns
+    }
+
+    fn parse(input: &str) -> Option<Column> {
+        let digits = input.bytes().take_while(u8::is_ascii_digit).count();
+        let width = input[..digits].parse().ok();
+        let input = &input[digits..];
+
+        let end = input.find(['(', '{']).unwrap_or(input.len());
+        let (property, mut input) = input.split_at(end);
+        if property.is_empty() || property.contains(char::is_whitespace) {
+            return None;
+        }
+
+        let mut title = None;
+        if let Some(rest) = input.strip_prefix('(') {
+            let (value, rest) = rest.split_once(')')?;
+            title = Some(value.to_string());
+            input = rest;
+        }
+
+        let mut summary = None;
+        if let Some(rest) = input.strip_prefix('{') {
+            let (value, _) = rest.split_once('}')?;
+            summary = Some(match value {
+                "+" => SummaryType::Sum,
+                ":" => SummaryType::Time,
+                "X" => SummaryType::Checkbox,
+                "X/" => SummaryType::CheckboxCount,
+                "X%" => SummaryType::CheckboxPercent,
+                "min" => SummaryType::Min,
+                "max" => SummaryType::Max,
+                "mean" => SummaryType::Mean,
+                "est+" => SummaryType::Estimate,
+                _ => SummaryType::Other(value.to_string()),
+            });
+        }
+
+        Some(Column {
+            property: property.to_string(),
+            title,
+            width,
+            summary,
+        })
+    }
+}
+
+/// Column view of a document or a subtree
+#[derive(Debug)]
+pub struct ColumnView<'a, 'b> {
+    pub columns: Vec<Column>,
+    /// Rows of headlines, in document order
+    pub rows: Vec<ColumnRow<'a, 'b>>,
+}
+
+/// Row of the column view
+#[derive(Debug)]
+pub struct ColumnRow<'a, 'b> {
+    pub headline: Headline,
+    pub title: &'b Title<'a>,
+    /// Values of each column, replaced by the summary of its children if
+    /// the column has a summary type and any child has a value
+    pub values: Vec<Option<String>>,
+}
+
+impl<'a> Org<'a> {
+    /// Computes the column view of the subtree of `headline`, or the whole
+    /// document if it's `None`.
+    ///
+    /// The columns format comes from the nearest `COLUMNS` property, the
+    /// `#+COLUMNS` keyword, or the default format of org-mode.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse(
+    ///     "#+COLUMNS: %ITEM %Effort{:}\n\
+    ///      * project\n\
+    ///      ** design\n:PROPERTIES:\n:Effort: 1:30\n:END:\n\
+    ///      ** build\n:PROPERTIES:\n:Effort: 2:15\n:END:\n",
+    /// );
+    /// let view = org.column_view(None);
+    ///
+    /// assert_eq!(view.rows[0].values[1].as_deref(), Some("3:45"));
+    /// assert_eq!(
+    ///     view.to_table(None),
+    ///     "| ITEM      | Effort |\n\
+    ///      |-----------+--------|\n\
+    ///      | * project | 3:45   |\n\
+    ///      | ** design | 1:30   |\n\
+    ///      | ** build  | 2:15   |\n"
+    /// );
+    /// ```
+    pub fn column_view<'b>(&'b self, headline: Option<Headline>) -> ColumnView<'a, 'b> {
+        let format = headline
+            .and_then(|headline| self.inherited_columns(headline))
+            .or_else(|| {
+                self.keywords()
+                    .find(|keyword| keyword.key.eq_ignore_ascii_case("COLUMNS"))
+                    .map(|keyword| &*keyword.value)
+            })
+            .unwrap_or(DEFAULT_FORMAT);
+        let columns = Column::parse_format(format);
+
+        let mut rows = Vec::new();
+        match headline {
+            Some(headline) => {
+                self.column_rows(headline, &columns, &mut rows);
+            }
+            None => {
+                for headline in self.document().children(self) {
+                    self.column_rows(headline, &columns, &mut rows);
+                }
+            }
+        }
+
+        ColumnView { columns, rows }
+    }
+
+    /// Regenerates the contents of all `#+BEGIN: columnview` dynamic blocks.
+    ///
+    /// `:id` (`local`, `global` or a headline's `ID` property) and
+    /// `:maxlevel` parameters are supported.
+    pub fn update_columnviews(&mut self) {
+        let mut edits = Vec::new();
+
+        for node in self.root.descendants(&self.arena) {
+            let block = match &self[node] {
+                Element::DynBlock(block) if block.block_name.eq_ignore_ascii_case("columnview") => {
+                    block
+                }
+                _ => continue,
+            };
+            let contents = match self.dyn_block_contents(node) {
+                Some(contents) => contents,
+                None => continue,
+            };
+
+            let headline =
+                match block.argument(":id") {
+                    Some("global") => None,
+                    None | Some("local") => node.ancestors(&self.arena).find_map(|node| match self
+                        [node]
+                    {
+                        Element::Headline { level } => Some(Headline::from_node(node, level, self)),
+                        _ => None,
+                    }),
+                    Some(id) => {
+                        let headline =
+                            self.headlines().find(|headline| {
+                                headline.title(self).properties.iter().any(|(key, value)| {
+                                    key.eq_ignore_ascii_case("ID") && value == id
+                                })
+                            });
+                        match headline {
+                            Some(headline) => Some(headline),
+                            None => continue,
+                        }
+                    }
+                };
+            let max_level = block
+                .argument(":maxlevel")
+                .and_then(|level| level.parse().ok());
+
+            edits.push((contents, self.column_view(headline).to_table(max_level)));
+        }
+
+        for (range, table) in edits.into_iter().rev() {
+            self.edit(range, &table);
+        }
+    }
+
+    fn inherited_columns(&self, headline: Headline) -> Option<&str> {
+        let mut headline = Some(headline);
+        while let Some(hdl) = headline {
+            let columns = hdl
+                .title(self)
+                .properties
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case("COLUMNS"));
+            if let Some((_, value)) = columns {
+                return Some(value);
+            }
+            headline = hdl.parent(self);
+        }
+        None
+    }
+
+    /// Pushes rows of this headline and its descendants, and returns the
+    /// index of this headline's row.
+    fn column_rows<'b>(
+        &'b self,
+        headline: Headline,
+        columns: &[Column],
+        rows: &mut Vec<ColumnRow<'a, 'b>>,
+    ) -> usize {
+        let title = headline.title(self);
+        let values = columns
+            .iter()
+            .map(|column| self.column_value(headline, title, &column.property))
+            .collect();
+
+        let index = rows.len();
+        rows.push(ColumnRow {
+            headline,
+            title,
+            values,
+        });
+
+        let children: Vec<_> = headline
+            .children(self)
+            .map(|child| self.column_rows(child, columns, rows))
+            .collect();
+
+        for (i, column) in columns.iter().enumerate() {
+            let summary = match &column.summary {
+                Some(summary) => summary,
+                None => continue,
+            };
+            let values: Vec<_> = children
+                .iter()
+                .filter_map(|&child| rows[child].values[i].as_deref())
+                .collect();
+            if !values.is_empty() {
+                if let Some(value) = summarize(summary, &values) {
+                    rows[index].values[i] = Some(value);
+                }
+            }
+        }
+
+        index
+    }
+
+    fn column_value(&self, headline: Headline, title: &Title, property: &str) -> Option<String> {
+        match &*property.to_ascii_uppercase() {
+            "ITEM" => Some(title.raw.to_string()),
+            "TODO" => title.keyword.as_ref().map(|keyword| keyword.to_string()),
+            "PRIORITY" => title.priority.map(|priority| priority.to_string()),
+            "TAGS" if !title.tags.is_empty() => Some(format!(":{}:", title.tags.join(":"))),
+            "TAGS" => None,
+            "CLOCKSUM" => {
+                let minutes: u64 = Some(headline.headline_node())
+                    .into_iter()
+                    .flat_map(|node| node.descendants(&self.arena))
+                    .filter(|&node| matches!(self[node], Element::Section))
+                    .map(|section| self.clocked_minutes(section, &(i64::MIN..i64::MAX)))
+                    .sum();
+                if minutes == 0 {
+                    None
+                } else {
+                    Some(Duration::from_minutes(minutes).to_string())
+                }
+            }
+            _ => title
+                .properties
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(property))
+                .map(|(_, value)| value.to_string()),
+        }
+    }
+}
+
+impl ColumnView<'_, '_> {
+    /// Renders this column view as an org-mode table, like the contents of
+    /// a columnview dynamic block. Headlines deeper than `max_level` are
+    /// omitted.
+    pub fn to_table(&self, max_level: Option<usize>) -> String {
+        let mut table = vec![self
+            .columns
+            .iter()
+            .map(|column| {
+                column
+                    .title
+                    .clone()
+                    .unwrap_or_else(|| column.property.clone())
+            })
+            .collect::<Vec<_>>()];
+
+        for row in &self.rows {
+            if max_level.is_some_and(|max| row.title.level > max) {
+                continue;
+            }
+            let cells = self
+                .columns
+                .iter()
+                .zip(&row.values)
+                .map(|(column, value)| match value {
+                    Some(value) if column.property.eq_ignore_ascii_case("ITEM") => {
+                        format!("{} {}", "*".repeat(row.title.level), value)
+                    }
+                    Some(value) => value.clone(),
+                    None => String::new(),
+                })
+                .collect();
+            table.push(cells);
+        }
+
+        format_table(&table, &[0])
+    }
+}
+
+/// Summarizes the values of children, or returns `None` if none of them
+/// can be summarized.
+fn summarize(summary: &SummaryType, values: &[&str]) -> Option<String> {
+    let numbers = || {
+        values
+            .iter()
+            .filter_map(|value| value.trim().parse::<f64>().ok())
+    };
+
+    match summary {
+        SummaryType::Sum => Some(format_number(numbers().sum())),
+        SummaryType::Time => Some(
+            values
+                .iter()
+                .filter_map(|value| Duration::parse(value))
+                .sum::<Duration>()
+                .to_string(),
+        ),
+        SummaryType::Checkbox => Some(if values.iter().all(|value| is_checked(value)) {
+            String::from("[X]")
+        } else {
+            String::from("[ ]")
+        }),
+        SummaryType::CheckboxCount => {
+            let checked = values.iter().filter(|value| is_checked(value)).count();
+            Some(format!("[{}/{}]", checked, values.len()))
+        }
+        SummaryType::CheckboxPercent => {
+            let checked = values.iter().filter(|value| is_checked(value)).count();
+            Some(format!("[{}%]", checked * 100 / values.len()))
+        }
+        SummaryType::Min => numbers().reduce(f64::min).map(format_number),
+        SummaryType::Max => numbers().reduce(f64::max).map(format_number),
+        SummaryType::Mean => {
+            let (sum, count) = numbers().fold((0.0, 0), |(sum, count), n| (sum + n, count + 1));
+            if count == 0 {
+                None
+            } else {
+                Some(format_number(sum / count as f64))
+            }
+        }
+        SummaryType::Estimate => {
+            let (mut mean, mut variance) = (0.0, 0.0);
+            for value in values {
+                let mut bounds = value.split('-').map(|n| n.trim().parse::<f64>().ok());
+                match (bounds.next(), bounds.next()) {
+                    (Some(Some(low)), Some(Some(high))) => {
+                        mean += (low + high) / 2.0;
+                        variance += ((high - low) / 2.0) * ((high - low) / 2.0);
+                    }
+                    (Some(Some(value)), None) => mean += value,
+                    _ => (),
+                }
+            }
+            let deviation = sqrt(variance);
+            Some(format!("{:.0}-{:.0}", mean - deviation, mean + deviation))
+        }
+        SummaryType::Other(_) => None,
+    }
+}
+
+fn is_checked(value: &str) -> bool {
+    let value = value.trim();
+    if value == "[X]" || value == "[100%]" {
+        return true;
+    }
+    value
+        .strip_prefix('[')
+        .and_then(|value| value.strip_suffix(']'))
+        .and_then(|value| value.split_once('/'))
+        .is_some_and(|(done, total)| done == total)
+}
+
+fn format_number(number: f64) -> String {
+    if number.fract() == 0.0 && number.abs() < 1e15 {
+        format!("{}", number as i64)
+    } else {
+        format!("{}", number)
+    }
+}
+
+/// Square root using Newton's method, since `f64::sqrt` needs `std`.
+fn sqrt(value: f64) -> f64 {
+    if value <= 0.0 {
+        return 0.0;
+    }
+    let mut x = if value > 1.0 { value } else { 1.0 };
+    for _ in 0..64 {
+        let next = (x + value / x) / 2.0;
+        if next >= x {
+            break;
+        }
+        x = next;
+    }
+    x
+}
+
+#[test]
+fn parse() {
+    assert_eq!(
+        Column::parse_format("%25ITEM(Task %) %TODO %3PRIORITY %Effort{:} %foo{bar} %(x)"),
+        vec![
+            Column {
+                property: "ITEM".into(),
+                title: Some("Task %".into()),
+                width: Some(25),
+                summary: None,
+            },
+            Column {
+                property: "TODO".into(),
+                title: None,
+                width: None,
+                summary: None,
+            },
+            Column {
+                property: "PRIORITY".into(),
+                title: None,
+                width: Some(3),
+                summary: None,
+            },
+            Column {
+                property: "Effort".into(),
+                title: None,
+                width: None,
+                summary: Some(SummaryType::Time),
+            },
+            Column {
+                property: "foo".into(),
+                title: None,
+                width: None,
+                summary: Some(SummaryType::Other("bar".into())),
+            },
+        ]
+    );
+    assert_eq!(
+        Column::parse_format("%ITEM %Done{X%} %Count{X/}"),
+        vec![
+            Column {
+                property: "ITEM".into(),
+                title: None,
+                width: None,
+                summary: None,
+            },
+            Column {
+                property: "Done".into(),
+                title: None,
+                width: None,
+                summary: Some(SummaryType::CheckboxPercent),
+            },
+            Column {
+                property: "Count".into(),
+                title: None,
+                width: None,
+                summary: Some(SummaryType::CheckboxCount),
+            },
+        ]
+    );
+
+    assert_eq!(
+        summarize(&SummaryType::Sum, &["1", "2.5", "x"]),
+        Some("3.5".into())
+    );
+    assert_eq!(
+        summarize(&SummaryType::Mean, &["1", "2"]),
+        Some("1.5".into())
+    );
+    assert_eq!(summarize(&SummaryType::Max, &["x"]), None);
+    assert_eq!(
+        summarize(&SummaryType::CheckboxCount, &["[X]", "[ ]", "[2/2]"]),
+        Some("[2/3]".into())
+    );
+    assert_eq!(
+        summarize(&SummaryType::CheckboxPercent, &["[X]", "[ ]", "[-]"]),
+        Some("[33%]".into())
+    );
+    assert_eq!(
+        summarize(&SummaryType::Checkbox, &["[X]", "[100%]"]),
+        Some("[X]".into())
+    );
+    assert_eq!(
+        summarize(&SummaryType::Estimate, &["1-3", "2-4", "5"]),
+        Some("9-11".into())
+    );
+    assert!((sqrt(2.0) - core::f64::consts::SQRT_2).abs() < 1e-12);
+}
@@ -0,0 +1,75 @@
+use indextree::{NodeEdge, NodeId};
+
+use crate::Org;
+
+impl Org<'_> {
+    /// Returns `true` if the subtrees of `a` and `b` have the same elements
+    /// in the same structure, regardless of their positions in the source
+    /// text.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse("* a\n*text*\n* b\n*text*\n* c\n/text/\n");
+    /// let sections: Vec<_> = org
+    ///     .headlines()
+    ///     .filter_map(|headline| headline.section_node())
+    ///     .collect();
+    ///
+    /// assert!(org.subtree_eq(sections[0], sections[1]));
+    /// assert!(!org.subtree_eq(sections[0], sections[2]));
+    /// ```
+    pub fn subtree_eq(&self, a: NodeId, b: NodeId) -> bool {
+        subtree_eq(self, a, self, b)
+    }
+
+    /// Returns `true` if the subtree of `a` in this document and the subtree
+    /// of `b` in `other` have the same elements in the same structure.
+    pub fn subtree_eq_with(&self, a: NodeId, other: &Org, b: NodeId) -> bool {
+        subtree_eq(self, a, other, b)
+    }
+}
+
+/// Compares whole documents structurally, like
+/// [`Org::subtree_eq`](struct.Org.html#method.subtree_eq).
+impl PartialEq for Org<'_> {
+    fn eq(&self, other: &Org) -> bool {
+        subtree_eq(self, self.root, other, other.root)
+    }
+}
+
+fn subtree_eq(org_a: &Org, a: NodeId, org_b: &Org, b: NodeId) -> bool {
+    let mut edges_a = a.traverse(&org_a.arena);
+    let mut edges_b = b.traverse(&org_b.arena);
+
+    loop {
+        match (edges_a.next(), edges_b.next()) {
+            (None, None) => return true,
+            (Some(NodeEdge::Start(a)), Some(NodeEdge::Start(b))) => {
+                if org_a[a] != org_b[b] {
+                    return false;
+                }
+                // contents of lazily parsed nodes are compared as texts
+                if org_a.is_pending(a) || org_b.is_pending(b) {
+                    if org_a.span(a).map(|span| &org_a.text[span])
+                        != org_b.span(b).map(|span| &org_b.text[span])
+                    {
+                        return false;
+                    }
+                    skip_subtree(&mut edges_a, a);
+                    skip_subtree(&mut edges_b, b);
+                }
+            }
+            (Some(NodeEdge::End(_)), Some(NodeEdge::End(_))) => (),
+            _ => return false,
+        }
+    }
+}
+
+fn skip_subtree(edges: &mut impl Iterator<Item = NodeEdge>, node: NodeId) {
+    for edge in edges {
+        if edge == NodeEdge::End(node) {
+            return;
+        }
+    }
+}
@@ -0,0 +1,11 @@
+//! Items which come from different crates with and without `std`.
+
+#[cfg(feature = "std")]
+pub use std::collections::HashMap;
+#[cfg(feature = "std")]
+pub use std::sync::OnceLock;
+
+#[cfg(not(feature = "std"))]
+pub use core::cell::OnceCell as OnceLock;
+#[cfg(not(feature = "std"))]
+pub use hashbrown::HashMap;
@@ -1,18 +1,184 @@
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::elements::Keyword;
+use crate::Extensions;
+
 /// Parse configuration
 #[derive(Clone, Debug)]
 pub struct ParseConfig {
     /// Headline's todo keywords
     pub todo_keywords: (Vec<String>, Vec<String>),
+    /// Highest, lowest and default priorities of headlines, like
+    /// `org-priority-highest`, `org-priority-lowest` and
+    /// `org-priority-default`
+    pub priorities: (char, char, char),
+    /// Org syntax version whose rules are applied
+    pub syntax: Syntax,
+    /// Markers of emphasis, verbatim and code objects to recognize
+    pub emphasis_markers: String,
+    /// Objects not to recognize
+    pub disabled_objects: Vec<ObjectKind>,
+    /// Custom drawer names, which are recognized even if they contain
+    /// characters other than letters, `-` and `_`
+    pub drawer_names: Vec<String>,
+    /// Handlers called with keywords of specific names
+    pub keyword_handlers: KeywordHandlers,
 }
 
 impl Default for ParseConfig {
     fn default() -> Self {
         ParseConfig {
             todo_keywords: (vec![String::from("TODO")], vec![String::from("DONE")]),
+            priorities: ('A', 'C', 'B'),
+            syntax: Syntax::default(),
+            emphasis_markers: String::from("*+/_=~"),
+            disabled_objects: Vec::new(),
+            drawer_names: Vec::new(),
+            keyword_handlers: KeywordHandlers::default(),
+        }
+    }
+}
+
+impl ParseConfig {
+    pub(crate) fn is_drawer_name(&self, name: &str) -> bool {
+        if self
+            .drawer_names
+            .iter()
+            .any(|drawer| drawer.eq_ignore_ascii_case(name))
+        {
+            return true;
+        }
+
+        match self.syntax {
+            Syntax::Org8 => ["PROPERTIES", "CLOCK", "LOGBOOK", "RESULTS"]
+                .iter()
+                .any(|drawer| drawer.eq_ignore_ascii_case(name)),
+            Syntax::Org9 => name
+                .chars()
+                .all(|c| c.is_ascii_alphabetic() || c == '-' || c == '_'),
+        }
+    }
+}
+
+type KeywordHandler = Arc<dyn Fn(&Keyword, &mut Extensions) + Send + Sync>;
+
+/// Registry of keyword handlers
+///
+/// Each handler is called with every keyword of its name, in document order,
+/// whenever the document is parsed or edited, and can store typed data in
+/// the document's [`Extensions`], which are cleared beforehand.
+///
+/// ```rust
+/// # use orgize::{Org, ParseConfig};
+/// #
+/// struct Aliases(Vec<String>);
+///
+/// let mut config = ParseConfig::default();
+/// config
+///     .keyword_handlers
+///     .register("ROAM_ALIAS", |keyword, extensions| {
+///         extensions
+///             .get_or_insert_with(|| Aliases(Vec::new()))
+///             .0
+///             .push(keyword.value.to_string());
+///     });
+///
+/// let org = Org::parse_custom("#+ROAM_ALIAS: foo\n#+roam_alias: bar\n", &config);
+///
+/// assert_eq!(org.extensions().get::<Aliases>().unwrap().0, ["foo", "bar"]);
+/// ```
+///
+/// [`Extensions`]: struct.Extensions.html
+#[derive(Clone, Default)]
+pub struct KeywordHandlers {
+    handlers: Vec<(String, KeywordHandler)>,
+}
+
+impl KeywordHandlers {
+    /// Registers `handler` for keywords named `key`, case-insensitively.
+    pub fn register<F>(&mut self, key: impl Into<String>, handler: F) -> &mut Self
+    where
+        F: Fn(&Keyword, &mut Extensions) + Send + Sync + 'static,
+    {
+        self.handlers.push((key.into(), Arc::new(handler)));
+        self
+    }
+
+    /// Returns `true` if no handler is registered.
+    pub fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+
+    pub(crate) fn call(&self, keyword: &Keyword, extensions: &mut Extensions) {
+        for (key, handler) in &self.handlers {
+            if key.eq_ignore_ascii_case(&keyword.key) {
+                handler(keyword, extensions);
+            }
         }
     }
 }
 
+impl fmt::Debug for KeywordHandlers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list()
+            .entries(self.handlers.iter().map(|(key, _)| key))
+            .finish()
+    }
+}
+
+/// Kinds of objects which can be disabled
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ObjectKind {
+    Citation,
+    Cookie,
+    FnRef,
+    InlineCall,
+    InlineSrc,
+    Link,
+    Macros,
+    RadioTarget,
+    Snippet,
+    Target,
+    Timestamp,
+}
+
+/// Org syntax version
+///
+/// Block names, including `#+BEGIN_SRC`, are matched case-insensitively by
+/// both versions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Syntax {
+    /// Org 8.x
+    ///
+    /// Blocks named after export backends, e.g. `#+BEGIN_HTML`, are export
+    /// blocks, which are written as `#+BEGIN_EXPORT html` since Org 9.0.
+    /// And only `PROPERTIES`, `CLOCK`, `LOGBOOK`, `RESULTS` and custom
+    /// drawer names are recognized as drawers. Citations are plain text, and
+    /// the planning line may also follow the property drawer.
+    Org8,
+    /// Org 9.x, with citations like `[cite:@key]` from Org 9.5
+    #[default]
+    Org9,
+}
+
+/// Parse mode
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Parses any input on a best-effort basis, e.g. unclosed blocks extend
+    /// to the end of document
+    #[default]
+    Lenient,
+    /// Rejects input with the first structural violation found by
+    /// [`Org::diagnostics`]
+    ///
+    /// [`Org::diagnostics`]: struct.Org.html#method.diagnostics
+    Strict,
+}
+
 lazy_static::lazy_static! {
     pub static ref DEFAULT_CONFIG: ParseConfig = ParseConfig::default();
 }
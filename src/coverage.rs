@@ -0,0 +1,121 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+use indextree::NodeId;
+
+use crate::Org;
+
+/// Coverage Error
+///
+/// See [`Org::verify_coverage`](struct.Org.html#method.verify_coverage).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoverageError {
+    /// Expected element to have a span
+    MissingSpan { at: NodeId },
+    /// Expected element to be nested within its parent
+    OutOfParent { at: NodeId },
+    /// Expected element to begin after its previous sibling ends
+    Overlap { at: NodeId },
+    /// Expected bytes in the contents of `parent` to be covered by its children
+    Gap { range: Range<usize>, parent: NodeId },
+}
+
+impl Org<'_> {
+    /// Verifies that every byte of the source text is covered exactly once.
+    ///
+    /// A byte is either covered by a leaf element, or by the syntax of a
+    /// container element, e.g. stars of headlines or `#+BEGIN_QUOTE` lines,
+    /// which is the part of its span outside its contents span. So every
+    /// element must nest within its parent, siblings mustn't overlap, and
+    /// the contents of container elements must be covered by their children.
+    ///
+    /// Returns the violations found, including gaps. Pending contents of
+    /// lazily parsed documents are skipped.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse("* title\n#+BEGIN_QUOTE\n*bold* text\n#+END_QUOTE\n");
+    ///
+    /// assert!(org.verify_coverage().is_empty());
+    /// ```
+    pub fn verify_coverage(&self) -> Vec<CoverageError> {
+        let mut errors = Vec::new();
+
+        match self.span(self.root) {
+            Some(span) if span == (0..self.text.len()) => (),
+            Some(span) => {
+                if span.start > 0 {
+                    errors.push(CoverageError::Gap {
+                        range: 0..span.start,
+                        parent: self.root,
+                    });
+                }
+                if span.end < self.text.len() {
+                    errors.push(CoverageError::Gap {
+                        range: span.end..self.text.len(),
+                        parent: self.root,
+                    });
+                }
+            }
+            None => errors.push(CoverageError::MissingSpan { at: self.root }),
+        }
+
+        for parent in self.root.descendants(&self.arena) {
+            let span = match self.span(parent) {
+                Some(span) => span,
+                None => continue,
+            };
+
+            let contents = self.contents_span(parent);
+            if let Some(contents) = &contents {
+                if contents.start < span.start || contents.end > span.end {
+                    errors.push(CoverageError::OutOfParent { at: parent });
+                }
+            }
+
+            // syntax of containers can also appear before their contents,
+            // e.g. titles of headlines
+            let mut pos = span.start;
+            let mut covered = contents.as_ref().map(|contents| contents.start);
+
+            for child in parent.children(&self.arena) {
+                let child_span = match self.span(child) {
+                    Some(child_span) => child_span,
+                    None => {
+                        errors.push(CoverageError::MissingSpan { at: child });
+                        continue;
+                    }
+                };
+
+                if child_span.start < span.start || child_span.end > span.end {
+                    errors.push(CoverageError::OutOfParent { at: child });
+                } else if child_span.start < pos {
+                    errors.push(CoverageError::Overlap { at: child });
+                }
+
+                if let (Some(contents), Some(start)) = (&contents, covered) {
+                    if child_span.start > start && start < contents.end {
+                        errors.push(CoverageError::Gap {
+                            range: start..child_span.start.min(contents.end),
+                            parent,
+                        });
+                    }
+                    covered = Some(start.max(child_span.end));
+                }
+
+                pos = pos.max(child_span.end);
+            }
+
+            if let (Some(contents), Some(start)) = (&contents, covered) {
+                if start < contents.end && !self.is_pending(parent) {
+                    errors.push(CoverageError::Gap {
+                        range: start..contents.end,
+                        parent,
+                    });
+                }
+            }
+        }
+
+        errors
+    }
+}
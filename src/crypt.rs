@@ -0,0 +1,107 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+use indextree::NodeId;
+
+use crate::elements::Element;
+use crate::{Headline, Org};
+
+impl Org<'_> {
+    /// Returns headlines tagged with `:crypt:` whose sections are encrypted,
+    /// in document order.
+    pub fn encrypted_headlines(&self) -> Vec<Headline> {
+        self.headlines()
+            .filter(|headline| self.encrypted_section(*headline).is_some())
+            .collect()
+    }
+
+    /// Decrypts the section of this headline with `decrypt`, like
+    /// `org-decrypt-entry`, and reparses the plaintext into its subtree.
+    ///
+    /// `decrypt` receives the ASCII-armored PGP message and returns its
+    /// plaintext. Returns `Ok(false)` if the section isn't encrypted.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let mut org = Org::parse(
+    ///     "* passwords :crypt:\n\
+    ///      -----BEGIN PGP MESSAGE-----\n\
+    ///      \n\
+    ///      KiogZW1haWwKaHVudGVyMgo=\n\
+    ///      -----END PGP MESSAGE-----\n\
+    ///      * notes\n",
+    /// );
+    /// let headline = org.encrypted_headlines()[0];
+    ///
+    /// let decrypted = org
+    ///     .decrypt_entry(headline, |_message| Ok::<_, ()>("** email\nhunter2\n".into()))
+    ///     .unwrap();
+    ///
+    /// assert!(decrypted);
+    /// assert!(org.encrypted_headlines().is_empty());
+    /// let headline = org.headlines().next().unwrap();
+    /// let email = headline.children(&org).next().unwrap();
+    /// assert_eq!(email.title(&org).raw, "email");
+    /// ```
+    pub fn decrypt_entry<F, E>(&mut self, headline: Headline, decrypt: F) -> Result<bool, E>
+    where
+        F: FnOnce(&str) -> Result<String, E>,
+    {
+        let range = match self.encrypted_section(headline) {
+            Some(node) => self.encrypted_range(node),
+            None => return Ok(false),
+        };
+
+        let plaintext = decrypt(&self.text[range.clone()])?;
+        self.edit(range, &with_newline(plaintext));
+        Ok(true)
+    }
+
+    /// Decrypts all encrypted sections with `decrypt`, like
+    /// `org-decrypt-entries`, and returns the number of decrypted sections.
+    ///
+    /// See [`Org::decrypt_entry`](#method.decrypt_entry) for more details.
+    pub fn decrypt_entries<F, E>(&mut self, mut decrypt: F) -> Result<usize, E>
+    where
+        F: FnMut(&str) -> Result<String, E>,
+    {
+        let ranges: Vec<_> = self
+            .encrypted_headlines()
+            .into_iter()
+            .filter_map(|headline| self.encrypted_section(headline))
+            .map(|node| self.encrypted_range(node))
+            .collect();
+
+        // decrypts from the end, so that edits don't move remaining sections
+        for range in ranges.iter().rev() {
+            let plaintext = decrypt(&self.text[range.clone()])?;
+            self.edit(range.clone(), &with_newline(plaintext));
+        }
+
+        Ok(ranges.len())
+    }
+
+    fn encrypted_section(&self, headline: Headline) -> Option<NodeId> {
+        headline
+            .headline_node()
+            .children(&self.arena)
+            .find(|&node| matches!(self[node], Element::EncryptedSection(_)))
+    }
+
+    /// Returns the range of the PGP message, without trailing blank lines.
+    fn encrypted_range(&self, node: NodeId) -> Range<usize> {
+        let start = self.spans[&node].start;
+        match &self[node] {
+            Element::EncryptedSection(section) => start..start + section.data.len(),
+            _ => start..start,
+        }
+    }
+}
+
+fn with_newline(mut text: String) -> String {
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+    text
+}
@@ -0,0 +1,104 @@
+use core::ops::Range;
+use indextree::NodeId;
+
+use crate::{elements::Element, Org};
+
+/// A cursor for walking an `Org` struct node by node.
+///
+/// ```rust
+/// # use orgize::{Element, Org};
+/// #
+/// let text = "* title\nsection";
+/// let org = Org::parse(text);
+///
+/// let mut cursor = org.cursor();
+/// assert!(matches!(cursor.element(), Element::Document { .. }));
+///
+/// assert!(cursor.goto_first_child());
+/// assert!(matches!(cursor.element(), Element::Headline { .. }));
+///
+/// assert!(cursor.goto_first_child());
+/// assert!(matches!(cursor.element(), Element::Title(_)));
+///
+/// assert!(cursor.goto_next_sibling());
+/// assert!(matches!(cursor.element(), Element::Section));
+/// assert_eq!(&text[cursor.span().unwrap()], "section");
+/// assert!(!cursor.goto_next_sibling());
+///
+/// assert!(cursor.goto_parent());
+/// assert!(cursor.goto_parent());
+/// assert!(!cursor.goto_parent());
+/// ```
+#[derive(Clone, Copy)]
+pub struct Cursor<'a, 'b> {
+    org: &'b Org<'a>,
+    node: NodeId,
+}
+
+impl<'a, 'b> Cursor<'a, 'b> {
+    /// Creates a new cursor pointing to `node`.
+    pub fn new(org: &'b Org<'a>, node: NodeId) -> Self {
+        Cursor { org, node }
+    }
+
+    /// Returns the node this cursor points to.
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// Returns the element this cursor points to.
+    pub fn element(&self) -> &'b Element<'a> {
+        &self.org[self.node]
+    }
+
+    /// Returns the byte range of the current element in the source text.
+    ///
+    /// See [`Org::span`](struct.Org.html#method.span) for more details.
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.org.span(self.node)
+    }
+
+    /// Moves this cursor to the first child of the current element.
+    ///
+    /// Returns `false` if the current element has no children.
+    pub fn goto_first_child(&mut self) -> bool {
+        self.goto(self.org.arena[self.node].first_child())
+    }
+
+    /// Moves this cursor to the next sibling of the current element.
+    ///
+    /// Returns `false` if the current element is the last child.
+    pub fn goto_next_sibling(&mut self) -> bool {
+        self.goto(self.org.arena[self.node].next_sibling())
+    }
+
+    /// Moves this cursor to the previous sibling of the current element.
+    ///
+    /// Returns `false` if the current element is the first child.
+    pub fn goto_previous_sibling(&mut self) -> bool {
+        self.goto(self.org.arena[self.node].previous_sibling())
+    }
+
+    /// Moves this cursor to the parent of the current element.
+    ///
+    /// Returns `false` if the current element is the root.
+    pub fn goto_parent(&mut self) -> bool {
+        self.goto(self.org.arena[self.node].parent())
+    }
+
+    fn goto(&mut self, node: Option<NodeId>) -> bool {
+        if let Some(node) = node {
+            self.node = node;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<'a> Org<'a> {
+    /// Returns a cursor pointing to the document.
+    pub fn cursor(&self) -> Cursor<'a, '_> {
+        Cursor::new(self, self.root)
+    }
+}
@@ -0,0 +1,79 @@
+use alloc::string::String;
+use core::fmt::Write;
+use indextree::NodeEdge;
+
+use crate::{elements::Element, Org};
+
+impl Org<'_> {
+    /// Returns the element tree as an indented text, for debugging.
+    ///
+    /// Each line shows an element's kind, its span in the source text if any,
+    /// and the values of texts, headline levels and pending contents of lazily
+    /// parsed documents.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse("* title\n*bold* text");
+    ///
+    /// assert_eq!(
+    ///     org.debug_tree(),
+    ///     r#"Document 0..19
+    ///   Headline 0..19 level=1
+    ///     Title 0..8
+    ///       Text 2..7 "title"
+    ///     Section 8..19
+    ///       Paragraph 8..19
+    ///         Bold 8..14
+    ///           Text 9..13 "bold"
+    ///         Text 14..19 " text"
+    /// "#
+    /// );
+    /// ```
+    pub fn debug_tree(&self) -> String {
+        let mut tree = String::new();
+        let mut depth = 0;
+
+        for edge in self.root.traverse(&self.arena) {
+            let node = match edge {
+                NodeEdge::Start(node) => node,
+                NodeEdge::End(_) => {
+                    depth -= 1;
+                    continue;
+                }
+            };
+
+            let element = &self[node];
+            let _ = write!(
+                tree,
+                "{:indent$}{:?}",
+                "",
+                element.kind(),
+                indent = depth * 2
+            );
+            if let Some(span) = self.span(node) {
+                let _ = write!(tree, " {:?}", span);
+            }
+            match element {
+                Element::Text { value } | Element::Verbatim { value } | Element::Code { value } => {
+                    let _ = write!(tree, " {:?}", value);
+                }
+                Element::Headline { level } => {
+                    let _ = write!(tree, " level={}", level);
+                }
+                Element::VerseLine { indent } => {
+                    let _ = write!(tree, " indent={}", indent);
+                }
+                _ => (),
+            }
+            if self.is_pending(node) {
+                tree.push_str(" (pending)");
+            }
+            tree.push('\n');
+
+            depth += 1;
+        }
+
+        tree
+    }
+}
@@ -0,0 +1,334 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+use indextree::NodeId;
+use memchr::memchr;
+
+use crate::elements::{emphasis::is_unicode_delimiter, Datetime, Element, Timestamp};
+use crate::Org;
+
+/// Recoverable issue in the source text
+///
+/// Malformed syntax is never rejected by the parser, e.g. a block without
+/// its end line just extends to the end of document, so these issues are
+/// reported afterwards instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Diagnostic {
+    /// Block or dynamic block without its end line
+    UnclosedBlock {
+        /// Expected end line, e.g. `#+END_SRC`
+        end: String,
+        span: Range<usize>,
+        at: NodeId,
+    },
+    /// Drawer without its `:END:` line
+    UnclosedDrawer {
+        name: String,
+        span: Range<usize>,
+        at: NodeId,
+    },
+    /// Emphasis marker which can't be matched with the following one
+    UnclosedEmphasis {
+        marker: char,
+        span: Range<usize>,
+        at: NodeId,
+    },
+    /// Timestamp with an out of range date or time
+    InvalidTimestamp { span: Range<usize>, at: NodeId },
+}
+
+impl Diagnostic {
+    /// Returns the byte range of this issue in the source text.
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            Diagnostic::UnclosedBlock { span, .. }
+            | Diagnostic::UnclosedDrawer { span, .. }
+            | Diagnostic::UnclosedEmphasis { span, .. }
+            | Diagnostic::InvalidTimestamp { span, .. } => span.clone(),
+        }
+    }
+
+    /// Returns the node this issue belongs to.
+    pub fn node(&self) -> NodeId {
+        match self {
+            Diagnostic::UnclosedBlock { at, .. }
+            | Diagnostic::UnclosedDrawer { at, .. }
+            | Diagnostic::UnclosedEmphasis { at, .. }
+            | Diagnostic::InvalidTimestamp { at, .. } => *at,
+        }
+    }
+
+    /// Returns a human-readable message of this issue.
+    pub fn message(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Diagnostic::UnclosedBlock { end, .. } => {
+                write!(f, "unclosed block, expected `{}`", end)
+            }
+            Diagnostic::UnclosedDrawer { name, .. } => {
+                write!(f, "unclosed drawer `{}`, expected `:END:`", name)
+            }
+            Diagnostic::UnclosedEmphasis { marker, .. } => {
+                write!(f, "unmatched emphasis marker `{}`", marker)
+            }
+            Diagnostic::InvalidTimestamp { .. } => write!(f, "invalid date or time in timestamp"),
+        }
+    }
+}
+
+impl Org<'_> {
+    /// Returns recoverable issues found in the source text.
+    ///
+    /// Elements without spans, e.g. ones inserted manually, and pending
+    /// contents of lazily parsed documents are skipped.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse("#+BEGIN_SRC rust\nfn main() {}\n");
+    /// let diagnostics = org.diagnostics();
+    ///
+    /// assert_eq!(diagnostics.len(), 1);
+    /// assert_eq!(diagnostics[0].span(), 0..16);
+    /// assert_eq!(diagnostics[0].message(), "unclosed block, expected `#+END_SRC`");
+    /// ```
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for at in self.root.descendants(&self.arena) {
+            let span = match self.span(at) {
+                Some(span) => span,
+                None => continue,
+            };
+
+            match &self[at] {
+                Element::SpecialBlock(_)
+                | Element::QuoteBlock(_)
+                | Element::CenterBlock(_)
+                | Element::VerseBlock(_)
+                | Element::CommentBlock(_)
+                | Element::ExampleBlock(_)
+                | Element::ExportBlock(_)
+                | Element::SourceBlock(_) => {
+                    let begin = first_line(&self.text, span.clone());
+                    let name = self.text[begin.clone()]
+                        .trim_start()
+                        .get("#+BEGIN_".len()..)
+                        .unwrap_or_default()
+                        .split(|c: char| !c.is_ascii_alphabetic())
+                        .next()
+                        .unwrap_or_default();
+                    let end = format!("#+END_{}", name);
+                    if !is_closed(&self.text[span], &end) {
+                        diagnostics.push(Diagnostic::UnclosedBlock {
+                            end,
+                            span: begin,
+                            at,
+                        });
+                    }
+                }
+                Element::DynBlock(_) if !is_closed(&self.text[span.clone()], "#+END:") => {
+                    diagnostics.push(Diagnostic::UnclosedBlock {
+                        end: "#+END:".into(),
+                        span: first_line(&self.text, span),
+                        at,
+                    });
+                }
+                Element::Drawer(drawer) if !is_closed(&self.text[span.clone()], ":END:") => {
+                    diagnostics.push(Diagnostic::UnclosedDrawer {
+                        name: drawer.name.to_string(),
+                        span: first_line(&self.text, span),
+                        at,
+                    });
+                }
+                Element::Title(title) => {
+                    // properties drawer is parsed as a part of title
+                    let mut lines = self.text[span.clone()].lines().map(str::trim);
+                    if lines.any(|line| line.eq_ignore_ascii_case(":PROPERTIES:"))
+                        && !lines.any(|line| line.eq_ignore_ascii_case(":END:"))
+                    {
+                        diagnostics.push(Diagnostic::UnclosedDrawer {
+                            name: "PROPERTIES".into(),
+                            span: first_line(&self.text, span.clone()),
+                            at,
+                        });
+                    }
+
+                    if let Some(planning) = &title.planning {
+                        let timestamps =
+                            [&planning.deadline, &planning.scheduled, &planning.closed];
+                        if timestamps
+                            .iter()
+                            .any(|t| t.as_ref().is_some_and(is_invalid))
+                        {
+                            diagnostics.push(Diagnostic::InvalidTimestamp { span, at });
+                        }
+                    }
+                }
+                Element::Timestamp(timestamp) if is_invalid(timestamp) => {
+                    diagnostics.push(Diagnostic::InvalidTimestamp { span, at });
+                }
+                Element::Clock(clock) if is_invalid(&clock.value()) => {
+                    diagnostics.push(Diagnostic::InvalidTimestamp { span, at });
+                }
+                Element::Text { .. } => self.unclosed_emphasis(at, span, &mut diagnostics),
+                _ => (),
+            }
+        }
+
+        diagnostics
+    }
+
+    fn unclosed_emphasis(&self, at: NodeId, span: Range<usize>, diagnostics: &mut Vec<Diagnostic>) {
+        let parent = match self.arena[at].parent() {
+            Some(parent) => parent,
+            None => return,
+        };
+
+        // markers can only be matched within the same container
+        let contents = match self.contents_span(parent) {
+            Some(contents) => contents,
+            None => return,
+        };
+
+        let bytes = self.text.as_bytes();
+
+        for i in span {
+            let marker = bytes[i];
+            if !b"*+/_=~".contains(&marker) {
+                continue;
+            }
+
+            let opening = (i == contents.start
+                || b" \t({'\"\n".contains(&bytes[i - 1])
+                || self.text[..i]
+                    .chars()
+                    .next_back()
+                    .is_some_and(is_unicode_delimiter))
+                && self.text[i + 1..contents.end]
+                    .chars()
+                    .next()
+                    .is_some_and(|c| !c.is_whitespace());
+
+            if opening && memchr(marker, &bytes[i + 1..contents.end]).is_some() {
+                diagnostics.push(Diagnostic::UnclosedEmphasis {
+                    marker: marker as char,
+                    span: i..i + 1,
+                    at,
+                });
+            }
+        }
+    }
+}
+
+/// Returns the range of the first line in `span`, without its line ending.
+fn first_line(text: &str, span: Range<usize>) -> Range<usize> {
+    let line = text[span.clone()].lines().next().unwrap_or_default();
+    span.start..span.start + line.trim_end().len()
+}
+
+/// Returns `true` if the last non-blank line in `text` is `end`.
+fn is_closed(text: &str, end: &str) -> bool {
+    let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+    // skips the first line, which can't be the end line
+    lines.next();
+    lines
+        .next_back()
+        .is_some_and(|line| line.eq_ignore_ascii_case(end))
+}
+
+fn is_invalid(timestamp: &Timestamp) -> bool {
+    match timestamp {
+        Timestamp::Active { start, .. } | Timestamp::Inactive { start, .. } => {
+            is_invalid_datetime(start)
+        }
+        Timestamp::ActiveRange { start, end, .. } | Timestamp::InactiveRange { start, end, .. } => {
+            is_invalid_datetime(start) || is_invalid_datetime(end)
+        }
+        Timestamp::Diary { .. } => false,
+    }
+}
+
+fn is_invalid_datetime(datetime: &Datetime) -> bool {
+    let days = match datetime.month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if datetime.year % 4 == 0 && (datetime.year % 100 != 0 || datetime.year % 400 == 0) => 29,
+        2 => 28,
+        _ => return true,
+    };
+
+    datetime.day == 0
+        || datetime.day > days
+        || datetime.hour.is_some_and(|hour| hour > 23)
+        || datetime.minute.is_some_and(|minute| minute > 59)
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn parse() {
+    let diagnostics = |text: &str| {
+        Org::parse(text)
+            .diagnostics()
+            .into_iter()
+            .map(|d| (d.span(), d.message()))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(diagnostics("#+BEGIN_QUOTE\nquote\n#+END_QUOTE\n"), vec![]);
+    assert_eq!(
+        diagnostics("#+begin_quote\nquote\n\n"),
+        vec![(0..13, "unclosed block, expected `#+END_quote`".into())]
+    );
+    assert_eq!(
+        diagnostics("#+BEGIN: clocktable\ntable\n"),
+        vec![(0..19, "unclosed block, expected `#+END:`".into())]
+    );
+    assert_eq!(diagnostics(":LOGBOOK:\n:END:"), vec![]);
+    assert_eq!(
+        diagnostics(":LOGBOOK:\nlog"),
+        vec![(0..9, "unclosed drawer `LOGBOOK`, expected `:END:`".into())]
+    );
+    assert_eq!(
+        diagnostics("* title\n:PROPERTIES:\n:ID: id\n"),
+        vec![(
+            0..7,
+            "unclosed drawer `PROPERTIES`, expected `:END:`".into()
+        )]
+    );
+    assert_eq!(
+        diagnostics("* title\n:PROPERTIES:\n:ID: id\n:END:\n"),
+        vec![]
+    );
+    assert_eq!(diagnostics("*bold* a * b 2*3 /a"), vec![]);
+    assert_eq!(diagnostics("「*粗体*」"), vec![]);
+    assert_eq!(
+        diagnostics("「*粗 *体"),
+        vec![(3..4, "unmatched emphasis marker `*`".into())]
+    );
+    assert_eq!(
+        diagnostics("a *b *c"),
+        vec![(2..3, "unmatched emphasis marker `*`".into())]
+    );
+    assert_eq!(diagnostics("<2019-02-28 Thu 23:59>"), vec![]);
+    assert_eq!(diagnostics("<2020-02-29 Sat>"), vec![]);
+    assert_eq!(
+        diagnostics("a <2019-02-29 Fri>"),
+        vec![(2..18, "invalid date or time in timestamp".into())]
+    );
+    assert_eq!(
+        diagnostics("[2019-01-01 Tue 24:00]"),
+        vec![(0..22, "invalid date or time in timestamp".into())]
+    );
+    assert_eq!(
+        diagnostics("* title\nSCHEDULED: <2019-13-01 Tue>\n").len(),
+        1
+    );
+}
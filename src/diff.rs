@@ -0,0 +1,317 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use indextree::NodeId;
+
+use crate::compat::HashMap;
+use crate::{Headline, Org};
+
+/// Kind of a change between two documents
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeKind {
+    /// Only exists in the new document
+    Added,
+    /// Only exists in the old document
+    Removed,
+    /// Exists in both documents with different contents. For headlines, it
+    /// means the title, planning or properties are different.
+    Modified,
+    /// Headline found by its `ID` property under a different outline path
+    Moved,
+}
+
+/// Change of a headline or an element between two documents
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub kind: ChangeKind,
+    /// Raw titles of the changed headline and its ancestors, or of the
+    /// headline containing the changed element. It's empty for elements
+    /// before the first headline.
+    pub path: Vec<String>,
+    /// Headline or element node in the old document
+    pub old: Option<NodeId>,
+    /// Headline or element node in the new document
+    pub new: Option<NodeId>,
+}
+
+impl Change {
+    /// Returns the outline path joined with `/`.
+    pub fn outline_path(&self) -> String {
+        self.path.join("/")
+    }
+}
+
+impl Org<'_> {
+    /// Compares this document with a newer version of it, and returns the
+    /// changed headlines and elements.
+    ///
+    /// Headlines are matched by their `ID` properties, or by their outline
+    /// paths otherwise. Children of added or removed headlines aren't
+    /// reported on their own. Elements directly inside matched headlines are
+    /// compared structurally, like
+    /// [`Org::subtree_eq`](struct.Org.html#method.subtree_eq).
+    ///
+    /// Changes are ordered by their positions in the new document, followed
+    /// by removed headlines in the old document.
+    ///
+    /// ```rust
+    /// # use orgize::{ChangeKind, Org};
+    /// #
+    /// let old = Org::parse("* a\nfirst\n\nsecond\n* b\n* c\n");
+    /// let new = Org::parse("* a\nfirst\n\nchanged\n* TODO b\n* d\n");
+    ///
+    /// let changes: Vec<_> = old
+    ///     .diff(&new)
+    ///     .iter()
+    ///     .map(|change| (change.kind, change.outline_path()))
+    ///     .collect();
+    ///
+    /// assert_eq!(
+    ///     changes,
+    ///     vec![
+    ///         (ChangeKind::Modified, "a".to_string()),
+    ///         (ChangeKind::Modified, "b".to_string()),
+    ///         (ChangeKind::Added, "d".to_string()),
+    ///         (ChangeKind::Removed, "c".to_string()),
+    ///     ]
+    /// );
+    /// ```
+    pub fn diff(&self, new: &Org) -> Vec<Change> {
+        let old_headlines = keyed_headlines(self);
+        let new_headlines = keyed_headlines(new);
+
+        let mut old_keys = HashMap::new();
+        for (i, (key, _, _)) in old_headlines.iter().enumerate() {
+            old_keys.entry(key).or_insert(i);
+        }
+
+        let mut changes = Vec::new();
+        diff_sections(
+            self,
+            self.document().section_node(),
+            new,
+            new.document().section_node(),
+            &[],
+            &mut changes,
+        );
+
+        let mut matched = Vec::with_capacity(new_headlines.len());
+        let mut old_matched = vec![false; old_headlines.len()];
+        for (key, path, headline) in &new_headlines {
+            let old_index = old_keys
+                .get(key)
+                .copied()
+                .filter(|&index| !old_matched[index]);
+            matched.push(old_index.is_some());
+
+            let (_, old_path, old_headline) = match old_index {
+                Some(index) => {
+                    old_matched[index] = true;
+                    &old_headlines[index]
+                }
+                None => {
+                    // children of added headlines are added as well
+                    if parent_matched(new, *headline, &new_headlines, &matched) {
+                        changes.push(Change {
+                            kind: ChangeKind::Added,
+                            path: path.clone(),
+                            old: None,
+                            new: Some(headline.headline_node()),
+                        });
+                    }
+                    continue;
+                }
+            };
+
+            let kind = if old_path[..old_path.len() - 1] != path[..path.len() - 1] {
+                Some(ChangeKind::Moved)
+            } else if self[old_headline.title_node()] != new[headline.title_node()] {
+                Some(ChangeKind::Modified)
+            } else {
+                None
+            };
+            if let Some(kind) = kind {
+                changes.push(Change {
+                    kind,
+                    path: path.clone(),
+                    old: Some(old_headline.headline_node()),
+                    new: Some(headline.headline_node()),
+                });
+            }
+
+            diff_sections(
+                self,
+                old_headline.section_node(),
+                new,
+                headline.section_node(),
+                path,
+                &mut changes,
+            );
+        }
+
+        for (i, (_, path, headline)) in old_headlines.iter().enumerate() {
+            if !old_matched[i] && parent_matched(self, *headline, &old_headlines, &old_matched) {
+                changes.push(Change {
+                    kind: ChangeKind::Removed,
+                    path: path.clone(),
+                    old: Some(headline.headline_node()),
+                    new: None,
+                });
+            }
+        }
+
+        changes
+    }
+}
+
+/// Key for matching headlines between documents
+#[derive(PartialEq, Eq, Hash)]
+enum Key {
+    Id(String),
+    /// Outline path, and the number of preceding headlines with the same path
+    Path(Vec<String>, usize),
+}
+
+/// Returns headlines of `org` with their keys and outline paths, in
+/// document order.
+fn keyed_headlines(org: &Org) -> Vec<(Key, Vec<String>, Headline)> {
+    let mut headlines: Vec<(Key, Vec<String>, Headline)> = Vec::new();
+    let mut occurrences = HashMap::new();
+
+    for headline in org.headlines() {
+        let mut path = match headline.parent(org) {
+            Some(parent) => headlines
+                .iter()
+                .find(|(_, _, h)| h.headline_node() == parent.headline_node())
+                .map(|(_, path, _)| path.clone())
+                .unwrap_or_default(),
+            None => Vec::new(),
+        };
+        path.push(headline.title(org).raw.to_string());
+
+        let key = match headline.id(org) {
+            Some(id) => Key::Id(id.to_string()),
+            None => {
+                let occurrence = occurrences.entry(path.clone()).or_insert(0);
+                *occurrence += 1;
+                Key::Path(path.clone(), *occurrence - 1)
+            }
+        };
+
+        headlines.push((key, path, headline));
+    }
+
+    headlines
+}
+
+/// Returns `true` if the parent of `headline` is matched, or it's a
+/// top-level headline.
+fn parent_matched(
+    org: &Org,
+    headline: Headline,
+    headlines: &[(Key, Vec<String>, Headline)],
+    matched: &[bool],
+) -> bool {
+    match headline.parent(org) {
+        Some(parent) => headlines
+            .iter()
+            .zip(matched)
+            .all(|((_, _, h), &matched)| matched || h.headline_node() != parent.headline_node()),
+        None => true,
+    }
+}
+
+/// Compares the children of two sections, pairing equal elements by their
+/// longest common subsequence.
+fn diff_sections(
+    old: &Org,
+    old_section: Option<NodeId>,
+    new: &Org,
+    new_section: Option<NodeId>,
+    path: &[String],
+    changes: &mut Vec<Change>,
+) {
+    let old_nodes: Vec<_> = old_section
+        .into_iter()
+        .flat_map(|section| section.children(&old.arena))
+        .collect();
+    let new_nodes: Vec<_> = new_section
+        .into_iter()
+        .flat_map(|section| section.children(&new.arena))
+        .collect();
+
+    // lengths[i][j] is the length of the longest common subsequence of
+    // old_nodes[i..] and new_nodes[j..]
+    let mut lengths = vec![vec![0; new_nodes.len() + 1]; old_nodes.len() + 1];
+    for i in (0..old_nodes.len()).rev() {
+        for j in (0..new_nodes.len()).rev() {
+            lengths[i][j] = if old.subtree_eq_with(old_nodes[i], new, new_nodes[j]) {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let (mut removed, mut added) = (Vec::new(), Vec::new());
+    while i < old_nodes.len() || j < new_nodes.len() {
+        if i < old_nodes.len()
+            && j < new_nodes.len()
+            && lengths[i][j] == lengths[i + 1][j + 1] + 1
+            && old.subtree_eq_with(old_nodes[i], new, new_nodes[j])
+        {
+            push_changes(old, &mut removed, new, &mut added, path, changes);
+            i += 1;
+            j += 1;
+        } else if j < new_nodes.len()
+            && (i == old_nodes.len() || lengths[i][j + 1] >= lengths[i + 1][j])
+        {
+            added.push(new_nodes[j]);
+            j += 1;
+        } else {
+            removed.push(old_nodes[i]);
+            i += 1;
+        }
+    }
+    push_changes(old, &mut removed, new, &mut added, path, changes);
+}
+
+/// Reports elements between two common elements. Removed and added elements
+/// of the same kind at the same offset are reported as modified.
+fn push_changes(
+    old: &Org,
+    removed: &mut Vec<NodeId>,
+    new: &Org,
+    added: &mut Vec<NodeId>,
+    path: &[String],
+    changes: &mut Vec<Change>,
+) {
+    let change = |kind, old, new| Change {
+        kind,
+        path: path.to_vec(),
+        old,
+        new,
+    };
+
+    let mut unpaired = Vec::new();
+    for (k, &new_node) in added.iter().enumerate() {
+        match removed.get(k) {
+            Some(&old_node) if old[old_node].kind() == new[new_node].kind() => {
+                changes.push(change(ChangeKind::Modified, Some(old_node), Some(new_node)));
+            }
+            Some(&old_node) => {
+                unpaired.push(old_node);
+                changes.push(change(ChangeKind::Added, None, Some(new_node)));
+            }
+            None => changes.push(change(ChangeKind::Added, None, Some(new_node))),
+        }
+    }
+    for &old_node in unpaired.iter().chain(removed.iter().skip(added.len())) {
+        changes.push(change(ChangeKind::Removed, Some(old_node), None));
+    }
+
+    removed.clear();
+    added.clear();
+}
@@ -0,0 +1,198 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt;
+use core::iter::Sum;
+use core::ops::{Add, AddAssign};
+
+/// Duration in minutes, as written in `Effort` properties and clock lines
+///
+/// ```rust
+/// # use orgize::Duration;
+/// #
+/// let duration = Duration::parse("1:30").unwrap();
+///
+/// assert_eq!(duration.minutes(), 90);
+/// assert_eq!((duration + Duration::from_minutes(35)).to_string(), "2:05");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Duration {
+    minutes: u64,
+}
+
+impl Duration {
+    pub fn from_minutes(minutes: u64) -> Duration {
+        Duration { minutes }
+    }
+
+    pub fn minutes(self) -> u64 {
+        self.minutes
+    }
+
+    /// Parses a duration written as `H:MM` or `H:MM:SS`, in which seconds
+    /// are ignored, or as a sequence of numbers with units like `2d 4h`,
+    /// optionally followed by `H:MM`.
+    ///
+    /// Units are `min`, `h`, `d`, `w`, `m` (30 days) and `y` (365 days), and
+    /// numbers may have fractions, e.g. `1.5h`.
+    ///
+    /// ```rust
+    /// # use orgize::Duration;
+    /// #
+    /// assert_eq!(Duration::parse("2d 4h").unwrap().minutes(), 3120);
+    /// assert_eq!(Duration::parse("1w").unwrap().minutes(), 10080);
+    /// assert_eq!(Duration::parse("1d 1:30").unwrap().minutes(), 1530);
+    /// ```
+    pub fn parse(input: &str) -> Option<Duration> {
+        let mut input = input.trim();
+        let mut total = 0;
+
+        while let Some((minutes, rest)) = parse_unit(input) {
+            total += minutes;
+            input = rest.trim_start();
+            if input.is_empty() {
+                return Some(Duration::from_minutes(total));
+            }
+        }
+
+        Some(Duration::from_minutes(total + parse_clock(input)?))
+    }
+
+    /// Formats like org-mode's default duration format, which writes days
+    /// separately, e.g. `1d 2:05`.
+    ///
+    /// ```rust
+    /// # use orgize::Duration;
+    /// #
+    /// assert_eq!(Duration::from_minutes(1565).format_with_days(), "1d 2:05");
+    /// assert_eq!(Duration::from_minutes(125).format_with_days(), "2:05");
+    /// ```
+    pub fn format_with_days(self) -> String {
+        let (days, minutes) = (
+            self.minutes / 1440,
+            Duration::from_minutes(self.minutes % 1440),
+        );
+        if days > 0 {
+            format!("{}d {}", days, minutes)
+        } else {
+            minutes.to_string()
+        }
+    }
+}
+
+/// Parses `H:MM` or `H:MM:SS`, returning minutes.
+fn parse_clock(input: &str) -> Option<u64> {
+    let mut parts = input.split(':');
+    let hours: u64 = parse_digits(parts.next()?)?;
+    let minutes: u64 = parse_digits(parts.next()?)?;
+    if let Some(seconds) = parts.next() {
+        parse_digits(seconds)?;
+    }
+    if parts.next().is_some() || minutes > 59 {
+        return None;
+    }
+
+    Some(hours * 60 + minutes)
+}
+
+/// Parses a number followed by a unit, returning minutes and the rest.
+fn parse_unit(input: &str) -> Option<(u64, &str)> {
+    let end = input
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(input.len());
+    let (number, rest) = input.split_at(end);
+
+    let unit_len = rest
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(rest.len());
+    let factor = match &rest[..unit_len] {
+        "min" => 1,
+        "h" => 60,
+        "d" => 1440,
+        "w" => 10080,
+        "m" => 43200,
+        "y" => 525_600,
+        _ => return None,
+    };
+
+    let (integer, fraction) = match number.find('.') {
+        Some(dot) => (&number[..dot], &number[dot + 1..]),
+        None => (number, ""),
+    };
+    let mut minutes = if integer.is_empty() {
+        0
+    } else {
+        parse_digits(integer)? * factor
+    };
+    if !fraction.is_empty() {
+        let fraction = fraction.get(..6).unwrap_or(fraction);
+        minutes += parse_digits(fraction)? * factor / 10u64.pow(fraction.len() as u32);
+    } else if integer.is_empty() {
+        return None;
+    }
+
+    Some((minutes, &rest[unit_len..]))
+}
+
+fn parse_digits(input: &str) -> Option<u64> {
+    if input.is_empty() || !input.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    input.parse().ok()
+}
+
+impl fmt::Display for Duration {
+    /// Formats as `H:MM`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{:02}", self.minutes / 60, self.minutes % 60)
+    }
+}
+
+impl Add for Duration {
+    type Output = Duration;
+
+    fn add(self, other: Duration) -> Duration {
+        Duration::from_minutes(self.minutes + other.minutes)
+    }
+}
+
+impl AddAssign for Duration {
+    fn add_assign(&mut self, other: Duration) {
+        self.minutes += other.minutes;
+    }
+}
+
+impl Sum for Duration {
+    fn sum<I: Iterator<Item = Duration>>(iter: I) -> Duration {
+        iter.fold(Duration::default(), Add::add)
+    }
+}
+
+#[test]
+fn parse() {
+    assert_eq!(Duration::parse("0:45"), Some(Duration::from_minutes(45)));
+    assert_eq!(
+        Duration::parse(" 12:05:30 "),
+        Some(Duration::from_minutes(725))
+    );
+    assert_eq!(Duration::parse("1:60"), None);
+    assert_eq!(Duration::parse("1:-1"), None);
+    assert_eq!(Duration::parse("90"), None);
+    assert_eq!(Duration::from_minutes(1500).to_string(), "25:00");
+
+    assert_eq!(Duration::parse("1h30min"), Some(Duration::from_minutes(90)));
+    assert_eq!(Duration::parse("1.5h"), Some(Duration::from_minutes(90)));
+    assert_eq!(Duration::parse(".5d"), Some(Duration::from_minutes(720)));
+    assert_eq!(
+        Duration::parse("1y 2m 3w"),
+        Some(Duration::from_minutes(525_600 + 86400 + 30240))
+    );
+    assert_eq!(
+        Duration::parse("3d 0:05"),
+        Some(Duration::from_minutes(4325))
+    );
+    assert_eq!(Duration::parse("1:30 2h"), None);
+    assert_eq!(Duration::parse("2x"), None);
+    assert_eq!(Duration::parse("h"), None);
+    assert_eq!(Duration::parse("1.h"), Some(Duration::from_minutes(60)));
+    assert_eq!(Duration::parse(""), None);
+}
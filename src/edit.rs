@@ -0,0 +1,268 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::mem::{replace, take};
+use core::ops::Range;
+use indextree::{Arena, NodeEdge, NodeId};
+
+use crate::{
+    compat::OnceLock,
+    config::{ParseConfig, DEFAULT_CONFIG},
+    elements::Element,
+    parsers::{parse_container, skip_empty_lines, Container},
+    Org,
+};
+
+impl<'a> Org<'a> {
+    /// Replaces the given byte range of the source text with `text`, and
+    /// reparses the affected part of the document.
+    ///
+    /// Only the top-level sections and headlines touched by this edit are
+    /// reparsed, while other elements keep their `NodeId`s and have their
+    /// spans shifted. If the edit changes the document structure around it,
+    /// e.g. turns a headline into a paragraph, the whole document is
+    /// reparsed instead.
+    ///
+    /// Elements which weren't parsed from the source text, e.g. created using
+    /// the mutable API, may be discarded by this method.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds or not on character boundaries.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let mut org = Org::parse("* foo\nbar\n* baz\n");
+    ///
+    /// let baz = org.headlines().nth(1).unwrap().headline_node();
+    /// org.edit(2..5, "qux");
+    ///
+    /// assert_eq!(org.headlines().next().unwrap().title(&org).raw, "qux");
+    /// assert_eq!(org.headlines().nth(1).unwrap().headline_node(), baz);
+    /// assert_eq!(org.span(baz), Some(10..16));
+    /// ```
+    pub fn edit(&mut self, range: Range<usize>, text: &str) {
+        self.edit_custom(range, text, &DEFAULT_CONFIG);
+    }
+
+    /// Replaces the given byte range of the source text with `text`, and
+    /// reparses the affected part of the document with custom `ParseConfig`.
+    ///
+    /// See [`Org::edit`](#method.edit) for more details.
+    pub fn edit_custom(&mut self, range: Range<usize>, text: &str, config: &ParseConfig) {
+        // pending contents must be parsed from the original text
+        self.expand_all();
+
+        let mut new_text = String::with_capacity(self.text.len() - range.len() + text.len());
+        new_text.push_str(&self.text[0..range.start]);
+        new_text.push_str(text);
+        new_text.push_str(&self.text[range.end..]);
+
+        // trailing text not covered by any span may be merged into the last
+        // child, so edits touching it are left to a full reparse
+        let children = self.edited_children(&range).filter(|(_, last)| {
+            self.spans[last].end >= range.end
+                && (self.arena[*last].next_sibling().is_some()
+                    || self.spans[last].end == self.text.len())
+        });
+
+        if let Some((first, last)) = children {
+            let start = self.spans[&first].start;
+            let end = self.spans[&last].end + text.len() - range.len();
+
+            let mut org = Org::with_text(Cow::Borrowed(&new_text), 0);
+            let root = org.root;
+
+            parse_container(
+                &mut org,
+                Container::Document {
+                    content: &new_text[start..end],
+                    node: root,
+                },
+                config,
+            );
+
+            if self.can_replace(first, last, &org, &new_text[start..end]) {
+                let old_end = self.spans[&last].end;
+                let next = self.arena[last].next_sibling();
+
+                let mut node = Some(first);
+                while let Some(child) = node {
+                    node = self.arena[child].next_sibling();
+                    self.remove_subtree(child);
+                    if child == last {
+                        break;
+                    }
+                }
+
+                let delta = text.len() as isize - range.len() as isize;
+                for span in self
+                    .spans
+                    .values_mut()
+                    .chain(self.contents_spans.values_mut())
+                {
+                    if span.start >= old_end {
+                        span.start = (span.start as isize + delta) as usize;
+                    }
+                    if span.end >= old_end {
+                        span.end = (span.end as isize + delta) as usize;
+                    }
+                }
+
+                self.graft(org, self.root, next, |e| e.into_owned());
+                self.text = Cow::Owned(new_text);
+                self.line_starts = OnceLock::new();
+                self.expand_links();
+                self.handle_keywords(config);
+                self.debug_validate();
+                return;
+            }
+        }
+
+        self.reparse(new_text, config);
+    }
+
+    /// Returns the first and last top-level sections or headlines which
+    /// may be affected by editing `range`.
+    fn edited_children(&self, range: &Range<usize>) -> Option<(NodeId, NodeId)> {
+        let mut first = None;
+        let mut last = None;
+
+        for child in self.root.children(&self.arena) {
+            let span = self.spans.get(&child)?;
+            // an edit at the beginning of an element may also affect the
+            // previous one, e.g. appending a paragraph to its section
+            if span.start < range.start || (first.is_none() && span.start == range.start) {
+                first = Some(child);
+            }
+            if span.start <= range.end {
+                last = Some(child);
+            }
+        }
+
+        Some((first?, last?))
+    }
+
+    /// Returns `true` if the elements reparsed in `org` can replace the
+    /// children from `first` to `last` without changing their neighbors.
+    fn can_replace(&self, first: NodeId, last: NodeId, org: &Org, content: &str) -> bool {
+        let prev = headline_level(&self.arena, self.arena[first].previous_sibling());
+        let next = headline_level(&self.arena, self.arena[last].next_sibling());
+        let new_first = org.arena[org.root].first_child();
+        let new_last = headline_level(&org.arena, org.arena[org.root].last_child());
+
+        // leading blank lines belong to the previous element
+        if new_first.is_none() || skip_empty_lines(content).len() != content.len() {
+            return false;
+        }
+
+        match (
+            self.arena[first].previous_sibling(),
+            headline_level(&org.arena, new_first),
+        ) {
+            (None, _) => (),
+            // a section can only be the first child
+            (Some(_), None) => return false,
+            // a headline contains all following headlines of higher levels
+            (Some(_), Some(new_first)) => {
+                if prev.map(|prev| new_first > prev).unwrap_or(false) {
+                    return false;
+                }
+            }
+        }
+
+        if let Some(next) = next {
+            if !content.ends_with('\n') || new_last.map(|l| next > l).unwrap_or(false) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Removes `node` and its descendants from the arena.
+    fn remove_subtree(&mut self, node: NodeId) {
+        let nodes: Vec<_> = node.descendants(&self.arena).collect();
+        for node in nodes.into_iter().rev() {
+            self.spans.remove(&node);
+            self.contents_spans.remove(&node);
+            node.remove(&mut self.arena);
+        }
+    }
+
+    /// Moves the children of `org`'s root into this `Org` struct, as children
+    /// of `parent` before `next`, converting each element with `convert`.
+    pub(crate) fn graft<'b>(
+        &mut self,
+        mut org: Org<'b>,
+        parent: NodeId,
+        next: Option<NodeId>,
+        convert: impl Fn(Element<'b>) -> Element<'a>,
+    ) {
+        let edges: Vec<_> = org.root.traverse(&org.arena).collect();
+        let mut parents: Vec<NodeId> = Vec::new();
+
+        for edge in edges {
+            match edge {
+                NodeEdge::Start(node) if node != org.root => {
+                    let element = replace(&mut org[node], Element::Section);
+                    let new = self.arena.new_node(convert(element));
+
+                    match (parents.last(), next) {
+                        (Some(parent), _) => parent.append(new, &mut self.arena),
+                        (None, Some(next)) => next.insert_before(new, &mut self.arena),
+                        (None, None) => parent.append(new, &mut self.arena),
+                    }
+
+                    if let Some(span) = org.spans.remove(&node) {
+                        self.spans.insert(new, span);
+                    }
+                    if let Some(span) = org.contents_spans.remove(&node) {
+                        self.contents_spans.insert(new, span);
+                    }
+
+                    parents.push(new);
+                }
+                NodeEdge::End(node) if node != org.root => {
+                    parents.pop();
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Reparses the whole document from `text`.
+    pub(crate) fn reparse(&mut self, text: String, config: &ParseConfig) {
+        let mut org = Org::parse_custom(&text, config);
+
+        let pre_blank = match org[org.root] {
+            Element::Document { pre_blank } => pre_blank,
+            _ => 0,
+        };
+
+        let extensions = if config.keyword_handlers.is_empty() {
+            take(&mut self.extensions)
+        } else {
+            take(&mut org.extensions)
+        };
+
+        *self = Org::with_text(Cow::Borrowed(""), pre_blank);
+        self.extensions = extensions;
+        self.spans.insert(self.root, 0..text.len());
+        if let Some(span) = org.contents_spans.get(&org.root) {
+            self.contents_spans.insert(self.root, span.clone());
+        }
+
+        let root = self.root;
+        self.graft(org, root, None, |e| e.into_owned());
+        self.text = Cow::Owned(text);
+    }
+}
+
+fn headline_level(arena: &Arena<Element>, node: Option<NodeId>) -> Option<usize> {
+    match arena[node?].get() {
+        Element::Headline { level } => Some(*level),
+        _ => None,
+    }
+}
@@ -0,0 +1,106 @@
+use alloc::vec::Vec;
+
+use crate::elements::Title;
+use crate::{Duration, Headline, Org};
+
+/// Estimated effort and clocked time of a subtree
+#[derive(Debug)]
+pub struct EffortSummary<'a, 'b> {
+    pub headline: Headline,
+    pub title: &'b Title<'a>,
+    /// Effort of this headline itself
+    pub effort: Option<Duration>,
+    /// Sum of the children's total efforts, or this headline's own effort
+    /// if none of its descendants has one, like `{:}` in column view
+    pub total_effort: Duration,
+    /// Clocked time of this headline and its descendants
+    pub clocked: Duration,
+}
+
+impl Headline {
+    /// Returns the parsed `Effort` property of this headline.
+    ///
+    /// ```rust
+    /// # use orgize::{Duration, Org};
+    /// #
+    /// let org = Org::parse("* task\n:PROPERTIES:\n:Effort: 1:30\n:END:\n");
+    /// let headline = org.headlines().next().unwrap();
+    ///
+    /// assert_eq!(headline.effort(&org), Some(Duration::from_minutes(90)));
+    /// ```
+    pub fn effort(self, org: &Org) -> Option<Duration> {
+        self.title(org)
+            .properties
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("Effort"))
+            .and_then(|(_, value)| Duration::parse(value))
+    }
+}
+
+impl<'a> Org<'a> {
+    /// Sums the estimated efforts and clocked time of every subtree,
+    /// returning a summary for each headline in document order.
+    ///
+    /// ```rust
+    /// # use orgize::{Duration, Org};
+    /// #
+    /// let org = Org::parse(
+    ///     "* project\n\
+    ///      ** design\n:PROPERTIES:\n:Effort: 2:00\n:END:\n\
+    ///      CLOCK: [2023-01-02 Mon 09:00]--[2023-01-02 Mon 10:30] =>  1:30\n\
+    ///      ** build\n:PROPERTIES:\n:Effort: 4:30\n:END:\n",
+    /// );
+    /// let summaries = org.effort_rollup();
+    ///
+    /// assert_eq!(summaries[0].total_effort.to_string(), "6:30");
+    /// assert_eq!(summaries[0].clocked.to_string(), "1:30");
+    /// ```
+    pub fn effort_rollup<'b>(&'b self) -> Vec<EffortSummary<'a, 'b>> {
+        let mut summaries = Vec::new();
+        for headline in self.document().children(self) {
+            self.effort_summary(headline, &mut summaries);
+        }
+        summaries
+    }
+
+    /// Pushes summaries of this headline and its descendants, and returns
+    /// whether any of them has an effort.
+    fn effort_summary<'b>(
+        &'b self,
+        headline: Headline,
+        summaries: &mut Vec<EffortSummary<'a, 'b>>,
+    ) -> bool {
+        let effort = headline.effort(self);
+        let clocked = headline.section_node().map_or(0, |section| {
+            self.clocked_minutes(section, &(i64::MIN..i64::MAX))
+        });
+
+        let index = summaries.len();
+        summaries.push(EffortSummary {
+            headline,
+            title: headline.title(self),
+            effort,
+            total_effort: Duration::default(),
+            clocked: Duration::from_minutes(clocked),
+        });
+
+        let mut children_effort = Duration::default();
+        let mut has_effort = false;
+        for child in headline.children(self) {
+            let child_index = summaries.len();
+            if self.effort_summary(child, summaries) {
+                has_effort = true;
+                children_effort += summaries[child_index].total_effort;
+            }
+            let clocked = summaries[child_index].clocked;
+            summaries[index].clocked += clocked;
+        }
+
+        summaries[index].total_effort = if has_effort {
+            children_effort
+        } else {
+            effort.unwrap_or_default()
+        };
+        has_effort || effort.is_some()
+    }
+}
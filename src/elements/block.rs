@@ -1,4 +1,7 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
 
 use nom::{
     bytes::complete::tag_no_case,
@@ -11,8 +14,7 @@ use nom::{
 use crate::parsers::{blank_lines, line, take_lines_while};
 
 /// Special Block Element
-#[derive(Debug)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 pub struct SpecialBlock<'a> {
     /// Block parameters
@@ -40,8 +42,7 @@ impl SpecialBlock<'_> {
 }
 
 /// Quote Block Element
-#[derive(Debug)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 pub struct QuoteBlock<'a> {
     /// Optional block parameters
@@ -66,8 +67,7 @@ impl QuoteBlock<'_> {
 }
 
 /// Center Block Element
-#[derive(Debug)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 pub struct CenterBlock<'a> {
     /// Optional block parameters
@@ -92,8 +92,7 @@ impl CenterBlock<'_> {
 }
 
 /// Verse Block Element
-#[derive(Debug)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 pub struct VerseBlock<'a> {
     /// Optional block parameters
@@ -118,8 +117,7 @@ impl VerseBlock<'_> {
 }
 
 /// Comment Block Element
-#[derive(Debug)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 pub struct CommentBlock<'a> {
     #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
@@ -142,13 +140,13 @@ impl CommentBlock<'_> {
 }
 
 /// Example Block Element
-#[derive(Debug)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 pub struct ExampleBlock<'a> {
     #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
     pub data: Option<Cow<'a, str>>,
-    ///  Block contents
+    /// Block contents, in which commas escaping lines starting with `*` or
+    /// `#+` are removed
     pub contents: Cow<'a, str>,
     /// Numbers of blank lines between last block's line and next non-blank line
     /// or buffer's end
@@ -166,12 +164,12 @@ impl ExampleBlock<'_> {
 }
 
 /// Export Block Element
-#[derive(Debug)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 pub struct ExportBlock<'a> {
     pub data: Cow<'a, str>,
-    ///  Block contents
+    /// Block contents, in which commas escaping lines starting with `*` or
+    /// `#+` are removed
     pub contents: Cow<'a, str>,
     /// Numbers of blank lines between last block's line and next non-blank line
     /// or buffer's end
@@ -189,11 +187,11 @@ impl ExportBlock<'_> {
 }
 
 /// Src Block Element
-#[derive(Debug, Default)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Default, PartialEq)]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 pub struct SourceBlock<'a> {
-    ///  Block contents
+    /// Block contents, in which commas escaping lines starting with `*` or
+    /// `#+` are removed
     pub contents: Cow<'a, str>,
     /// Language of the code in the block
     pub language: Cow<'a, str>,
@@ -213,14 +211,248 @@ impl SourceBlock<'_> {
         }
     }
 
-    // TODO: fn number_lines() -> Some(New) | Some(Continued) | None {  }
-    // TODO: fn preserve_indent() -> bool {  }
-    // TODO: fn use_labels() -> bool {  }
-    // TODO: fn label_fmt() -> Option<String> {  }
-    // TODO: fn retain_labels() -> bool {  }
+    /// Returns how lines of this block are numbered, set by the `-n` or
+    /// `+n` switch.
+    ///
+    /// ```rust
+    /// # use orgize::elements::{NumberLines, SourceBlock};
+    /// #
+    /// let block = SourceBlock {
+    ///     contents: "fn main() {}\n".into(),
+    ///     language: "rust".into(),
+    ///     arguments: "-n 10 -i :tangle main.rs".into(),
+    ///     post_blank: 0,
+    /// };
+    ///
+    /// assert_eq!(block.number_lines(), Some(NumberLines::New(10)));
+    /// assert!(block.preserve_indent());
+    /// ```
+    pub fn number_lines(&self) -> Option<NumberLines> {
+        number_lines(&self.arguments)
+    }
+
+    /// Returns `true` if the indentation of contents is preserved, i.e. the
+    /// `-i` switch is set.
+    pub fn preserve_indent(&self) -> bool {
+        switches(&self.arguments).any(|switch| switch == "-i")
+    }
+
+    /// Returns the format of coderef labels, set by the `-l` switch, or
+    /// `(ref:%s)` by default.
+    pub fn label_fmt(&self) -> &str {
+        label_fmt(&self.arguments)
+    }
+
+    /// Returns `false` if coderef labels are removed from exported code,
+    /// i.e. the `-r` switch is set.
+    pub fn retain_labels(&self) -> bool {
+        retain_labels(&self.arguments)
+    }
+
+    /// Returns `true` if links to coderefs show their labels rather than
+    /// line numbers.
+    pub fn use_labels(&self) -> bool {
+        use_labels(&self.arguments)
+    }
+
+    /// Returns coderef labels of this block with their line numbers,
+    /// starting from one.
+    ///
+    /// ```rust
+    /// # use orgize::elements::SourceBlock;
+    /// #
+    /// let block = SourceBlock {
+    ///     contents: "a = 1\nb = a + 1  (ref:inc)\nprint(b) <<out>>\n".into(),
+    ///     language: "python".into(),
+    ///     arguments: r#"-r -l "<<%s>>" :results output"#.into(),
+    ///     post_blank: 0,
+    /// };
+    ///
+    /// assert_eq!(block.label_fmt(), "<<%s>>");
+    /// assert!(!block.retain_labels());
+    /// assert_eq!(block.coderefs(), vec![(3, "out")]);
+    /// ```
+    pub fn coderefs(&self) -> Vec<(usize, &str)> {
+        coderefs(&self.contents, self.label_fmt())
+    }
+}
+
+impl ExampleBlock<'_> {
+    /// Returns how lines of this block are numbered, set by the `-n` or
+    /// `+n` switch.
+    pub fn number_lines(&self) -> Option<NumberLines> {
+        number_lines(self.data.as_deref().unwrap_or_default())
+    }
+
+    /// Returns `true` if the indentation of contents is preserved, i.e. the
+    /// `-i` switch is set.
+    pub fn preserve_indent(&self) -> bool {
+        switches(self.data.as_deref().unwrap_or_default()).any(|switch| switch == "-i")
+    }
+
+    /// Returns the format of coderef labels, set by the `-l` switch, or
+    /// `(ref:%s)` by default.
+    pub fn label_fmt(&self) -> &str {
+        label_fmt(self.data.as_deref().unwrap_or_default())
+    }
+
+    /// Returns `false` if coderef labels are removed from exported code,
+    /// i.e. the `-r` switch is set.
+    pub fn retain_labels(&self) -> bool {
+        retain_labels(self.data.as_deref().unwrap_or_default())
+    }
+
+    /// Returns `true` if links to coderefs show their labels rather than
+    /// line numbers.
+    pub fn use_labels(&self) -> bool {
+        use_labels(self.data.as_deref().unwrap_or_default())
+    }
+
+    /// Returns coderef labels of this block with their line numbers,
+    /// starting from one.
+    pub fn coderefs(&self) -> Vec<(usize, &str)> {
+        coderefs(&self.contents, self.label_fmt())
+    }
+}
+
+/// Line numbering of a source or example block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberLines {
+    /// Numbering starting from this number, set by `-n` or `-n 10`
+    New(usize),
+    /// Numbering continued from the previous numbered block, adding this
+    /// offset, set by `+n` or `+n 10`
+    Continued(usize),
+}
+
+/// Returns switches of block arguments, which come before header arguments.
+fn switches(arguments: &str) -> impl Iterator<Item = &str> {
+    arguments
+        .split_whitespace()
+        .take_while(|arg| !arg.starts_with(':'))
+}
+
+fn number_lines(arguments: &str) -> Option<NumberLines> {
+    let mut switches = switches(arguments).peekable();
+    while let Some(switch) = switches.next() {
+        let number = switches.peek().and_then(|number| number.parse().ok());
+        match switch {
+            "-n" => return Some(NumberLines::New(number.unwrap_or(1))),
+            "+n" => return Some(NumberLines::Continued(number.unwrap_or(0))),
+            _ => (),
+        }
+    }
+    None
+}
+
+fn label_fmt(arguments: &str) -> &str {
+    arguments
+        .find("-l \"")
+        .map(|i| &arguments[i + 4..])
+        .and_then(|fmt| fmt.split_once('"'))
+        .map(|(fmt, _)| fmt)
+        .filter(|fmt| fmt.contains("%s"))
+        .unwrap_or("(ref:%s)")
+}
+
+fn retain_labels(arguments: &str) -> bool {
+    !switches(arguments).any(|switch| switch == "-r")
+        || (switches(arguments).any(|switch| switch == "-n" || switch == "+n")
+            && switches(arguments).any(|switch| switch == "-k"))
+}
+
+fn use_labels(arguments: &str) -> bool {
+    retain_labels(arguments) && !switches(arguments).any(|switch| switch == "-k")
+}
+
+fn coderefs<'a>(contents: &'a str, fmt: &str) -> Vec<(usize, &'a str)> {
+    contents
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| Some((i + 1, coderef(line, fmt)?.1)))
+        .collect()
+}
+
+/// Finds the coderef label at the end of `line`, and returns the position
+/// where the label and whitespaces before it start, with the label.
+pub(crate) fn coderef<'a>(line: &'a str, fmt: &str) -> Option<(usize, &'a str)> {
+    let (prefix, suffix) = fmt.split_once("%s")?;
+    let rest = line.trim_end().strip_suffix(suffix)?;
+    let start = rest.rfind(prefix)?;
+    let label = &rest[start + prefix.len()..];
+
+    if label.is_empty()
+        || !label
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == ' ')
+    {
+        return None;
+    }
+
+    Some((line[..start].trim_end().len(), label))
 }
 
 #[inline]
+/// Returns the position of the comma escaping `line`, i.e. the comma
+/// before `*` or `#+` after leading whitespaces and commas.
+fn escaping_comma(line: &str) -> Option<usize> {
+    let trimmed = line.trim_start_matches([' ', '\t']);
+    let rest = trimmed.trim_start_matches(',');
+    if rest.starts_with('*') || rest.starts_with("#+") {
+        Some(line.len() - trimmed.len())
+    } else {
+        None
+    }
+}
+
+/// Removes commas escaping lines starting with `*` or `#+` in block contents,
+/// like `org-unescape-code-in-string`.
+pub(crate) fn unescape(contents: &str) -> Cow<'_, str> {
+    let escaped = |line: &str| escaping_comma(line).filter(|&i| line[i..].starts_with(','));
+    if !contents
+        .split_inclusive('\n')
+        .any(|line| escaped(line).is_some())
+    {
+        return Cow::Borrowed(contents);
+    }
+
+    let mut unescaped = String::with_capacity(contents.len());
+    for line in contents.split_inclusive('\n') {
+        match escaped(line) {
+            Some(i) => {
+                unescaped.push_str(&line[..i]);
+                unescaped.push_str(&line[i + 1..]);
+            }
+            None => unescaped.push_str(line),
+        }
+    }
+    Cow::Owned(unescaped)
+}
+
+/// Escapes lines starting with `*` or `#+` in block contents with commas,
+/// like `org-escape-code-in-string`.
+pub(crate) fn escape(contents: &str) -> Cow<'_, str> {
+    if !contents
+        .split_inclusive('\n')
+        .any(|line| escaping_comma(line).is_some())
+    {
+        return Cow::Borrowed(contents);
+    }
+
+    let mut escaped = String::with_capacity(contents.len() + 1);
+    for line in contents.split_inclusive('\n') {
+        match escaping_comma(line) {
+            Some(i) => {
+                escaped.push_str(&line[..i]);
+                escaped.push(',');
+                escaped.push_str(&line[i..]);
+            }
+            None => escaped.push_str(line),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
 pub fn parse_block_element(input: &str) -> Option<(&str, (&str, Option<&str>, &str, usize))> {
     parse_block_element_internal::<()>(input).ok()
 }
@@ -253,6 +485,7 @@ fn parse_block_element_internal<'a, E: ParseError<&'a str>>(
     ))
 }
 
+#[cfg(feature = "std")]
 #[test]
 fn parse() {
     use nom::error::VerboseError;
@@ -291,3 +524,39 @@ console.log('Hello World!');
     );
     // TODO: more testing
 }
+
+#[test]
+fn coderefs_() {
+    assert_eq!(coderef("x = 1  (ref:one)", "(ref:%s)"), Some((5, "one")));
+    assert_eq!(coderef("x = 1 ((one))  ", "((%s))"), Some((5, "one")));
+    assert_eq!(coderef("f(ref:)", "(ref:%s)"), None);
+    assert_eq!(coderef("(ref:a) b", "(ref:%s)"), None);
+    assert_eq!(label_fmt(r#"-n -l "[%s]" :tangle yes"#), "[%s]");
+    assert_eq!(label_fmt("-n"), "(ref:%s)");
+    assert!(retain_labels("-n :exports code"));
+    assert!(!retain_labels("-r :var r=-r"));
+    assert!(retain_labels("-n -r -k"));
+    assert!(!use_labels("-n -r -k"));
+}
+
+#[test]
+fn switches_() {
+    assert_eq!(number_lines("-n"), Some(NumberLines::New(1)));
+    assert_eq!(
+        number_lines("-r -n 20 :var n=1"),
+        Some(NumberLines::New(20))
+    );
+    assert_eq!(number_lines("+n 5"), Some(NumberLines::Continued(5)));
+    assert_eq!(number_lines("+n -r"), Some(NumberLines::Continued(0)));
+    assert_eq!(number_lines(":var -n"), None);
+}
+
+#[test]
+fn escape_() {
+    let contents = "* a\n  #+b\n,* c\n ,,#+d\n,e\n#f\n";
+
+    assert_eq!(escape(contents), ",* a\n  ,#+b\n,,* c\n ,,,#+d\n,e\n#f\n");
+    assert_eq!(unescape(contents), "* a\n  #+b\n* c\n ,#+d\n,e\n#f\n");
+    assert_eq!(unescape(&escape(contents)), contents);
+    assert!(matches!(unescape("a\n,b\n"), Cow::Borrowed(_)));
+}
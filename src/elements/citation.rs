@@ -0,0 +1,115 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+use nom::{
+    bytes::complete::{tag, take_while, take_while1},
+    combinator::{opt, verify},
+    error::ParseError,
+    sequence::{delimited, preceded},
+    IResult,
+};
+
+/// Citation Object
+///
+/// Introduced in Org 9.5, e.g. `[cite/t:see @key p. 12]`.
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[derive(Debug, PartialEq)]
+pub struct Citation<'a> {
+    /// Citation style and variant, e.g. `t` or `t/b`
+    pub style: Option<Cow<'a, str>>,
+    /// Keys of cited references, without the leading `@`
+    pub keys: Vec<Cow<'a, str>>,
+    /// Citation contents, including prefixes and suffixes
+    pub value: Cow<'a, str>,
+}
+
+impl Citation<'_> {
+    pub(crate) fn parse(input: &str) -> Option<(&str, Citation<'_>)> {
+        parse_citation::<()>(input).ok()
+    }
+
+    pub fn into_owned(self) -> Citation<'static> {
+        Citation {
+            style: self.style.map(|style| style.into_owned().into()),
+            keys: self
+                .keys
+                .into_iter()
+                .map(|key| key.into_owned().into())
+                .collect(),
+            value: self.value.into_owned().into(),
+        }
+    }
+}
+
+/// Returns keys of references separated by `;`, each of which is a key
+/// starting with `@` between an optional prefix and suffix.
+fn keys(value: &str) -> impl Iterator<Item = &str> {
+    value.split(';').filter_map(|reference| {
+        let key = &reference[reference.find('@')? + 1..];
+        let len = key
+            .find(|c: char| !c.is_alphanumeric() && !"-.:?!`'/*@+|(){}<>&_^$#%~".contains(c))
+            .unwrap_or(key.len());
+        Some(&key[0..len]).filter(|key| !key.is_empty())
+    })
+}
+
+#[inline]
+fn parse_citation<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Citation<'a>, E> {
+    let (input, style) = preceded(
+        tag("[cite"),
+        opt(preceded(
+            tag("/"),
+            take_while1(|c: char| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '/'),
+        )),
+    )(input)?;
+
+    let (input, value) = delimited(
+        tag(":"),
+        verify(take_while(|c| c != '[' && c != ']'), |value: &str| {
+            keys(value).next().is_some()
+        }),
+        tag("]"),
+    )(input)?;
+
+    Ok((
+        input,
+        Citation {
+            style: style.map(Into::into),
+            keys: keys(value).map(Into::into).collect(),
+            value: value.into(),
+        },
+    ))
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn parse() {
+    use nom::error::VerboseError;
+
+    assert_eq!(
+        parse_citation::<VerboseError<&str>>("[cite:@key]"),
+        Ok((
+            "",
+            Citation {
+                style: None,
+                keys: vec!["key".into()],
+                value: "@key".into(),
+            }
+        ))
+    );
+    assert_eq!(
+        parse_citation::<VerboseError<&str>>("[cite/t/b:see @doe p. 12; @roe]"),
+        Ok((
+            "",
+            Citation {
+                style: Some("t/b".into()),
+                keys: vec!["doe".into(), "roe".into()],
+                value: "see @doe p. 12; @roe".into(),
+            }
+        ))
+    );
+    assert!(parse_citation::<VerboseError<&str>>("[cite:key]").is_err());
+    assert!(parse_citation::<VerboseError<&str>>("[cite:@]").is_err());
+    assert!(parse_citation::<VerboseError<&str>>("[cite:@key").is_err());
+    assert!(parse_citation::<VerboseError<&str>>("[cite:[@key]]").is_err());
+}
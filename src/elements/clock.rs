@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
 
 use nom::{
     bytes::complete::tag,
@@ -14,10 +14,9 @@ use crate::elements::timestamp::{parse_inactive, Datetime, Timestamp};
 use crate::parsers::{blank_lines, eol};
 
 /// Clock Element
-#[cfg_attr(test, derive(PartialEq))]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 #[cfg_attr(feature = "ser", serde(untagged))]
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum Clock<'a> {
     /// Closed Clock
     Closed {
@@ -192,6 +191,7 @@ fn parse_clock<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&str, Cloc
     }
 }
 
+#[cfg(feature = "std")]
 #[test]
 fn parse() {
     use nom::error::VerboseError;
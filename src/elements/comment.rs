@@ -1,8 +1,8 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
 
 use crate::parsers::{blank_lines, take_lines_while};
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, PartialEq)]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 pub struct Comment<'a> {
     /// Comments value, with pound signs
@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
 
 use nom::{
     branch::alt,
@@ -11,9 +11,8 @@ use nom::{
 };
 
 /// Statistics Cookie Object
-#[cfg_attr(test, derive(PartialEq))]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Cookie<'a> {
     /// Full cookie value
     pub value: Cow<'a, str>,
@@ -50,6 +49,7 @@ fn parse_cookie<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&str, Coo
     ))
 }
 
+#[cfg(feature = "std")]
 #[test]
 fn parse() {
     use nom::error::VerboseError;
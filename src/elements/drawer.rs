@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
 
 use nom::{
     bytes::complete::{tag, take_while1},
@@ -11,8 +11,7 @@ use nom::{
 use crate::parsers::{blank_lines, eol, line, take_lines_while};
 
 /// Drawer Element
-#[derive(Debug, Default)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Default, PartialEq)]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 pub struct Drawer<'a> {
     /// Drawer name
@@ -60,7 +59,7 @@ pub fn parse_drawer_without_blank<'a, E: ParseError<&'a str>>(
     let (input, _) = space0(input)?;
     let (input, name) = delimited(
         tag(":"),
-        take_while1(|c: char| c.is_ascii_alphabetic() || c == '-' || c == '_'),
+        take_while1(|c: char| c != ':' && !c.is_whitespace()),
         tag(":"),
     )(input)?;
     let (input, _) = eol(input)?;
@@ -81,6 +80,7 @@ pub fn parse_drawer_without_blank<'a, E: ParseError<&'a str>>(
     ))
 }
 
+#[cfg(feature = "std")]
 #[test]
 fn parse() {
     use nom::error::VerboseError;
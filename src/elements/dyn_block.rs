@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
 
 use nom::{
     bytes::complete::tag_no_case,
@@ -10,8 +10,7 @@ use nom::{
 use crate::parsers::{blank_lines, line, take_lines_while};
 
 /// Dynamic Block Element
-#[derive(Debug, Default)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Default, PartialEq)]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 pub struct DynBlock<'a> {
     /// Block name
@@ -32,6 +31,53 @@ impl DynBlock<'_> {
         parse_dyn_block::<()>(input).ok()
     }
 
+    /// Returns the value of parameter `key` in the arguments, with
+    /// surrounding quotes removed.
+    ///
+    /// ```rust
+    /// # use orgize::{elements::DynBlock, Element, Event, Org};
+    /// #
+    /// let org = Org::parse("#+BEGIN: clocktable :scope file :tstart \"<2023-01-01 Sun>\"\n#+END:\n");
+    /// let block = org
+    ///     .iter()
+    ///     .find_map(|event| match event {
+    ///         Event::Start(Element::DynBlock(block)) => Some(block),
+    ///         _ => None,
+    ///     })
+    ///     .unwrap();
+    ///
+    /// assert_eq!(block.argument(":scope"), Some("file"));
+    /// assert_eq!(block.argument(":tstart"), Some("<2023-01-01 Sun>"));
+    /// assert_eq!(block.argument(":maxlevel"), None);
+    /// ```
+    pub fn argument(&self, key: &str) -> Option<&str> {
+        let mut rest = self.arguments.as_deref()?;
+        while let Some(i) = rest.find(':') {
+            rest = &rest[i..];
+            let name_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            let (name, value) = rest.split_at(name_end);
+            let value = value.trim_start();
+
+            let (value, next) = if let Some(quoted) = value.strip_prefix('"') {
+                let end = quoted.find('"').unwrap_or(quoted.len());
+                (&quoted[..end], &quoted[(end + 1).min(quoted.len())..])
+            } else {
+                let end = value.find(char::is_whitespace).unwrap_or(value.len());
+                (&value[..end], &value[end..])
+            };
+
+            if name.eq_ignore_ascii_case(key) {
+                return Some(value);
+            }
+            rest = if value.starts_with(':') {
+                &rest[name_end..]
+            } else {
+                next
+            };
+        }
+        None
+    }
+
     pub fn into_owned(self) -> DynBlock<'static> {
         DynBlock {
             block_name: self.block_name.into_owned().into(),
@@ -75,6 +121,7 @@ fn parse_dyn_block<'a, E: ParseError<&'a str>>(
     ))
 }
 
+#[cfg(feature = "std")]
 #[test]
 fn parse() {
     use nom::error::VerboseError;
@@ -1,48 +1,123 @@
-use bytecount::count;
 use memchr::memchr_iter;
 
-#[inline]
-pub(crate) fn parse_emphasis(text: &str, marker: u8) -> Option<(&str, &str)> {
-    debug_assert!(text.len() >= 3);
+use crate::parsers::Memo;
+
+/// Finds the closing marker of the emphasis beginning at `start` of `text`,
+/// whose container ends at `end`.
+///
+/// Whether a marker can close an emphasis doesn't depend on where the
+/// emphasis begins, so `closing` memoizes the last marker found for the
+/// following searches. `newlines` are the offsets of all newlines in `text`.
+pub(crate) fn parse_emphasis(
+    text: &str,
+    start: usize,
+    end: usize,
+    marker: u8,
+    closing: &mut Memo,
+    newlines: &[usize],
+) -> Option<usize> {
+    debug_assert!(end - start >= 3);
 
     let bytes = text.as_bytes();
 
-    if bytes[1].is_ascii_whitespace() {
+    if next_char(text, start + 1).map_or(true, char::is_whitespace) {
         return None;
     }
 
-    for i in memchr_iter(marker, bytes).skip(1) {
-        if count(&bytes[1..i], b'\n') >= 2 {
-            break;
-        } else if validate_marker(i, text) {
-            return Some((&text[i + 1..], &text[1..i]));
-        }
-    }
+    // contents can't be empty, so searching starts after the first character
+    let pos = closing
+        .find(start + 2, |from| {
+            memchr_iter(marker, &bytes[from..])
+                .map(|i| i + from)
+                .find(|&i| validate_marker(i, text))
+        })
+        .filter(|&i| i + 1 < end)
+        .or_else(|| {
+            // marker at the end of container is always valid
+            let i = end - 1;
+            if bytes[i] == marker && !prev_char(text, i).map_or(true, char::is_whitespace) {
+                Some(i)
+            } else {
+                None
+            }
+        })?;
+
+    // emphasis spans at most three lines
+    let lines = newlines.partition_point(|&i| i < pos) - newlines.partition_point(|&i| i <= start);
 
-    None
+    if lines >= 2 {
+        None
+    } else {
+        Some(pos)
+    }
 }
 
 fn validate_marker(pos: usize, text: &str) -> bool {
-    if text.as_bytes()[pos - 1].is_ascii_whitespace() {
+    if prev_char(text, pos).map_or(true, char::is_whitespace) {
         false
-    } else if let Some(&post) = text.as_bytes().get(pos + 1) {
+    } else if let Some(post) = next_char(text, pos + 1) {
         match post {
-            b' ' | b'-' | b'.' | b',' | b':' | b'!' | b'?' | b'\'' | b'\n' | b')' | b'}' => true,
-            _ => false,
+            ' ' | '-' | '.' | ',' | ':' | '!' | '?' | '\'' | '\n' | ')' | '}' => true,
+            _ => is_unicode_delimiter(post),
         }
     } else {
         true
     }
 }
 
+/// Returns `true` if `c` is a non-ASCII whitespace or punctuation, which can
+/// precede an opening marker or follow a closing marker just like the ASCII
+/// ones, e.g. `「*粗体*」`.
+pub(crate) fn is_unicode_delimiter(c: char) -> bool {
+    !c.is_ascii()
+        && (c.is_whitespace()
+            || matches!(
+                c,
+                // ¡ « · » ¿
+                '\u{a1}' | '\u{ab}' | '\u{b7}' | '\u{bb}' | '\u{bf}'
+                // general punctuation, e.g. dashes, quotes and ellipsis
+                | '\u{2010}'..='\u{2027}'
+                | '\u{2030}'..='\u{205e}'
+                // CJK symbols and punctuation
+                | '\u{3001}'..='\u{303f}'
+                // fullwidth punctuation
+                | '\u{ff01}'..='\u{ff0f}'
+                | '\u{ff1a}'..='\u{ff20}'
+                | '\u{ff3b}'..='\u{ff40}'
+                | '\u{ff5b}'..='\u{ff65}'
+            ))
+}
+
+fn prev_char(text: &str, pos: usize) -> Option<char> {
+    text[..pos].chars().next_back()
+}
+
+fn next_char(text: &str, pos: usize) -> Option<char> {
+    text.get(pos..)?.chars().next()
+}
+
+#[cfg(feature = "std")]
 #[test]
 fn parse() {
+    let parse_emphasis = |text: &'static str, marker| {
+        let newlines: Vec<_> = memchr_iter(b'\n', text.as_bytes()).collect();
+        parse_emphasis(text, 0, text.len(), marker, &mut Memo::default(), &newlines)
+            .map(|i| (&text[i + 1..], &text[1..i]))
+    };
+
     assert_eq!(parse_emphasis("*bold*", b'*'), Some(("", "bold")));
     assert_eq!(parse_emphasis("*bo*ld*", b'*'), Some(("", "bo*ld")));
     assert_eq!(parse_emphasis("*bo\nld*", b'*'), Some(("", "bo\nld")));
     assert_eq!(parse_emphasis("*bold*a", b'*'), None);
     assert_eq!(parse_emphasis("*bold*", b'/'), None);
+    assert_eq!(parse_emphasis("**)", b'*'), None);
+    assert_eq!(parse_emphasis("***)", b'*'), Some((")", "*")));
     assert_eq!(parse_emphasis("*bold *", b'*'), None);
     assert_eq!(parse_emphasis("* bold*", b'*'), None);
+    assert_eq!(parse_emphasis("*粗体*", b'*'), Some(("", "粗体")));
+    assert_eq!(parse_emphasis("*粗体*」", b'*'), Some(("」", "粗体")));
+    assert_eq!(parse_emphasis("*粗体*体", b'*'), None);
+    assert_eq!(parse_emphasis("*\u{3000}bold*", b'*'), None);
+    assert_eq!(parse_emphasis("*bold\u{3000}*", b'*'), None);
     assert_eq!(parse_emphasis("*b\nol\nd*", b'*'), None);
 }
@@ -0,0 +1,80 @@
+use alloc::borrow::Cow;
+
+use memchr::memchr;
+
+use crate::parsers::blank_lines;
+
+const BEGIN: &str = "-----BEGIN PGP MESSAGE-----";
+const END: &str = "-----END PGP MESSAGE-----";
+
+/// Section of a headline tagged with `:crypt:`, encrypted by `org-crypt`
+#[derive(Debug, Default, PartialEq)]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+pub struct EncryptedSection<'a> {
+    /// ASCII-armored PGP message
+    pub data: Cow<'a, str>,
+    /// Numbers of blank lines between the end of the message and next
+    /// non-blank line or buffer's end
+    pub post_blank: usize,
+}
+
+impl EncryptedSection<'_> {
+    /// Parses the whole section `input`, which must contain nothing but a
+    /// PGP message.
+    pub(crate) fn parse(input: &str) -> Option<EncryptedSection<'_>> {
+        if !input.starts_with(BEGIN) {
+            return None;
+        }
+
+        let end = input.find(END)? + END.len();
+        let end = memchr(b'\n', &input.as_bytes()[end..]).map_or(input.len(), |i| end + i + 1);
+        if !input[BEGIN.len()..end].trim_end().ends_with(END) {
+            return None;
+        }
+
+        let (tail, post_blank) = blank_lines(&input[end..]);
+        if !tail.trim().is_empty() {
+            return None;
+        }
+
+        Some(EncryptedSection {
+            data: input[0..end].into(),
+            post_blank,
+        })
+    }
+
+    pub fn into_owned(self) -> EncryptedSection<'static> {
+        EncryptedSection {
+            data: self.data.into_owned().into(),
+            post_blank: self.post_blank,
+        }
+    }
+}
+
+#[test]
+fn parse() {
+    assert_eq!(
+        EncryptedSection::parse(
+            "-----BEGIN PGP MESSAGE-----\n\nhQEMA\n-----END PGP MESSAGE-----\n\n"
+        ),
+        Some(EncryptedSection {
+            data: "-----BEGIN PGP MESSAGE-----\n\nhQEMA\n-----END PGP MESSAGE-----\n".into(),
+            post_blank: 1
+        })
+    );
+    assert_eq!(
+        EncryptedSection::parse("-----BEGIN PGP MESSAGE-----\n-----END PGP MESSAGE-----"),
+        Some(EncryptedSection {
+            data: "-----BEGIN PGP MESSAGE-----\n-----END PGP MESSAGE-----".into(),
+            post_blank: 0
+        })
+    );
+    assert_eq!(
+        EncryptedSection::parse("-----BEGIN PGP MESSAGE-----\nhQEMA\n"),
+        None
+    );
+    assert_eq!(
+        EncryptedSection::parse("-----BEGIN PGP MESSAGE-----\n-----END PGP MESSAGE-----\nplain\n"),
+        None
+    );
+}
@@ -1,9 +1,8 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
 
 use crate::parsers::{blank_lines, take_lines_while};
 
-#[derive(Debug, Default)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Default, PartialEq)]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 pub struct FixedWidth<'a> {
     /// Fxied width value
@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
 
 use nom::{
     bytes::complete::{tag, take_while1},
@@ -10,9 +10,8 @@ use nom::{
 use crate::parsers::{blank_lines, line};
 
 /// Footnote Definition Element
-#[cfg_attr(test, derive(PartialEq))]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
-#[derive(Debug, Default)]
+#[derive(Debug, Default, PartialEq)]
 pub struct FnDef<'a> {
     /// Footnote label, used for refrence
     pub label: Cow<'a, str>,
@@ -56,6 +55,7 @@ fn parse_fn_def<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&str, (Fn
     ))
 }
 
+#[cfg(feature = "std")]
 #[test]
 fn parse() {
     use nom::error::VerboseError;
@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
 
 use memchr::memchr2_iter;
 use nom::{
@@ -10,9 +10,8 @@ use nom::{
 };
 
 /// Footnote Reference Element
-#[cfg_attr(test, derive(PartialEq))]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct FnRef<'a> {
     /// Footnote label
     pub label: Cow<'a, str>,
@@ -64,6 +63,7 @@ fn balanced_brackets<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&str
     Err(Err::Error(E::from_error_kind(input, ErrorKind::Tag)))
 }
 
+#[cfg(feature = "std")]
 #[test]
 fn parse() {
     use nom::error::VerboseError;
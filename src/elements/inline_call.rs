@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
 
 use nom::{
     bytes::complete::{tag, take_till},
@@ -9,9 +9,8 @@ use nom::{
 };
 
 /// Inline Babel Call Object
-#[cfg_attr(test, derive(PartialEq))]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
-#[derive(Debug, Default)]
+#[derive(Debug, Default, PartialEq)]
 pub struct InlineCall<'a> {
     /// Called code block name
     pub name: Cow<'a, str>,
@@ -70,6 +69,7 @@ fn parse_inline_call<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&str
     ))
 }
 
+#[cfg(feature = "std")]
 #[test]
 fn parse() {
     use nom::error::VerboseError;
@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
 
 use nom::{
     bytes::complete::{tag, take_till},
@@ -9,12 +9,12 @@ use nom::{
     IResult,
 };
 
+use crate::elements::InlineCall;
 use crate::parsers::{blank_lines, line};
 
 /// Keyword Elemenet
-#[cfg_attr(test, derive(PartialEq))]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Keyword<'a> {
     /// Keyword name
     pub key: Cow<'a, str>,
@@ -38,10 +38,76 @@ impl Keyword<'_> {
     }
 }
 
+/// How values of a keyword appearing multiple times are combined, like the
+/// behaviors of `org-export-options-alist`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeywordBehavior {
+    /// Keeps the first value
+    First,
+    /// Keeps the last value
+    Last,
+    /// Joins values with spaces
+    Space,
+    /// Joins values with newlines
+    Newline,
+    /// Splits values at whitespaces, and joins the words with single spaces
+    Split,
+}
+
+impl KeywordBehavior {
+    /// Returns the behavior org-export applies to keyword `key`. Unknown
+    /// keywords are joined with spaces.
+    ///
+    /// ```rust
+    /// # use orgize::elements::KeywordBehavior;
+    /// #
+    /// assert_eq!(KeywordBehavior::of("author"), KeywordBehavior::Space);
+    /// assert_eq!(KeywordBehavior::of("HTML_HEAD"), KeywordBehavior::Newline);
+    /// assert_eq!(KeywordBehavior::of("LANGUAGE"), KeywordBehavior::First);
+    /// ```
+    pub fn of(key: &str) -> KeywordBehavior {
+        const FIRST: &[&str] = &[
+            "CREATOR",
+            "EMAIL",
+            "EXPORT_FILE_NAME",
+            "HTML_CONTAINER",
+            "HTML_DOCTYPE",
+            "HTML_LINK_HOME",
+            "HTML_LINK_UP",
+            "LANGUAGE",
+            "REVEAL_ROOT",
+            "REVEAL_THEME",
+            "REVEAL_TITLE_SLIDE",
+            "REVEAL_TRANS",
+            "SETUPFILE",
+        ];
+        const NEWLINE: &[&str] = &[
+            "DESCRIPTION",
+            "HTML_HEAD",
+            "HTML_HEAD_EXTRA",
+            "LATEX_HEADER",
+            "LATEX_HEADER_EXTRA",
+            "REVEAL_EXTRA_CSS",
+            "REVEAL_INIT_OPTIONS",
+        ];
+        const SPLIT: &[&str] = &["EXCLUDE_TAGS", "SELECT_TAGS"];
+
+        let is = |keys: &[&str]| keys.iter().any(|k| k.eq_ignore_ascii_case(key));
+        if is(FIRST) {
+            KeywordBehavior::First
+        } else if is(NEWLINE) {
+            KeywordBehavior::Newline
+        } else if is(SPLIT) {
+            KeywordBehavior::Split
+        } else {
+            KeywordBehavior::Space
+        }
+    }
+}
+
 /// Babel Call Elemenet
-#[cfg_attr(test, derive(PartialEq))]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct BabelCall<'a> {
     /// Babel call value
     pub value: Cow<'a, str>,
@@ -57,6 +123,59 @@ impl BabelCall<'_> {
             post_blank: self.post_blank,
         }
     }
+
+    /// Parses the value into the called name, header arguments and
+    /// arguments, e.g. `square[:results output](4) :results html`.
+    ///
+    /// ```rust
+    /// # use orgize::elements::BabelCall;
+    /// #
+    /// let call = BabelCall {
+    ///     value: "square[:eval yes](x=4) :results html".into(),
+    ///     post_blank: 0,
+    /// };
+    /// let call = call.call().unwrap();
+    ///
+    /// assert_eq!(call.name, "square");
+    /// assert_eq!(call.inside_header.as_deref(), Some(":eval yes"));
+    /// assert_eq!(call.arguments, "x=4");
+    /// assert_eq!(call.end_header.as_deref(), Some(":results html"));
+    /// ```
+    pub fn call(&self) -> Option<InlineCall<'_>> {
+        let value = self.value.trim();
+        let end = value
+            .find(|c: char| c == '[' || c == '(' || c.is_whitespace())
+            .unwrap_or(value.len());
+        let (name, mut rest) = value.split_at(end);
+        if name.is_empty() {
+            return None;
+        }
+
+        let mut inside_header = None;
+        if let Some(header) = rest.strip_prefix('[') {
+            let (header, after) = header.split_once(']')?;
+            inside_header = Some(header.into());
+            rest = after;
+        }
+        let mut arguments = "";
+        if let Some(args) = rest.strip_prefix('(') {
+            let (args, after) = args.split_once(')')?;
+            arguments = args;
+            rest = after;
+        }
+        let rest = rest.trim();
+
+        Some(InlineCall {
+            name: name.into(),
+            inside_header,
+            arguments: arguments.into(),
+            end_header: if rest.is_empty() {
+                None
+            } else {
+                Some(rest.into())
+            },
+        })
+    }
 }
 
 #[inline]
@@ -83,6 +202,7 @@ fn parse_keyword_internal<'a, E: ParseError<&'a str>>(
     Ok((input, (key, optional, value.trim(), blank)))
 }
 
+#[cfg(feature = "std")]
 #[test]
 fn parse() {
     use nom::error::VerboseError;
@@ -126,3 +246,21 @@ fn parse() {
         Ok(("", ("CAPTION", Some("Short caption"), "Longer caption.", 0)))
     );
 }
+
+#[test]
+fn behavior() {
+    use crate::Org;
+
+    let org = Org::parse("#+SELECT_TAGS: a  b\n#+SELECT_TAGS: c\n#+TITLE: x\n#+TITLE: y\n");
+    assert_eq!(org.keyword("SELECT_TAGS").unwrap(), "a b c");
+    assert_eq!(
+        org.keyword_with("SELECT_TAGS", KeywordBehavior::Newline)
+            .unwrap(),
+        "a  b\nc"
+    );
+    assert_eq!(org.keyword("TITLE").unwrap(), "x y");
+    assert_eq!(
+        org.keyword_with("TITLE", KeywordBehavior::Last).unwrap(),
+        "y"
+    );
+}
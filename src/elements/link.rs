@@ -1,4 +1,8 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+
+use crate::compat::HashMap;
 
 use nom::{
     bytes::complete::{tag, take_while},
@@ -9,14 +13,17 @@ use nom::{
 };
 
 /// Link Object
-#[cfg_attr(test, derive(PartialEq))]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Link<'a> {
     /// Link destination
     pub path: Cow<'a, str>,
     #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
     pub desc: Option<Cow<'a, str>>,
+    /// Link destination with its abbreviation expanded, if `path` is
+    /// abbreviated by a `#+LINK` keyword, e.g. `gh:theiceshelf/orgize`
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub expanded: Option<Cow<'a, str>>,
 }
 
 impl Link<'_> {
@@ -29,8 +36,82 @@ impl Link<'_> {
         Link {
             path: self.path.into_owned().into(),
             desc: self.desc.map(Into::into).map(Cow::Owned),
+            expanded: self.expanded.map(Into::into).map(Cow::Owned),
         }
     }
+
+    /// Returns the expanded destination if any, or `path` otherwise.
+    pub fn target(&self) -> &str {
+        self.expanded.as_ref().unwrap_or(&self.path)
+    }
+
+    /// Returns the label of a coderef link, e.g. `[[(name)]]`.
+    pub fn coderef(&self) -> Option<&str> {
+        self.path
+            .strip_prefix('(')
+            .and_then(|path| path.strip_suffix(')'))
+            .filter(|label| !label.is_empty())
+    }
+
+    /// Returns `true` if this link has no description and its destination
+    /// is an image file, which is inlined by exporters.
+    ///
+    /// ```rust
+    /// # use orgize::elements::Link;
+    /// #
+    /// let link = Link {
+    ///     path: "attachment:photo.JPG".into(),
+    ///     desc: None,
+    ///     expanded: None,
+    /// };
+    /// assert!(link.is_image());
+    /// ```
+    pub fn is_image(&self) -> bool {
+        const EXTENSIONS: [&str; 9] = [
+            "png", "jpg", "jpeg", "gif", "svg", "webp", "bmp", "tif", "tiff",
+        ];
+
+        self.desc.is_none()
+            && self.target().rsplit_once('.').is_some_and(|(_, extension)| {
+                EXTENSIONS
+                    .iter()
+                    .any(|e| e.eq_ignore_ascii_case(extension))
+            })
+    }
+}
+
+/// Expands abbreviated link `path` with `abbreviations`, which maps
+/// lowercased abbreviations to their replacement texts.
+///
+/// `path` is split as `key:tag` or `key::tag`. The first `%s` in the
+/// replacement text is replaced with `tag`, the first `%h` is replaced with
+/// url-encoded `tag`, otherwise `tag` is appended to it.
+pub(crate) fn expand_link(path: &str, abbreviations: &HashMap<String, String>) -> Option<String> {
+    let (key, tag) = match path.find(':') {
+        Some(i) => {
+            let tag = &path[i + 1..];
+            (&path[..i], tag.strip_prefix(':').unwrap_or(tag))
+        }
+        None => (path, ""),
+    };
+
+    let replacement = abbreviations.get(&key.to_lowercase())?;
+
+    Some(if let Some(i) = replacement.find("%s") {
+        format!("{}{}{}", &replacement[..i], tag, &replacement[i + 2..])
+    } else if let Some(i) = replacement.find("%h") {
+        let mut encoded = String::with_capacity(tag.len());
+        for &b in tag.as_bytes() {
+            if b.is_ascii_alphanumeric() || b"-_.~".contains(&b) {
+                encoded.push(b as char);
+            } else {
+                encoded.push_str(&format!("%{:02X}", b));
+            }
+        }
+        format!("{}{}{}", &replacement[..i], encoded, &replacement[i + 2..])
+    } else {
+        format!("{}{}", replacement, tag)
+    })
 }
 
 #[inline]
@@ -51,10 +132,12 @@ fn parse_link<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&str, Link,
         Link {
             path: path.into(),
             desc: desc.map(Into::into),
+            expanded: None,
         },
     ))
 }
 
+#[cfg(feature = "std")]
 #[test]
 fn parse() {
     use nom::error::VerboseError;
@@ -65,7 +148,8 @@ fn parse() {
             "",
             Link {
                 path: "#id".into(),
-                desc: None
+                desc: None,
+                expanded: None,
             }
         ))
     );
@@ -75,9 +159,43 @@ fn parse() {
             "",
             Link {
                 path: "#id".into(),
-                desc: Some("desc".into())
+                desc: Some("desc".into()),
+                expanded: None,
             }
         ))
     );
     assert!(parse_link::<VerboseError<&str>>("[[#id][desc]").is_err());
+
+    let mut abbreviations = HashMap::new();
+    abbreviations.insert("gh".to_string(), "https://github.com/%s".to_string());
+    abbreviations.insert(
+        "ddg".to_string(),
+        "https://duckduckgo.com/?q=%h".to_string(),
+    );
+    abbreviations.insert(
+        "wiki".to_string(),
+        "https://en.wikipedia.org/wiki/".to_string(),
+    );
+
+    assert_eq!(
+        expand_link("gh:theiceshelf/orgize", &abbreviations),
+        Some("https://github.com/theiceshelf/orgize".into())
+    );
+    assert_eq!(
+        expand_link("GH::orgize", &abbreviations),
+        Some("https://github.com/orgize".into())
+    );
+    assert_eq!(
+        expand_link("ddg:org mode&more", &abbreviations),
+        Some("https://duckduckgo.com/?q=org%20mode%26more".into())
+    );
+    assert_eq!(
+        expand_link("wiki:Org-mode", &abbreviations),
+        Some("https://en.wikipedia.org/wiki/Org-mode".into())
+    );
+    assert_eq!(
+        expand_link("wiki", &abbreviations),
+        Some("https://en.wikipedia.org/wiki/".into())
+    );
+    assert_eq!(expand_link("https://example.com", &abbreviations), None);
 }
@@ -1,5 +1,5 @@
-use std::borrow::Cow;
-use std::iter::once;
+use alloc::borrow::Cow;
+use core::iter::once;
 
 use memchr::{memchr, memchr_iter};
 use nom::{
@@ -13,9 +13,8 @@ use nom::{
 };
 
 /// Plain List Element
-#[cfg_attr(test, derive(PartialEq))]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct List {
     /// List indent, number of whitespaces
     pub indent: usize,
@@ -27,9 +26,8 @@ pub struct List {
 }
 
 /// List Item Elemenet
-#[cfg_attr(test, derive(PartialEq))]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct ListItem<'a> {
     /// List item bullet
     pub bullet: Cow<'a, str>,
@@ -57,6 +55,28 @@ impl ListItem<'_> {
     }
 }
 
+/// Checkbox of a list item
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Checkbox {
+    /// Unchecked, `[ ]`
+    Off,
+    /// Checked, `[X]`
+    On,
+    /// Partially checked, `[-]`
+    Trans,
+}
+
+impl Checkbox {
+    /// Returns this checkbox as written in org files, e.g. `[X]`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Checkbox::Off => "[ ]",
+            Checkbox::On => "[X]",
+            Checkbox::Trans => "[-]",
+        }
+    }
+}
+
 fn list_item<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&str, (ListItem, &str), E> {
     let (input, indent) = map(space0, |s: &str| s.len())(input)?;
     let (input, bullet) = recognize(alt((
@@ -79,6 +99,32 @@ fn list_item<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&str, (ListI
     ))
 }
 
+/// Parses the counter at the beginning of list item contents, e.g. `[@5]`.
+pub(crate) fn parse_counter(input: &str) -> Option<usize> {
+    input.strip_prefix("[@")?.split_once(']')?.0.parse().ok()
+}
+
+/// Parses the checkbox at the beginning of list item contents, after the
+/// counter if any, e.g. `[X]` or `[@5] [ ]`. Returns its offset and value.
+pub(crate) fn parse_checkbox(input: &str) -> Option<(usize, Checkbox)> {
+    let mut offset = 0;
+    if parse_counter(input).is_some() {
+        let (_, rest) = input.split_once(']')?;
+        offset = input.len() - rest.trim_start_matches([' ', '\t']).len();
+    }
+
+    let checkbox = match input[offset..].get(..3)? {
+        "[ ]" => Checkbox::Off,
+        "[X]" | "[x]" => Checkbox::On,
+        "[-]" => Checkbox::Trans,
+        _ => return None,
+    };
+    match input[offset + 3..].chars().next() {
+        None | Some(' ') | Some('\t') | Some('\n') | Some('\r') => Some((offset, checkbox)),
+        _ => None,
+    }
+}
+
 fn list_item_contents(input: &str, indent: usize) -> (&str, &str) {
     let mut last_end = memchr(b'\n', input.as_bytes())
         .map(|i| i + 1)
@@ -120,6 +166,7 @@ fn list_item_contents(input: &str, indent: usize) -> (&str, &str) {
     ("", input)
 }
 
+#[cfg(feature = "std")]
 #[test]
 fn parse() {
     use nom::error::VerboseError;
@@ -317,3 +364,20 @@ fn parse() {
         ))
     );
 }
+
+#[test]
+fn counter() {
+    assert_eq!(parse_counter("[@5] item"), Some(5));
+    assert_eq!(parse_counter("[@a] item"), None);
+    assert_eq!(parse_counter("[X] item"), None);
+}
+
+#[test]
+fn checkbox() {
+    assert_eq!(parse_checkbox("[ ] item"), Some((0, Checkbox::Off)));
+    assert_eq!(parse_checkbox("[x]\n"), Some((0, Checkbox::On)));
+    assert_eq!(parse_checkbox("[@5]  [-] item"), Some((6, Checkbox::Trans)));
+    assert_eq!(parse_checkbox("[X]item"), None);
+    assert_eq!(parse_checkbox("[@5] item"), None);
+    assert_eq!(parse_checkbox("item"), None);
+}
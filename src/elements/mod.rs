@@ -1,12 +1,14 @@
 //! Org-mode elements
 
 pub(crate) mod block;
+pub(crate) mod citation;
 pub(crate) mod clock;
 pub(crate) mod comment;
 pub(crate) mod cookie;
 pub(crate) mod drawer;
 pub(crate) mod dyn_block;
 pub(crate) mod emphasis;
+pub(crate) mod encrypted;
 pub(crate) mod fixed_width;
 pub(crate) mod fn_def;
 pub(crate) mod fn_ref;
@@ -27,36 +29,118 @@ pub(crate) mod title;
 
 pub use self::{
     block::{
-        CenterBlock, CommentBlock, ExampleBlock, ExportBlock, QuoteBlock, SourceBlock,
-        SpecialBlock, VerseBlock,
+        CenterBlock, CommentBlock, ExampleBlock, ExportBlock, NumberLines, QuoteBlock,
+        SourceBlock, SpecialBlock, VerseBlock,
     },
+    citation::Citation,
     clock::Clock,
     comment::Comment,
     cookie::Cookie,
     drawer::Drawer,
     dyn_block::DynBlock,
+    encrypted::EncryptedSection,
     fixed_width::FixedWidth,
     fn_def::FnDef,
     fn_ref::FnRef,
     inline_call::InlineCall,
     inline_src::InlineSrc,
-    keyword::{BabelCall, Keyword},
+    keyword::{BabelCall, Keyword, KeywordBehavior},
     link::Link,
-    list::{List, ListItem},
+    list::{Checkbox, List, ListItem},
     macros::Macros,
     planning::Planning,
     rule::Rule,
     snippet::Snippet,
     table::{Table, TableCell, TableRow},
     target::Target,
-    timestamp::{Datetime, Timestamp},
+    timestamp::{
+        Datetime, DayNames, Delay, DelayType, Repeater, RepeaterType, TimeUnit, Timestamp,
+    },
     title::Title,
 };
 
-use std::borrow::Cow;
+use alloc::borrow::Cow;
+
+/// Kind of an element, without its data
+///
+/// ```rust
+/// # use orgize::{ElementKind, Event, Org};
+/// #
+/// let org = Org::parse("* title\n*bold*");
+/// let kinds: Vec<_> = org
+///     .iter()
+///     .filter_map(|event| match event {
+///         Event::Start(element) => Some(element.kind()),
+///         Event::End(_) => None,
+///     })
+///     .filter(|kind| *kind != ElementKind::Text)
+///     .collect();
+///
+/// assert_eq!(
+///     kinds,
+///     vec![
+///         ElementKind::Document,
+///         ElementKind::Headline,
+///         ElementKind::Title,
+///         ElementKind::Section,
+///         ElementKind::Paragraph,
+///         ElementKind::Bold,
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ElementKind {
+    SpecialBlock,
+    QuoteBlock,
+    CenterBlock,
+    VerseBlock,
+    CommentBlock,
+    ExampleBlock,
+    ExportBlock,
+    SourceBlock,
+    BabelCall,
+    Section,
+    EncryptedSection,
+    Clock,
+    Cookie,
+    RadioTarget,
+    Drawer,
+    Document,
+    DynBlock,
+    FnDef,
+    FnRef,
+    Headline,
+    InlineCall,
+    InlineSrc,
+    Keyword,
+    Link,
+    List,
+    ListItem,
+    Macros,
+    Snippet,
+    Citation,
+    Text,
+    Paragraph,
+    VerseLine,
+    Rule,
+    Timestamp,
+    Target,
+    Bold,
+    Strike,
+    Italic,
+    Underline,
+    Verbatim,
+    Code,
+    Comment,
+    FixedWidth,
+    Title,
+    Table,
+    TableRow,
+    TableCell,
+}
 
 /// Element Enum
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 #[cfg_attr(feature = "ser", serde(tag = "type", rename_all = "kebab-case"))]
 pub enum Element<'a> {
@@ -70,6 +154,7 @@ pub enum Element<'a> {
     SourceBlock(SourceBlock<'a>),
     BabelCall(BabelCall<'a>),
     Section,
+    EncryptedSection(EncryptedSection<'a>),
     Clock(Clock<'a>),
     Cookie(Cookie<'a>),
     RadioTarget,
@@ -87,8 +172,12 @@ pub enum Element<'a> {
     ListItem(ListItem<'a>),
     Macros(Macros<'a>),
     Snippet(Snippet<'a>),
+    Citation(Citation<'a>),
     Text { value: Cow<'a, str> },
     Paragraph { post_blank: usize },
+    /// Line of a verse block, whose leading whitespaces are counted by
+    /// `indent`
+    VerseLine { indent: usize },
     Rule(Rule),
     Timestamp(Timestamp<'a>),
     Target(Target<'a>),
@@ -121,6 +210,7 @@ impl Element<'_> {
             | Element::List(_)
             | Element::ListItem(_)
             | Element::Paragraph { .. }
+            | Element::VerseLine { .. }
             | Element::Section
             | Element::Strike
             | Element::Underline
@@ -133,6 +223,59 @@ impl Element<'_> {
         }
     }
 
+    /// Returns the kind of this element.
+    pub fn kind(&self) -> ElementKind {
+        match self {
+            Element::SpecialBlock(_) => ElementKind::SpecialBlock,
+            Element::QuoteBlock(_) => ElementKind::QuoteBlock,
+            Element::CenterBlock(_) => ElementKind::CenterBlock,
+            Element::VerseBlock(_) => ElementKind::VerseBlock,
+            Element::CommentBlock(_) => ElementKind::CommentBlock,
+            Element::ExampleBlock(_) => ElementKind::ExampleBlock,
+            Element::ExportBlock(_) => ElementKind::ExportBlock,
+            Element::SourceBlock(_) => ElementKind::SourceBlock,
+            Element::BabelCall(_) => ElementKind::BabelCall,
+            Element::Section => ElementKind::Section,
+            Element::EncryptedSection(_) => ElementKind::EncryptedSection,
+            Element::Clock(_) => ElementKind::Clock,
+            Element::Cookie(_) => ElementKind::Cookie,
+            Element::RadioTarget => ElementKind::RadioTarget,
+            Element::Drawer(_) => ElementKind::Drawer,
+            Element::Document { .. } => ElementKind::Document,
+            Element::DynBlock(_) => ElementKind::DynBlock,
+            Element::FnDef(_) => ElementKind::FnDef,
+            Element::FnRef(_) => ElementKind::FnRef,
+            Element::Headline { .. } => ElementKind::Headline,
+            Element::InlineCall(_) => ElementKind::InlineCall,
+            Element::InlineSrc(_) => ElementKind::InlineSrc,
+            Element::Keyword(_) => ElementKind::Keyword,
+            Element::Link(_) => ElementKind::Link,
+            Element::List(_) => ElementKind::List,
+            Element::ListItem(_) => ElementKind::ListItem,
+            Element::Macros(_) => ElementKind::Macros,
+            Element::Snippet(_) => ElementKind::Snippet,
+            Element::Citation(_) => ElementKind::Citation,
+            Element::Text { .. } => ElementKind::Text,
+            Element::Paragraph { .. } => ElementKind::Paragraph,
+            Element::VerseLine { .. } => ElementKind::VerseLine,
+            Element::Rule(_) => ElementKind::Rule,
+            Element::Timestamp(_) => ElementKind::Timestamp,
+            Element::Target(_) => ElementKind::Target,
+            Element::Bold => ElementKind::Bold,
+            Element::Strike => ElementKind::Strike,
+            Element::Italic => ElementKind::Italic,
+            Element::Underline => ElementKind::Underline,
+            Element::Verbatim { .. } => ElementKind::Verbatim,
+            Element::Code { .. } => ElementKind::Code,
+            Element::Comment(_) => ElementKind::Comment,
+            Element::FixedWidth(_) => ElementKind::FixedWidth,
+            Element::Title(_) => ElementKind::Title,
+            Element::Table(_) => ElementKind::Table,
+            Element::TableRow(_) => ElementKind::TableRow,
+            Element::TableCell(_) => ElementKind::TableCell,
+        }
+    }
+
     pub fn into_owned(self) -> Element<'static> {
         use Element::*;
 
@@ -147,6 +290,7 @@ impl Element<'_> {
             SourceBlock(e) => SourceBlock(e.into_owned()),
             BabelCall(e) => BabelCall(e.into_owned()),
             Section => Section,
+            EncryptedSection(e) => EncryptedSection(e.into_owned()),
             Clock(e) => Clock(e.into_onwed()),
             Cookie(e) => Cookie(e.into_owned()),
             RadioTarget => RadioTarget,
@@ -164,10 +308,12 @@ impl Element<'_> {
             ListItem(e) => ListItem(e.into_owned()),
             Macros(e) => Macros(e.into_owned()),
             Snippet(e) => Snippet(e.into_owned()),
+            Citation(e) => Citation(e.into_owned()),
             Text { value } => Text {
                 value: value.into_owned().into(),
             },
             Paragraph { post_blank } => Paragraph { post_blank },
+            VerseLine { indent } => VerseLine { indent },
             Rule(e) => Rule(e),
             Timestamp(e) => Timestamp(e.into_owned()),
             Target(e) => Target(e.into_owned()),
@@ -213,12 +359,14 @@ macro_rules! impl_from {
 impl_from!(
     BabelCall,
     CenterBlock,
+    Citation,
     Clock,
     Comment,
     CommentBlock,
     Cookie,
     Drawer,
     DynBlock,
+    EncryptedSection,
     ExampleBlock,
     ExportBlock,
     FixedWidth,
@@ -29,6 +29,7 @@ fn parse_radio_target_internal<'a, E: ParseError<&'a str>>(
     Ok((input, contents))
 }
 
+#[cfg(feature = "std")]
 #[test]
 fn parse() {
     use nom::error::VerboseError;
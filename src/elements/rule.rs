@@ -4,8 +4,7 @@ use nom::{
 
 use crate::parsers::{blank_lines, eol};
 
-#[derive(Debug, Default)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Default, PartialEq)]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 pub struct Rule {
     /// Numbers of blank lines between rule line and next non-blank line or
@@ -27,6 +26,7 @@ fn parse_rule<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&str, Rule,
     Ok((input, Rule { post_blank: blank }))
 }
 
+#[cfg(feature = "std")]
 #[test]
 fn parse() {
     use nom::error::VerboseError;
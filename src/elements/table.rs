@@ -1,12 +1,15 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
 
 use memchr::memchr;
 
 use crate::parsers::{blank_lines, take_lines_while};
 
 /// Table Elemenet
-#[derive(Debug)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 #[cfg_attr(feature = "ser", serde(tag = "table_type"))]
 pub enum Table<'a> {
@@ -104,8 +107,7 @@ impl Table<'_> {
 /// |-----+-----+-----| <- ignores
 /// ```
 ///
-#[derive(Debug)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 #[cfg_attr(feature = "ser", serde(tag = "table_row_type"))]
 #[cfg_attr(feature = "ser", serde(rename_all = "kebab-case"))]
@@ -121,8 +123,7 @@ pub enum TableRow {
 }
 
 /// Table Cell Elemenet
-#[derive(Debug)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 #[cfg_attr(feature = "ser", serde(tag = "table_cell_type"))]
 #[cfg_attr(feature = "ser", serde(rename_all = "kebab-case"))]
@@ -133,6 +134,181 @@ pub enum TableCell {
     Body,
 }
 
+/// Formats rows of cells as an org table with aligned columns, drawing a
+/// horizontal rule after each row whose index is in `rules_after`.
+pub(crate) fn format_table(rows: &[Vec<String>], rules_after: &[usize]) -> String {
+    let mut lines = Vec::new();
+    for (i, row) in rows.iter().enumerate() {
+        lines.push(Some(&row[..]));
+        if rules_after.contains(&i) {
+            lines.push(None);
+        }
+    }
+    write_rows(&lines, "", &[])
+}
+
+impl Table<'_> {
+    /// Re-aligns the source text of an org table, like `org-table-align`.
+    ///
+    /// Columns are padded to their widest cell, and horizontal rules are
+    /// redrawn as `|---+---|`. Columns mostly containing numbers are aligned
+    /// to the right, unless a row of `<l>`, `<c>` or `<r>` cookies says
+    /// otherwise. The indentation of the first line is kept.
+    ///
+    /// ```rust
+    /// # use orgize::elements::Table;
+    /// #
+    /// assert_eq!(
+    ///     Table::realign("  | name | qty |\n|-\n| apple | 3\n| kiwi|12|\n"),
+    ///     "  | name  | qty |\n  |-------+-----|\n  | apple |   3 |\n  | kiwi  |  12 |\n"
+    /// );
+    /// ```
+    pub fn realign(text: &str) -> String {
+        let indent = &text[0..text.len() - text.trim_start().len()];
+        let indent = &indent[indent.rfind('\n').map_or(0, |i| i + 1)..];
+
+        let rows: Vec<Option<Vec<&str>>> = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| line.starts_with('|'))
+            .map(|line| {
+                if line.starts_with("|-") {
+                    None
+                } else {
+                    Some(line[1..].split_terminator('|').map(str::trim).collect())
+                }
+            })
+            .collect();
+
+        let columns = rows.iter().flatten().map(Vec::len).max().unwrap_or(0);
+        let cookies = rows.iter().flatten().filter(|row| is_cookie_row(row)).fold(
+            vec![None; columns],
+            |mut aligns, row| {
+                for (align, cell) in aligns.iter_mut().zip(row.iter()) {
+                    *align = match cell.trim_start_matches('<').as_bytes().first() {
+                        Some(b'l') => Some(Align::Left),
+                        Some(b'c') => Some(Align::Center),
+                        Some(b'r') => Some(Align::Right),
+                        _ => *align,
+                    };
+                }
+                aligns
+            },
+        );
+        let aligns: Vec<_> = cookies
+            .into_iter()
+            .enumerate()
+            .map(|(i, cookie)| {
+                cookie.unwrap_or_else(|| {
+                    let cells = rows
+                        .iter()
+                        .flatten()
+                        .filter(|row| !is_cookie_row(row))
+                        .filter_map(|row| row.get(i))
+                        .filter(|cell| !cell.is_empty());
+                    let (numbers, total) = cells.fold((0, 0), |(numbers, total), cell| {
+                        (numbers + is_number(cell) as usize, total + 1)
+                    });
+                    if total > 0 && numbers * 2 >= total {
+                        Align::Right
+                    } else {
+                        Align::Left
+                    }
+                })
+            })
+            .collect();
+
+        let rows: Vec<_> = rows.iter().map(|row| row.as_deref()).collect();
+        write_rows(&rows, indent, &aligns)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+/// Writes rows of cells with padded columns, or horizontal rules for `None`.
+/// Columns without alignment in `aligns` are aligned to the left.
+fn write_rows<S: AsRef<str>>(rows: &[Option<&[S]>], indent: &str, aligns: &[Align]) -> String {
+    let columns = rows
+        .iter()
+        .flatten()
+        .map(|row| row.len())
+        .max()
+        .unwrap_or(0);
+    let widths: Vec<_> = (0..columns)
+        .map(|i| {
+            rows.iter()
+                .flatten()
+                .filter_map(|row| row.get(i))
+                .map(|cell| cell.as_ref().chars().count())
+                .max()
+                .unwrap_or(0)
+                .max(1)
+        })
+        .collect();
+
+    let mut s = String::new();
+    for row in rows {
+        s.push_str(indent);
+        match row {
+            Some(row) => {
+                for (i, width) in widths.iter().enumerate() {
+                    let cell = row.get(i).map(AsRef::as_ref).unwrap_or_default();
+                    let padding = width - cell.chars().count();
+                    let left = match aligns.get(i) {
+                        Some(Align::Right) => padding,
+                        Some(Align::Center) => padding / 2,
+                        _ => 0,
+                    };
+                    s.push_str("| ");
+                    s.push_str(&" ".repeat(left));
+                    s.push_str(cell);
+                    s.push_str(&" ".repeat(padding - left + 1));
+                }
+                s.push_str("|\n");
+            }
+            None => {
+                let rule: Vec<_> = widths.iter().map(|width| "-".repeat(width + 2)).collect();
+                s.push_str(&format!("|{}|\n", rule.join("+")));
+            }
+        }
+    }
+    s
+}
+
+/// Returns `true` if all non-empty cells of this row are alignment or width
+/// cookies, e.g. `<r>` or `<l10>`.
+fn is_cookie_row(row: &[&str]) -> bool {
+    row.iter().any(|cell| !cell.is_empty())
+        && row.iter().all(|cell| {
+            cell.is_empty()
+                || cell
+                    .strip_prefix('<')
+                    .and_then(|cell| cell.strip_suffix('>'))
+                    .map(|cell| cell.trim_start_matches(['l', 'c', 'r']))
+                    .is_some_and(|width| width.bytes().all(|c| c.is_ascii_digit()))
+        })
+}
+
+/// Returns `true` if this cell looks like a number, like
+/// `org-table-number-regexp`.
+fn is_number(cell: &str) -> bool {
+    let cell = cell.strip_prefix(['<', '>']).unwrap_or(cell);
+    match cell.find(|c: char| c.is_ascii_digit()) {
+        Some(i) => {
+            cell[..i].chars().all(|c| "-+^.".contains(c))
+                && cell[i..]
+                    .chars()
+                    .all(|c| "-+^.0123456789eEdDx()%:".contains(c))
+        }
+        None => false,
+    }
+}
+
 #[test]
 fn parse_table_el_() {
     assert_eq!(
@@ -158,3 +334,21 @@ fn parse_table_el_() {
     assert!(Table::parse_table_el("").is_none());
     assert!(Table::parse_table_el("+----|---").is_none());
 }
+
+#[test]
+fn realign() {
+    assert_eq!(
+        Table::realign("|a|b|\n|-+-|\n|1|x|\n|22|\n|-\n"),
+        "|  a | b |\n|----+---|\n|  1 | x |\n| 22 |   |\n|----+---|\n"
+    );
+    assert_eq!(
+        Table::realign("| <c> | <l> |\n| abc | 1 |\n| d | 23 |\n"),
+        "| <c> | <l> |\n| abc | 1   |\n|  d  | 23  |\n"
+    );
+    assert_eq!(Table::realign("|  |\n"), "|   |\n");
+    assert!(is_number("-1.5e3"));
+    assert!(is_number("1:30"));
+    assert!(!is_number("v1.0"));
+    assert!(is_cookie_row(&["<r>", "", "<10>"]));
+    assert!(!is_cookie_row(&["<b>"]));
+}
@@ -1,18 +1,26 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
 
 use nom::{
-    bytes::complete::{tag, take, take_till, take_while, take_while_m_n},
-    character::complete::{space0, space1},
-    combinator::{map, map_res, opt},
+    branch::alt,
+    bytes::complete::{tag, take, take_till, take_while1, take_while_m_n},
+    character::complete::{digit1, one_of, space0, space1},
+    combinator::{map, map_res, opt, recognize},
     error::ParseError,
-    sequence::preceded,
+    sequence::{preceded, tuple},
     IResult,
 };
 
 /// Datetime Struct
-#[cfg_attr(test, derive(PartialEq))]
+///
+/// Datetimes are ordered chronologically, and a date without time comes
+/// before any time of the same day.
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Datetime<'a> {
     pub year: u16,
     pub month: u8,
@@ -24,6 +32,33 @@ pub struct Datetime<'a> {
     pub minute: Option<u8>,
 }
 
+impl Ord for Datetime<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (
+            self.year,
+            self.month,
+            self.day,
+            self.hour,
+            self.minute,
+            &self.dayname,
+        )
+            .cmp(&(
+                other.year,
+                other.month,
+                other.day,
+                other.hour,
+                other.minute,
+                &other.dayname,
+            ))
+    }
+}
+
+impl PartialOrd for Datetime<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Datetime<'_> {
     pub fn into_owned(self) -> Datetime<'static> {
         Datetime {
@@ -35,12 +70,330 @@ impl Datetime<'_> {
             minute: self.minute,
         }
     }
+
+    /// Returns the day of week, counted from Monday, e.g. `0` for Monday
+    /// and `6` for Sunday, or `None` if the month is out of range.
+    pub fn weekday(&self) -> Option<u8> {
+        const OFFSETS: [u32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+
+        let offset = OFFSETS.get(usize::from(self.month).checked_sub(1)?)?;
+        let year = u32::from(self.year) + 400 - u32::from(self.month < 3);
+        let sunday = (year + year / 4 - year / 100 + year / 400 + offset + u32::from(self.day)) % 7;
+
+        Some(((sunday + 6) % 7) as u8)
+    }
+
+    /// Returns the number of days since 1970-01-01, or `None` if the month
+    /// or the day is out of range.
+    pub(crate) fn days(&self) -> Option<i64> {
+        if !(1..=12).contains(&self.month)
+            || self.day == 0
+            || self.day > days_in_month(self.year, self.month)
+        {
+            return None;
+        }
+
+        let (month, day) = (i64::from(self.month), i64::from(self.day));
+        let year = i64::from(self.year) - i64::from(month <= 2);
+        let era = year.div_euclid(400);
+        let yoe = year - era * 400;
+        let doy = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+        Some(era * 146_097 + doe - 719_468)
+    }
+
+    /// Returns the number of minutes since 1970-01-01 00:00, or `None` if the
+    /// date or the time is out of range. Datetime without time is counted
+    /// from midnight.
+    pub(crate) fn minutes(&self) -> Option<i64> {
+        let (hour, minute) = (self.hour.unwrap_or(0), self.minute.unwrap_or(0));
+        if hour > 23 || minute > 59 {
+            return None;
+        }
+        Some(self.days()? * 1440 + i64::from(hour) * 60 + i64::from(minute))
+    }
+
+    /// Creates a date-only datetime from the number of days since 1970-01-01,
+    /// with an English day name, or `None` if its year isn't in `0..=9999`.
+    pub(crate) fn from_days(days: i64) -> Option<Datetime<'static>> {
+        let days = days.checked_add(719_468)?;
+        let era = days.div_euclid(146_097);
+        let doe = days - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + i64::from(month <= 2);
+        if !(0..=9999).contains(&year) {
+            return None;
+        }
+
+        let mut datetime = Datetime {
+            year: year as u16,
+            month: month as u8,
+            day: day as u8,
+            dayname: "".into(),
+            hour: None,
+            minute: None,
+        };
+        if let Some(weekday) = datetime.weekday() {
+            datetime.dayname = DayNames::ENGLISH.0[usize::from(weekday)].into();
+        }
+        Some(datetime)
+    }
+
+    /// Returns this datetime shifted by `value` units, or `None` if it's
+    /// invalid or the result is out of range. Days overflowing a shorter month are clamped, e.g. one month
+    /// after `2023-01-31` is `2023-02-28`.
+    pub(crate) fn shift(&self, value: i64, unit: TimeUnit) -> Option<Datetime<'static>> {
+        let mut datetime = match unit {
+            TimeUnit::Hour => {
+                let minutes = self.minutes()?.checked_add(value.checked_mul(60)?)?;
+                let mut datetime = Datetime::from_days(minutes.div_euclid(1440))?;
+                datetime.hour = Some((minutes.rem_euclid(1440) / 60) as u8);
+                datetime.minute = Some(minutes.rem_euclid(60) as u8);
+                return Some(self.with_day_names(datetime));
+            }
+            TimeUnit::Day | TimeUnit::Week => {
+                let days = if unit == TimeUnit::Week {
+                    value.checked_mul(7)?
+                } else {
+                    value
+                };
+                Datetime::from_days(self.days()?.checked_add(days)?)?
+            }
+            TimeUnit::Month | TimeUnit::Year => {
+                self.days()?;
+                let months = if unit == TimeUnit::Year {
+                    value.checked_mul(12)?
+                } else {
+                    value
+                };
+                let months =
+                    (i64::from(self.year) * 12 + i64::from(self.month) - 1).checked_add(months)?;
+                let year = u16::try_from(months.div_euclid(12)).ok()?;
+                let month = (months.rem_euclid(12) + 1) as u8;
+                let day = self.day.min(days_in_month(year, month));
+                Datetime::from_days(
+                    Datetime {
+                        year,
+                        month,
+                        day,
+                        dayname: "".into(),
+                        hour: None,
+                        minute: None,
+                    }
+                    .days()?,
+                )?
+            }
+        };
+        datetime.hour = self.hour;
+        datetime.minute = self.minute;
+        Some(self.with_day_names(datetime))
+    }
+
+    /// Rewrites the day name of `datetime` in the locale of this datetime.
+    fn with_day_names(&self, mut datetime: Datetime<'static>) -> Datetime<'static> {
+        datetime.dayname = if self.dayname.is_empty() {
+            "".into()
+        } else {
+            let weekday = datetime.weekday().unwrap_or_default();
+            self.day_names().0[usize::from(weekday)].into()
+        };
+        datetime
+    }
+
+    /// Returns the day names whose locale this datetime's day name is
+    /// written in, or English if it's unknown.
+    pub(crate) fn day_names(&self) -> DayNames {
+        let weekday = match self.weekday() {
+            Some(weekday) => usize::from(weekday),
+            None => return DayNames::ENGLISH,
+        };
+
+        [
+            DayNames::ENGLISH,
+            DayNames::GERMAN,
+            DayNames::FRENCH,
+            DayNames::SPANISH,
+            DayNames::JAPANESE,
+        ]
+        .iter()
+        .find(|names| names.0[weekday] == self.dayname)
+        .copied()
+        .unwrap_or(DayNames::ENGLISH)
+    }
+
+    /// Formats this datetime with the day name from `day_names`, e.g.
+    /// `2023-02-01 Mi 10:00`. The original day name is kept if the weekday
+    /// can't be determined.
+    ///
+    /// ```rust
+    /// # use orgize::elements::{Datetime, DayNames};
+    /// #
+    /// let datetime = Datetime {
+    ///     year: 2023,
+    ///     month: 2,
+    ///     day: 1,
+    ///     dayname: "Wed".into(),
+    ///     hour: Some(10),
+    ///     minute: Some(0),
+    /// };
+    ///
+    /// assert_eq!(datetime.format(&DayNames::GERMAN), "2023-02-01 Mi 10:00");
+    /// ```
+    pub fn format(&self, day_names: &DayNames) -> String {
+        let dayname = match self.weekday() {
+            Some(weekday) => day_names.0[usize::from(weekday)],
+            None => &self.dayname,
+        };
+
+        let mut s = format!("{:04}-{:02}-{:02}", self.year, self.month, self.day);
+        if !dayname.is_empty() {
+            s.push(' ');
+            s.push_str(dayname);
+        }
+        if let (Some(hour), Some(minute)) = (self.hour, self.minute) {
+            s.push_str(&format!(" {:02}:{:02}", hour, minute));
+        }
+        s
+    }
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Abbreviated day names of a locale, from Monday to Sunday
+///
+/// Day names are never validated when parsing, so timestamps written by
+/// any locale can be parsed, while these ones are used to format them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayNames(pub [&'static str; 7]);
+
+impl DayNames {
+    pub const ENGLISH: DayNames = DayNames(["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]);
+    pub const GERMAN: DayNames = DayNames(["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"]);
+    pub const FRENCH: DayNames = DayNames(["lun.", "mar.", "mer.", "jeu.", "ven.", "sam.", "dim."]);
+    pub const SPANISH: DayNames = DayNames(["lun", "mar", "mié", "jue", "vie", "sáb", "dom"]);
+    pub const JAPANESE: DayNames = DayNames(["月", "火", "水", "木", "金", "土", "日"]);
+}
+
+impl Default for DayNames {
+    fn default() -> Self {
+        DayNames::ENGLISH
+    }
 }
 
 #[cfg(feature = "chrono")]
 mod chrono {
-    use super::Datetime;
+    use super::{Datetime, Timestamp};
     use chrono::*;
+    use core::cmp::Ordering;
+
+    impl Datetime<'_> {
+        /// Converts into `NaiveDate`, returns `None` if the date is invalid.
+        pub fn to_date(&self) -> Option<NaiveDate> {
+            NaiveDate::from_ymd_opt(self.year.into(), self.month.into(), self.day.into())
+        }
+
+        /// Converts into `NaiveDateTime`, returns `None` if the date or time
+        /// is invalid. Datetime without time is converted to midnight.
+        pub fn to_naive_datetime(&self) -> Option<NaiveDateTime> {
+            let time = NaiveTime::from_hms_opt(
+                self.hour.unwrap_or_default().into(),
+                self.minute.unwrap_or_default().into(),
+                0,
+            )?;
+            Some(NaiveDateTime::new(self.to_date()?, time))
+        }
+    }
+
+    impl Timestamp<'_> {
+        /// Converts the start of this timestamp into `NaiveDate`.
+        ///
+        /// Returns `None` for diary timestamps or invalid dates.
+        ///
+        /// ```rust
+        /// # use chrono::NaiveDate;
+        /// # use orgize::elements::Timestamp;
+        /// # use orgize::{Element, Event, Org};
+        /// #
+        /// let org = Org::parse("<2023-02-01 Wed 10:00>--<2023-02-03 Fri>");
+        /// let timestamp = org
+        ///     .iter()
+        ///     .find_map(|event| match event {
+        ///         Event::Start(Element::Timestamp(timestamp)) => Some(timestamp),
+        ///         _ => None,
+        ///     })
+        ///     .unwrap();
+        ///
+        /// assert_eq!(timestamp.to_date(), NaiveDate::from_ymd_opt(2023, 2, 1));
+        /// assert_eq!(
+        ///     timestamp.end_naive_datetime(),
+        ///     NaiveDate::from_ymd_opt(2023, 2, 3).unwrap().and_hms_opt(0, 0, 0)
+        /// );
+        /// assert!(*timestamp > NaiveDate::from_ymd_opt(2023, 1, 31).unwrap());
+        /// assert!(*timestamp < NaiveDate::from_ymd_opt(2023, 2, 1).unwrap().and_hms_opt(12, 0, 0).unwrap());
+        /// ```
+        pub fn to_date(&self) -> Option<NaiveDate> {
+            self.start()?.to_date()
+        }
+
+        /// Converts the start of this timestamp into `NaiveDateTime`.
+        ///
+        /// Returns `None` for diary timestamps or invalid dates.
+        pub fn to_naive_datetime(&self) -> Option<NaiveDateTime> {
+            self.start()?.to_naive_datetime()
+        }
+
+        /// Converts the end of this timestamp into `NaiveDateTime`, which is
+        /// the same as its start unless it's a range.
+        ///
+        /// Returns `None` for diary timestamps or invalid dates.
+        pub fn end_naive_datetime(&self) -> Option<NaiveDateTime> {
+            match self {
+                Timestamp::ActiveRange { end, .. } | Timestamp::InactiveRange { end, .. } => {
+                    end.to_naive_datetime()
+                }
+                _ => self.to_naive_datetime(),
+            }
+        }
+    }
+
+    /// Compares the start of timestamp with a date, only the date part of
+    /// timestamp is compared.
+    impl PartialEq<NaiveDate> for Timestamp<'_> {
+        fn eq(&self, other: &NaiveDate) -> bool {
+            self.to_date().as_ref() == Some(other)
+        }
+    }
+
+    impl PartialOrd<NaiveDate> for Timestamp<'_> {
+        fn partial_cmp(&self, other: &NaiveDate) -> Option<Ordering> {
+            self.to_date()?.partial_cmp(other)
+        }
+    }
+
+    /// Compares the start of timestamp with a datetime.
+    impl PartialEq<NaiveDateTime> for Timestamp<'_> {
+        fn eq(&self, other: &NaiveDateTime) -> bool {
+            self.to_naive_datetime().as_ref() == Some(other)
+        }
+    }
+
+    impl PartialOrd<NaiveDateTime> for Timestamp<'_> {
+        fn partial_cmp(&self, other: &NaiveDateTime) -> Option<Ordering> {
+            self.to_naive_datetime()?.partial_cmp(other)
+        }
+    }
 
     impl Into<NaiveDate> for Datetime<'_> {
         fn into(self) -> NaiveDate {
@@ -68,17 +421,18 @@ mod chrono {
 
     impl Into<NaiveDate> for &Datetime<'_> {
         fn into(self) -> NaiveDate {
-            NaiveDate::from_ymd(self.year.into(), self.month.into(), self.day.into())
+            self.to_date().expect("invalid date")
         }
     }
 
     impl Into<NaiveTime> for &Datetime<'_> {
         fn into(self) -> NaiveTime {
-            NaiveTime::from_hms(
+            NaiveTime::from_hms_opt(
                 self.hour.unwrap_or_default().into(),
                 self.minute.unwrap_or_default().into(),
                 0,
             )
+            .expect("invalid time")
         }
     }
 
@@ -90,17 +444,178 @@ mod chrono {
 
     impl Into<DateTime<Utc>> for &Datetime<'_> {
         fn into(self) -> DateTime<Utc> {
-            DateTime::from_utc(self.into(), Utc)
+            DateTime::from_naive_utc_and_offset(self.into(), Utc)
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time {
+    use super::{Datetime, Timestamp};
+    use core::convert::TryFrom;
+    use time::{error::ComponentRange, Date, Duration, Month, PrimitiveDateTime, Time};
+
+    impl TryFrom<&Datetime<'_>> for Date {
+        type Error = ComponentRange;
+
+        fn try_from(datetime: &Datetime<'_>) -> Result<Self, Self::Error> {
+            Date::from_calendar_date(
+                datetime.year.into(),
+                Month::try_from(datetime.month)?,
+                datetime.day,
+            )
+        }
+    }
+
+    impl TryFrom<&Datetime<'_>> for Time {
+        type Error = ComponentRange;
+
+        fn try_from(datetime: &Datetime<'_>) -> Result<Self, Self::Error> {
+            Time::from_hms(
+                datetime.hour.unwrap_or_default(),
+                datetime.minute.unwrap_or_default(),
+                0,
+            )
+        }
+    }
+
+    /// Datetime without time is converted to midnight.
+    impl TryFrom<&Datetime<'_>> for PrimitiveDateTime {
+        type Error = ComponentRange;
+
+        fn try_from(datetime: &Datetime<'_>) -> Result<Self, Self::Error> {
+            Ok(PrimitiveDateTime::new(
+                Date::try_from(datetime)?,
+                Time::try_from(datetime)?,
+            ))
+        }
+    }
+
+    impl Datetime<'_> {
+        /// Returns this datetime moved by `duration`, or `None` if it's
+        /// invalid or the result is out of range.
+        ///
+        /// Time is kept if this datetime has time or `duration` isn't a
+        /// multiple of days. Day name is written in the same locale if it's
+        /// one of [`DayNames`], otherwise in English.
+        ///
+        /// ```rust
+        /// # use orgize::elements::{Datetime, DayNames};
+        /// # use time::Duration;
+        /// #
+        /// let datetime = Datetime {
+        ///     year: 2023,
+        ///     month: 2,
+        ///     day: 27,
+        ///     dayname: "Mo".into(),
+        ///     hour: None,
+        ///     minute: None,
+        /// };
+        ///
+        /// let next = datetime.checked_add(Duration::days(2)).unwrap();
+        /// assert_eq!(next.format(&DayNames::GERMAN), "2023-03-01 Mi");
+        /// assert_eq!(next.dayname, "Mi");
+        ///
+        /// let later = datetime.checked_add(Duration::minutes(90)).unwrap();
+        /// assert_eq!(later.format(&DayNames::GERMAN), "2023-02-27 Mo 01:30");
+        /// ```
+        pub fn checked_add(&self, duration: Duration) -> Option<Datetime<'static>> {
+            let result = PrimitiveDateTime::try_from(self)
+                .ok()?
+                .checked_add(duration)?;
+
+            let year = u16::try_from(result.year()).ok()?;
+            let has_time = self.hour.is_some() || duration.whole_seconds() % 86_400 != 0;
+
+            let mut datetime = Datetime {
+                year,
+                month: result.month().into(),
+                day: result.day(),
+                dayname: "".into(),
+                hour: if has_time { Some(result.hour()) } else { None },
+                minute: if has_time {
+                    Some(result.minute())
+                } else {
+                    None
+                },
+            };
+
+            if !self.dayname.is_empty() {
+                let names = self.day_names();
+                datetime.dayname = names.0[usize::from(datetime.weekday()?)].into();
+            }
+
+            Some(datetime)
+        }
+
+        /// Returns this datetime moved back by `duration`, see [`checked_add`].
+        ///
+        /// [`checked_add`]: #method.checked_add
+        pub fn checked_sub(&self, duration: Duration) -> Option<Datetime<'static>> {
+            self.checked_add(-duration)
+        }
+    }
+
+    impl Timestamp<'_> {
+        /// Converts the start of this timestamp into `time::Date`.
+        ///
+        /// Returns `None` for diary timestamps or invalid dates.
+        pub fn to_time_date(&self) -> Option<Date> {
+            Date::try_from(self.start()?).ok()
+        }
+
+        /// Converts the start of this timestamp into `PrimitiveDateTime`.
+        ///
+        /// Returns `None` for diary timestamps or invalid dates.
+        pub fn to_primitive_datetime(&self) -> Option<PrimitiveDateTime> {
+            PrimitiveDateTime::try_from(self.start()?).ok()
+        }
+
+        /// Returns the duration from the start to the end of this timestamp,
+        /// which is zero unless it's a range.
+        ///
+        /// Returns `None` for diary timestamps or invalid dates.
+        ///
+        /// ```rust
+        /// # use orgize::{Element, Event, Org};
+        /// # use time::Duration;
+        /// #
+        /// let org = Org::parse("<2023-02-01 Wed 10:00-11:30>");
+        /// let timestamp = org
+        ///     .iter()
+        ///     .find_map(|event| match event {
+        ///         Event::Start(Element::Timestamp(timestamp)) => Some(timestamp),
+        ///         _ => None,
+        ///     })
+        ///     .unwrap();
+        ///
+        /// assert_eq!(timestamp.range_duration(), Some(Duration::minutes(90)));
+        /// ```
+        pub fn range_duration(&self) -> Option<Duration> {
+            match self {
+                Timestamp::ActiveRange { start, end, .. }
+                | Timestamp::InactiveRange { start, end, .. } => Some(
+                    PrimitiveDateTime::try_from(end).ok()?
+                        - PrimitiveDateTime::try_from(start).ok()?,
+                ),
+                Timestamp::Active { start, .. } | Timestamp::Inactive { start, .. } => {
+                    PrimitiveDateTime::try_from(start).ok()?;
+                    Some(Duration::ZERO)
+                }
+                Timestamp::Diary { .. } => None,
+            }
         }
     }
 }
 
 /// Timestamp Object
-#[cfg_attr(test, derive(PartialEq))]
+///
+/// Timestamps are ordered by their starts and then their ends, while diary
+/// timestamps come first.
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 #[cfg_attr(feature = "ser", serde(rename_all = "kebab-case"))]
 #[cfg_attr(feature = "ser", serde(tag = "timestamp_type"))]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Timestamp<'a> {
     Active {
         start: Datetime<'a>,
@@ -150,6 +665,463 @@ impl Timestamp<'_> {
         parse_diary::<()>(input).ok()
     }
 
+    /// Returns the start of this timestamp, or `None` for diary timestamps.
+    pub fn start(&self) -> Option<&Datetime<'_>> {
+        match self {
+            Timestamp::Active { start, .. }
+            | Timestamp::Inactive { start, .. }
+            | Timestamp::ActiveRange { start, .. }
+            | Timestamp::InactiveRange { start, .. } => Some(start),
+            Timestamp::Diary { .. } => None,
+        }
+    }
+
+    /// Returns the end of this range timestamp, or `None` for other
+    /// timestamps.
+    pub fn end(&self) -> Option<&Datetime<'_>> {
+        match self {
+            Timestamp::ActiveRange { end, .. } | Timestamp::InactiveRange { end, .. } => Some(end),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of days from `now` until the start of this
+    /// timestamp, negative if it's in the past, or `None` if it's invalid or
+    /// a diary timestamp.
+    ///
+    /// ```rust
+    /// # use orgize::elements::{Datetime, Timestamp};
+    /// # use orgize::{Element, Event, Org};
+    /// #
+    /// let org = Org::parse("DEADLINE: <2023-03-01 Wed> <2023-02-20 Mon 10:00>");
+    /// let timestamps: Vec<_> = org
+    ///     .iter()
+    ///     .filter_map(|event| match event {
+    ///         Event::Start(Element::Timestamp(timestamp)) => Some(timestamp),
+    ///         _ => None,
+    ///     })
+    ///     .collect();
+    /// let now = timestamps[1].start().unwrap();
+    ///
+    /// assert_eq!(timestamps[0].days_until(now), Some(9));
+    /// assert!(!timestamps[0].is_overdue(now));
+    /// assert!(timestamps[1] < timestamps[0]);
+    /// ```
+    pub fn days_until(&self, now: &Datetime) -> Option<i64> {
+        Some(self.start()?.days()? - now.days()?)
+    }
+
+    /// Returns `true` if the start of this timestamp is before `now`.
+    /// Timestamps without time are overdue from the next day on.
+    pub fn is_overdue(&self, now: &Datetime) -> bool {
+        let start = match self.start() {
+            Some(start) => start,
+            None => return false,
+        };
+        if start.hour.is_some() {
+            matches!((start.minutes(), now.minutes()), (Some(start), Some(now)) if start < now)
+        } else {
+            matches!((start.days(), now.days()), (Some(start), Some(now)) if start < now)
+        }
+    }
+
+    /// Returns the values compared by the ordering of timestamps.
+    #[allow(clippy::type_complexity)]
+    fn ordering_key(
+        &self,
+    ) -> (
+        Option<&Datetime<'_>>,
+        Option<&Datetime<'_>>,
+        u8,
+        Option<&str>,
+        Option<&str>,
+        Option<&str>,
+    ) {
+        let (kind, repeater, delay, value) = match self {
+            Timestamp::Active {
+                repeater, delay, ..
+            } => (0, repeater, delay, None),
+            Timestamp::Inactive {
+                repeater, delay, ..
+            } => (1, repeater, delay, None),
+            Timestamp::ActiveRange {
+                repeater, delay, ..
+            } => (2, repeater, delay, None),
+            Timestamp::InactiveRange {
+                repeater, delay, ..
+            } => (3, repeater, delay, None),
+            Timestamp::Diary { value } => (4, &None, &None, Some(&**value)),
+        };
+        (
+            self.start(),
+            self.end(),
+            kind,
+            repeater.as_deref(),
+            delay.as_deref(),
+            value,
+        )
+    }
+
+    /// Returns the repeater and the delay of this timestamp, each preceded
+    /// by a space, e.g. ` +1w -2d`.
+    pub(crate) fn repeater_and_delay(&self) -> String {
+        let (repeater, delay) = match self {
+            Timestamp::Active {
+                repeater, delay, ..
+            }
+            | Timestamp::Inactive {
+                repeater, delay, ..
+            }
+            | Timestamp::ActiveRange {
+                repeater, delay, ..
+            }
+            | Timestamp::InactiveRange {
+                repeater, delay, ..
+            } => (repeater, delay),
+            Timestamp::Diary { .. } => return String::new(),
+        };
+
+        let mut s = String::new();
+        for value in repeater.iter().chain(delay) {
+            s.push(' ');
+            s.push_str(value);
+        }
+        s
+    }
+
+    /// Returns the parsed repeater of this timestamp.
+    pub fn repeater(&self) -> Option<Repeater> {
+        match self {
+            Timestamp::Active { repeater, .. }
+            | Timestamp::Inactive { repeater, .. }
+            | Timestamp::ActiveRange { repeater, .. }
+            | Timestamp::InactiveRange { repeater, .. } => Repeater::parse(repeater.as_ref()?),
+            Timestamp::Diary { .. } => None,
+        }
+    }
+
+    /// Returns the parsed delay of this timestamp.
+    pub fn delay(&self) -> Option<Delay> {
+        match self {
+            Timestamp::Active { delay, .. }
+            | Timestamp::Inactive { delay, .. }
+            | Timestamp::ActiveRange { delay, .. }
+            | Timestamp::InactiveRange { delay, .. } => Delay::parse(delay.as_ref()?),
+            Timestamp::Diary { .. } => None,
+        }
+    }
+
+    /// Returns the next occurrence of this repeating timestamp, after the
+    /// task is completed at `now`, as org-mode does when marking it done:
+    ///
+    /// + `+1w` shifts it by one week,
+    /// + `++1w` shifts it by weeks until it's after `now`,
+    /// + `.+1w` shifts it to one week after `now`, keeping the time of day.
+    ///
+    /// Returns `None` if this timestamp has no repeater, its interval is zero
+    /// or it's invalid.
+    ///
+    /// ```rust
+    /// # use orgize::elements::{DayNames, Timestamp};
+    /// # use orgize::{Element, Event, Org};
+    /// #
+    /// let org = Org::parse("<2023-01-02 Mon 09:00 ++1w> <2023-01-20 Fri 18:00>");
+    /// let timestamps: Vec<_> = org
+    ///     .iter()
+    ///     .filter_map(|event| match event {
+    ///         Event::Start(Element::Timestamp(timestamp)) => Some(timestamp),
+    ///         _ => None,
+    ///     })
+    ///     .collect();
+    /// let now = timestamps[1].start().unwrap();
+    ///
+    /// assert_eq!(
+    ///     timestamps[0].next_repeat(now).unwrap().format(&DayNames::ENGLISH),
+    ///     "<2023-01-23 Mon 09:00 ++1w>"
+    /// );
+    /// ```
+    pub fn next_repeat(&self, now: &Datetime) -> Option<Timestamp<'static>> {
+        let repeater = self.repeater()?;
+        let start = self.start()?;
+        let value = i64::try_from(repeater.value)
+            .ok()
+            .filter(|value| *value > 0)?;
+
+        let next = match repeater.ty {
+            RepeaterType::Cumulate => start.shift(value, repeater.unit)?,
+            RepeaterType::CatchUp => {
+                let has_time = start.hour.is_some() || repeater.unit == TimeUnit::Hour;
+                let is_future = |datetime: &Datetime| {
+                    if has_time {
+                        datetime.minutes() > now.minutes()
+                    } else {
+                        datetime.days() > now.days()
+                    }
+                };
+                // skips the repeats before `now` at once, then steps to the
+                // first one after it
+                let mut n = (units_between(start, now, repeater.unit)? / value).max(1);
+                let mut next = start.shift(value.checked_mul(n)?, repeater.unit)?;
+                while !is_future(&next) {
+                    n += 1;
+                    next = start.shift(value.checked_mul(n)?, repeater.unit)?;
+                }
+                next
+            }
+            RepeaterType::Restart => {
+                let mut base = now.clone().into_owned();
+                if repeater.unit != TimeUnit::Hour {
+                    base.hour = start.hour;
+                    base.minute = start.minute;
+                }
+                base.dayname = start.dayname.clone().into_owned().into();
+                base.shift(value, repeater.unit)?
+            }
+        };
+
+        let delta = next.minutes()? - start.minutes()?;
+        let shift_end = |end: &Datetime| {
+            let minutes = end.minutes()?.checked_add(delta)?;
+            let mut next = Datetime::from_days(minutes.div_euclid(1440))?;
+            if end.hour.is_some() {
+                next.hour = Some((minutes.rem_euclid(1440) / 60) as u8);
+                next.minute = Some(minutes.rem_euclid(60) as u8);
+            }
+            Some(end.with_day_names(next))
+        };
+
+        Some(match self.clone().into_owned() {
+            Timestamp::Active {
+                repeater, delay, ..
+            } => Timestamp::Active {
+                start: next,
+                repeater,
+                delay,
+            },
+            Timestamp::Inactive {
+                repeater, delay, ..
+            } => Timestamp::Inactive {
+                start: next,
+                repeater,
+                delay,
+            },
+            Timestamp::ActiveRange {
+                end,
+                repeater,
+                delay,
+                ..
+            } => Timestamp::ActiveRange {
+                end: shift_end(&end)?,
+                start: next,
+                repeater,
+                delay,
+            },
+            Timestamp::InactiveRange {
+                end,
+                repeater,
+                delay,
+                ..
+            } => Timestamp::InactiveRange {
+                end: shift_end(&end)?,
+                start: next,
+                repeater,
+                delay,
+            },
+            Timestamp::Diary { .. } => return None,
+        })
+    }
+
+    /// Formats this timestamp with the day names from `day_names`.
+    /// Its `Display` implementation keeps the locale of its own day names.
+    ///
+    /// ```rust
+    /// # use orgize::elements::{DayNames, Timestamp};
+    /// # use orgize::{Element, Org};
+    /// #
+    /// let org = Org::parse("<2023-02-01 Mi>--<2023-02-03 Fr>");
+    /// let timestamp = org
+    ///     .iter()
+    ///     .find_map(|event| match event {
+    ///         orgize::Event::Start(Element::Timestamp(timestamp)) => Some(timestamp),
+    ///         _ => None,
+    ///     })
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     timestamp.format(&DayNames::ENGLISH),
+    ///     "<2023-02-01 Wed>--<2023-02-03 Fri>"
+    /// );
+    /// ```
+    pub fn format(&self, day_names: &DayNames) -> String {
+        let suffix = self.repeater_and_delay();
+        match self {
+            Timestamp::Active { start, .. } => format!("<{}{}>", start.format(day_names), suffix),
+            Timestamp::Inactive { start, .. } => {
+                format!("[{}{}]", start.format(day_names), suffix)
+            }
+            Timestamp::ActiveRange { start, end, .. } => format!(
+                "<{}{}>--<{}>",
+                start.format(day_names),
+                suffix,
+                end.format(day_names)
+            ),
+            Timestamp::InactiveRange { start, end, .. } => format!(
+                "[{}{}]--[{}]",
+                start.format(day_names),
+                suffix,
+                end.format(day_names)
+            ),
+            Timestamp::Diary { value } => format!("<%%({})>", value),
+        }
+    }
+
+    /// Returns `true` if this timestamp is active, i.e. it's written in
+    /// angle brackets and shows up in agenda views.
+    pub fn is_active(&self) -> bool {
+        !matches!(
+            self,
+            Timestamp::Inactive { .. } | Timestamp::InactiveRange { .. }
+        )
+    }
+
+    /// Returns an active copy of this timestamp.
+    pub fn to_active(&self) -> Timestamp<'static> {
+        match self.clone().into_owned() {
+            Timestamp::Inactive {
+                start,
+                repeater,
+                delay,
+            } => Timestamp::Active {
+                start,
+                repeater,
+                delay,
+            },
+            Timestamp::InactiveRange {
+                start,
+                end,
+                repeater,
+                delay,
+            } => Timestamp::ActiveRange {
+                start,
+                end,
+                repeater,
+                delay,
+            },
+            timestamp => timestamp,
+        }
+    }
+
+    /// Returns an inactive copy of this timestamp. Diary timestamps are
+    /// always active, so they're returned unchanged.
+    ///
+    /// ```rust
+    /// # use orgize::{Element, Event, Org};
+    /// #
+    /// let org = Org::parse("<2023-01-02 Mon 10:00 +1w>--<2023-01-03 Tue>");
+    /// let timestamp = org
+    ///     .iter()
+    ///     .find_map(|event| match event {
+    ///         Event::Start(Element::Timestamp(timestamp)) => Some(timestamp),
+    ///         _ => None,
+    ///     })
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     timestamp.to_inactive().to_string(),
+    ///     "[2023-01-02 Mon 10:00 +1w]--[2023-01-03 Tue]"
+    /// );
+    /// ```
+    pub fn to_inactive(&self) -> Timestamp<'static> {
+        match self.clone().into_owned() {
+            Timestamp::Active {
+                start,
+                repeater,
+                delay,
+            } => Timestamp::Inactive {
+                start,
+                repeater,
+                delay,
+            },
+            Timestamp::ActiveRange {
+                start,
+                end,
+                repeater,
+                delay,
+            } => Timestamp::InactiveRange {
+                start,
+                end,
+                repeater,
+                delay,
+            },
+            timestamp => timestamp,
+        }
+    }
+
+    /// Returns a copy of this timestamp with its start and end shifted by
+    /// `days`, keeping the time of day and the locale of day names.
+    ///
+    /// Returns `None` if it's invalid or a diary timestamp.
+    ///
+    /// ```rust
+    /// # use orgize::{Element, Event, Org};
+    /// #
+    /// let org = Org::parse("<2023-01-30 Mo 10:00>");
+    /// let timestamp = org
+    ///     .iter()
+    ///     .find_map(|event| match event {
+    ///         Event::Start(Element::Timestamp(timestamp)) => Some(timestamp),
+    ///         _ => None,
+    ///     })
+    ///     .unwrap();
+    ///
+    /// assert_eq!(timestamp.shift(3).unwrap().to_string(), "<2023-02-02 Do 10:00>");
+    /// assert_eq!(timestamp.shift(-30).unwrap().to_string(), "<2022-12-31 Sa 10:00>");
+    /// ```
+    pub fn shift(&self, days: i64) -> Option<Timestamp<'static>> {
+        let mut timestamp = self.clone().into_owned();
+        match &mut timestamp {
+            Timestamp::Active { start, .. } | Timestamp::Inactive { start, .. } => {
+                *start = start.shift(days, TimeUnit::Day)?;
+            }
+            Timestamp::ActiveRange { start, end, .. }
+            | Timestamp::InactiveRange { start, end, .. } => {
+                *start = start.shift(days, TimeUnit::Day)?;
+                *end = end.shift(days, TimeUnit::Day)?;
+            }
+            Timestamp::Diary { .. } => return None,
+        }
+        Some(timestamp)
+    }
+
+    /// Returns a copy of this timestamp with the time of its start set to
+    /// `time` as hour and minute, or removed if `time` is `None`. The end of
+    /// range timestamps is left unchanged.
+    ///
+    /// Returns `None` if `time` is invalid, it's after the end of this range
+    /// timestamp, or this is a diary timestamp.
+    pub fn with_time(&self, time: Option<(u8, u8)>) -> Option<Timestamp<'static>> {
+        if matches!(time, Some((hour, minute)) if hour > 23 || minute > 59) {
+            return None;
+        }
+        let mut timestamp = self.clone().into_owned();
+        match &mut timestamp {
+            Timestamp::Active { start, .. }
+            | Timestamp::Inactive { start, .. }
+            | Timestamp::ActiveRange { start, .. }
+            | Timestamp::InactiveRange { start, .. } => {
+                start.hour = time.map(|(hour, _)| hour);
+                start.minute = time.map(|(_, minute)| minute);
+            }
+            Timestamp::Diary { .. } => return None,
+        }
+        if let (Some(start), Some(end)) = (timestamp.start(), timestamp.end()) {
+            if end.hour.is_some() && start.minutes()? > end.minutes()? {
+                return None;
+            }
+        }
+        Some(timestamp)
+    }
+
     pub fn into_owned(self) -> Timestamp<'static> {
         match self {
             Timestamp::Active {
@@ -199,14 +1171,42 @@ impl Timestamp<'_> {
     }
 }
 
+impl fmt::Display for Timestamp<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let day_names = self
+            .start()
+            .map_or(DayNames::ENGLISH, |start| start.day_names());
+        f.write_str(&self.format(&day_names))
+    }
+}
+
+impl PartialEq for Timestamp<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ordering_key() == other.ordering_key()
+    }
+}
+
+impl Eq for Timestamp<'_> {}
+
+impl Ord for Timestamp<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.ordering_key().cmp(&other.ordering_key())
+    }
+}
+
+impl PartialOrd for Timestamp<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 pub fn parse_active<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&str, Timestamp, E> {
     let (input, _) = tag("<")(input)?;
     let (input, start) = parse_datetime(input)?;
 
     if input.starts_with('-') {
         let (input, (hour, minute)) = parse_time(&input[1..])?;
-        let (input, _) = space0(input)?;
-        // TODO: delay-or-repeater
+        let (input, (repeater, delay)) = parse_repeater_and_delay(input)?;
         let (input, _) = tag(">")(input)?;
         let mut end = start.clone();
         end.hour = Some(hour);
@@ -216,28 +1216,26 @@ pub fn parse_active<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&str,
             Timestamp::ActiveRange {
                 start,
                 end,
-                repeater: None,
-                delay: None,
+                repeater: repeater.map(Into::into),
+                delay: delay.map(Into::into),
             },
         ));
     }
 
-    let (input, _) = space0(input)?;
-    // TODO: delay-or-repeater
+    let (input, (repeater, delay)) = parse_repeater_and_delay(input)?;
     let (input, _) = tag(">")(input)?;
 
     if input.starts_with("--<") {
         let (input, end) = parse_datetime(&input["--<".len()..])?;
-        let (input, _) = space0(input)?;
-        // TODO: delay-or-repeater
+        let (input, (end_repeater, end_delay)) = parse_repeater_and_delay(input)?;
         let (input, _) = tag(">")(input)?;
         Ok((
             input,
             Timestamp::ActiveRange {
                 start,
                 end,
-                repeater: None,
-                delay: None,
+                repeater: repeater.or(end_repeater).map(Into::into),
+                delay: delay.or(end_delay).map(Into::into),
             },
         ))
     } else {
@@ -245,8 +1243,8 @@ pub fn parse_active<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&str,
             input,
             Timestamp::Active {
                 start,
-                repeater: None,
-                delay: None,
+                repeater: repeater.map(Into::into),
+                delay: delay.map(Into::into),
             },
         ))
     }
@@ -258,8 +1256,7 @@ pub fn parse_inactive<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&st
 
     if input.starts_with('-') {
         let (input, (hour, minute)) = parse_time(&input[1..])?;
-        let (input, _) = space0(input)?;
-        // TODO: delay-or-repeater
+        let (input, (repeater, delay)) = parse_repeater_and_delay(input)?;
         let (input, _) = tag("]")(input)?;
         let mut end = start.clone();
         end.hour = Some(hour);
@@ -269,28 +1266,26 @@ pub fn parse_inactive<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&st
             Timestamp::InactiveRange {
                 start,
                 end,
-                repeater: None,
-                delay: None,
+                repeater: repeater.map(Into::into),
+                delay: delay.map(Into::into),
             },
         ));
     }
 
-    let (input, _) = space0(input)?;
-    // TODO: delay-or-repeater
+    let (input, (repeater, delay)) = parse_repeater_and_delay(input)?;
     let (input, _) = tag("]")(input)?;
 
     if input.starts_with("--[") {
         let (input, end) = parse_datetime(&input["--[".len()..])?;
-        let (input, _) = space0(input)?;
-        // TODO: delay-or-repeater
+        let (input, (end_repeater, end_delay)) = parse_repeater_and_delay(input)?;
         let (input, _) = tag("]")(input)?;
         Ok((
             input,
             Timestamp::InactiveRange {
                 start,
                 end,
-                repeater: None,
-                delay: None,
+                repeater: repeater.or(end_repeater).map(Into::into),
+                delay: delay.or(end_delay).map(Into::into),
             },
         ))
     } else {
@@ -298,8 +1293,8 @@ pub fn parse_inactive<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&st
             input,
             Timestamp::Inactive {
                 start,
-                repeater: None,
-                delay: None,
+                repeater: repeater.map(Into::into),
+                delay: delay.map(Into::into),
             },
         ))
     }
@@ -318,6 +1313,34 @@ pub fn parse_diary<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&str,
     ))
 }
 
+/// Parses the optional repeater and delay at the end of a timestamp, e.g.
+/// `+1w` and `-2d`, in any order.
+fn parse_repeater_and_delay<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, (Option<&'a str>, Option<&'a str>), E> {
+    let unit = |input| one_of("hdwmy")(input);
+    let repeater = |input| {
+        recognize(tuple((
+            alt((tag("++"), tag(".+"), tag("+"))),
+            digit1,
+            unit,
+            opt(tuple((tag("/"), digit1, unit))),
+        )))(input)
+    };
+    let delay = |input| recognize(tuple((alt((tag("--"), tag("-"))), digit1, unit)))(input);
+
+    let (input, repeater1) = opt(preceded(space0, repeater))(input)?;
+    let (input, delay) = opt(preceded(space0, delay))(input)?;
+    let (input, repeater2) = if repeater1.is_none() {
+        opt(preceded(space0, repeater))(input)?
+    } else {
+        (input, None)
+    };
+    let (input, _) = space0(input)?;
+
+    Ok((input, (repeater1.or(repeater2), delay)))
+}
+
 fn parse_time<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&str, (u8, u8), E> {
     let (input, hour) = map_res(take_while_m_n(1, 2, |c: char| c.is_ascii_digit()), |num| {
         u8::from_str_radix(num, 10)
@@ -335,15 +1358,18 @@ fn parse_datetime<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&str, D
     let (input, month) = map_res(take(2usize), parse_u8)(input)?;
     let (input, _) = tag("-")(input)?;
     let (input, day) = map_res(take(2usize), parse_u8)(input)?;
-    let (input, _) = space1(input)?;
-    let (input, dayname) = take_while(|c: char| {
-        !c.is_ascii_whitespace()
-            && !c.is_ascii_digit()
-            && c != '+'
-            && c != '-'
-            && c != ']'
-            && c != '>'
-    })(input)?;
+    // day name is optional and can be any word, e.g. `Mi` or `水`
+    let (input, dayname) = opt(preceded(
+        space1,
+        take_while1(|c: char| {
+            !c.is_ascii_whitespace()
+                && !c.is_ascii_digit()
+                && c != '+'
+                && c != '-'
+                && c != ']'
+                && c != '>'
+        }),
+    ))(input)?;
     let (input, (hour, minute)) = map(opt(preceded(space1, parse_time)), |time| {
         (time.map(|t| t.0), time.map(|t| t.1))
     })(input)?;
@@ -354,60 +1380,143 @@ fn parse_datetime<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&str, D
             year,
             month,
             day,
-            dayname: dayname.into(),
+            dayname: dayname.unwrap_or_default().into(),
             hour,
             minute,
         },
     ))
 }
 
-// TODO
-// #[cfg_attr(test, derive(PartialEq))]
-// #[cfg_attr(feature = "ser", derive(serde::Serialize))]
-// #[derive(Debug, Copy, Clone)]
-// pub enum RepeaterType {
-//     Cumulate,
-//     CatchUp,
-//     Restart,
-// }
-
-// #[cfg_attr(test, derive(PartialEq))]
-// #[cfg_attr(feature = "ser", derive(serde::Serialize))]
-// #[derive(Debug, Copy, Clone)]
-// pub enum DelayType {
-//     All,
-//     First,
-// }
-
-// #[cfg_attr(test, derive(PartialEq))]
-// #[cfg_attr(feature = "ser", derive(serde::Serialize))]
-// #[derive(Debug, Copy, Clone)]
-// pub enum TimeUnit {
-//     Hour,
-//     Day,
-//     Week,
-//     Month,
-//     Year,
-// }
-
-// #[cfg_attr(test, derive(PartialEq))]
-// #[cfg_attr(feature = "ser", derive(serde::Serialize))]
-// #[derive(Debug, Copy, Clone)]
-// pub struct Repeater {
-//     pub ty: RepeaterType,
-//     pub value: usize,
-//     pub unit: TimeUnit,
-// }
-
-// #[cfg_attr(test, derive(PartialEq))]
-// #[cfg_attr(feature = "ser", derive(serde::Serialize))]
-// #[derive(Debug, Copy, Clone)]
-// pub struct Delay {
-//     pub ty: DelayType,
-//     pub value: usize,
-//     pub unit: TimeUnit,
-// }
+/// Repeater type of timestamp
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RepeaterType {
+    /// `+`, shifts the date by the interval once
+    Cumulate,
+    /// `++`, shifts the date by the interval until it's in the future
+    CatchUp,
+    /// `.+`, shifts the date to the interval after completion
+    Restart,
+}
+
+/// Delay type of timestamp
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DelayType {
+    /// `-`, applies to all repetitions
+    All,
+    /// `--`, applies to the first repetition only
+    First,
+}
+
+/// Time unit of repeater and delay
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TimeUnit {
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl TimeUnit {
+    fn parse(c: char) -> Option<TimeUnit> {
+        match c {
+            'h' => Some(TimeUnit::Hour),
+            'd' => Some(TimeUnit::Day),
+            'w' => Some(TimeUnit::Week),
+            'm' => Some(TimeUnit::Month),
+            'y' => Some(TimeUnit::Year),
+            _ => None,
+        }
+    }
+}
 
+/// Repeater of timestamp, e.g. `+1w` or `.+2d/3d`
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Repeater {
+    pub ty: RepeaterType,
+    pub value: usize,
+    pub unit: TimeUnit,
+    /// Maximum interval of habits, e.g. `3d` in `.+2d/3d`
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub deadline: Option<(usize, TimeUnit)>,
+}
+
+impl Repeater {
+    /// Parses a repeater string, e.g. `++1w`. Zero intervals like `+0d`
+    /// never repeat, so they're rejected.
+    pub fn parse(input: &str) -> Option<Repeater> {
+        let (ty, input) = if let Some(input) = input.strip_prefix("++") {
+            (RepeaterType::CatchUp, input)
+        } else if let Some(input) = input.strip_prefix(".+") {
+            (RepeaterType::Restart, input)
+        } else {
+            (RepeaterType::Cumulate, input.strip_prefix('+')?)
+        };
+        let (interval, deadline) = match input.split_once('/') {
+            Some((interval, deadline)) => {
+                let deadline = parse_interval(deadline).filter(|(value, _)| *value > 0)?;
+                (interval, Some(deadline))
+            }
+            None => (input, None),
+        };
+        let (value, unit) = parse_interval(interval).filter(|(value, _)| *value > 0)?;
+
+        Some(Repeater {
+            ty,
+            value,
+            unit,
+            deadline,
+        })
+    }
+}
+
+/// Delay of timestamp, e.g. `-2d`
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Delay {
+    pub ty: DelayType,
+    pub value: usize,
+    pub unit: TimeUnit,
+}
+
+impl Delay {
+    /// Parses a delay string, e.g. `--2d`. Zero delays like `-0d` are
+    /// accepted, which disable warnings of deadlines.
+    pub fn parse(input: &str) -> Option<Delay> {
+        let (ty, input) = match input.strip_prefix("--") {
+            Some(input) => (DelayType::First, input),
+            None => (DelayType::All, input.strip_prefix('-')?),
+        };
+        let (value, unit) = parse_interval(input)?;
+
+        Some(Delay { ty, value, unit })
+    }
+}
+
+/// Returns the number of whole `unit`s from `start` to `end`, negative if
+/// `end` is before `start`.
+pub(crate) fn units_between(start: &Datetime, end: &Datetime, unit: TimeUnit) -> Option<i64> {
+    let months = |datetime: &Datetime| i64::from(datetime.year) * 12 + i64::from(datetime.month);
+    Some(match unit {
+        TimeUnit::Hour => (end.minutes()? - start.minutes()?).div_euclid(60),
+        TimeUnit::Day => end.days()? - start.days()?,
+        TimeUnit::Week => (end.days()? - start.days()?).div_euclid(7),
+        TimeUnit::Month => months(end) - months(start),
+        TimeUnit::Year => i64::from(end.year) - i64::from(start.year),
+    })
+}
+
+fn parse_interval(input: &str) -> Option<(usize, TimeUnit)> {
+    let unit = TimeUnit::parse(input.chars().last()?)?;
+    let value = input[..input.len() - 1].parse().ok()?;
+    Some((value, unit))
+}
+
+#[cfg(feature = "std")]
 #[test]
 fn parse() {
     use nom::error::VerboseError;
@@ -482,4 +1591,169 @@ fn parse() {
             },
         ))
     );
+
+    let datetime = |input| match parse_active::<()>(input) {
+        Ok(("", Timestamp::Active { start, .. })) => {
+            (start.dayname.to_string(), start.hour, start.weekday())
+        }
+        _ => panic!("failed to parse {}", input),
+    };
+
+    assert_eq!(datetime("<2023-02-01 Mi>"), ("Mi".into(), None, Some(2)));
+    assert_eq!(
+        datetime("<2023-02-01 mer.>"),
+        ("mer.".into(), None, Some(2))
+    );
+    assert_eq!(
+        datetime("<2023-02-01 水 10:00>"),
+        ("水".into(), Some(10), Some(2))
+    );
+    assert_eq!(datetime("<2023-02-01>"), ("".into(), None, Some(2)));
+    assert_eq!(
+        datetime("<2023-02-01 10:00>"),
+        ("".into(), Some(10), Some(2))
+    );
+    assert_eq!(datetime("<2000-01-01 Sat>").2, Some(5));
+    assert_eq!(datetime("<2024-02-29 Thu>").2, Some(3));
+    assert_eq!(datetime("<0000-01-02 Sun>").2, Some(6));
+    assert_eq!(datetime("<2023-13-01 Mi>").2, None);
+
+    let (_, timestamp) = parse_inactive::<()>("[2023-02-01 Mi 10:00]").unwrap();
+    assert_eq!(
+        timestamp.format(&DayNames::ENGLISH),
+        "[2023-02-01 Wed 10:00]"
+    );
+    assert_eq!(
+        timestamp.format(&DayNames::JAPANESE),
+        "[2023-02-01 水 10:00]"
+    );
+
+    let (_, timestamp) = parse_active::<()>("<2024-03-01 Fri>").unwrap();
+    let days = timestamp.start().unwrap().days();
+    assert_eq!(days, Some(19783));
+    assert_eq!(
+        Datetime::from_days(19782)
+            .unwrap()
+            .format(&DayNames::ENGLISH),
+        "2024-02-29 Thu"
+    );
+    assert_eq!(
+        Datetime::from_days(0).unwrap().format(&DayNames::ENGLISH),
+        "1970-01-01 Thu"
+    );
+    assert_eq!(
+        Datetime::from_days(2_932_896)
+            .unwrap()
+            .format(&DayNames::ENGLISH),
+        "9999-12-31 Fri"
+    );
+    assert_eq!(Datetime::from_days(2_932_897), None);
+    assert_eq!(Datetime::from_days(-719_528).unwrap().year, 0);
+    assert_eq!(Datetime::from_days(-719_529), None);
+    assert_eq!(Datetime::from_days(i64::MAX), None);
+    let (_, timestamp) = parse_active::<()>("<2023-02-29 Wed>").unwrap();
+    assert_eq!(timestamp.start().unwrap().days(), None);
+
+    let (_, timestamp) = parse_active::<()>("<2023-01-31 Di 10:00-11:00 -2d .+1m/2m>").unwrap();
+    assert_eq!(
+        timestamp.repeater(),
+        Some(Repeater {
+            ty: RepeaterType::Restart,
+            value: 1,
+            unit: TimeUnit::Month,
+            deadline: Some((2, TimeUnit::Month)),
+        })
+    );
+    assert_eq!(
+        timestamp.delay(),
+        Some(Delay {
+            ty: DelayType::All,
+            value: 2,
+            unit: TimeUnit::Day,
+        })
+    );
+
+    let next = |input, now| {
+        let (_, timestamp) = parse_active::<()>(input).unwrap();
+        let (_, now) = parse_active::<()>(now).unwrap();
+        let next = timestamp.next_repeat(now.start().unwrap());
+        next.map(|next| next.format(&DayNames::ENGLISH))
+    };
+    assert_eq!(
+        next("<2023-01-31 Di 10:00-11:00 .+1m>", "<2023-03-31 Fri 08:00>"),
+        Some("<2023-04-30 Sun 10:00 .+1m>--<2023-04-30 Sun 11:00>".into())
+    );
+    assert_eq!(
+        next("<2023-01-31 Tue +1m -1d>", "<2023-03-31 Fri>"),
+        Some("<2023-02-28 Tue +1m -1d>".into())
+    );
+    assert_eq!(
+        next("<2023-01-01 Sun ++1d>", "<2023-03-31 Fri>"),
+        Some("<2023-04-01 Sat ++1d>".into())
+    );
+    assert_eq!(
+        next("<2023-01-01 Sun 23:30 +2h>", "<2023-03-31 Fri>"),
+        Some("<2023-01-02 Mon 01:30 +2h>".into())
+    );
+    assert_eq!(next("<2023-01-01 Sun>", "<2023-03-31 Fri>"), None);
+    assert_eq!(next("<2023-01-01 Sun ++0d>", "<2023-03-31 Fri>"), None);
+    assert_eq!(
+        next("<1970-01-31 Sat 10:00 ++1m>", "<9999-12-31 Fri 10:00>"),
+        None
+    );
+    assert_eq!(
+        next("<2023-01-02 Mon 09:00 ++3h>", "<2023-01-05 Thu 09:00>"),
+        Some("<2023-01-05 Thu 12:00 ++3h>".into())
+    );
+    assert_eq!(
+        next("<2020-02-29 Sat ++1y>", "<2023-03-01 Wed>"),
+        Some("<2024-02-29 Thu ++1y>".into())
+    );
+
+    assert_eq!(Repeater::parse("+0d"), None);
+    assert_eq!(Repeater::parse(".+1d/0d"), None);
+    assert_eq!(Delay::parse("-0d").map(|delay| delay.value), Some(0));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn compare() {
+    let parse = |input| parse_inactive::<()>(input).unwrap().1;
+    let now = parse("[2023-03-01 Wed 12:00]");
+    let now = now.start().unwrap();
+
+    assert!(parse("[2023-03-01 Wed 11:59]").is_overdue(now));
+    assert!(!parse("[2023-03-01 Wed 12:00]").is_overdue(now));
+    assert!(!parse("[2023-03-01 Wed]").is_overdue(now));
+    assert!(parse("[2023-02-28 Tue]").is_overdue(now));
+    let (_, diary) = parse_diary::<()>("<%%(diary-float t 4 2)>").unwrap();
+    assert!(!diary.is_overdue(now));
+
+    assert_eq!(parse("[2023-02-28 Tue]").days_until(now), Some(-1));
+    assert_eq!(parse("[2024-03-01 Fri]").days_until(now), Some(366));
+
+    let mut timestamps = [
+        parse("[2023-03-01 Wed 08:00]--[2023-03-02 Thu]"),
+        parse("[2023-03-01 Wed]"),
+        parse("[2023-03-01 Wed 08:00]"),
+        parse("[2023-02-28 Tue 23:00]"),
+    ];
+    timestamps.sort();
+    assert_eq!(
+        timestamps
+            .iter()
+            .map(|timestamp| timestamp.format(&DayNames::ENGLISH))
+            .collect::<Vec<_>>(),
+        vec![
+            "[2023-02-28 Tue 23:00]",
+            "[2023-03-01 Wed]",
+            "[2023-03-01 Wed 08:00]",
+            "[2023-03-01 Wed 08:00]--[2023-03-02 Thu]",
+        ]
+    );
+    assert_eq!(parse("[2023-03-01 Wed]"), parse("[2023-03-01 Wed]"));
+    assert_ne!(
+        parse("[2023-03-01 Wed]"),
+        parse_active::<()>("<2023-03-01 Wed>").unwrap().1
+    );
 }
@@ -1,7 +1,8 @@
 //! Headline Title
 
-use std::borrow::Cow;
-use std::collections::HashMap;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
 
 use memchr::memrchr;
 use nom::{
@@ -14,16 +15,16 @@ use nom::{
     Err, IResult,
 };
 
+use crate::compat::HashMap;
 use crate::{
-    config::ParseConfig,
+    config::{ParseConfig, Syntax},
     elements::{drawer::parse_drawer_without_blank, Planning, Timestamp},
     parsers::{blank_lines, line, skip_empty_lines, take_one_word},
 };
 
 /// Title Elemenet
-#[cfg_attr(test, derive(PartialEq))]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Title<'a> {
     /// Headline level, number of stars
     pub level: usize,
@@ -82,7 +83,7 @@ impl Title<'_> {
 
     /// Returns `true` if this headline is commented
     pub fn is_commented(&self) -> bool {
-        self.raw.starts_with("COMMENT  ")
+        self.raw == "COMMENT" || self.raw.starts_with("COMMENT ")
     }
 
     pub fn into_owned(self) -> Title<'static> {
@@ -168,6 +169,15 @@ fn parse_title<'a, E: ParseError<&'a str>>(
         .unwrap_or((input, None));
 
     let (input, properties) = opt(parse_properties_drawer)(input)?;
+
+    // Org 8.x also accepts the planning line after the property drawer
+    let (input, planning) = match planning {
+        None if properties.is_some() && config.syntax == Syntax::Org8 => Planning::parse(input)
+            .map(|(input, planning)| (input, Some(Box::new(planning))))
+            .unwrap_or((input, None)),
+        planning => (input, planning),
+    };
+
     let (input, blank) = blank_lines(input);
 
     Ok((
@@ -212,13 +222,12 @@ fn parse_node_property<'a, E: ParseError<&'a str>>(
     input: &'a str,
 ) -> IResult<&str, (&str, &str), E> {
     let input = skip_empty_lines(input).trim_start();
-    let (input, name) = map(delimited(tag(":"), take_until(":"), tag(":")), |s: &str| {
-        s.trim_end_matches('+')
-    })(input)?;
+    let (input, name) = delimited(tag(":"), take_until(":"), tag(":"))(input)?;
     let (input, value) = line(input)?;
     Ok((input, (name, value.trim())))
 }
 
+#[cfg(feature = "std")]
 #[test]
 fn parse_title_() {
     use nom::error::VerboseError;
@@ -414,6 +423,7 @@ fn parse_title_() {
     );
 }
 
+#[cfg(feature = "std")]
 #[test]
 fn parse_properties_drawer_() {
     use nom::error::VerboseError;
@@ -428,5 +438,14 @@ fn parse_properties_drawer_() {
                 .into_iter()
                 .collect::<HashMap<_, _>>()
         ))
-    )
+    );
+    assert_eq!(
+        parse_properties_drawer::<VerboseError<&str>>(":PROPERTIES:\n:VAR: a\n:VAR+: b\n:END:"),
+        Ok((
+            "",
+            vec![("VAR".into(), "a".into()), ("VAR+".into(), "b".into())]
+                .into_iter()
+                .collect::<HashMap<_, _>>()
+        ))
+    );
 }
@@ -0,0 +1,279 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use indextree::NodeId;
+
+use crate::elements::{block::escape, Element};
+use crate::{HeaderArgs, Org};
+
+/// Output produced when there are more lines than this is wrapped in an
+/// example block, instead of fixed width lines
+const MIN_LINES_FOR_BLOCK: usize = 10;
+
+/// What a source block collects as its result, from `:results`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResultCollection {
+    /// Value of the last expression, `:results value`
+    Value,
+    /// Everything printed to standard output, `:results output`
+    Output,
+}
+
+/// Executor of source blocks
+///
+/// It's also implemented for closures with the same signature as
+/// [`Executor::execute`].
+///
+/// [`Executor::execute`]: #tymethod.execute
+pub trait Executor {
+    type Error;
+
+    /// Runs `code` written in `language`, and returns its result as text.
+    fn execute(
+        &mut self,
+        language: &str,
+        code: &str,
+        collection: ResultCollection,
+        args: &HeaderArgs,
+    ) -> Result<String, Self::Error>;
+}
+
+impl<F, E> Executor for F
+where
+    F: FnMut(&str, &str, ResultCollection, &HeaderArgs) -> Result<String, E>,
+{
+    type Error = E;
+
+    fn execute(
+        &mut self,
+        language: &str,
+        code: &str,
+        collection: ResultCollection,
+        args: &HeaderArgs,
+    ) -> Result<String, E> {
+        self(language, code, collection, args)
+    }
+}
+
+impl Org<'_> {
+    /// Executes the source block `node` with `executor`, and inserts or
+    /// replaces its `#+RESULTS:`, like `org-babel-execute-src-block`.
+    ///
+    /// Returns the result, or `None` if `node` isn't a source block or its
+    /// `:eval` header argument is `no` or `never`. Results aren't inserted
+    /// if `:results` contains `silent` or `none`.
+    ///
+    /// Results are formatted according to `:results` (`raw`, `drawer`,
+    /// `code` or `list`) and `:wrap`, and are written as fixed width lines
+    /// or an example block by default.
+    ///
+    /// ```rust
+    /// # use orgize::{Element, Event, HeaderArgs, Org, ResultCollection};
+    /// #
+    /// let mut org = Org::parse("#+BEGIN_SRC sh :results output\necho hi\n#+END_SRC\n");
+    /// let block = org.src_blocks()[0];
+    ///
+    /// let mut shell = |_: &str, code: &str, _: ResultCollection, _: &HeaderArgs| {
+    ///     Ok::<_, ()>(code.replace("echo ", ""))
+    /// };
+    /// org.execute_src_block(block, &mut shell).unwrap();
+    ///
+    /// let result = org.iter().find_map(|event| match event {
+    ///     Event::Start(Element::FixedWidth(fixed_width)) => Some(fixed_width.value.clone()),
+    ///     _ => None,
+    /// });
+    /// assert_eq!(result.as_deref(), Some(": hi\n"));
+    /// ```
+    pub fn execute_src_block<E: Executor>(
+        &mut self,
+        node: NodeId,
+        executor: &mut E,
+    ) -> Result<Option<String>, E::Error> {
+        let language = match &self[node] {
+            Element::SourceBlock(block) => block.language.to_string(),
+            _ => return Ok(None),
+        };
+        let args = self.header_args(node);
+        if let Some("no") | Some("never") = args.get("eval") {
+            return Ok(None);
+        }
+
+        let code = match args.get("noweb") {
+            Some("yes") | Some("eval") | Some("no-export") | Some("strip-export") => {
+                self.expand_noweb(node)
+            }
+            _ => self.src_body(node),
+        };
+        let results = args.get("results").unwrap_or_default();
+        let collection = if results.split_whitespace().any(|word| word == "output") {
+            ResultCollection::Output
+        } else {
+            ResultCollection::Value
+        };
+
+        let output = executor.execute(&language, &code.unwrap_or_default(), collection, &args)?;
+
+        if !results
+            .split_whitespace()
+            .any(|word| word == "silent" || word == "none")
+        {
+            let name = self.element_name(node).map(String::from);
+            let text = format_results(&output, &language, name.as_deref(), &args);
+            if let Some(range) = self.results_range(node) {
+                self.edit(range, &text);
+            } else if let Some((contents, _)) = self.src_contents(node) {
+                let end = self.text[contents.end..]
+                    .find('\n')
+                    .map_or(self.text.len(), |i| contents.end + i + 1);
+                let text = if end == self.text.len() && !self.text.ends_with('\n') {
+                    format!("\n\n{}", text)
+                } else {
+                    format!("\n{}", text)
+                };
+                self.edit(end..end, &text);
+            }
+        }
+
+        Ok(Some(output))
+    }
+
+    /// Executes all source blocks in document order with `executor`, and
+    /// returns the number of executed blocks. Stops at the first error.
+    ///
+    /// See [`execute_src_block`](#method.execute_src_block) for details.
+    pub fn execute_src_blocks<E: Executor>(&mut self, executor: &mut E) -> Result<usize, E::Error> {
+        let mut count = 0;
+        let mut index = 0;
+        // node ids may change after inserting results, so look them up again
+        while let Some(&node) = self.src_blocks().get(index) {
+            if self.execute_src_block(node, executor)?.is_some() {
+                count += 1;
+            }
+            index += 1;
+        }
+        Ok(count)
+    }
+
+    /// Returns the byte range of the existing `#+RESULTS:` of this block,
+    /// including the result element but not blank lines after it.
+    fn results_range(&self, node: NodeId) -> Option<core::ops::Range<usize>> {
+        let keyword = self.arena[node].next_sibling()?;
+        let post_blank = match &self[keyword] {
+            Element::Keyword(keyword) if keyword.key.eq_ignore_ascii_case("RESULTS") => {
+                keyword.post_blank
+            }
+            _ => return None,
+        };
+        let start = self.span(keyword)?.start;
+
+        let mut end = self.span(keyword)?.end;
+        if post_blank == 0 {
+            if let Some(result) = self.arena[keyword].next_sibling() {
+                if !matches!(self[result], Element::Keyword(_)) {
+                    end = self.span(result)?.end;
+                }
+            }
+        }
+        let len = self.text[start..end].trim_end().len();
+        Some(start..end.min(start + len + 1))
+    }
+}
+
+/// Formats `output` as a `#+RESULTS:` keyword and a result element.
+fn format_results(output: &str, language: &str, name: Option<&str>, args: &HeaderArgs) -> String {
+    let mut text = match name {
+        Some(name) => format!("#+RESULTS: {}\n", name),
+        None => String::from("#+RESULTS:\n"),
+    };
+    let output = output.trim_end_matches('\n');
+    if output.is_empty() {
+        return text;
+    }
+    let results = args.get("results").unwrap_or_default();
+    let has = |word| results.split_whitespace().any(|w| w == word);
+
+    let wrap = |text: &mut String, begin: &str, end: &str| {
+        text.push_str(begin);
+        text.push('\n');
+        text.push_str(&escape(output));
+        text.push('\n');
+        text.push_str(end);
+        text.push('\n');
+    };
+
+    if let Some(wrapper) = args.get("wrap") {
+        let wrapper = if wrapper.is_empty() {
+            "results"
+        } else {
+            wrapper
+        };
+        let name = wrapper.split_whitespace().next().unwrap_or(wrapper);
+        wrap(
+            &mut text,
+            &format!("#+begin_{}", wrapper),
+            &format!("#+end_{}", name),
+        );
+    } else if has("drawer") {
+        text.push_str(":results:\n");
+        text.push_str(output);
+        text.push_str("\n:end:\n");
+    } else if has("raw") || has("org") {
+        text.push_str(output);
+        text.push('\n');
+    } else if has("code") {
+        wrap(&mut text, &format!("#+begin_src {}", language), "#+end_src");
+    } else if has("list") {
+        for line in output.lines() {
+            text.push_str("- ");
+            text.push_str(line);
+            text.push('\n');
+        }
+    } else if output.lines().count() >= MIN_LINES_FOR_BLOCK {
+        wrap(&mut text, "#+begin_example", "#+end_example");
+    } else {
+        for line in output.lines() {
+            if line.is_empty() {
+                text.push_str(":\n");
+            } else {
+                text.push_str(": ");
+                text.push_str(line);
+                text.push('\n');
+            }
+        }
+    }
+
+    text
+}
+
+#[test]
+fn format() {
+    let args = HeaderArgs::parse(":results output");
+    assert_eq!(
+        format_results("a\n\nb\n", "sh", Some("x"), &args),
+        "#+RESULTS: x\n: a\n:\n: b\n"
+    );
+    assert_eq!(format_results("", "sh", None, &args), "#+RESULTS:\n");
+
+    let args = HeaderArgs::parse(":results drawer");
+    assert_eq!(
+        format_results("*a*", "sh", None, &args),
+        "#+RESULTS:\n:results:\n*a*\n:end:\n"
+    );
+
+    let args = HeaderArgs::parse(":results code");
+    assert_eq!(
+        format_results("* a", "org", None, &args),
+        "#+RESULTS:\n#+begin_src org\n,* a\n#+end_src\n"
+    );
+
+    let args = HeaderArgs::parse(":wrap export html");
+    assert_eq!(
+        format_results("<b>", "sh", None, &args),
+        "#+RESULTS:\n#+begin_export html\n<b>\n#+end_export\n"
+    );
+
+    let args = HeaderArgs::parse("");
+    assert_eq!(
+        format_results("1\n2\n3\n4\n5\n6\n7\n8\n9\n10", "sh", None, &args),
+        "#+RESULTS:\n#+begin_example\n1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n#+end_example\n"
+    );
+}
@@ -0,0 +1,258 @@
+use indextree::{NodeEdge, NodeId, Traverse};
+use pulldown_cmark::{
+    Alignment, CodeBlockKind, CowStr, Event, HeadingLevel, LinkType, Tag, TagEnd,
+};
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+
+use crate::elements::{Element, Table, TableRow};
+use crate::export::org::write_timestamp;
+use crate::Org;
+
+/// Iterator of pulldown-cmark `Event`s converted from an `Org` struct
+///
+/// Headlines, emphasis, links, lists, blocks, tables and footnotes are mapped
+/// to their CommonMark counterparts, while elements which can't be represented,
+/// e.g. keywords, drawers and comments, are skipped with their contents.
+///
+/// ```rust
+/// # use orgize::Org;
+/// # use orgize::pulldown_cmark::html::push_html;
+/// #
+/// let org = Org::parse("* Title\n*bold* /italic/ [[https://example.com][link]]\n- item\n");
+///
+/// let mut html = String::new();
+/// push_html(&mut html, org.cmark_events());
+///
+/// assert_eq!(
+///     html,
+///     "<h1>Title</h1>\n<p><strong>bold</strong> <em>italic</em> \
+///      <a href=\"https://example.com\">link</a></p>\n\
+///      <ul>\n<li>\n<p>item</p>\n</li>\n</ul>\n"
+/// );
+/// ```
+pub struct CmarkEvents<'a, 'b> {
+    org: &'b Org<'a>,
+    traverse: Traverse<'b, Element<'a>>,
+    queue: VecDeque<Event<'b>>,
+    /// End tags to emit when leaving each open element
+    ends: Vec<Option<TagEnd>>,
+    /// Element whose contents are being skipped
+    skipping: Option<NodeId>,
+    /// Whether the head of current table has been emitted
+    table_head: bool,
+}
+
+impl<'a, 'b> CmarkEvents<'a, 'b> {
+    pub(crate) fn new(org: &'b Org<'a>) -> Self {
+        CmarkEvents {
+            org,
+            traverse: org.root.traverse(&org.arena),
+            queue: VecDeque::new(),
+            ends: Vec::new(),
+            skipping: None,
+            table_head: false,
+        }
+    }
+
+    fn start(&mut self, node: NodeId) {
+        let mut empty_head = false;
+        let tag = match &self.org[node] {
+            Element::Document { .. }
+            | Element::Headline { .. }
+            | Element::Section
+            | Element::CenterBlock(_)
+            | Element::SpecialBlock(_)
+            | Element::VerseBlock(_) => None,
+            Element::Title(title) => Some(Tag::Heading {
+                level: HeadingLevel::try_from(title.level.clamp(1, 6)).unwrap(),
+                id: None,
+                classes: Vec::new(),
+                attrs: Vec::new(),
+            }),
+            Element::Paragraph { .. } => Some(Tag::Paragraph),
+            Element::QuoteBlock(_) => Some(Tag::BlockQuote(None)),
+            Element::List(list) => {
+                let start = if list.ordered {
+                    let first = self.org.arena[node].first_child();
+                    Some(match first.map(|item| &self.org[item]) {
+                        Some(Element::ListItem(item)) => item
+                            .bullet
+                            .trim()
+                            .trim_end_matches(['.', ')'])
+                            .parse()
+                            .unwrap_or(1),
+                        _ => 1,
+                    })
+                } else {
+                    None
+                };
+                Some(Tag::List(start))
+            }
+            Element::ListItem(_) => Some(Tag::Item),
+            Element::FnDef(fn_def) => Some(Tag::FootnoteDefinition(borrow(&fn_def.label))),
+            Element::Table(Table::Org { .. }) => {
+                self.table_head = false;
+                let columns = node
+                    .children(&self.org.arena)
+                    .map(|row| row.children(&self.org.arena).count())
+                    .max()
+                    .unwrap_or_default();
+                // pulldown-cmark expects a head, which is empty if there's no
+                // header row
+                let has_head = node
+                    .children(&self.org.arena)
+                    .any(|row| matches!(self.org[row], Element::TableRow(TableRow::Header)));
+                if !has_head {
+                    self.table_head = true;
+                    empty_head = true;
+                }
+                Some(Tag::Table(vec![Alignment::None; columns]))
+            }
+            Element::TableRow(TableRow::Header) if !self.table_head => {
+                self.table_head = true;
+                Some(Tag::TableHead)
+            }
+            Element::TableRow(TableRow::Header) | Element::TableRow(TableRow::Body) => {
+                Some(Tag::TableRow)
+            }
+            Element::TableCell(_) => Some(Tag::TableCell),
+            Element::Bold => Some(Tag::Strong),
+            Element::Italic => Some(Tag::Emphasis),
+            Element::Strike => Some(Tag::Strikethrough),
+            Element::Underline => {
+                self.queue.push_back(Event::InlineHtml("<u>".into()));
+                self.ends.push(None);
+                return;
+            }
+            element => {
+                self.leaf(element);
+                self.skipping = Some(node);
+                return;
+            }
+        };
+
+        self.ends.push(tag.as_ref().map(Tag::to_end));
+        if let Some(tag) = tag {
+            self.queue.push_back(Event::Start(tag));
+        }
+        if empty_head {
+            self.queue.push_back(Event::Start(Tag::TableHead));
+            self.queue.push_back(Event::End(TagEnd::TableHead));
+        }
+    }
+
+    fn end(&mut self, node: NodeId) {
+        if let Element::Underline = &self.org[node] {
+            self.queue.push_back(Event::InlineHtml("</u>".into()));
+        }
+        if let Some(Some(end)) = self.ends.pop() {
+            self.queue.push_back(Event::End(end));
+        }
+    }
+
+    /// Emits events of an element whose contents are skipped.
+    fn leaf(&mut self, element: &'b Element<'a>) {
+        let events = &mut self.queue;
+        match element {
+            Element::Text { value } => events.push_back(Event::Text(borrow(value))),
+            Element::Verbatim { value } | Element::Code { value } => {
+                events.push_back(Event::Code(borrow(value)))
+            }
+            Element::InlineSrc(inline_src) => {
+                events.push_back(Event::Code(borrow(&inline_src.body)))
+            }
+            Element::Cookie(cookie) => events.push_back(Event::Text(borrow(&cookie.value))),
+            Element::FnRef(fn_ref) => {
+                events.push_back(Event::FootnoteReference(borrow(&fn_ref.label)))
+            }
+            Element::Link(link) => {
+                let tag = Tag::Link {
+                    link_type: LinkType::Inline,
+                    dest_url: CowStr::Borrowed(link.target()),
+                    title: "".into(),
+                    id: "".into(),
+                };
+                events.push_back(Event::Start(tag));
+                let text = link.desc.as_ref().unwrap_or(&link.path);
+                events.push_back(Event::Text(borrow(text)));
+                events.push_back(Event::End(TagEnd::Link));
+            }
+            Element::Snippet(snippet) if snippet.name.eq_ignore_ascii_case("HTML") => {
+                events.push_back(Event::InlineHtml(borrow(&snippet.value)))
+            }
+            Element::Timestamp(timestamp) => {
+                let mut text = Vec::new();
+                if write_timestamp(&mut text, timestamp).is_ok() {
+                    let text = String::from_utf8(text).unwrap_or_default();
+                    events.push_back(Event::Text(text.into()));
+                }
+            }
+            Element::SourceBlock(block) => {
+                let kind = CodeBlockKind::Fenced(borrow(&block.language));
+                code_block(events, kind, &block.contents);
+            }
+            Element::ExampleBlock(block) => {
+                code_block(events, CodeBlockKind::Fenced("".into()), &block.contents)
+            }
+            Element::FixedWidth(fixed_width) => {
+                let contents: String = fixed_width
+                    .value
+                    .lines()
+                    .map(|line| {
+                        let line = line.trim_start();
+                        let line = line.strip_prefix(':').unwrap_or(line);
+                        format!("{}\n", line.strip_prefix(' ').unwrap_or(line))
+                    })
+                    .collect();
+                code_block(events, CodeBlockKind::Fenced("".into()), &contents);
+            }
+            Element::ExportBlock(block) if block.data.eq_ignore_ascii_case("HTML") => {
+                events.push_back(Event::Start(Tag::HtmlBlock));
+                events.push_back(Event::Html(borrow(&block.contents)));
+                events.push_back(Event::End(TagEnd::HtmlBlock));
+            }
+            Element::Rule(_) => events.push_back(Event::Rule),
+            _ => (),
+        }
+    }
+}
+
+impl<'b> Iterator for CmarkEvents<'_, 'b> {
+    type Item = Event<'b>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(event) = self.queue.pop_front() {
+                return Some(event);
+            }
+
+            match self.traverse.next()? {
+                NodeEdge::Start(node) if self.skipping.is_none() => self.start(node),
+                NodeEdge::End(node) if self.skipping == Some(node) => self.skipping = None,
+                NodeEdge::End(node) if self.skipping.is_none() => self.end(node),
+                _ => (),
+            }
+        }
+    }
+}
+
+fn borrow(s: &str) -> CowStr<'_> {
+    CowStr::Borrowed(s)
+}
+
+fn code_block<'b>(events: &mut VecDeque<Event<'b>>, kind: CodeBlockKind<'b>, contents: &str) {
+    events.push_back(Event::Start(Tag::CodeBlock(kind)));
+    events.push_back(Event::Text(contents.to_string().into()));
+    events.push_back(Event::End(TagEnd::CodeBlock));
+}
+
+impl<'a> Org<'a> {
+    /// Returns an iterator of pulldown-cmark `Event`s converted from this
+    /// `Org` struct, see [`CmarkEvents`].
+    ///
+    /// [`CmarkEvents`]: export/struct.CmarkEvents.html
+    pub fn cmark_events<'b>(&'b self) -> CmarkEvents<'a, 'b> {
+        CmarkEvents::new(self)
+    }
+}
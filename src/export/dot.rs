@@ -0,0 +1,172 @@
+use indextree::NodeId;
+use std::collections::{HashMap, HashSet};
+use std::io::{Error, Write};
+
+use crate::{Headline, Org, Workspace};
+
+/// Styles of GraphViz DOT graphs
+#[derive(Clone, Debug)]
+pub struct DotConfig {
+    /// Fill colors of headlines by tags, the first matching tag wins
+    pub tag_colors: Vec<(String, String)>,
+    /// Border color of headlines with todo keywords
+    pub todo_color: String,
+    /// Border color of headlines with done keywords
+    pub done_color: String,
+    /// Whether edges from files and headlines to their child headlines are
+    /// drawn, or only links
+    pub structure: bool,
+}
+
+impl Default for DotConfig {
+    fn default() -> Self {
+        DotConfig {
+            tag_colors: Vec::new(),
+            todo_color: "red".into(),
+            done_color: "forestgreen".into(),
+            structure: true,
+        }
+    }
+}
+
+impl Workspace {
+    /// Writes a GraphViz DOT graph, whose nodes are files and headlines, and
+    /// whose edges are links between them.
+    ///
+    /// Links are drawn from their enclosing headlines, or from their files if
+    /// they're before the first headline. Cross-file links are blue, and
+    /// child headlines are connected to their parents by gray lines.
+    ///
+    /// ```rust
+    /// # use orgize::export::DotConfig;
+    /// # use orgize::Workspace;
+    /// #
+    /// let mut workspace = Workspace::default();
+    /// workspace.insert("a.org", "* TODO read\n[[file:b.org]]\n");
+    /// workspace.insert("b.org", "");
+    ///
+    /// let mut writer = Vec::new();
+    /// workspace.write_dot(&mut writer, &DotConfig::default()).unwrap();
+    /// let dot = String::from_utf8(writer).unwrap();
+    ///
+    /// assert!(dot.contains("\"f0\" [label=\"a.org\", shape=folder];"));
+    /// assert!(dot.contains("[label=\"TODO read\", color=\"red\"];"));
+    /// assert!(dot.contains("\"f0h0\" -> \"f1\" [color=\"blue\"];"));
+    /// ```
+    pub fn write_dot<W: Write>(&self, mut w: W, config: &DotConfig) -> Result<(), Error> {
+        let files: Vec<_> = self.files().collect();
+        let indices: HashMap<&str, usize> = files
+            .iter()
+            .enumerate()
+            .map(|(i, (path, _))| (*path, i))
+            .collect();
+        let headlines: Vec<HashMap<NodeId, usize>> = files
+            .iter()
+            .map(|(_, org)| {
+                org.headlines()
+                    .enumerate()
+                    .map(|(i, headline)| (headline.headline_node(), i))
+                    .collect()
+            })
+            .collect();
+        let node_id = |file: usize, headline: Option<Headline>| match headline
+            .and_then(|h| headlines[file].get(&h.headline_node()))
+        {
+            Some(headline) => format!("f{}h{}", file, headline),
+            None => format!("f{}", file),
+        };
+
+        writeln!(w, "digraph org {{")?;
+        writeln!(w, "    node [shape=box];")?;
+
+        for (i, (path, org)) in files.iter().enumerate() {
+            writeln!(
+                w,
+                "    \"f{}\" [label=\"{}\", shape=folder];",
+                i,
+                escape(path)
+            )?;
+            for headline in org.headlines() {
+                writeln!(
+                    w,
+                    "    \"{}\" [{}];",
+                    node_id(i, Some(headline)),
+                    self.dot_attributes(org, headline, config)
+                )?;
+            }
+        }
+
+        if config.structure {
+            for (i, (_, org)) in files.iter().enumerate() {
+                for headline in org.headlines() {
+                    writeln!(
+                        w,
+                        "    \"{}\" -> \"{}\" [color=\"gray\", arrowhead=none];",
+                        node_id(i, headline.parent(org)),
+                        node_id(i, Some(headline))
+                    )?;
+                }
+            }
+        }
+
+        // edges are written in the order of links, without duplicates
+        let mut edges = Vec::new();
+        let mut seen = HashSet::new();
+        for link in self.links() {
+            let (source, target) = match (indices.get(link.source), indices.get(link.target)) {
+                (Some(&source), Some(&target)) => (source, target),
+                _ => continue,
+            };
+            let edge = (
+                node_id(source, link.source_headline),
+                node_id(target, link.target_headline),
+                source != target,
+            );
+            if edge.0 != edge.1 && seen.insert(edge.clone()) {
+                edges.push(edge);
+            }
+        }
+        for (source, target, cross_file) in edges {
+            if cross_file {
+                writeln!(w, "    \"{}\" -> \"{}\" [color=\"blue\"];", source, target)?;
+            } else {
+                writeln!(w, "    \"{}\" -> \"{}\";", source, target)?;
+            }
+        }
+
+        writeln!(w, "}}")
+    }
+
+    fn dot_attributes(&self, org: &Org, headline: Headline, config: &DotConfig) -> String {
+        let title = headline.title(org);
+        let label = match &title.keyword {
+            Some(keyword) => format!("{} {}", keyword, title.raw),
+            None => title.raw.to_string(),
+        };
+        let mut attributes = format!("label=\"{}\"", escape(&label));
+
+        let (todo_keywords, done_keywords) = &self.config.todo_keywords;
+        if let Some(keyword) = &title.keyword {
+            if todo_keywords.iter().any(|k| k == keyword) {
+                attributes.push_str(&format!(", color=\"{}\"", escape(&config.todo_color)));
+            } else if done_keywords.iter().any(|k| k == keyword) {
+                attributes.push_str(&format!(", color=\"{}\"", escape(&config.done_color)));
+            }
+        }
+
+        let fill = config
+            .tag_colors
+            .iter()
+            .find(|(tag, _)| title.tags.iter().any(|t| t == tag));
+        if let Some((_, color)) = fill {
+            attributes.push_str(&format!(", style=filled, fillcolor=\"{}\"", escape(color)));
+        }
+
+        attributes
+    }
+}
+
+/// Escapes `"` and `\` in quoted DOT strings.
+fn escape(input: &str) -> String {
+    input.replace('\\', "\\\\").replace('"', "\\\"")
+}
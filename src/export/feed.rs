@@ -0,0 +1,317 @@
+use std::io::{Error, Write};
+
+use crate::elements::{Datetime, DayNames, Element, Timestamp};
+use crate::export::{DefaultHtmlHandler, HtmlEscape};
+use crate::{Headline, Org, Workspace};
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Atom or RSS feed, like `ox-rss`
+///
+/// ```rust
+/// # use orgize::export::Feed;
+/// # use orgize::Org;
+/// #
+/// let org = Org::parse(
+///     "* First post :blog:\n\
+///      :PROPERTIES:\n:PUBDATE: <2023-03-01 Wed 10:00>\n:CUSTOM_ID: first\n:END:\n\
+///      Hello *world*.\n\
+///      * Draft :blog:\n",
+/// );
+/// let feed = Feed {
+///     title: "Blog".into(),
+///     link: "https://example.com/".into(),
+///     entries: org.feed_entries(|org, headline| {
+///         headline.title(org).tags.iter().any(|tag| tag == "blog")
+///     }),
+///     ..Default::default()
+/// };
+///
+/// // headlines without dates are skipped
+/// assert_eq!(feed.entries.len(), 1);
+///
+/// let mut writer = Vec::new();
+/// feed.write_atom(&mut writer).unwrap();
+/// let atom = String::from_utf8(writer).unwrap();
+/// assert!(atom.contains("<updated>2023-03-01T10:00:00Z</updated>"));
+/// assert!(atom.contains("<link href=\"https://example.com/#first\"/>"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Feed {
+    /// Feed title
+    pub title: String,
+    /// Url of the site, entry links are relative to it
+    pub link: String,
+    /// Feed description
+    pub description: String,
+    /// Author of the feed
+    pub author: String,
+    /// Feed entries, newest first
+    pub entries: Vec<FeedEntry>,
+}
+
+/// Entry of a feed, i.e. a dated headline
+#[derive(Clone, Debug)]
+pub struct FeedEntry {
+    /// Headline title
+    pub title: String,
+    /// Publishing date
+    pub date: Datetime<'static>,
+    /// Link of the entry, from the `RSS_PERMALINK` property
+    pub permalink: Option<String>,
+    /// Anchor of the entry, from the `CUSTOM_ID` or `ID` property
+    pub anchor: Option<String>,
+    /// Html content of the headline section and its subtree
+    pub content: String,
+}
+
+impl Feed {
+    /// Writes this feed in Atom format.
+    pub fn write_atom<W: Write>(&self, mut w: W) -> Result<(), Error> {
+        writeln!(w, "<?xml version=\"1.0\" encoding=\"utf-8\"?>")?;
+        writeln!(w, "<feed xmlns=\"http://www.w3.org/2005/Atom\">")?;
+        writeln!(w, "<title>{}</title>", HtmlEscape(&self.title))?;
+        if !self.description.is_empty() {
+            writeln!(w, "<subtitle>{}</subtitle>", HtmlEscape(&self.description))?;
+        }
+        writeln!(w, "<link href=\"{}\"/>", HtmlEscape(&self.link))?;
+        writeln!(w, "<id>{}</id>", HtmlEscape(&self.link))?;
+        match self.entries.iter().map(|entry| &entry.date).max() {
+            Some(date) => writeln!(w, "<updated>{}</updated>", rfc3339(date))?,
+            None => writeln!(w, "<updated>1970-01-01T00:00:00Z</updated>")?,
+        }
+        if !self.author.is_empty() {
+            writeln!(
+                w,
+                "<author><name>{}</name></author>",
+                HtmlEscape(&self.author)
+            )?;
+        }
+        for entry in &self.entries {
+            let link = self.entry_link(entry);
+            writeln!(w, "<entry>")?;
+            writeln!(w, "<title>{}</title>", HtmlEscape(&entry.title))?;
+            writeln!(w, "<link href=\"{}\"/>", HtmlEscape(&link))?;
+            writeln!(w, "<id>{}</id>", HtmlEscape(&link))?;
+            writeln!(w, "<updated>{}</updated>", rfc3339(&entry.date))?;
+            writeln!(
+                w,
+                "<content type=\"html\">{}</content>",
+                HtmlEscape(&entry.content)
+            )?;
+            writeln!(w, "</entry>")?;
+        }
+        writeln!(w, "</feed>")
+    }
+
+    /// Writes this feed in RSS 2.0 format.
+    pub fn write_rss<W: Write>(&self, mut w: W) -> Result<(), Error> {
+        writeln!(w, "<?xml version=\"1.0\" encoding=\"utf-8\"?>")?;
+        writeln!(w, "<rss version=\"2.0\">")?;
+        writeln!(w, "<channel>")?;
+        writeln!(w, "<title>{}</title>", HtmlEscape(&self.title))?;
+        writeln!(w, "<link>{}</link>", HtmlEscape(&self.link))?;
+        writeln!(
+            w,
+            "<description>{}</description>",
+            HtmlEscape(&self.description)
+        )?;
+        if let Some(date) = self.entries.iter().map(|entry| &entry.date).max() {
+            writeln!(w, "<lastBuildDate>{}</lastBuildDate>", rfc822(date))?;
+        }
+        for entry in &self.entries {
+            let link = self.entry_link(entry);
+            writeln!(w, "<item>")?;
+            writeln!(w, "<title>{}</title>", HtmlEscape(&entry.title))?;
+            writeln!(w, "<link>{}</link>", HtmlEscape(&link))?;
+            writeln!(w, "<guid>{}</guid>", HtmlEscape(&link))?;
+            writeln!(w, "<pubDate>{}</pubDate>", rfc822(&entry.date))?;
+            writeln!(
+                w,
+                "<description>{}</description>",
+                HtmlEscape(&entry.content)
+            )?;
+            writeln!(w, "</item>")?;
+        }
+        writeln!(w, "</channel>\n</rss>")
+    }
+
+    /// Returns the absolute link of `entry`, which is also used as its id.
+    ///
+    /// Entries without permalinks or anchors are linked by their titles and
+    /// dates, e.g. `https://example.com#first-post-2023-03-01`, so that their
+    /// ids stay unique.
+    fn entry_link(&self, entry: &FeedEntry) -> String {
+        match (&entry.permalink, &entry.anchor) {
+            (Some(permalink), _) if permalink.contains("://") => permalink.clone(),
+            (Some(permalink), _) => format!(
+                "{}/{}",
+                self.link.trim_end_matches('/'),
+                permalink.trim_start_matches('/')
+            ),
+            (None, Some(anchor)) => format!("{}#{}", self.link, anchor),
+            (None, None) => format!(
+                "{}#{}-{:04}-{:02}-{:02}",
+                self.link,
+                slugify(&entry.title),
+                entry.date.year,
+                entry.date.month,
+                entry.date.day
+            ),
+        }
+    }
+}
+
+impl Org<'_> {
+    /// Returns feed entries of headlines matching `predicate`, newest first.
+    ///
+    /// The date of an entry comes from the `PUBDATE` or `DATE` property, or
+    /// the closed, scheduled or deadline timestamp, or the first timestamp in
+    /// the title. Headlines without dates are skipped.
+    pub fn feed_entries<F>(&self, mut predicate: F) -> Vec<FeedEntry>
+    where
+        F: FnMut(&Org, Headline) -> bool,
+    {
+        let mut entries: Vec<_> = self
+            .headlines()
+            .filter(|headline| predicate(self, *headline))
+            .filter_map(|headline| self.feed_entry(headline))
+            .collect();
+        entries.sort_by(|a, b| b.date.cmp(&a.date));
+        entries
+    }
+
+    fn feed_entry(&self, headline: Headline) -> Option<FeedEntry> {
+        let title = headline.title(self);
+        let property = |key: &str| {
+            title
+                .properties
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(key))
+                .map(|(_, v)| v.trim())
+        };
+
+        let date = property("PUBDATE")
+            .or_else(|| property("DATE"))
+            .and_then(|value| {
+                Timestamp::parse_active(value)
+                    .or_else(|| Timestamp::parse_inactive(value))
+                    .and_then(|(_, timestamp)| {
+                        timestamp.start().map(|start| start.clone().into_owned())
+                    })
+            })
+            .or_else(|| {
+                title
+                    .closed()
+                    .or_else(|| title.scheduled())
+                    .or_else(|| title.deadline())
+                    .and_then(Timestamp::start)
+                    .map(|start| start.clone().into_owned())
+            })
+            .or_else(|| {
+                headline
+                    .title_node()
+                    .descendants(&self.arena)
+                    .find_map(|node| match &self[node] {
+                        Element::Timestamp(timestamp) => {
+                            timestamp.start().map(|start| start.clone().into_owned())
+                        }
+                        _ => None,
+                    })
+            })?;
+
+        let mut content = Vec::new();
+        if let Some(section) = headline.section_node() {
+            self.write_html_node(&mut content, &mut DefaultHtmlHandler, section)
+                .ok()?;
+        }
+        for child in headline.children(self).filter(|h| self.is_exported(*h)) {
+            self.write_html_headline(&mut content, &mut DefaultHtmlHandler, child)
+                .ok()?;
+        }
+
+        Some(FeedEntry {
+            title: title.raw.trim().to_string(),
+            date,
+            permalink: property("RSS_PERMALINK").map(Into::into),
+            anchor: property("CUSTOM_ID")
+                .or_else(|| property("ID"))
+                .map(Into::into),
+            content: String::from_utf8(content).ok()?,
+        })
+    }
+}
+
+impl Workspace {
+    /// Returns feed entries of headlines matching `predicate` in all files,
+    /// newest first.
+    pub fn feed_entries<F>(&self, mut predicate: F) -> Vec<FeedEntry>
+    where
+        F: FnMut(&Org, Headline) -> bool,
+    {
+        let mut entries: Vec<_> = self
+            .files()
+            .flat_map(|(_, org)| org.feed_entries(&mut predicate))
+            .collect();
+        entries.sort_by(|a, b| b.date.cmp(&a.date));
+        entries
+    }
+}
+
+/// Lowercases `text` and joins its alphanumeric words with `-`.
+fn slugify(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Formats `date` like `2023-03-01T10:00:00Z`.
+fn rfc3339(date: &Datetime) -> String {
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:00Z",
+        date.year,
+        date.month,
+        date.day,
+        date.hour.unwrap_or(0),
+        date.minute.unwrap_or(0)
+    )
+}
+
+/// Formats `date` like `Wed, 01 Mar 2023 10:00:00 +0000`.
+fn rfc822(date: &Datetime) -> String {
+    format!(
+        "{}, {:02} {} {:04} {:02}:{:02}:00 +0000",
+        DayNames::ENGLISH.0[usize::from(date.weekday().unwrap_or_default())],
+        date.day,
+        MONTHS[usize::from(date.month.clamp(1, 12) - 1)],
+        date.year,
+        date.hour.unwrap_or(0),
+        date.minute.unwrap_or(0)
+    )
+}
+
+#[test]
+fn dates() {
+    let date = Datetime {
+        year: 2023,
+        month: 3,
+        day: 1,
+        dayname: "Wed".into(),
+        hour: None,
+        minute: None,
+    };
+    assert_eq!(rfc3339(&date), "2023-03-01T00:00:00Z");
+    assert_eq!(rfc822(&date), "Wed, 01 Mar 2023 00:00:00 +0000");
+}
+
+#[test]
+fn slugs() {
+    assert_eq!(slugify("First post"), "first-post");
+    assert_eq!(slugify(" Rust & Org: 2023! "), "rust-org-2023");
+    assert_eq!(slugify("Über café"), "über-café");
+    assert_eq!(slugify("***"), "");
+}
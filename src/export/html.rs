@@ -3,7 +3,7 @@ use std::io::{Error, Result as IOResult, Write};
 
 use jetscii::{bytes, BytesConst};
 
-use crate::elements::{Element, Table, TableCell, TableRow, Timestamp};
+use crate::elements::{block::coderef, Element, Table, TableCell, TableRow, Timestamp};
 use crate::export::write_datetime;
 
 /// A wrapper for escaping sensitive characters in html.
@@ -49,6 +49,34 @@ impl<S: AsRef<str>> fmt::Display for HtmlEscape<S> {
     }
 }
 
+/// Escapes code of a source or example block, wrapping lines labeled by
+/// coderefs in `<span id="coderef-label">`, and removing their labels unless
+/// `retain_labels` is set.
+fn code_html(contents: &str, label_fmt: &str, retain_labels: bool) -> String {
+    let mut html = String::new();
+    for line in contents.split_inclusive('\n') {
+        match coderef(line, label_fmt) {
+            Some((end, label)) => {
+                let code = if retain_labels {
+                    line.trim_end()
+                } else {
+                    &line[..end]
+                };
+                html.push_str(&format!(
+                    "<span id=\"coderef-{}\" class=\"coderef-off\">{}</span>",
+                    HtmlEscape(label),
+                    HtmlEscape(code)
+                ));
+                if line.ends_with('\n') {
+                    html.push('\n');
+                }
+            }
+            None => html.push_str(&HtmlEscape(line).to_string()),
+        }
+    }
+    html
+}
+
 pub trait HtmlHandler<E: From<Error>>: Default {
     fn start<W: Write>(&mut self, w: W, element: &Element) -> Result<(), E>;
     fn end<W: Write>(&mut self, w: W, element: &Element) -> Result<(), E>;
@@ -80,6 +108,7 @@ impl HtmlHandler<Error> for DefaultHtmlHandler {
             Element::Italic => write!(w, "<i>")?,
             Element::ListItem(_) => write!(w, "<li>")?,
             Element::Paragraph { .. } => write!(w, "<p>")?,
+            Element::VerseLine { indent } => write!(w, "{}", "&#xa0;".repeat(*indent))?,
             Element::Section => write!(w, "<section>")?,
             Element::Strike => write!(w, "<s>")?,
             Element::Underline => write!(w, "<u>")?,
@@ -88,7 +117,7 @@ impl HtmlHandler<Error> for DefaultHtmlHandler {
             Element::ExampleBlock(block) => write!(
                 w,
                 "<pre class=\"example\">{}</pre>",
-                HtmlEscape(&block.contents)
+                code_html(&block.contents, block.label_fmt(), block.retain_labels())
             )?,
             Element::ExportBlock(block) => {
                 if block.data.eq_ignore_ascii_case("HTML") {
@@ -96,18 +125,14 @@ impl HtmlHandler<Error> for DefaultHtmlHandler {
                 }
             }
             Element::SourceBlock(block) => {
+                let code = code_html(&block.contents, block.label_fmt(), block.retain_labels());
                 if block.language.is_empty() {
-                    write!(
-                        w,
-                        "<pre class=\"example\">{}</pre>",
-                        HtmlEscape(&block.contents)
-                    )?;
+                    write!(w, "<pre class=\"example\">{}</pre>", code)?;
                 } else {
                     write!(
                         w,
                         "<div class=\"org-src-container\"><pre class=\"src src-{}\">{}</pre></div>",
-                        block.language,
-                        HtmlEscape(&block.contents)
+                        block.language, code
                     )?;
                 }
             }
@@ -121,11 +146,26 @@ impl HtmlHandler<Error> for DefaultHtmlHandler {
             Element::Code { value } => write!(w, "<code>{}</code>", HtmlEscape(value))?,
             Element::FnRef(_fn_ref) => (),
             Element::InlineCall(_) => (),
+            Element::Link(link) if link.is_image() => {
+                let src = link.target();
+                let src = src.strip_prefix("file:").unwrap_or(src);
+                write!(
+                    w,
+                    "<img src=\"{}\" alt=\"{}\">",
+                    HtmlEscape(src),
+                    HtmlEscape(src.rsplit('/').next().unwrap_or(src)),
+                )?
+            }
             Element::Link(link) => write!(
                 w,
                 "<a href=\"{}\">{}</a>",
-                HtmlEscape(&link.path),
-                HtmlEscape(link.desc.as_ref().unwrap_or(&link.path)),
+                HtmlEscape(link.target()),
+                HtmlEscape(
+                    link.desc
+                        .as_deref()
+                        .or_else(|| link.coderef())
+                        .unwrap_or(&link.path)
+                ),
             )?,
             Element::Macros(_macros) => (),
             Element::RadioTarget => (),
@@ -134,6 +174,16 @@ impl HtmlHandler<Error> for DefaultHtmlHandler {
                     write!(w, "{}", snippet.value)?;
                 }
             }
+            Element::Citation(citation) => {
+                write!(&mut w, "<cite>")?;
+                for (i, key) in citation.keys.iter().enumerate() {
+                    if i > 0 {
+                        write!(&mut w, "; ")?;
+                    }
+                    write!(&mut w, "{}", HtmlEscape(key))?;
+                }
+                write!(&mut w, "</cite>")?;
+            }
             Element::Target(_target) => (),
             Element::Text { value } => write!(w, "{}", HtmlEscape(value))?,
             Element::Timestamp(timestamp) => {
@@ -142,19 +192,20 @@ impl HtmlHandler<Error> for DefaultHtmlHandler {
                     "<span class=\"timestamp-wrapper\"><span class=\"timestamp\">"
                 )?;
 
+                let suffix = timestamp.repeater_and_delay();
                 match timestamp {
                     Timestamp::Active { start, .. } => {
-                        write_datetime(&mut w, "&lt;", start, "&gt;")?;
+                        write_datetime(&mut w, "&lt;", start, &format!("{}&gt;", suffix))?;
                     }
                     Timestamp::Inactive { start, .. } => {
-                        write_datetime(&mut w, "[", start, "]")?;
+                        write_datetime(&mut w, "[", start, &format!("{}]", suffix))?;
                     }
                     Timestamp::ActiveRange { start, end, .. } => {
-                        write_datetime(&mut w, "&lt;", start, "&gt;&#x2013;")?;
+                        write_datetime(&mut w, "&lt;", start, &format!("{}&gt;&#x2013;", suffix))?;
                         write_datetime(&mut w, "&lt;", end, "&gt;")?;
                     }
                     Timestamp::InactiveRange { start, end, .. } => {
-                        write_datetime(&mut w, "[", start, "]&#x2013;")?;
+                        write_datetime(&mut w, "[", start, &format!("{}]&#x2013;", suffix))?;
                         write_datetime(&mut w, "[", end, "]")?;
                     }
                     Timestamp::Diary { value } => {
@@ -175,6 +226,7 @@ impl HtmlHandler<Error> for DefaultHtmlHandler {
             )?,
             Element::Keyword(_keyword) => (),
             Element::Drawer(_drawer) => (),
+            Element::EncryptedSection(_) => (),
             Element::Rule(_) => write!(w, "<hr>")?,
             Element::Cookie(cookie) => write!(w, "<code>{}</code>", cookie.value)?,
             Element::Title(title) => {
@@ -225,6 +277,7 @@ impl HtmlHandler<Error> for DefaultHtmlHandler {
             Element::Italic => write!(w, "</i>")?,
             Element::ListItem(_) => write!(w, "</li>")?,
             Element::Paragraph { .. } => write!(w, "</p>")?,
+            Element::VerseLine { .. } => write!(w, "<br/>")?,
             Element::Section => write!(w, "</section>")?,
             Element::Strike => write!(w, "</s>")?,
             Element::Underline => write!(w, "</u>")?,
@@ -1,12 +1,23 @@
 //! Export `Org` struct to various formats.
 
+#[cfg(feature = "pulldown-cmark")]
+mod cmark;
+mod dot;
+mod feed;
 mod html;
 mod org;
+mod reveal;
+mod standalone;
 
+#[cfg(feature = "pulldown-cmark")]
+pub use cmark::CmarkEvents;
+pub use dot::DotConfig;
+pub use feed::{Feed, FeedEntry};
 #[cfg(feature = "syntect")]
 pub use html::SyntectHtmlHandler;
 pub use html::{DefaultHtmlHandler, HtmlEscape, HtmlHandler};
 pub use org::{DefaultOrgHandler, OrgHandler};
+pub use standalone::{StandaloneConfig, DEFAULT_CSS};
 
 use std::io::{Error, Write};
 
@@ -21,9 +32,12 @@ pub(crate) fn write_datetime<W: Write>(
     write!(w, "{}", start)?;
     write!(
         w,
-        "{}-{:02}-{:02} {}",
-        datetime.year, datetime.month, datetime.day, datetime.dayname
+        "{:04}-{:02}-{:02}",
+        datetime.year, datetime.month, datetime.day
     )?;
+    if !datetime.dayname.is_empty() {
+        write!(w, " {}", datetime.dayname)?;
+    }
     if let (Some(hour), Some(minute)) = (datetime.hour, datetime.minute) {
         write!(w, " {:02}:{:02}", hour, minute)?;
     }
@@ -1,6 +1,6 @@
 use std::io::{Error, Result as IOResult, Write};
 
-use crate::elements::{Clock, Element, Table, Timestamp};
+use crate::elements::{block::escape, Clock, Element, Table, Timestamp};
 use crate::export::write_datetime;
 
 pub trait OrgHandler<E: From<Error>>: Default {
@@ -52,7 +52,15 @@ impl OrgHandler<Error> for DefaultOrgHandler {
                 write!(&mut w, "{}", list_item.bullet)?;
             }
             Element::Paragraph { .. } => (),
+            Element::VerseLine { indent } => write!(w, "{:1$}", "", indent)?,
             Element::Section => (),
+            Element::EncryptedSection(section) => {
+                write!(&mut w, "{}", section.data)?;
+                if !section.data.ends_with('\n') {
+                    writeln!(&mut w)?;
+                }
+                write_blank_lines(&mut w, section.post_blank)?;
+            }
             Element::Strike => write!(w, "+")?,
             Element::Underline => write!(w, "_")?,
             Element::Drawer(drawer) => {
@@ -68,19 +76,19 @@ impl OrgHandler<Error> for DefaultOrgHandler {
             }
             Element::ExampleBlock(block) => {
                 writeln!(&mut w, "#+BEGIN_EXAMPLE")?;
-                write!(&mut w, "{}", block.contents)?;
+                write!(&mut w, "{}", escape(&block.contents))?;
                 writeln!(&mut w, "#+END_EXAMPLE")?;
                 write_blank_lines(&mut w, block.post_blank)?;
             }
             Element::ExportBlock(block) => {
                 writeln!(&mut w, "#+BEGIN_EXPORT {}", block.data)?;
-                write!(&mut w, "{}", block.contents)?;
+                write!(&mut w, "{}", escape(&block.contents))?;
                 writeln!(&mut w, "#+END_EXPORT")?;
                 write_blank_lines(&mut w, block.post_blank)?;
             }
             Element::SourceBlock(block) => {
                 writeln!(&mut w, "#+BEGIN_SRC {}", block.language)?;
-                write!(&mut w, "{}", block.contents)?;
+                write!(&mut w, "{}", escape(&block.contents))?;
                 writeln!(&mut w, "#+END_SRC")?;
                 write_blank_lines(&mut w, block.post_blank)?;
             }
@@ -123,6 +131,13 @@ impl OrgHandler<Error> for DefaultOrgHandler {
             Element::Macros(_macros) => (),
             Element::RadioTarget => (),
             Element::Snippet(snippet) => write!(w, "@@{}:{}@@", snippet.name, snippet.value)?,
+            Element::Citation(citation) => {
+                write!(&mut w, "[cite")?;
+                if let Some(style) = &citation.style {
+                    write!(&mut w, "/{}", style)?;
+                }
+                write!(&mut w, ":{}]", citation.value)?;
+            }
             Element::Target(_target) => (),
             Element::Text { value } => write!(w, "{}", value)?,
             Element::Timestamp(timestamp) => {
@@ -176,7 +191,7 @@ impl OrgHandler<Error> for DefaultOrgHandler {
                 writeln!(w, "-----")?;
                 write_blank_lines(&mut w, rule.post_blank)?;
             }
-            Element::Cookie(_cookie) => (),
+            Element::Cookie(cookie) => write!(w, "{}", cookie.value)?,
             Element::Title(title) => {
                 for _ in 0..title.level {
                     write!(&mut w, "*")?;
@@ -231,6 +246,7 @@ impl OrgHandler<Error> for DefaultOrgHandler {
             Element::Paragraph { post_blank } => {
                 write_blank_lines(w, post_blank + 1)?;
             }
+            Element::VerseLine { .. } => writeln!(w)?,
             Element::Section => (),
             Element::Strike => write!(w, "+")?,
             Element::Underline => write!(w, "_")?,
@@ -299,20 +315,21 @@ fn write_blank_lines<W: Write>(mut w: W, count: usize) -> Result<(), Error> {
     Ok(())
 }
 
-fn write_timestamp<W: Write>(mut w: W, timestamp: &Timestamp) -> Result<(), Error> {
+pub(crate) fn write_timestamp<W: Write>(mut w: W, timestamp: &Timestamp) -> Result<(), Error> {
+    let suffix = timestamp.repeater_and_delay();
     match timestamp {
         Timestamp::Active { start, .. } => {
-            write_datetime(w, "<", start, ">")?;
+            write_datetime(w, "<", start, &format!("{}>", suffix))?;
         }
         Timestamp::Inactive { start, .. } => {
-            write_datetime(w, "[", start, "]")?;
+            write_datetime(w, "[", start, &format!("{}]", suffix))?;
         }
         Timestamp::ActiveRange { start, end, .. } => {
-            write_datetime(&mut w, "<", start, ">--")?;
+            write_datetime(&mut w, "<", start, &format!("{}>--", suffix))?;
             write_datetime(&mut w, "<", end, ">")?;
         }
         Timestamp::InactiveRange { start, end, .. } => {
-            write_datetime(&mut w, "[", start, "]--")?;
+            write_datetime(&mut w, "[", start, &format!("{}]--", suffix))?;
             write_datetime(&mut w, "[", end, "]")?;
         }
         Timestamp::Diary { value } => write!(w, "<%%({})>", value)?,
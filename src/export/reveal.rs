@@ -0,0 +1,161 @@
+use std::io::{Error, Write};
+
+use crate::export::{DefaultHtmlHandler, HtmlEscape, HtmlHandler};
+use crate::Org;
+
+/// Default location of reveal.js, like `org-reveal-root`
+const REVEAL_ROOT: &str = "https://cdn.jsdelivr.net/npm/reveal.js";
+
+impl Org<'_> {
+    /// Writes an `Org` struct as a reveal.js presentation, like `ox-reveal`.
+    ///
+    /// Top-level headlines become slides, and their children become vertical
+    /// slides below them. Headlines tagged with `noexport` or commented out
+    /// are skipped with their subtrees. Contents before the first headline go
+    /// to the title slide.
+    ///
+    /// The page honors these keywords:
+    ///
+    /// - `REVEAL_ROOT`: location of reveal.js
+    /// - `REVEAL_THEME`: theme name, defaults to `black`
+    /// - `REVEAL_TRANS`: transition style
+    /// - `REVEAL_EXTRA_CSS`: an extra stylesheet
+    /// - `REVEAL_INIT_OPTIONS`: extra options passed to `Reveal.initialize`
+    /// - `REVEAL_TITLE_SLIDE`: no title slide if it's `nil`
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse(
+    ///     "#+TITLE: Talk\n#+REVEAL_THEME: white\n\
+    ///      * Intro\n** Details\n* Secret :noexport:\n* End\n",
+    /// );
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_reveal(&mut writer).unwrap();
+    /// let html = String::from_utf8(writer).unwrap();
+    ///
+    /// assert!(html.contains("/dist/theme/white.css"));
+    /// assert!(html.contains(
+    ///     "<section><section><h1>Intro</h1></section>\
+    ///      <section><h2>Details</h2></section></section>\n\
+    ///      <section><h1>End</h1></section>\n"
+    /// ));
+    /// assert!(!html.contains("Secret"));
+    /// ```
+    pub fn write_reveal<W>(&self, writer: W) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        self.write_reveal_custom(writer, &mut DefaultHtmlHandler)
+    }
+
+    /// Writes an `Org` struct as a reveal.js presentation with custom
+    /// `HtmlHandler`.
+    pub fn write_reveal_custom<W, H, E>(&self, mut writer: W, handler: &mut H) -> Result<(), E>
+    where
+        W: Write,
+        E: From<Error>,
+        H: HtmlHandler<E>,
+    {
+        let title = self.keyword("TITLE");
+        let root = self.keyword("REVEAL_ROOT");
+        let root = root.as_deref().unwrap_or(REVEAL_ROOT).trim_end_matches('/');
+        let theme = self.keyword("REVEAL_THEME");
+
+        writeln!(
+            writer,
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">"
+        )?;
+        if let Some(title) = &title {
+            writeln!(writer, "<title>{}</title>", HtmlEscape(title))?;
+        }
+        writeln!(
+            writer,
+            "<link rel=\"stylesheet\" href=\"{}/dist/reveal.css\">",
+            HtmlEscape(root)
+        )?;
+        writeln!(
+            writer,
+            "<link rel=\"stylesheet\" href=\"{}/dist/theme/{}.css\">",
+            HtmlEscape(root),
+            HtmlEscape(theme.as_deref().unwrap_or("black"))
+        )?;
+        for css in self.keyword_values("REVEAL_EXTRA_CSS") {
+            writeln!(
+                writer,
+                "<link rel=\"stylesheet\" href=\"{}\">",
+                HtmlEscape(css)
+            )?;
+        }
+        writeln!(
+            writer,
+            "</head>\n<body>\n<div class=\"reveal\">\n<div class=\"slides\">"
+        )?;
+
+        let document = self.document();
+        let title_slide =
+            !matches!(self.keyword("REVEAL_TITLE_SLIDE"), Some(value) if value == "nil");
+        if title_slide {
+            write!(writer, "<section id=\"title-slide\">")?;
+            if let Some(title) = &title {
+                write!(writer, "<h1 class=\"title\">{}</h1>", HtmlEscape(title))?;
+            }
+            if let Some(author) = self.keyword("AUTHOR") {
+                write!(writer, "<p class=\"author\">{}</p>", HtmlEscape(author))?;
+            }
+            if let Some(section) = document.section_node() {
+                self.write_html_node(&mut writer, handler, section)?;
+            }
+            writeln!(writer, "</section>")?;
+        }
+
+        for headline in document.children(self).filter(|h| self.is_exported(*h)) {
+            let children: Vec<_> = headline
+                .children(self)
+                .filter(|h| self.is_exported(*h))
+                .collect();
+            if children.is_empty() {
+                write!(writer, "<section>")?;
+                self.write_html_headline(&mut writer, handler, headline)?;
+                writeln!(writer, "</section>")?;
+                continue;
+            }
+
+            write!(writer, "<section><section>")?;
+            self.write_html_node(&mut writer, handler, headline.title_node())?;
+            if let Some(section) = headline.section_node() {
+                self.write_html_node(&mut writer, handler, section)?;
+            }
+            write!(writer, "</section>")?;
+            for child in children {
+                write!(writer, "<section>")?;
+                self.write_html_headline(&mut writer, handler, child)?;
+                write!(writer, "</section>")?;
+            }
+            writeln!(writer, "</section>")?;
+        }
+
+        let mut options = Vec::new();
+        if let Some(transition) = self.keyword("REVEAL_TRANS") {
+            options.push(format!("transition: '{}'", transition.replace('\'', "\\'")));
+        }
+        if let Some(init) = self.keyword("REVEAL_INIT_OPTIONS") {
+            options.push(init);
+        }
+        writeln!(writer, "</div>\n</div>")?;
+        writeln!(
+            writer,
+            "<script src=\"{}/dist/reveal.js\"></script>",
+            HtmlEscape(root)
+        )?;
+        writeln!(
+            writer,
+            "<script>\nReveal.initialize({{{}}});\n</script>",
+            options.join(", ")
+        )?;
+        writeln!(writer, "</body>\n</html>")?;
+
+        Ok(())
+    }
+}
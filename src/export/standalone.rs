@@ -0,0 +1,212 @@
+use std::fs;
+use std::io::{Error, Write};
+use std::path::PathBuf;
+
+use crate::elements::{Element, Link};
+use crate::export::{DefaultHtmlHandler, HtmlEscape, HtmlHandler};
+use crate::{Event, Org};
+
+/// Default stylesheet embedded in standalone html pages
+pub const DEFAULT_CSS: &str = "\
+body{margin:2em auto;max-width:48em;padding:0 1em;line-height:1.5;\
+font-family:-apple-system,'Segoe UI',Helvetica,Arial,sans-serif;color:#222}\
+h1.title{text-align:center}\
+pre{background:#f6f8fa;border-radius:4px;padding:.8em;overflow:auto}\
+code{background:#f6f8fa;border-radius:3px;padding:0 .2em}\
+pre code{padding:0}\
+blockquote{margin-left:0;padding-left:1em;border-left:4px solid #ddd;color:#555}\
+table{border-collapse:collapse}\
+td,th{border:1px solid #ccc;padding:.2em .6em}\
+img{max-width:100%}\
+.center{text-align:center}\
+.timestamp{color:#6a737d}";
+
+/// Configuration of standalone html pages
+#[derive(Clone, Debug)]
+pub struct StandaloneConfig {
+    /// Stylesheet embedded in the `<head>`, nothing is embedded if it's empty
+    pub css: String,
+    /// Whether local images are inlined as data URIs
+    pub inline_images: bool,
+    /// Directory which relative image paths are resolved against
+    pub base_dir: PathBuf,
+}
+
+impl Default for StandaloneConfig {
+    fn default() -> Self {
+        StandaloneConfig {
+            css: DEFAULT_CSS.into(),
+            inline_images: false,
+            base_dir: PathBuf::from("."),
+        }
+    }
+}
+
+impl Org<'_> {
+    /// Writes an `Org` struct as a complete html page, with a `<head>`
+    /// built from `TITLE`, `AUTHOR`, `DESCRIPTION`, `KEYWORDS` and `LANGUAGE`
+    /// keywords.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// # use orgize::export::StandaloneConfig;
+    /// #
+    /// let org = Org::parse("#+TITLE: Notes\n#+AUTHOR: Alice\n\ntext");
+    /// let config = StandaloneConfig {
+    ///     css: String::new(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_html_standalone(&mut writer, &config).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+    ///      <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+    ///      <title>Notes</title>\n<meta name=\"author\" content=\"Alice\">\n\
+    ///      </head>\n<body>\n<h1 class=\"title\">Notes</h1>\n\
+    ///      <main><section><p>text</p></section></main>\n</body>\n</html>\n"
+    /// );
+    /// ```
+    pub fn write_html_standalone<W>(
+        &self,
+        writer: W,
+        config: &StandaloneConfig,
+    ) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        self.write_html_standalone_custom(writer, &mut DefaultHtmlHandler, config)
+    }
+
+    /// Writes an `Org` struct as a complete html page with custom
+    /// `HtmlHandler`.
+    pub fn write_html_standalone_custom<W, H, E>(
+        &self,
+        mut writer: W,
+        handler: &mut H,
+        config: &StandaloneConfig,
+    ) -> Result<(), E>
+    where
+        W: Write,
+        E: From<Error>,
+        H: HtmlHandler<E>,
+    {
+        let title = self.keyword("TITLE");
+        let language = self.keyword("LANGUAGE");
+
+        writeln!(writer, "<!DOCTYPE html>")?;
+        writeln!(
+            writer,
+            "<html lang=\"{}\">",
+            HtmlEscape(language.as_deref().unwrap_or("en"))
+        )?;
+        writeln!(writer, "<head>\n<meta charset=\"utf-8\">")?;
+        writeln!(
+            writer,
+            "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">"
+        )?;
+        if let Some(title) = &title {
+            writeln!(writer, "<title>{}</title>", HtmlEscape(title))?;
+        }
+        for (key, name) in &[
+            ("AUTHOR", "author"),
+            ("DESCRIPTION", "description"),
+            ("KEYWORDS", "keywords"),
+        ] {
+            if let Some(value) = self.keyword(key) {
+                writeln!(
+                    writer,
+                    "<meta name=\"{}\" content=\"{}\">",
+                    name,
+                    HtmlEscape(value)
+                )?;
+            }
+        }
+        if !config.css.is_empty() {
+            writeln!(writer, "<style>{}</style>", config.css)?;
+        }
+        writeln!(writer, "</head>\n<body>")?;
+        if let Some(title) = &title {
+            writeln!(writer, "<h1 class=\"title\">{}</h1>", HtmlEscape(title))?;
+        }
+
+        for event in self.iter() {
+            match event {
+                Event::Start(element @ Element::Link(link))
+                    if config.inline_images && link.is_image() =>
+                {
+                    match inline_image(link, config) {
+                        Some(src) => {
+                            let path = link.target();
+                            let path = path.strip_prefix("file:").unwrap_or(path);
+                            write!(
+                                writer,
+                                "<img src=\"{}\" alt=\"{}\">",
+                                src,
+                                HtmlEscape(path.rsplit('/').next().unwrap_or(path))
+                            )?;
+                        }
+                        None => handler.start(&mut writer, element)?,
+                    }
+                }
+                Event::Start(element) => handler.start(&mut writer, element)?,
+                Event::End(element) => handler.end(&mut writer, element)?,
+            }
+        }
+
+        writeln!(writer, "\n</body>\n</html>")?;
+
+        Ok(())
+    }
+}
+
+/// Reads the local image of `link` and encodes it as a data URI.
+fn inline_image(link: &Link, config: &StandaloneConfig) -> Option<String> {
+    let target = link.target();
+    let path = target.strip_prefix("file:").unwrap_or(target);
+    if path.contains("://") {
+        return None;
+    }
+
+    let extension = path.rsplit('.').next()?.to_ascii_lowercase();
+    let mime = match &*extension {
+        "jpg" | "jpeg" => "jpeg",
+        "svg" => "svg+xml",
+        "tif" | "tiff" => "tiff",
+        extension => extension,
+    };
+    let data = fs::read(config.base_dir.join(path)).ok()?;
+
+    Some(format!("data:image/{};base64,{}", mime, base64(&data)))
+}
+
+/// Encodes `bytes` in standard base64 with padding.
+fn base64(bytes: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(TABLE[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[test]
+fn encode() {
+    assert_eq!(base64(b""), "");
+    assert_eq!(base64(b"f"), "Zg==");
+    assert_eq!(base64(b"fo"), "Zm8=");
+    assert_eq!(base64(b"foo"), "Zm9v");
+    assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+}
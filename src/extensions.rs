@@ -0,0 +1,89 @@
+use alloc::boxed::Box;
+use core::any::{Any, TypeId};
+
+use crate::compat::HashMap;
+use crate::config::ParseConfig;
+use crate::elements::Element;
+use crate::Org;
+
+/// Typed data stored on a document, at most one value per type
+///
+/// See [`KeywordHandlers`](struct.KeywordHandlers.html).
+#[derive(Default)]
+pub struct Extensions {
+    map: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl Extensions {
+    /// Inserts `value`, returning the previous value of the same type.
+    pub fn insert<T: Any + Send + Sync>(&mut self, value: T) -> Option<T> {
+        self.map
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .and_then(|old| old.downcast().ok())
+            .map(|old| *old)
+    }
+
+    /// Returns a reference to the value of type `T`.
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.map.get(&TypeId::of::<T>())?.downcast_ref()
+    }
+
+    /// Returns a mutable reference to the value of type `T`.
+    pub fn get_mut<T: Any + Send + Sync>(&mut self) -> Option<&mut T> {
+        self.map.get_mut(&TypeId::of::<T>())?.downcast_mut()
+    }
+
+    /// Returns a mutable reference to the value of type `T`, inserting the
+    /// result of `f` if it doesn't exist.
+    pub fn get_or_insert_with<T: Any + Send + Sync>(&mut self, f: impl FnOnce() -> T) -> &mut T {
+        self.map
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(f()))
+            .downcast_mut()
+            .expect("value is always of its key type")
+    }
+
+    /// Removes and returns the value of type `T`.
+    pub fn remove<T: Any + Send + Sync>(&mut self) -> Option<T> {
+        self.map
+            .remove(&TypeId::of::<T>())
+            .and_then(|old| old.downcast().ok())
+            .map(|old| *old)
+    }
+
+    /// Returns `true` if no value is stored.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Removes all values.
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+}
+
+impl Org<'_> {
+    /// Returns data stored by keyword handlers.
+    pub fn extensions(&self) -> &Extensions {
+        &self.extensions
+    }
+
+    /// Returns data stored by keyword handlers, mutably.
+    pub fn extensions_mut(&mut self) -> &mut Extensions {
+        &mut self.extensions
+    }
+
+    /// Calls keyword handlers from `config` with all keywords, in document order.
+    pub(crate) fn handle_keywords(&mut self, config: &ParseConfig) {
+        if config.keyword_handlers.is_empty() {
+            return;
+        }
+
+        self.extensions.clear();
+        for node in self.root.descendants(&self.arena) {
+            if let Element::Keyword(keyword) = &self.arena[node].get() {
+                config.keyword_handlers.call(keyword, &mut self.extensions);
+            }
+        }
+    }
+}
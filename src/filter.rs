@@ -0,0 +1,140 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use indextree::{NodeEdge, NodeId, Traverse};
+
+use crate::elements::Element;
+use crate::{Event, Org};
+
+/// Options of [`Org::iter_filtered`] for skipping contents which aren't
+/// visible or exported
+///
+/// All filters are enabled by default, and `exclude_tags` defaults to
+/// `noexport`.
+///
+/// [`Org::iter_filtered`]: struct.Org.html#method.iter_filtered
+#[derive(Debug, Clone)]
+pub struct IterFilter {
+    /// Skips subtrees of headlines starting with `COMMENT`
+    pub skip_commented: bool,
+    /// Skips subtrees of headlines tagged with `:ARCHIVE:`
+    pub skip_archived: bool,
+    /// Skips drawers and their contents
+    pub skip_drawers: bool,
+    /// Skips subtrees of headlines tagged with one of these tags. Like
+    /// org-mode's `org-export-exclude-tags`, only tags written on headlines
+    /// are matched, since skipping a subtree already skips the headlines
+    /// inheriting the tag, while `#+FILETAGS` are ignored.
+    pub exclude_tags: Vec<String>,
+}
+
+impl Default for IterFilter {
+    fn default() -> Self {
+        IterFilter {
+            skip_commented: true,
+            skip_archived: true,
+            skip_drawers: true,
+            exclude_tags: vec!["noexport".into()],
+        }
+    }
+}
+
+/// Iterator of `Event`s skipping filtered elements, created by
+/// [`Org::iter_filtered`]
+///
+/// [`Org::iter_filtered`]: struct.Org.html#method.iter_filtered
+pub struct FilteredIter<'a, 'b> {
+    org: &'b Org<'a>,
+    traverse: Traverse<'b, Element<'a>>,
+    filter: IterFilter,
+    /// Element whose subtree is being skipped
+    skipping: Option<NodeId>,
+}
+
+impl<'a, 'b> FilteredIter<'a, 'b> {
+    fn is_filtered(&self, node: NodeId) -> bool {
+        match &self.org[node] {
+            Element::Drawer(_) => self.filter.skip_drawers,
+            Element::Headline { .. } => {
+                let title = match node
+                    .children(&self.org.arena)
+                    .next()
+                    .map(|child| &self.org[child])
+                {
+                    Some(Element::Title(title)) => title,
+                    _ => return false,
+                };
+
+                (self.filter.skip_commented && title.is_commented())
+                    || (self.filter.skip_archived && title.is_archived())
+                    || title
+                        .tags
+                        .iter()
+                        .any(|tag| self.filter.exclude_tags.iter().any(|t| t == tag))
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<'a, 'b> Iterator for FilteredIter<'a, 'b> {
+    type Item = Event<'a, 'b>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.traverse.next()? {
+                NodeEdge::Start(node) if self.skipping.is_none() => {
+                    if self.is_filtered(node) {
+                        self.skipping = Some(node);
+                    } else {
+                        return Some(Event::Start(&self.org[node]));
+                    }
+                }
+                NodeEdge::End(node) if self.skipping == Some(node) => self.skipping = None,
+                NodeEdge::End(node) if self.skipping.is_none() => {
+                    return Some(Event::End(&self.org[node]));
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+impl<'a> Org<'a> {
+    /// Returns an iterator of `Event`s like [`Org::iter`], but skips
+    /// elements filtered by `filter`, e.g. commented or archived subtrees.
+    ///
+    /// [`Org::iter`]: #method.iter
+    ///
+    /// ```rust
+    /// # use orgize::{Element, Event, IterFilter, Org};
+    /// #
+    /// let org = Org::parse(
+    ///     "* draft :noexport:\n** child\n* COMMENT todo\n* post\n\
+    ///      :LOGBOOK:\n- note\n:END:\ntext\n",
+    /// );
+    ///
+    /// let titles: Vec<_> = org
+    ///     .iter_filtered(IterFilter::default())
+    ///     .filter_map(|event| match event {
+    ///         Event::Start(Element::Title(title)) => Some(&*title.raw),
+    ///         _ => None,
+    ///     })
+    ///     .collect();
+    /// assert_eq!(titles, vec!["post"]);
+    ///
+    /// let lists = org
+    ///     .iter_filtered(IterFilter::default())
+    ///     .filter(|event| matches!(event, Event::Start(Element::List(_))))
+    ///     .count();
+    /// assert_eq!(lists, 0);
+    /// ```
+    pub fn iter_filtered<'b>(&'b self, filter: IterFilter) -> FilteredIter<'a, 'b> {
+        FilteredIter {
+            org: self,
+            traverse: self.root.traverse(&self.arena),
+            filter,
+            skipping: None,
+        }
+    }
+}
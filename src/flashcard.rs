@@ -0,0 +1,321 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::elements::{Datetime, Element, Timestamp};
+use crate::{Headline, Org};
+
+/// Spaced repetition package a flashcard belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FlashcardKind {
+    /// org-drill card, tagged with `drill`
+    Drill,
+    /// org-fc card, tagged with `fc`
+    Fc,
+}
+
+/// Flashcard extracted from a headline
+#[derive(Debug, Clone)]
+pub struct Flashcard {
+    pub headline: Headline,
+    pub kind: FlashcardKind,
+    /// Card type, from the `DRILL_CARD_TYPE` or `FC_TYPE` property
+    pub card_type: Option<String>,
+    /// Question text, in which cloze deletions are hidden as `[...]` or
+    /// `[hint...]`
+    pub question: String,
+    /// Answer text, from the first subheading, or the question text with
+    /// cloze deletions revealed
+    pub answer: String,
+    pub scheduling: Scheduling,
+}
+
+/// Scheduling data of a flashcard
+#[derive(Debug, Clone, Default)]
+pub struct Scheduling {
+    /// Next review date, from the scheduled timestamp of org-drill cards or
+    /// the review data of org-fc cards
+    pub due: Option<Datetime<'static>>,
+    /// Last review date, from the `DRILL_LAST_REVIEWED` property
+    pub last_reviewed: Option<Datetime<'static>>,
+    /// Interval in days
+    pub interval: Option<f64>,
+    /// Ease factor
+    pub ease: Option<f64>,
+    /// Total number of reviews, from the `DRILL_TOTAL_REPEATS` property or
+    /// the box of org-fc cards
+    pub repeats: Option<u32>,
+    /// Number of failed reviews, from the `DRILL_FAILURE_COUNT` property
+    pub failures: Option<u32>,
+}
+
+impl Headline {
+    /// Returns the flashcard of this headline, or `None` if it isn't tagged
+    /// with `drill` or `fc`.
+    ///
+    /// Cloze deletions are written as `[text]` or `[text||hint]` in org-drill
+    /// cards, and as `{{text}@0}` or `{{text}{hint}@0}` in org-fc cards.
+    ///
+    /// ```rust
+    /// # use orgize::{FlashcardKind, Org};
+    /// #
+    /// let org = Org::parse(
+    ///     "* Capital :drill:\n\
+    ///      SCHEDULED: <2023-03-01 Wed>\n\
+    ///      :PROPERTIES:\n:DRILL_EASE: 2.5\n:DRILL_TOTAL_REPEATS: 3\n:END:\n\
+    ///      The capital of France is [Paris||city].\n",
+    /// );
+    /// let card = org.headlines().next().unwrap().flashcard(&org).unwrap();
+    ///
+    /// assert_eq!(card.kind, FlashcardKind::Drill);
+    /// assert_eq!(card.question, "The capital of France is [city...].");
+    /// assert_eq!(card.answer, "The capital of France is Paris.");
+    /// assert_eq!(card.scheduling.ease, Some(2.5));
+    /// assert_eq!(card.scheduling.repeats, Some(3));
+    /// assert_eq!(card.scheduling.due.unwrap().day, 1);
+    /// ```
+    pub fn flashcard(self, org: &Org) -> Option<Flashcard> {
+        let title = self.title(org);
+        let kind = if title.tags.iter().any(|tag| tag == "drill") {
+            FlashcardKind::Drill
+        } else if title.tags.iter().any(|tag| tag == "fc") {
+            FlashcardKind::Fc
+        } else {
+            return None;
+        };
+        let property = |key: &str| {
+            title
+                .properties
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(key))
+                .map(|(_, v)| v.trim())
+        };
+
+        // drawers like `:REVIEW_DATA:` aren't part of the question
+        let mut body = String::new();
+        let mut review_data = None;
+        for node in self
+            .section_node()
+            .into_iter()
+            .flat_map(|n| n.children(&org.arena))
+        {
+            match &org[node] {
+                Element::Drawer(drawer) if drawer.name.eq_ignore_ascii_case("REVIEW_DATA") => {
+                    review_data = org.span(node).map(|span| &org.text[span]);
+                }
+                Element::Drawer(_) => (),
+                _ => body.push_str(org.span(node).map_or("", |span| &org.text[span])),
+            }
+        }
+        let body = body.trim();
+        let text = if body.is_empty() { &*title.raw } else { body };
+
+        let (question, revealed) = match kind {
+            FlashcardKind::Drill => drill_clozes(text),
+            FlashcardKind::Fc => fc_clozes(text),
+        };
+        let answer = match self.first_child(org) {
+            Some(child) if question == revealed => child
+                .section_node()
+                .and_then(|section| org.span(section))
+                .map(|span| org.text[span].trim().to_string())
+                .unwrap_or_default(),
+            _ => revealed,
+        };
+
+        let scheduling = match kind {
+            FlashcardKind::Drill => Scheduling {
+                due: title
+                    .scheduled()
+                    .and_then(Timestamp::start)
+                    .map(|start| start.clone().into_owned()),
+                last_reviewed: property("DRILL_LAST_REVIEWED").and_then(|value| {
+                    let (_, timestamp) = Timestamp::parse_inactive(value)
+                        .or_else(|| Timestamp::parse_active(value))?;
+                    Some(timestamp.start()?.clone().into_owned())
+                }),
+                interval: property("DRILL_LAST_INTERVAL").and_then(|v| v.parse().ok()),
+                ease: property("DRILL_EASE").and_then(|v| v.parse().ok()),
+                repeats: property("DRILL_TOTAL_REPEATS").and_then(|v| v.parse().ok()),
+                failures: property("DRILL_FAILURE_COUNT").and_then(|v| v.parse().ok()),
+            },
+            FlashcardKind::Fc => review_data.map(fc_scheduling).unwrap_or_default(),
+        };
+
+        Some(Flashcard {
+            headline: self,
+            kind,
+            card_type: property(match kind {
+                FlashcardKind::Drill => "DRILL_CARD_TYPE",
+                FlashcardKind::Fc => "FC_TYPE",
+            })
+            .map(Into::into),
+            question,
+            answer,
+            scheduling,
+        })
+    }
+}
+
+impl Org<'_> {
+    /// Returns flashcards of all headlines tagged with `drill` or `fc`.
+    pub fn flashcards(&self) -> Vec<Flashcard> {
+        self.headlines()
+            .filter_map(|headline| headline.flashcard(self))
+            .collect()
+    }
+}
+
+/// Returns `text` with org-drill clozes hidden and revealed.
+fn drill_clozes(text: &str) -> (String, String) {
+    let (mut question, mut answer) = (String::new(), String::new());
+    let mut rest = text;
+    while let Some(start) = rest.find('[') {
+        let (before, after) = rest.split_at(start);
+        question.push_str(before);
+        answer.push_str(before);
+
+        let inner = &after[1..];
+        let cloze = inner
+            .find(['[', ']', '\n'])
+            .filter(|&end| {
+                let content = &inner[..end];
+                // skips links, checkboxes, footnotes and timestamps
+                inner[end..].starts_with(']')
+                    && !question.ends_with('[')
+                    && !inner[end + 1..].starts_with(']')
+                    && !matches!(content, "" | " " | "X" | "x" | "-")
+                    && !content.starts_with("fn:")
+                    && Timestamp::parse_inactive(&after[..end + 2]).is_none()
+            })
+            .map(|end| &inner[..end]);
+        match cloze {
+            Some(cloze) => {
+                let (text, hint) = match cloze.split_once("||") {
+                    Some((text, hint)) => (text, hint),
+                    None => (cloze, ""),
+                };
+                question.push('[');
+                question.push_str(hint);
+                question.push_str("...]");
+                answer.push_str(text);
+                rest = &after[cloze.len() + 2..];
+            }
+            None => {
+                question.push('[');
+                answer.push('[');
+                rest = &after[1..];
+            }
+        }
+    }
+    question.push_str(rest);
+    answer.push_str(rest);
+    (question, answer)
+}
+
+/// Returns `text` with org-fc clozes like `{{text}{hint}@0}` hidden and
+/// revealed.
+fn fc_clozes(text: &str) -> (String, String) {
+    let (mut question, mut answer) = (String::new(), String::new());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let (before, after) = rest.split_at(start);
+        question.push_str(before);
+        answer.push_str(before);
+
+        match fc_cloze(after) {
+            Some((text, hint, len)) => {
+                question.push('[');
+                question.push_str(hint);
+                question.push_str("...]");
+                answer.push_str(text);
+                rest = &after[len..];
+            }
+            None => {
+                question.push_str("{{");
+                answer.push_str("{{");
+                rest = &after[2..];
+            }
+        }
+    }
+    question.push_str(rest);
+    answer.push_str(rest);
+    (question, answer)
+}
+
+/// Parses an org-fc cloze, returning its text, hint and length.
+fn fc_cloze(input: &str) -> Option<(&str, &str, usize)> {
+    let rest = input.strip_prefix("{{")?;
+    let (text, rest) = rest.split_once('}')?;
+    let (hint, rest) = match rest.strip_prefix('{') {
+        Some(rest) => rest.split_once('}')?,
+        None => ("", rest),
+    };
+    let rest = rest.strip_prefix('@')?;
+    let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+    let rest = rest[digits..].strip_prefix('}')?;
+    if digits == 0 || text.contains('\n') {
+        return None;
+    }
+    Some((text, hint, input.len() - rest.len()))
+}
+
+/// Parses the first row of an org-fc `:REVIEW_DATA:` drawer table, like
+/// `| front | 2.50 | 3 | 6.00 | 2023-03-01T10:00:00Z |`.
+fn fc_scheduling(drawer: &str) -> Scheduling {
+    let row = drawer
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with('|') && !line.starts_with("|-"))
+        .nth(1);
+    let cells: Vec<_> = match row {
+        Some(row) => row.trim_matches('|').split('|').map(str::trim).collect(),
+        None => return Scheduling::default(),
+    };
+
+    Scheduling {
+        due: cells.get(4).and_then(|due| iso_datetime(due)),
+        last_reviewed: None,
+        interval: cells.get(3).and_then(|v| v.parse().ok()),
+        ease: cells.get(1).and_then(|v| v.parse().ok()),
+        repeats: cells.get(2).and_then(|v| v.parse().ok()),
+        failures: None,
+    }
+}
+
+/// Parses a datetime like `2023-03-01T10:00:00Z`.
+fn iso_datetime(input: &str) -> Option<Datetime<'static>> {
+    let (date, time) = input.split_once('T').unwrap_or((input, ""));
+    let mut date = date.splitn(3, '-');
+    let mut time = time.splitn(3, ':');
+
+    let mut datetime = Datetime {
+        year: date.next()?.parse().ok()?,
+        month: date.next()?.parse().ok()?,
+        day: date.next()?.parse().ok()?,
+        dayname: "".into(),
+        hour: time.next().and_then(|v| v.parse().ok()),
+        minute: time.next().and_then(|v| v.parse().ok()),
+    };
+    if datetime.minute.is_none() {
+        datetime.hour = None;
+    }
+    Some(datetime)
+}
+
+#[test]
+fn clozes() {
+    assert_eq!(
+        drill_clozes("[a] and [b||hint], not [[link]] or [X] or [2023-01-01 Sun]"),
+        (
+            "[...] and [hint...], not [[link]] or [X] or [2023-01-01 Sun]".into(),
+            "a and b, not [[link]] or [X] or [2023-01-01 Sun]".into()
+        )
+    );
+    assert_eq!(
+        fc_clozes("{{Paris}@0} is in {{France}{country}@1}, {{not}"),
+        (
+            "[...] is in [country...], {{not}".into(),
+            "Paris is in France, {{not}".into()
+        )
+    );
+}
@@ -0,0 +1,131 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use indextree::NodeId;
+
+use crate::compat::HashMap;
+use crate::elements::{Element, FnRef};
+use crate::Org;
+
+/// Footnote gathered for a footnote section
+#[derive(Debug, Clone, PartialEq)]
+pub struct Footnote<'b> {
+    /// Number of this footnote, starting from 1 in the order of first
+    /// references
+    pub number: usize,
+    /// Original label, which is empty for anonymous footnotes
+    pub label: &'b str,
+    /// Definition of this footnote, or `None` if it isn't defined
+    pub definition: Option<FootnoteDefinition<'b>>,
+    /// Footnote reference nodes in document order
+    pub references: Vec<NodeId>,
+}
+
+/// Definition of a footnote
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FootnoteDefinition<'b> {
+    /// Footnote definition element like `[fn:1] text`
+    Node(NodeId),
+    /// Inline definition like `[fn:1:text]` or `[fn::text]`
+    Inline(&'b str),
+}
+
+impl Org<'_> {
+    /// Gathers referenced footnotes, ordered and numbered by their first
+    /// references like org-export does.
+    ///
+    /// Footnotes referenced inside a footnote definition are placed right
+    /// after the footnote being defined. Unreferenced definitions are
+    /// skipped, and every anonymous footnote is a footnote of its own.
+    ///
+    /// ```rust
+    /// # use orgize::{FootnoteDefinition, Org};
+    /// #
+    /// let org = Org::parse(
+    ///     "Org[fn:org] and Rust[fn::A language.] and org again[fn:org].\n\n\
+    ///      [fn:unused] Never referenced.\n\n\
+    ///      [fn:org] A markup.\n",
+    /// );
+    ///
+    /// let footnotes = org.footnotes();
+    /// assert_eq!(footnotes.len(), 2);
+    /// assert_eq!((footnotes[0].number, footnotes[0].label), (1, "org"));
+    /// assert_eq!(footnotes[0].references.len(), 2);
+    /// assert!(matches!(footnotes[0].definition, Some(FootnoteDefinition::Node(_))));
+    /// assert_eq!((footnotes[1].number, footnotes[1].label), (2, ""));
+    /// assert_eq!(
+    ///     footnotes[1].definition,
+    ///     Some(FootnoteDefinition::Inline("A language."))
+    /// );
+    /// ```
+    pub fn footnotes(&self) -> Vec<Footnote<'_>> {
+        let mut definitions = HashMap::new();
+        for node in self.root.descendants(&self.arena) {
+            if let Element::FnDef(fn_def) = &self[node] {
+                definitions.entry(&*fn_def.label).or_insert(node);
+            }
+        }
+
+        let mut footnotes = Vec::new();
+        self.collect_footnotes(self.root, &definitions, &mut footnotes);
+        footnotes
+    }
+
+    fn collect_footnotes<'b>(
+        &'b self,
+        node: NodeId,
+        definitions: &HashMap<&str, NodeId>,
+        footnotes: &mut Vec<Footnote<'b>>,
+    ) {
+        for child in node.children(&self.arena) {
+            match &self[child] {
+                // definitions are walked when they're referenced first
+                Element::FnDef(_) => (),
+                Element::FnRef(fn_ref) => {
+                    self.collect_reference(child, fn_ref, definitions, footnotes)
+                }
+                _ => self.collect_footnotes(child, definitions, footnotes),
+            }
+        }
+    }
+
+    fn collect_reference<'b>(
+        &'b self,
+        node: NodeId,
+        fn_ref: &'b FnRef,
+        definitions: &HashMap<&str, NodeId>,
+        footnotes: &mut Vec<Footnote<'b>>,
+    ) {
+        let inline = fn_ref.definition.as_deref().map(FootnoteDefinition::Inline);
+
+        if !fn_ref.label.is_empty() {
+            let footnote = footnotes
+                .iter_mut()
+                .find(|footnote| footnote.label == fn_ref.label);
+            if let Some(footnote) = footnote {
+                footnote.references.push(node);
+                if footnote.definition.is_none() {
+                    footnote.definition = inline;
+                }
+                return;
+            }
+        }
+
+        let definition = inline.or_else(|| {
+            definitions
+                .get(&*fn_ref.label)
+                .filter(|_| !fn_ref.label.is_empty())
+                .map(|&node| FootnoteDefinition::Node(node))
+        });
+        footnotes.push(Footnote {
+            number: footnotes.len() + 1,
+            label: &fn_ref.label,
+            definition,
+            references: vec![node],
+        });
+
+        if let Some(FootnoteDefinition::Node(definition)) = definition {
+            self.collect_footnotes(definition, definitions, footnotes);
+        }
+    }
+}
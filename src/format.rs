@@ -0,0 +1,165 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::Range;
+use indextree::NodeId;
+
+use crate::config::{ParseConfig, DEFAULT_CONFIG};
+use crate::elements::{list::parse_counter, Element, Table};
+use crate::parsers::take_lines_while;
+use crate::Org;
+
+impl Org<'_> {
+    /// Re-aligns the org table `node` in the source text, like pressing
+    /// `TAB` in a table in Emacs, and reparses it.
+    ///
+    /// Returns `false` if `node` isn't an org table. See
+    /// [`Table::realign`](elements/enum.Table.html#method.realign) for more
+    /// details.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let mut org = Org::parse("|a|bb|\n|-\n|ccc|d|\n\ntext\n");
+    /// let section = org.document().section_node().unwrap();
+    /// let table = section.children(org.arena()).next().unwrap();
+    ///
+    /// assert!(org.realign_table(table));
+    ///
+    /// // "| a   | bb |\n|-----+----|\n| ccc | d  |\n\n"
+    /// let section = org.document().section_node().unwrap();
+    /// let table = section.children(org.arena()).next().unwrap();
+    /// assert_eq!(org.span(table), Some(0..40));
+    /// ```
+    pub fn realign_table(&mut self, node: NodeId) -> bool {
+        match self.realigned_table(node) {
+            Some(edit) => {
+                self.apply_edits(edit.into_iter().collect());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Renumbers items of the ordered list `node` in the source text, like
+    /// `org-list-repair`, and reparses it.
+    ///
+    /// Items are numbered from one, and `[@n]` counters set the number of
+    /// their items. Returns `false` if `node` isn't an ordered list.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let mut org = Org::parse("1. one\n1. two\n5. [@10] ten\n1. eleven\n");
+    /// let section = org.document().section_node().unwrap();
+    /// let list = section.children(org.arena()).next().unwrap();
+    ///
+    /// assert!(org.renumber_list(list));
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "1. one\n2. two\n10. [@10] ten\n11. eleven\n"
+    /// );
+    /// ```
+    pub fn renumber_list(&mut self, node: NodeId) -> bool {
+        match self.renumbered_list(node) {
+            Some(edits) => {
+                self.apply_edits(edits);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Formats the whole document, re-aligning all org tables and
+    /// renumbering all ordered lists.
+    pub fn format(&mut self) {
+        let mut edits = Vec::new();
+        for node in self.root.descendants(&self.arena) {
+            edits.extend(self.realigned_table(node).into_iter().flatten());
+            edits.extend(self.renumbered_list(node).into_iter().flatten());
+        }
+        edits.sort_by_key(|(range, _)| range.start);
+        self.apply_edits(edits);
+    }
+
+    /// Returns the edit re-aligning the org table `node`, if it's changed.
+    fn realigned_table(&self, node: NodeId) -> Option<Option<(Range<usize>, String)>> {
+        if !matches!(self[node], Element::Table(Table::Org { .. })) {
+            return None;
+        }
+
+        // trailing blank lines aren't part of rows
+        let span = self.span(node)?;
+        let (_, rows) =
+            take_lines_while(|line| line.trim_start().starts_with('|'))(&self.text[span.clone()]);
+        let mut realigned = Table::realign(rows);
+        if !rows.ends_with('\n') {
+            realigned.pop();
+        }
+
+        Some(if realigned != rows {
+            Some((span.start..span.start + rows.len(), realigned))
+        } else {
+            None
+        })
+    }
+
+    /// Returns edits of changed bullets of the ordered list `node`.
+    fn renumbered_list(&self, node: NodeId) -> Option<Vec<(Range<usize>, String)>> {
+        match &self[node] {
+            Element::List(list) if list.ordered => (),
+            _ => return None,
+        }
+
+        let mut edits = Vec::new();
+        let mut number = 1;
+        for child in node.children(&self.arena) {
+            let item = match &self[child] {
+                Element::ListItem(item) if item.ordered => item,
+                _ => continue,
+            };
+            let start = self.span(child)?.start + item.indent;
+            let digits = item.bullet.trim_end_matches(['.', ' ']).len();
+
+            if let Some(counter) = parse_counter(&self.text[start + item.bullet.len()..]) {
+                number = counter;
+            }
+            let bullet = number.to_string();
+            if bullet != item.bullet[..digits] {
+                edits.push((start..start + digits, bullet));
+            }
+            number += 1;
+        }
+
+        Some(edits)
+    }
+
+    /// Applies edits sorted by their positions at once.
+    pub(crate) fn apply_edits(&mut self, edits: Vec<(Range<usize>, String)>) {
+        self.apply_edits_custom(edits, &DEFAULT_CONFIG);
+    }
+
+    /// Like `apply_edits`, but reparses with custom `ParseConfig`.
+    pub(crate) fn apply_edits_custom(
+        &mut self,
+        edits: Vec<(Range<usize>, String)>,
+        config: &ParseConfig,
+    ) {
+        let (start, end) = match (edits.first(), edits.last()) {
+            (Some((first, _)), Some((last, _))) => (first.start, last.end),
+            _ => return,
+        };
+
+        let mut text = String::new();
+        let mut last_end = start;
+        for (range, replacement) in edits {
+            text.push_str(&self.text[last_end..range.start]);
+            text.push_str(&replacement);
+            last_end = range.end;
+        }
+
+        self.edit_custom(start..end, &text, config);
+    }
+}
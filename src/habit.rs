@@ -0,0 +1,200 @@
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use crate::elements::{Datetime, Repeater, TimeUnit, Timestamp};
+use crate::{Headline, Org};
+
+/// Habit-style task, whose `STYLE` property is `habit`
+#[derive(Debug, Clone)]
+pub struct Habit {
+    /// Current scheduled date
+    pub scheduled: Datetime<'static>,
+    /// Repeater of the scheduled timestamp
+    pub repeater: Repeater,
+    /// Dates of state changes into a done keyword, oldest first
+    pub completions: Vec<Datetime<'static>>,
+}
+
+/// State of a habit on a single day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HabitState {
+    /// Before the habit is due
+    Early,
+    /// Between the scheduled date and the deadline
+    Due,
+    /// After the deadline
+    Overdue,
+}
+
+/// Habit data of a single day, like a column in org-habit's graph
+#[derive(Debug, Clone)]
+pub struct HabitDay {
+    pub date: Datetime<'static>,
+    /// Whether the habit was completed on this day
+    pub done: bool,
+    pub state: HabitState,
+}
+
+impl Headline {
+    /// Returns the habit data of this headline, or `None` if it isn't a
+    /// habit or has no repeating scheduled timestamp.
+    ///
+    /// Completions are collected from state change notes like
+    /// `- State "DONE" from "TODO" [2023-01-02 Mon 10:00]`, whose new state
+    /// is one of `done_keywords`.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse(
+    ///     "* TODO exercise\n\
+    ///      SCHEDULED: <2023-01-06 Fri .+2d/3d>\n\
+    ///      :PROPERTIES:\n\
+    ///      :STYLE: habit\n\
+    ///      :END:\n\
+    ///      :LOGBOOK:\n\
+    ///      - State \"DONE\"       from \"TODO\"       [2023-01-04 Wed 08:00]\n\
+    ///      - State \"DONE\"       from \"TODO\"       [2023-01-01 Sun 08:00]\n\
+    ///      :END:\n",
+    /// );
+    /// let habit = org.headlines().next().unwrap().habit(&org, &["DONE"]).unwrap();
+    ///
+    /// assert_eq!(habit.completions.len(), 2);
+    /// assert_eq!(habit.streak(&habit.scheduled), 2);
+    /// ```
+    pub fn habit(self, org: &Org, done_keywords: &[&str]) -> Option<Habit> {
+        let title = self.title(org);
+        let is_habit = title
+            .properties
+            .iter()
+            .any(|(key, value)| key.eq_ignore_ascii_case("STYLE") && value == "habit");
+        if !is_habit {
+            return None;
+        }
+
+        let scheduled = title.scheduled()?;
+        let repeater = scheduled.repeater()?;
+
+        let mut completions: Vec<_> = self
+            .section_node()
+            .and_then(|section| org.span(section))
+            .map(|span| {
+                org.text[span]
+                    .lines()
+                    .filter_map(|line| completion(line, done_keywords))
+                    .collect()
+            })
+            .unwrap_or_default();
+        completions.sort_by_key(Datetime::minutes);
+
+        Some(Habit {
+            scheduled: scheduled.start()?.clone().into_owned(),
+            repeater,
+            completions,
+        })
+    }
+}
+
+/// Parses a state change note, and returns its date if the new state is
+/// one of `done_keywords`.
+fn completion(line: &str, done_keywords: &[&str]) -> Option<Datetime<'static>> {
+    let line = line.trim_start().strip_prefix("- State \"")?;
+    let (state, rest) = line.split_once('"')?;
+    if !done_keywords.contains(&state) {
+        return None;
+    }
+
+    let (_, timestamp) = Timestamp::parse_inactive(&rest[rest.find('[')?..])?;
+    Some(timestamp.start()?.clone().into_owned())
+}
+
+impl Habit {
+    /// Returns the number of consecutive completions which were done in
+    /// time, counted backwards from the last one. Returns `0` if the habit
+    /// is already overdue at `today`.
+    pub fn streak(&self, today: &Datetime) -> usize {
+        let days: Vec<_> = self.completions.iter().filter_map(Datetime::days).collect();
+        let (last, today) = match (days.last(), today.days()) {
+            (Some(&last), Some(today)) => (last, today),
+            _ => return 0,
+        };
+        let max = self.max_interval();
+        if today - last > max {
+            return 0;
+        }
+
+        1 + days
+            .windows(2)
+            .rev()
+            .take_while(|pair| pair[1] - pair[0] <= max)
+            .count()
+    }
+
+    /// Returns the habit data of each day between `start` and `end`
+    /// inclusively. Days before the first completion are considered early.
+    pub fn days(&self, start: &Datetime, end: &Datetime) -> Vec<HabitDay> {
+        let (start, end) = match (start.days(), end.days()) {
+            (Some(start), Some(end)) => (start, end),
+            _ => return Vec::new(),
+        };
+        let completions: Vec<_> = self.completions.iter().filter_map(Datetime::days).collect();
+        let (min, max) = (self.min_interval(), self.max_interval());
+
+        (start..=end)
+            .filter_map(|date| {
+                let last = completions.iter().rev().find(|&&done| done < date);
+                let state = match last {
+                    Some(last) if date - last < min => HabitState::Early,
+                    Some(last) if date - last <= max => HabitState::Due,
+                    Some(_) => HabitState::Overdue,
+                    None => HabitState::Early,
+                };
+                Some(HabitDay {
+                    date: Datetime::from_days(date)?,
+                    done: completions.contains(&date),
+                    state,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the ratio of days between `start` and `end` inclusively
+    /// which aren't overdue, from `0.0` to `1.0`.
+    pub fn consistency(&self, start: &Datetime, end: &Datetime) -> f64 {
+        let days = self.days(start, end);
+        if days.is_empty() {
+            return 1.0;
+        }
+        let kept = days
+            .iter()
+            .filter(|day| day.state != HabitState::Overdue)
+            .count();
+        kept as f64 / days.len() as f64
+    }
+
+    /// Returns the repeater interval in days.
+    fn min_interval(&self) -> i64 {
+        interval_days(self.repeater.value, self.repeater.unit)
+    }
+
+    /// Returns the habit deadline interval in days, which defaults to the
+    /// repeater interval.
+    fn max_interval(&self) -> i64 {
+        match self.repeater.deadline {
+            Some((value, unit)) => interval_days(value, unit),
+            None => self.min_interval(),
+        }
+    }
+}
+
+/// Returns the interval in days, saturated on overflow.
+fn interval_days(value: usize, unit: TimeUnit) -> i64 {
+    let value = i64::try_from(value).unwrap_or(i64::MAX);
+    match unit {
+        TimeUnit::Hour => value / 24,
+        TimeUnit::Day => value,
+        TimeUnit::Week => value.saturating_mul(7),
+        TimeUnit::Month => value.saturating_mul(30),
+        TimeUnit::Year => value.saturating_mul(365),
+    }
+}
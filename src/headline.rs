@@ -1,10 +1,13 @@
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::{Range, RangeInclusive};
+use core::usize;
 use indextree::NodeId;
-use std::borrow::Cow;
-use std::ops::RangeInclusive;
-use std::usize;
 
 use crate::{
-    config::ParseConfig,
+    config::{ParseConfig, DEFAULT_CONFIG},
     elements::{Element, Title},
     parsers::{parse_container, Container, OwnedArena},
     validate::{ValidationError, ValidationResult},
@@ -358,6 +361,13 @@ pub struct Headline {
     sec_n: Option<NodeId>,
 }
 
+/// Direction of cycling through values like priorities
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+}
+
 impl Headline {
     /// Creates a new detaced Headline.
     pub fn new<'a>(ttl: Title<'a>, org: &mut Org<'a>) -> Headline {
@@ -438,6 +448,16 @@ impl Headline {
         }
     }
 
+    /// Returns the non-empty `ID` property of this headline.
+    pub(crate) fn id<'b>(self, org: &'b Org) -> Option<&'b str> {
+        self.title(org)
+            .properties
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("ID"))
+            .map(|(_, value)| value.trim())
+            .filter(|value| !value.is_empty())
+    }
+
     /// Returns a mutual reference to the title element of this headline.
     ///
     /// Don't change the level and content of the `&mut Titile` directly.
@@ -469,6 +489,106 @@ impl Headline {
         }
     }
 
+    /// Changes the priority of this headline in the source text, or removes
+    /// it if `priority` is `None`.
+    ///
+    /// The document is reparsed after changing, so previously returned
+    /// headlines and nodes are invalid.
+    pub fn set_priority(self, priority: Option<char>, org: &mut Org) {
+        self.set_priority_custom(priority, &DEFAULT_CONFIG, org);
+    }
+
+    fn set_priority_custom(self, priority: Option<char>, config: &ParseConfig, org: &mut Org) {
+        if self.title(org).priority == priority {
+            return;
+        }
+        match self.priority_edit(priority, org) {
+            Some((range, text)) => org.edit_custom(range, &text, config),
+            // headlines not parsed from the source text
+            None => self.title_mut(org).priority = priority,
+        }
+    }
+
+    /// Returns the edit of the priority cookie like `[#A]` in the source
+    /// text, which follows the stars and the todo keyword.
+    fn priority_edit(self, priority: Option<char>, org: &Org) -> Option<(Range<usize>, String)> {
+        let title = self.title(org);
+        let start = org.span(self.ttl_n)?.start + title.level;
+        let line = &org.text[start..];
+        let line = &line[..line.find('\n').unwrap_or(line.len())];
+
+        // end of the stars or the todo keyword
+        let prefix_end = match &title.keyword {
+            Some(keyword) => {
+                let rest = line.trim_start_matches([' ', '\t']);
+                rest.strip_prefix(&**keyword)?;
+                start + line.len() - rest.len() + keyword.len()
+            }
+            None => start,
+        };
+        let rest = &org.text[prefix_end..start + line.len()];
+        let cookie_start = prefix_end + rest.len() - rest.trim_start_matches([' ', '\t']).len();
+
+        match (title.priority, priority) {
+            (Some(_), Some(priority)) => {
+                Some((cookie_start..cookie_start + 4, format!("[#{}]", priority)))
+            }
+            (Some(_), None) => Some((prefix_end..cookie_start + 4, String::new())),
+            (None, Some(priority)) => Some((prefix_end..prefix_end, format!(" [#{}]", priority))),
+            (None, None) => None,
+        }
+    }
+
+    /// Raises or lowers the priority of this headline within
+    /// `config.priorities`, like `org-priority-up` and `org-priority-down`,
+    /// and returns the new priority.
+    ///
+    /// Headlines without priority get the default one, and the priority is
+    /// removed after going beyond the highest or the lowest one. Like
+    /// [`Headline::set_priority`](#method.set_priority), the source text is
+    /// changed and reparsed with `config`.
+    ///
+    /// ```rust
+    /// # use orgize::{Direction, Org, ParseConfig};
+    /// #
+    /// let mut org = Org::parse("* TODO [#B] task\n");
+    /// let config = ParseConfig::default();
+    /// let task = org.headlines().next().unwrap();
+    ///
+    /// assert_eq!(task.cycle_priority(Direction::Up, &config, &mut org), Some('A'));
+    /// let task = org.headlines().next().unwrap();
+    /// assert_eq!(task.cycle_priority(Direction::Up, &config, &mut org), None);
+    /// let task = org.headlines().next().unwrap();
+    /// assert_eq!(task.cycle_priority(Direction::Down, &config, &mut org), Some('B'));
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(String::from_utf8(writer).unwrap(), "* TODO [#B] task\n");
+    /// ```
+    pub fn cycle_priority(
+        self,
+        direction: Direction,
+        config: &ParseConfig,
+        org: &mut Org,
+    ) -> Option<char> {
+        let (highest, lowest, default) = config.priorities;
+        let priority = match self.title(org).priority {
+            Some(priority) => {
+                let priority = u32::from(priority);
+                let priority = match direction {
+                    Direction::Up => priority.checked_sub(1),
+                    Direction::Down => priority.checked_add(1),
+                };
+                priority
+                    .and_then(char::from_u32)
+                    .filter(|priority| (highest..=lowest).contains(priority))
+            }
+            None => Some(default),
+        };
+        self.set_priority_custom(priority, config, org);
+        priority
+    }
+
     /// Changes the level of this headline.
     ///
     /// Returns an error if this headline is attached and the given new level
@@ -0,0 +1,234 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use indextree::NodeId;
+
+use crate::compat::HashMap;
+use crate::elements::{Element, Link};
+use crate::{Headline, Org};
+
+/// Headline found by its `ID` property
+#[derive(Clone, Copy)]
+pub struct IdTarget<'a, 'b> {
+    /// Name of the file containing the headline
+    pub file: &'b str,
+    pub org: &'b Org<'a>,
+    pub headline: Headline,
+}
+
+/// Index of headlines' `ID` properties across documents, for resolving
+/// `id:` links between files
+///
+/// ```rust
+/// # use orgize::{IdIndex, Org};
+/// #
+/// let inbox = Org::parse("* idea\nSee [[id:b2c4][rust notes]].\n");
+/// let notes = Org::parse("* rust\n:PROPERTIES:\n:ID: b2c4\n:END:\n");
+///
+/// let mut index = IdIndex::default();
+/// index.add("inbox.org", &inbox).add("notes.org", &notes);
+///
+/// let target = index.get("b2c4").unwrap();
+/// assert_eq!(target.file, "notes.org");
+/// assert_eq!(target.headline.title(target.org).raw, "rust");
+/// assert!(index.get("missing").is_none());
+/// ```
+#[derive(Default)]
+pub struct IdIndex<'a, 'b> {
+    files: Vec<(&'b str, &'b Org<'a>)>,
+    ids: HashMap<String, (usize, Headline)>,
+}
+
+impl<'a, 'b> IdIndex<'a, 'b> {
+    /// Adds a document with its file name to this index. If an ID is
+    /// already indexed, the headline added first is kept.
+    pub fn add(&mut self, file: &'b str, org: &'b Org<'a>) -> &mut Self {
+        let index = self.files.len();
+        self.files.push((file, org));
+
+        for headline in org.headlines() {
+            if let Some(id) = headline.id(org) {
+                self.ids.entry(id.to_string()).or_insert((index, headline));
+            }
+        }
+
+        self
+    }
+
+    /// Returns the headline with this ID.
+    pub fn get(&self, id: &str) -> Option<IdTarget<'a, 'b>> {
+        let &(index, headline) = self.ids.get(id)?;
+        let (file, org) = self.files[index];
+        Some(IdTarget {
+            file,
+            org,
+            headline,
+        })
+    }
+
+    /// Resolves an `id:` link, e.g. `[[id:b2c4]]` or `[[id:b2c4::search]]`,
+    /// to its headline. Returns `None` for other links.
+    pub fn resolve(&self, link: &Link) -> Option<IdTarget<'a, 'b>> {
+        let id = link.path.strip_prefix("id:")?;
+        let id = id.split_once("::").map_or(id, |(id, _)| id);
+        self.get(id.trim())
+    }
+
+    /// Returns all indexed IDs in arbitrary order.
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.ids.keys().map(|id| id.as_str())
+    }
+
+    /// Returns the number of indexed IDs.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Returns `true` if no IDs are indexed.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+}
+
+impl Org<'_> {
+    /// Returns the `ID` property of headline `node`, generating a random
+    /// UUID and inserting it into the properties drawer if it doesn't have
+    /// one. Returns `None` if `node` isn't a headline.
+    ///
+    /// The ID is written into the source text, which is reparsed, so
+    /// previously returned nodes are invalid after inserting an ID.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let mut org = Org::parse("* task\n* note\n:PROPERTIES:\n:ID: n1\n:END:\n");
+    /// let nodes = |org: &Org| -> Vec<_> { org.headlines().map(|h| h.headline_node()).collect() };
+    ///
+    /// let id = org.ensure_id(nodes(&org)[0]).unwrap();
+    /// assert_eq!(id.len(), 36);
+    /// assert_eq!(org.ensure_id(nodes(&org)[0]).unwrap(), id);
+    /// assert_eq!(org.ensure_id(nodes(&org)[1]).unwrap(), "n1");
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert!(String::from_utf8(writer)
+    ///     .unwrap()
+    ///     .starts_with(&format!("* task\n:PROPERTIES:\n:ID: {}\n:END:\n", id)));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn ensure_id(&mut self, node: NodeId) -> Option<String> {
+        self.ensure_id_with(node, uuid)
+    }
+
+    /// Like [`Org::ensure_id`](#method.ensure_id), but generates IDs with
+    /// `generate`.
+    pub fn ensure_id_with<F>(&mut self, node: NodeId, generate: F) -> Option<String>
+    where
+        F: FnOnce() -> String,
+    {
+        let headline = match self[node] {
+            Element::Headline { level } => Headline::from_node(node, level, self),
+            _ => return None,
+        };
+        if let Some(id) = headline.id(self) {
+            return Some(id.to_string());
+        }
+
+        let id = generate();
+        match self.id_edit(headline, &id) {
+            Some((range, text)) => self.edit(range, &text),
+            None => {
+                // headlines not parsed from the source text
+                let properties = &mut headline.title_mut(self).properties;
+                let key = properties
+                    .keys()
+                    .find(|key| key.eq_ignore_ascii_case("ID"))
+                    .cloned()
+                    .unwrap_or_else(|| "ID".into());
+                properties.insert(key, id.clone().into());
+            }
+        }
+        Some(id)
+    }
+
+    /// Returns the edit inserting `id` into the properties drawer of
+    /// `headline`. An empty `ID` property is replaced, keeping the case of
+    /// its key, and a drawer is added if there's none.
+    fn id_edit(&self, headline: Headline, id: &str) -> Option<(Range<usize>, String)> {
+        let span = self.span(headline.title_node())?;
+
+        let mut drawer = false;
+        let mut offset = span.start;
+        for line in self.text[span.clone()].split_inclusive('\n') {
+            let start = offset;
+            offset += line.len();
+            let trimmed = line.trim();
+            let indent = line.len() - line.trim_start().len();
+            if trimmed.eq_ignore_ascii_case(":PROPERTIES:") {
+                drawer = true;
+            } else if drawer && trimmed.eq_ignore_ascii_case(":END:") {
+                let indent = &line[..indent];
+                return Some((start..start, format!("{}:ID: {}\n", indent, id)));
+            } else if drawer && trimmed.len() == 4 && trimmed.eq_ignore_ascii_case(":ID:") {
+                let key_end = start + indent + 4;
+                let line_end = start + line.trim_end().len();
+                return Some((key_end..line_end, format!(" {}", id)));
+            }
+        }
+
+        let end = span.end;
+        let newline = if self.text[..end].ends_with('\n') {
+            ""
+        } else {
+            "\n"
+        };
+        let drawer = format!("{}:PROPERTIES:\n:ID: {}\n:END:\n", newline, id);
+        Some((end..end, drawer))
+    }
+}
+
+/// Generates a random version 4 UUID, like `org-id-uuid`.
+#[cfg(feature = "std")]
+fn uuid() -> String {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_nanos());
+    let mut bytes = [0; 16];
+    for (i, chunk) in bytes.chunks_mut(8).enumerate() {
+        // every `RandomState` is seeded differently
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u128(nanos);
+        hasher.write_usize(i);
+        chunk.copy_from_slice(&hasher.finish().to_le_bytes());
+    }
+    bytes[6] = bytes[6] & 0x0f | 0x40;
+    bytes[8] = bytes[8] & 0x3f | 0x80;
+
+    let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn uuid_format() {
+    let a = uuid();
+    let b = uuid();
+    assert_ne!(a, b);
+    assert_eq!(a.len(), 36);
+    assert_eq!(a.as_bytes()[14], b'4');
+    assert!(matches!(a.as_bytes()[19], b'8' | b'9' | b'a' | b'b'));
+    assert_eq!(a.matches('-').count(), 4);
+}
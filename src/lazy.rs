@@ -0,0 +1,136 @@
+use alloc::borrow::Cow;
+use alloc::vec;
+use alloc::vec::Vec;
+use indextree::NodeId;
+
+use crate::{
+    config::{ParseConfig, DEFAULT_CONFIG},
+    parsers::{blank_lines, parse_container, parse_container_content, Container, ElementArena},
+    Org,
+};
+
+impl<'a> Org<'a> {
+    /// Parses string `text` into `Org` struct lazily.
+    ///
+    /// Only headlines and their titles' keywords, priorities, tags, planning
+    /// and properties are parsed up front, while the contents of sections
+    /// and titles are left pending until [`Org::expand`] is called on them.
+    ///
+    /// Pending elements have no children. [`Org::write_org`], [`Org::subtree_eq`]
+    /// and [`Org::edit`] take their contents from the source text, and
+    /// [`Org::replace_text`] expands them first, but every other consumer
+    /// taking `&Org`, like [`Org::iter`], html exports and serialization,
+    /// skips them, so [`Org::expand_all`] must be called before those.
+    ///
+    /// [`Org::expand`]: #method.expand
+    /// [`Org::expand_all`]: #method.expand_all
+    /// [`Org::write_org`]: #method.write_org
+    /// [`Org::subtree_eq`]: #method.subtree_eq
+    /// [`Org::edit`]: #method.edit
+    /// [`Org::replace_text`]: #method.replace_text
+    /// [`Org::iter`]: #method.iter
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let mut org = Org::parse_lazy("* TODO title :tag:\nsection *bold*");
+    ///
+    /// let h1 = org.headlines().next().unwrap();
+    /// assert_eq!(h1.title(&org).keyword.as_deref(), Some("TODO"));
+    ///
+    /// let section = h1.section_node().unwrap();
+    /// assert!(org.is_pending(section));
+    /// assert_eq!(org.arena()[section].first_child(), None);
+    ///
+    /// org.expand(section);
+    /// assert!(!org.is_pending(section));
+    /// assert_eq!(org.text_content(section), "section bold");
+    /// ```
+    pub fn parse_lazy(text: &'a str) -> Org<'a> {
+        Org::parse_lazy_custom(text, &DEFAULT_CONFIG)
+    }
+
+    /// Parses string `text` into `Org` struct lazily with custom `ParseConfig`.
+    ///
+    /// See [`Org::parse_lazy`](#method.parse_lazy) for more details.
+    pub fn parse_lazy_custom(text: &'a str, config: &ParseConfig) -> Org<'a> {
+        let (content, pre_blank) = blank_lines(text);
+        let mut org = Org::with_text(Cow::Borrowed(text), pre_blank);
+        let node = org.root;
+        org.set_span(node, text);
+
+        let containers = &mut vec![Container::Document { content, node }];
+
+        while let Some(container) = containers.pop() {
+            match container {
+                Container::Block { node, .. }
+                | Container::Inline { node, .. }
+                | Container::Verse { node, .. } => {
+                    org.pending.insert(node, container);
+                }
+                _ => parse_container_content(&mut org, container, containers, config),
+            }
+        }
+
+        if !org.pending.is_empty() {
+            org.pending_config = Some(config.clone());
+        }
+
+        org.expand_links();
+        org.handle_keywords(config);
+        org.debug_validate();
+
+        org
+    }
+
+    /// Returns `true` if the contents of `node` haven't been parsed yet.
+    pub fn is_pending(&self, node: NodeId) -> bool {
+        self.pending.contains_key(&node)
+    }
+
+    /// Parses the pending contents of `node` and its descendants.
+    ///
+    /// Does nothing if `node` was parsed eagerly.
+    pub fn expand(&mut self, node: NodeId) {
+        if self.pending.is_empty() {
+            return;
+        }
+
+        let nodes: Vec<_> = node
+            .descendants(&self.arena)
+            .filter(|node| self.pending.contains_key(node))
+            .collect();
+
+        self.expand_nodes(nodes);
+    }
+
+    /// Parses all pending contents in this `Org` struct.
+    pub fn expand_all(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+
+        let nodes: Vec<_> = self.pending.keys().cloned().collect();
+
+        self.expand_nodes(nodes);
+    }
+
+    fn expand_nodes(&mut self, nodes: Vec<NodeId>) {
+        let config = self.pending_config.take().unwrap_or_default();
+
+        for node in nodes {
+            if let Some(container) = self.pending.remove(&node) {
+                parse_container(self, container, &config);
+            }
+        }
+
+        self.expand_links();
+        self.handle_keywords(&config);
+
+        if !self.pending.is_empty() {
+            self.pending_config = Some(config);
+        }
+
+        self.debug_validate();
+    }
+}
@@ -206,35 +206,157 @@
 //!
 //! # Features
 //!
-//! By now, orgize provides three features:
+//! By now, orgize provides the following features:
 //!
 //! + `ser`: adds the ability to serialize `Org` and other elements using `serde`, enabled by default.
 //!
 //! + `chrono`: adds the ability to convert `Datetime` into `chrono` structs, disabled by default.
 //!
+//! + `time`: adds the ability to convert `Datetime` into `time` structs, disabled by default.
+//!
 //! + `syntect`: provides [`SyntectHtmlHandler`] for highlighting code block, disabled by default.
 //!
+//! + `pulldown-cmark`: provides [`CmarkEvents`] for converting `Org` into `pulldown-cmark`
+//!   events, disabled by default.
+//!
+//! + `std`: enabled by default. Without it, orgize builds with `#![no_std]` and
+//!   the `alloc` feature, and exporting and reading from I/O are unavailable.
+//!
+//! + `alloc`: builds orgize for `no_std` targets with an allocator, using
+//!   `hashbrown` for hash maps, disabled by default.
+//!
+//! + `regex`: provides [`Org::replace_text`] for replacing text by regular expressions,
+//!   disabled by default.
+//!
+//! + `rayon`: provides [`Org::parse_parallel`] for parsing top-level sections and
+//!   headlines in parallel, disabled by default.
+//!
+//! + `wasm`: provides [`wasm`] bindings for using orgize from JavaScript, disabled by default.
+//!
+//! + `pyo3`: provides [`python`] bindings for using orgize from Python, disabled by default.
+//!
+//! + `cli`: builds the `orgize` binary, which converts org files into html, json or
+//!   markdown, disabled by default.
+//!
+//! [`CmarkEvents`]: export/struct.CmarkEvents.html
 //! [`SyntectHtmlHandler`]: export/struct.SyntectHtmlHandler.html
+//! [`Org::replace_text`]: struct.Org.html#method.replace_text
+//! [`Org::parse_parallel`]: struct.Org.html#method.parse_parallel
+//! [`python`]: python/index.html
+//! [`wasm`]: wasm/index.html
 //!
 //! # License
 //!
 //! MIT
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(any(feature = "std", feature = "alloc")))]
+compile_error!("either feature `std` or `alloc` must be enabled");
+
+extern crate alloc;
+
+mod agenda;
+mod archive;
+mod attach;
+mod babel;
+mod category;
+mod checkbox;
+mod clock;
+mod clocktable;
+mod coderef;
+mod columns;
+mod compare;
+mod compat;
 mod config;
+mod coverage;
+mod crypt;
+mod cursor;
+mod debug;
+mod diff;
+mod diagnostics;
+mod duration;
+mod edit;
+mod effort;
 pub mod elements;
+mod execute;
+#[cfg(feature = "std")]
 pub mod export;
+mod extensions;
+mod filter;
+mod flashcard;
+mod footnote;
+mod format;
+mod habit;
 mod headline;
+mod id_index;
+mod lazy;
+mod lob;
+mod lsp;
+mod memory;
+mod merge;
+mod narrow;
 mod org;
+#[cfg(feature = "rayon")]
+mod parallel;
 mod parsers;
+mod position;
+mod property;
+mod refile;
+#[cfg(feature = "regex")]
+mod replace;
+mod search;
+mod tags;
+mod tangle;
+mod timestamp;
+mod todo;
+#[cfg(feature = "pyo3")]
+pub mod python;
 mod validate;
+mod visit;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+mod workspace;
 
 // Re-export of the indextree crate.
 pub use indextree;
+#[cfg(feature = "pulldown-cmark")]
+pub use pulldown_cmark;
+#[cfg(feature = "regex")]
+pub use regex;
 #[cfg(feature = "syntect")]
 pub use syntect;
 
-pub use config::ParseConfig;
-pub use elements::Element;
-pub use headline::{Document, Headline};
+pub use agenda::{Agenda, AgendaEntry, AgendaKind};
+pub use archive::ArchiveLocation;
+pub use babel::HeaderArgs;
+pub use clocktable::{ClockReport, ClockRow};
+pub use coderef::Coderef;
+pub use columns::{Column, ColumnRow, ColumnView, SummaryType};
+pub use config::{KeywordHandlers, ObjectKind, ParseConfig, ParseMode, Syntax};
+pub use coverage::CoverageError;
+pub use cursor::Cursor;
+pub use diff::{Change, ChangeKind};
+pub use diagnostics::Diagnostic;
+pub use duration::Duration;
+pub use effort::EffortSummary;
+pub use elements::{Element, ElementKind};
+pub use execute::{Executor, ResultCollection};
+pub use extensions::Extensions;
+pub use filter::{FilteredIter, IterFilter};
+pub use flashcard::{Flashcard, FlashcardKind, Scheduling};
+pub use footnote::{Footnote, FootnoteDefinition};
+pub use habit::{Habit, HabitDay, HabitState};
+pub use headline::{Direction, Document, Headline};
+pub use id_index::{IdIndex, IdTarget};
+pub use lob::CallTarget;
+pub use lsp::{DocumentSymbol, FoldingKind, FoldingRange, Position, SymbolKind};
+pub use memory::MemoryUsage;
 pub use org::{Event, Org};
+pub use refile::{refile_targets, RefileConfig, RefileTarget};
+pub use search::{SearchHit, SearchIndex};
+pub use tangle::TangledFile;
+pub use todo::{LogDone, TodoLog};
 pub use validate::ValidationError;
+pub use visit::{Visitor, VisitorMut};
+pub use workspace::{Workspace, WorkspaceLink};
@@ -0,0 +1,148 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use indextree::NodeId;
+
+use crate::elements::{Element, InlineCall};
+use crate::{HeaderArgs, Org};
+
+/// Source block called by `#+CALL:` or `call_name()`, resolved from a
+/// document or a library of babel
+pub struct CallTarget<'o, 'a> {
+    /// Document containing the called source block
+    pub org: &'o Org<'a>,
+    /// Called source block
+    pub block: NodeId,
+    /// Header arguments of the block merged with the call's inside header
+    /// arguments, arguments as `:var`, and end header arguments, in this
+    /// order
+    pub args: HeaderArgs,
+}
+
+impl<'a> Org<'a> {
+    /// Resolves the babel call or inline babel call `node` to the source
+    /// block of the same `#+NAME`, which is searched in this document first
+    /// and then in `library` in order.
+    ///
+    /// Arguments are passed as `:var` header arguments. Positional
+    /// arguments take names of the block's own `:var` arguments in order.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let library = Org::parse(
+    ///     "#+NAME: square\n#+BEGIN_SRC python :var x=1 :results value\nreturn x * x\n#+END_SRC\n",
+    /// );
+    /// let org = Org::parse("#+CALL: square(4) :results raw\n");
+    /// let target = org.resolve_call(org.babel_calls()[0], &[&library]).unwrap();
+    ///
+    /// assert_eq!(target.org.element_name(target.block), Some("square"));
+    /// assert_eq!(target.args.get("var"), Some("x=4"));
+    /// assert_eq!(target.args.get("results"), Some("raw"));
+    /// ```
+    pub fn resolve_call<'o>(
+        &'o self,
+        node: NodeId,
+        library: &[&'o Org<'a>],
+    ) -> Option<CallTarget<'o, 'a>> {
+        let babel_call;
+        let call = match &self[node] {
+            Element::BabelCall(call) => {
+                babel_call = call.call()?;
+                &babel_call
+            }
+            Element::InlineCall(call) => call,
+            _ => return None,
+        };
+
+        let (org, block) = Some(self)
+            .into_iter()
+            .chain(library.iter().copied())
+            .find_map(|org| {
+                org.src_blocks()
+                    .into_iter()
+                    .find(|&block| org.element_name(block) == Some(&*call.name))
+                    .map(|block| (org, block))
+            })?;
+
+        let args = merge_call_args(org.header_args(block), call);
+        Some(CallTarget { org, block, args })
+    }
+
+    /// Returns all babel calls and inline babel calls in document order.
+    pub fn babel_calls(&self) -> Vec<NodeId> {
+        self.root
+            .descendants(&self.arena)
+            .filter(|&node| matches!(self[node], Element::BabelCall(_) | Element::InlineCall(_)))
+            .collect()
+    }
+}
+
+fn merge_call_args(mut args: HeaderArgs, call: &InlineCall) -> HeaderArgs {
+    let names: Vec<String> = args
+        .get_all("var")
+        .map(|var| var.split('=').next().unwrap_or_default().trim().into())
+        .collect();
+
+    if let Some(header) = &call.inside_header {
+        args.extend(header);
+    }
+    for (i, argument) in split_arguments(&call.arguments).into_iter().enumerate() {
+        if argument.contains('=') {
+            args.extend(&format!(":var {}", argument));
+        } else if let Some(name) = names.get(i) {
+            args.extend(&format!(":var {}={}", name, argument));
+        }
+    }
+    if let Some(header) = &call.end_header {
+        args.extend(header);
+    }
+
+    args
+}
+
+/// Splits arguments by commas outside of quotes and parentheses.
+fn split_arguments(input: &str) -> Vec<&str> {
+    let mut arguments = Vec::new();
+    let (mut depth, mut quoted, mut start) = (0, false, 0);
+
+    for (i, c) in input.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted => depth -= 1,
+            ',' if !quoted && depth == 0 => {
+                arguments.push(input[start..i].trim());
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    arguments.push(input[start..].trim());
+    arguments.retain(|argument| !argument.is_empty());
+
+    arguments
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn parse() {
+    assert_eq!(
+        split_arguments(r#"a=1, b="x, y", c=(f 1 2)"#),
+        vec!["a=1", r#"b="x, y""#, "c=(f 1 2)"]
+    );
+    assert_eq!(split_arguments(" "), Vec::<&str>::new());
+
+    let call = InlineCall {
+        name: "f".into(),
+        inside_header: Some(":results output".into()),
+        arguments: "2, 3, z=4".into(),
+        end_header: Some(":results html".into()),
+    };
+    let args = merge_call_args(HeaderArgs::parse(":var x=0 :var y=0 :results value"), &call);
+    assert_eq!(
+        args.get_all("var").collect::<Vec<_>>(),
+        vec!["x=0", "y=0", "x=2", "y=3", "z=4"]
+    );
+    assert_eq!(args.get("results"), Some("html"));
+}
@@ -0,0 +1,265 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::Range;
+use indextree::NodeId;
+
+use crate::{elements::Element, Headline, Org};
+
+/// Position in the source text, as defined by the Language Server Protocol
+///
+/// Both line and character are zero-based, and character is counted in
+/// UTF-16 code units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    pub line: usize,
+    pub character: usize,
+}
+
+/// Kind of document symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymbolKind {
+    /// Headline without todo keyword
+    Headline,
+    /// Headline with todo keyword
+    Task,
+}
+
+impl SymbolKind {
+    /// Returns the value of LSP's `SymbolKind` this kind is mapped to.
+    pub fn lsp_value(self) -> u32 {
+        match self {
+            // SymbolKind.String
+            SymbolKind::Headline => 15,
+            // SymbolKind.Event
+            SymbolKind::Task => 24,
+        }
+    }
+}
+
+/// Document symbol, shaped like LSP's `DocumentSymbol`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentSymbol {
+    /// Headline's title, without the stars and the tags
+    pub name: String,
+    /// Headline's todo keyword and tags, e.g. `TODO :work:`
+    pub detail: Option<String>,
+    pub kind: SymbolKind,
+    /// Range of the whole headline, without trailing blank lines
+    pub range: Range<Position>,
+    /// Range of the headline's title
+    pub selection_range: Range<Position>,
+    pub children: Vec<DocumentSymbol>,
+    /// Headline node
+    pub node: NodeId,
+}
+
+/// Kind of folding range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FoldingKind {
+    Headline,
+    Block,
+    Drawer,
+}
+
+/// Folding range, shaped like LSP's `FoldingRange`
+///
+/// Lines are zero-based and inclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoldingRange {
+    pub start_line: usize,
+    pub end_line: usize,
+    pub kind: FoldingKind,
+    pub node: NodeId,
+}
+
+impl Org<'_> {
+    /// Converts a byte offset in the source text into a LSP [`Position`].
+    ///
+    /// Returns `None` if the offset is out of bounds or not on a character boundary.
+    ///
+    /// [`Position`]: struct.Position.html
+    ///
+    /// ```rust
+    /// # use orgize::{Org, Position};
+    /// #
+    /// let org = Org::parse("* 😀 title");
+    ///
+    /// assert_eq!(org.lsp_position(6), Some(Position { line: 0, character: 4 }));
+    /// ```
+    pub fn lsp_position(&self, offset: usize) -> Option<Position> {
+        let (line, _) = self.position(offset)?;
+        let start = self.line_starts()[line];
+
+        Some(Position {
+            line,
+            character: self.text[start..offset].encode_utf16().count(),
+        })
+    }
+
+    /// Returns the headline hierarchy as LSP document symbols.
+    ///
+    /// Headlines which weren't parsed from the source text are skipped.
+    ///
+    /// ```rust
+    /// # use orgize::{Org, Position, SymbolKind};
+    /// #
+    /// let org = Org::parse("* TODO title :work:\n** sub title\nsection\n\n* title 2\n");
+    /// let symbols = org.document_symbols();
+    ///
+    /// assert_eq!(symbols.len(), 2);
+    /// assert_eq!(symbols[0].name, "title");
+    /// assert_eq!(symbols[0].detail.as_deref(), Some("TODO :work:"));
+    /// assert_eq!(symbols[0].kind, SymbolKind::Task);
+    /// assert_eq!(
+    ///     symbols[0].range,
+    ///     Position { line: 0, character: 0 }..Position { line: 2, character: 7 }
+    /// );
+    /// assert_eq!(symbols[0].children[0].name, "sub title");
+    /// ```
+    pub fn document_symbols(&self) -> Vec<DocumentSymbol> {
+        self.document()
+            .children(self)
+            .filter_map(|headline| self.document_symbol(headline))
+            .collect()
+    }
+
+    fn document_symbol(&self, headline: Headline) -> Option<DocumentSymbol> {
+        let title = headline.title(self);
+        let span = self.trimmed_span(headline.headline_node())?;
+        let selection = self
+            .contents_span(headline.title_node())
+            .or_else(|| self.trimmed_span(headline.title_node()))?;
+
+        let mut detail: Vec<String> = title.keyword.iter().map(|k| k.to_string()).collect();
+        if !title.tags.is_empty() {
+            let mut tags = String::from(":");
+            for tag in &title.tags {
+                tags.push_str(tag);
+                tags.push(':');
+            }
+            detail.push(tags);
+        }
+
+        Some(DocumentSymbol {
+            name: title.raw.to_string(),
+            detail: if detail.is_empty() {
+                None
+            } else {
+                Some(detail.join(" "))
+            },
+            kind: if title.keyword.is_some() {
+                SymbolKind::Task
+            } else {
+                SymbolKind::Headline
+            },
+            range: self.lsp_position(span.start)?..self.lsp_position(span.end)?,
+            selection_range: self.lsp_position(selection.start)?
+                ..self.lsp_position(selection.end)?,
+            children: headline
+                .children(self)
+                .filter_map(|child| self.document_symbol(child))
+                .collect(),
+            node: headline.headline_node(),
+        })
+    }
+
+    /// Returns the folding ranges of headlines, blocks and drawers, in
+    /// document order. Elements spanning a single line are skipped.
+    ///
+    /// ```rust
+    /// # use orgize::{FoldingKind, Org};
+    /// #
+    /// let org = Org::parse("* title\n:LOGBOOK:\nlog\n:END:\n#+BEGIN_SRC\ncode\n#+END_SRC\n\n");
+    /// let ranges: Vec<_> = org
+    ///     .folding_ranges()
+    ///     .into_iter()
+    ///     .map(|range| (range.start_line, range.end_line, range.kind))
+    ///     .collect();
+    ///
+    /// assert_eq!(
+    ///     ranges,
+    ///     vec![
+    ///         (0, 6, FoldingKind::Headline),
+    ///         (1, 3, FoldingKind::Drawer),
+    ///         (4, 6, FoldingKind::Block),
+    ///     ]
+    /// );
+    /// ```
+    pub fn folding_ranges(&self) -> Vec<FoldingRange> {
+        let mut ranges = Vec::new();
+
+        for node in self.root.descendants(&self.arena) {
+            let kind = match &self[node] {
+                Element::Headline { .. } => FoldingKind::Headline,
+                Element::SpecialBlock(_)
+                | Element::QuoteBlock(_)
+                | Element::CenterBlock(_)
+                | Element::VerseBlock(_)
+                | Element::CommentBlock(_)
+                | Element::ExampleBlock(_)
+                | Element::ExportBlock(_)
+                | Element::SourceBlock(_)
+                | Element::DynBlock(_) => FoldingKind::Block,
+                Element::Drawer(_) => FoldingKind::Drawer,
+                Element::Title(_) => {
+                    // properties drawer is parsed as a part of title
+                    if let Some(range) = self.properties_lines(node) {
+                        ranges.push(FoldingRange {
+                            start_line: range.start,
+                            end_line: range.end,
+                            kind: FoldingKind::Drawer,
+                            node,
+                        });
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+
+            let span = match self.trimmed_span(node) {
+                Some(span) => span,
+                None => continue,
+            };
+
+            if let (Some((start_line, _)), Some((end_line, _))) =
+                (self.position(span.start), self.position(span.end))
+            {
+                if end_line > start_line {
+                    ranges.push(FoldingRange {
+                        start_line,
+                        end_line,
+                        kind,
+                        node,
+                    });
+                }
+            }
+        }
+
+        ranges
+    }
+
+    /// Returns the first and last lines of the properties drawer in `title`.
+    fn properties_lines(&self, title: NodeId) -> Option<Range<usize>> {
+        let span = self.span(title)?;
+        let (first_line, _) = self.position(span.start)?;
+
+        let mut start = None;
+        for (i, line) in self.text[span].lines().enumerate() {
+            let line = line.trim();
+            if line.eq_ignore_ascii_case(":PROPERTIES:") {
+                start = Some(first_line + i);
+            } else if line.eq_ignore_ascii_case(":END:") {
+                return Some(start?..first_line + i);
+            }
+        }
+
+        None
+    }
+
+    /// Returns the span of `node` without trailing whitespaces.
+    fn trimmed_span(&self, node: NodeId) -> Option<Range<usize>> {
+        let span = self.span(node)?;
+        let len = self.text[span.clone()].trim_end().len();
+        Some(span.start..span.start + len)
+    }
+}
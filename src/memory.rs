@@ -0,0 +1,117 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::mem::size_of;
+use core::ops::Range;
+use indextree::{Arena, NodeEdge, NodeId};
+
+use crate::{compat::HashMap, elements::Element, Org};
+
+/// Approximate memory used by an `Org` struct.
+///
+/// Strings owned by elements aren't included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MemoryUsage {
+    /// Number of elements in the tree
+    pub nodes: usize,
+    /// Number of removed elements still occupying the arena
+    pub removed_nodes: usize,
+    /// Bytes used by the arena
+    pub arena_bytes: usize,
+    /// Bytes used by the source text, zero if it's borrowed
+    pub text_bytes: usize,
+    /// Bytes used by the spans of elements
+    pub spans_bytes: usize,
+}
+
+impl MemoryUsage {
+    /// Returns the total bytes used.
+    pub fn total_bytes(&self) -> usize {
+        self.arena_bytes + self.text_bytes + self.spans_bytes
+    }
+}
+
+impl Org<'_> {
+    /// Returns the approximate memory used by this `Org` struct.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let mut org = Org::parse("* title 1\n* title 2");
+    /// org.edit(0..10, "");
+    ///
+    /// let usage = org.memory_usage();
+    /// assert_eq!(usage.text_bytes, 9);
+    /// assert!(usage.removed_nodes > 0);
+    ///
+    /// org.compact();
+    /// assert_eq!(org.memory_usage().removed_nodes, 0);
+    /// ```
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let removed_nodes = self.arena.iter().filter(|node| node.is_removed()).count();
+        let span_size = size_of::<(NodeId, Range<usize>)>();
+
+        MemoryUsage {
+            nodes: self.arena.iter().count() - removed_nodes,
+            removed_nodes,
+            arena_bytes: self.arena.iter().count() * size_of::<indextree::Node<Element>>(),
+            text_bytes: match &self.text {
+                Cow::Owned(text) => text.capacity(),
+                Cow::Borrowed(_) => 0,
+            },
+            spans_bytes: (self.spans.capacity() + self.contents_spans.capacity()) * span_size,
+        }
+    }
+
+    /// Rebuilds the arena without removed elements, and shrinks all buffers
+    /// to fit.
+    ///
+    /// Every `NodeId` obtained before calling this method is invalidated.
+    pub fn compact(&mut self) {
+        let mut old_arena = core::mem::replace(&mut self.arena, Arena::new());
+        let edges: Vec<_> = self.root.traverse(&old_arena).collect();
+        // only nodes in the tree are kept, each of which has two edges
+        let mut arena = Arena::with_capacity(edges.len() / 2);
+        let mut nodes: HashMap<NodeId, NodeId> = HashMap::with_capacity(edges.len() / 2);
+
+        for edge in edges {
+            if let NodeEdge::Start(old) = edge {
+                // the old arena is dropped afterwards, so elements are moved out
+                let element = core::mem::replace(old_arena[old].get_mut(), Element::Section);
+                let node = arena.new_node(element);
+                if let Some(parent) = old_arena[old].parent() {
+                    nodes[&parent].append(node, &mut arena);
+                }
+                nodes.insert(old, node);
+            }
+        }
+
+        let remap = |spans: &mut HashMap<NodeId, Range<usize>>| {
+            let mut remapped: HashMap<_, _> = spans
+                .drain()
+                .filter_map(|(old, span)| Some((*nodes.get(&old)?, span)))
+                .collect();
+            remapped.shrink_to_fit();
+            *spans = remapped;
+        };
+        remap(&mut self.spans);
+        remap(&mut self.contents_spans);
+
+        let mut pending: HashMap<_, _> = self
+            .pending
+            .drain()
+            .filter_map(|(old, container)| {
+                let node = *nodes.get(&old)?;
+                Some((node, container.with_node(node).1))
+            })
+            .collect();
+        pending.shrink_to_fit();
+        self.pending = pending;
+
+        if let Cow::Owned(text) = &mut self.text {
+            text.shrink_to_fit();
+        }
+
+        self.root = nodes[&self.root];
+        self.arena = arena;
+    }
+}
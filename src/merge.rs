@@ -0,0 +1,80 @@
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use crate::compat::HashMap;
+use crate::{Headline, Org};
+
+impl Org<'_> {
+    /// Updates headlines of this document from headlines in `source` with
+    /// the same `ID` properties, and reparses the document. Returns the
+    /// number of updated headlines.
+    ///
+    /// The title, planning, properties and section of each headline are
+    /// replaced, while its level and child headlines are kept. Headlines
+    /// without IDs, or whose IDs aren't found in this document, are ignored.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let mut local = Org::parse(
+    ///     "* TODO task\n:PROPERTIES:\n:ID: t1\n:END:\nold notes\n** local child\n",
+    /// );
+    /// let remote = Org::parse(
+    ///     "** DONE task\n:PROPERTIES:\n:ID: t1\n:END:\nnew notes\n*** remote child\n",
+    /// );
+    ///
+    /// assert_eq!(local.merge_by_id(&remote), 1);
+    ///
+    /// let mut writer = Vec::new();
+    /// local.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "* DONE task\n:PROPERTIES:\n:ID: t1\n:END:\nnew notes\n** local child\n"
+    /// );
+    /// ```
+    pub fn merge_by_id(&mut self, source: &Org) -> usize {
+        let mut targets = HashMap::new();
+        for headline in self.headlines() {
+            if let Some(id) = headline.id(self) {
+                targets.entry(id.to_string()).or_insert(headline);
+            }
+        }
+
+        let mut edits = Vec::new();
+        for headline in source.headlines() {
+            // each target is updated by the first source headline only
+            let target = match headline.id(source).and_then(|id| targets.remove(id)) {
+                Some(target) => target,
+                None => continue,
+            };
+
+            let (start, end) = own_range(self, target);
+            let (source_start, source_end) = own_range(source, headline);
+
+            let mut text = "*".repeat(target.level());
+            text += &source.text[source_start + headline.level()..source_end];
+            if !text.ends_with('\n') && end < self.text.len() {
+                text.push('\n');
+            }
+            if text != self.text[start..end] {
+                edits.push((start..end, text));
+            }
+        }
+
+        let count = edits.len();
+        edits.sort_by_key(|(range, _)| range.start);
+        self.apply_edits(edits);
+
+        count
+    }
+}
+
+/// Returns the range of `headline` without its child headlines.
+fn own_range(org: &Org, headline: Headline) -> (usize, usize) {
+    let title = org.span(headline.title_node()).unwrap_or_default();
+    let end = headline
+        .section_node()
+        .and_then(|section| org.span(section))
+        .map_or(title.end, |section| section.end);
+    (title.start, end)
+}
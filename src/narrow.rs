@@ -0,0 +1,42 @@
+use indextree::NodeId;
+
+use crate::config::{ParseConfig, DEFAULT_CONFIG};
+use crate::Org;
+
+impl Org<'_> {
+    /// Parses the source text of `node` into a new `Org` struct, like
+    /// narrowing to a subtree in Emacs. Returns `None` if `node` doesn't come
+    /// from the source text, e.g. it's created by the mutable API.
+    ///
+    /// The new struct borrows the text of this one, and its offsets are
+    /// relative to the beginning of `node`, i.e. `self.span(node).start`.
+    /// Headlines keep their levels.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse("* a\n** b\ntext\n*** c\n* d\n");
+    /// let b = org.headlines().nth(1).unwrap();
+    ///
+    /// let narrowed = org.narrow(b.headline_node()).unwrap();
+    /// let headlines: Vec<_> = narrowed
+    ///     .headlines()
+    ///     .map(|h| (h.level(), h.title(&narrowed).raw.to_string()))
+    ///     .collect();
+    /// assert_eq!(headlines, vec![(2, "b".to_string()), (3, "c".to_string())]);
+    ///
+    /// let c = narrowed.headlines().nth(1).unwrap();
+    /// assert_eq!(narrowed.span(c.headline_node()), Some(10..16));
+    /// assert_eq!(org.span(b.headline_node()).unwrap().start, 4);
+    /// ```
+    pub fn narrow(&self, node: NodeId) -> Option<Org<'_>> {
+        self.narrow_custom(node, &DEFAULT_CONFIG)
+    }
+
+    /// Like [`Org::narrow`](#method.narrow), but parses with custom
+    /// `ParseConfig`.
+    pub fn narrow_custom(&self, node: NodeId, config: &ParseConfig) -> Option<Org<'_>> {
+        let span = self.span(node)?;
+        Some(Org::parse_custom(&self.text[span], config))
+    }
+}
@@ -1,17 +1,42 @@
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::{Index, IndexMut, Range};
 use indextree::{Arena, NodeEdge, NodeId};
-use std::io::{Error, Write};
-use std::ops::{Index, IndexMut};
 
+#[cfg(feature = "std")]
+use crate::export::{DefaultHtmlHandler, DefaultOrgHandler, HtmlHandler, OrgHandler};
+#[cfg(feature = "std")]
+use crate::headline::Headline;
 use crate::{
-    config::{ParseConfig, DEFAULT_CONFIG},
-    elements::{Element, Keyword},
-    export::{DefaultHtmlHandler, DefaultOrgHandler, HtmlHandler, OrgHandler},
-    parsers::{blank_lines, parse_container, Container},
+    compat::{HashMap, OnceLock},
+    config::{ParseConfig, ParseMode, DEFAULT_CONFIG},
+    diagnostics::Diagnostic,
+    elements::{link::expand_link, Element, Keyword, KeywordBehavior},
+    extensions::Extensions,
+    parsers::{blank_lines, parse_container, Container, ElementArena},
 };
+#[cfg(feature = "std")]
+use std::io::{BufRead, Error, Write};
 
 pub struct Org<'a> {
     pub(crate) arena: Arena<Element<'a>>,
     pub(crate) root: NodeId,
+    /// Source text this `Org` struct was parsed from
+    pub(crate) text: Cow<'a, str>,
+    /// Byte ranges of parsed elements in the source text
+    pub(crate) spans: HashMap<NodeId, Range<usize>>,
+    /// Byte ranges of parsed container elements' contents in the source text
+    pub(crate) contents_spans: HashMap<NodeId, Range<usize>>,
+    /// Offsets of each line's beginning, built on first use
+    pub(crate) line_starts: OnceLock<Vec<usize>>,
+    /// Containers whose contents haven't been parsed yet
+    pub(crate) pending: HashMap<NodeId, Container<'a>>,
+    /// Config for parsing pending containers
+    pub(crate) pending_config: Option<ParseConfig>,
+    /// Data stored by keyword handlers
+    pub(crate) extensions: Extensions,
 }
 
 #[derive(Debug)]
@@ -23,9 +48,24 @@ pub enum Event<'a, 'b> {
 impl<'a> Org<'a> {
     /// Creates a new empty `Org` struct.
     pub fn new() -> Org<'static> {
+        Org::with_text(Cow::Borrowed(""), 0)
+    }
+
+    /// Creates a new `Org` struct with only a document element.
+    pub(crate) fn with_text(text: Cow<'a, str>, pre_blank: usize) -> Org<'a> {
         let mut arena = Arena::new();
-        let root = arena.new_node(Element::Document { pre_blank: 0 });
-        Org { arena, root }
+        let root = arena.new_node(Element::Document { pre_blank });
+        Org {
+            arena,
+            root,
+            text,
+            spans: HashMap::new(),
+            contents_spans: HashMap::new(),
+            line_starts: OnceLock::new(),
+            pending: HashMap::new(),
+            pending_config: None,
+            extensions: Extensions::default(),
+        }
     }
 
     /// Parses string `text` into `Org` struct.
@@ -35,25 +75,142 @@ impl<'a> Org<'a> {
 
     /// Parses string `text` into `Org` struct with custom `ParseConfig`.
     pub fn parse_custom(text: &'a str, config: &ParseConfig) -> Org<'a> {
-        let mut arena = Arena::new();
-        let (text, pre_blank) = blank_lines(text);
-        let root = arena.new_node(Element::Document { pre_blank });
-        let mut org = Org { arena, root };
+        let (content, pre_blank) = blank_lines(text);
+        let mut org = Org::with_text(Cow::Borrowed(text), pre_blank);
+        let node = org.root;
+        org.set_span(node, text);
 
-        parse_container(
-            &mut org.arena,
-            Container::Document {
-                content: text,
-                node: org.root,
-            },
-            config,
-        );
+        parse_container(&mut org, Container::Document { content, node }, config);
 
+        org.expand_links();
+        org.handle_keywords(config);
         org.debug_validate();
 
         org
     }
 
+    /// Parses string `text` into `Org` struct with `config`.
+    ///
+    /// ```rust
+    /// # use orgize::{ObjectKind, Org, ParseConfig};
+    /// #
+    /// let org = Org::with_config(
+    ///     "* TASK title\n*bold* /italic/ [[link]]",
+    ///     ParseConfig {
+    ///         todo_keywords: (vec!["TASK".into()], vec![]),
+    ///         emphasis_markers: "*".into(),
+    ///         disabled_objects: vec![ObjectKind::Link],
+    ///         ..Default::default()
+    ///     },
+    /// );
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_html(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "<main><h1>title</h1><section><p><b>bold</b> /italic/ [[link]]</p></section></main>"
+    /// );
+    /// ```
+    pub fn with_config(text: &'a str, config: ParseConfig) -> Org<'a> {
+        Org::parse_custom(text, &config)
+    }
+
+    /// Parses string `text` into `Org` struct in the given `ParseMode`.
+    ///
+    /// In [`ParseMode::Strict`], returns the first issue found by
+    /// [`Org::diagnostics`] as an error.
+    ///
+    /// [`ParseMode::Strict`]: enum.ParseMode.html#variant.Strict
+    /// [`Org::diagnostics`]: #method.diagnostics
+    ///
+    /// ```rust
+    /// # use orgize::{Org, ParseMode};
+    /// #
+    /// let text = "#+BEGIN_QUOTE\nquote";
+    ///
+    /// assert!(Org::parse_with_mode(text, ParseMode::Lenient).is_ok());
+    ///
+    /// let err = Org::parse_with_mode(text, ParseMode::Strict).err().unwrap();
+    /// assert_eq!(err.span(), 0..13);
+    /// ```
+    pub fn parse_with_mode(text: &'a str, mode: ParseMode) -> Result<Org<'a>, Diagnostic> {
+        Org::parse_custom_with_mode(text, &DEFAULT_CONFIG, mode)
+    }
+
+    /// Parses string `text` into `Org` struct in the given `ParseMode` with
+    /// custom `ParseConfig`.
+    pub fn parse_custom_with_mode(
+        text: &'a str,
+        config: &ParseConfig,
+        mode: ParseMode,
+    ) -> Result<Org<'a>, Diagnostic> {
+        let org = Org::parse_custom(text, config);
+
+        if mode == ParseMode::Strict {
+            if let Some(diagnostic) = org.diagnostics().into_iter().next() {
+                return Err(diagnostic);
+            }
+        }
+
+        Ok(org)
+    }
+
+    /// Parses bytes `bytes` into `Org` struct, replacing invalid UTF-8
+    /// sequences with `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// Text is only copied if it contains invalid sequences.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::new_lossy(b"* title \xF0\x90\x80");
+    ///
+    /// let h1 = org.headlines().next().unwrap();
+    /// assert_eq!(h1.title(&org).raw, "title \u{FFFD}");
+    /// ```
+    pub fn new_lossy(bytes: &'a [u8]) -> Org<'a> {
+        match String::from_utf8_lossy(bytes) {
+            Cow::Borrowed(text) => Org::parse(text),
+            Cow::Owned(text) => {
+                let mut org = Org::new();
+                org.reparse(text, &DEFAULT_CONFIG);
+                org
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    /// Parses the text read from `reader` into `Org` struct.
+    ///
+    /// The text is accumulated into a buffer owned by the returned `Org`
+    /// struct, so it doesn't borrow from the caller.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::from_reader("* title\nsection".as_bytes()).unwrap();
+    ///
+    /// assert_eq!(org.headlines().count(), 1);
+    /// ```
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Org<'static>, Error> {
+        Org::from_reader_custom(reader, &DEFAULT_CONFIG)
+    }
+
+    #[cfg(feature = "std")]
+    /// Parses the text read from `reader` into `Org` struct with custom `ParseConfig`.
+    pub fn from_reader_custom<R: BufRead>(
+        mut reader: R,
+        config: &ParseConfig,
+    ) -> Result<Org<'static>, Error> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+
+        let mut org = Org::new();
+        org.reparse(text, config);
+
+        Ok(org)
+    }
+
     /// Returns a refrence to the underlay arena.
     pub fn arena(&self) -> &Arena<Element<'a>> {
         &self.arena
@@ -65,6 +222,9 @@ impl<'a> Org<'a> {
     }
 
     /// Returns an iterator of `Event`s.
+    ///
+    /// Pending contents of a lazily parsed document are skipped, see
+    /// [`Org::parse_lazy`](#method.parse_lazy).
     pub fn iter<'b>(&'b self) -> impl Iterator<Item = Event<'a, 'b>> + 'b {
         self.root.traverse(&self.arena).map(move |edge| match edge {
             NodeEdge::Start(node) => Event::Start(&self[node]),
@@ -83,7 +243,265 @@ impl<'a> Org<'a> {
             })
     }
 
+    /// Returns trimmed values of all keywords named `key`, in document order.
+    /// Names are matched case-insensitively.
+    pub fn keyword_values(&self, key: &str) -> Vec<&str> {
+        self.keywords()
+            .filter(|keyword| keyword.key.eq_ignore_ascii_case(key))
+            .map(|keyword| keyword.value.trim())
+            .collect()
+    }
+
+    /// Returns values of all keywords named `key` combined by the behavior
+    /// org-export applies to it, see [`KeywordBehavior::of`], or `None` if
+    /// there's no such keyword.
+    ///
+    /// [`KeywordBehavior::of`]: elements/enum.KeywordBehavior.html#method.of
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse(
+    ///     "#+AUTHOR: Alice\n#+AUTHOR: Bob\n\
+    ///      #+HTML_HEAD: <style></style>\n#+HTML_HEAD: <script></script>\n\
+    ///      #+LANGUAGE: en\n#+LANGUAGE: fr\n",
+    /// );
+    ///
+    /// assert_eq!(org.keyword_values("author"), vec!["Alice", "Bob"]);
+    /// assert_eq!(org.keyword("AUTHOR").unwrap(), "Alice Bob");
+    /// assert_eq!(org.keyword("HTML_HEAD").unwrap(), "<style></style>\n<script></script>");
+    /// assert_eq!(org.keyword("LANGUAGE").unwrap(), "en");
+    /// assert!(org.keyword("TITLE").is_none());
+    /// ```
+    pub fn keyword(&self, key: &str) -> Option<String> {
+        self.keyword_with(key, KeywordBehavior::of(key))
+    }
+
+    /// Returns values of all keywords named `key` combined by `behavior`,
+    /// or `None` if there's no such keyword.
+    pub fn keyword_with(&self, key: &str, behavior: KeywordBehavior) -> Option<String> {
+        let values = self.keyword_values(key);
+        if values.is_empty() {
+            return None;
+        }
+        Some(match behavior {
+            KeywordBehavior::First => values[0].into(),
+            KeywordBehavior::Last => values[values.len() - 1].into(),
+            KeywordBehavior::Space => values.join(" "),
+            KeywordBehavior::Newline => values.join("\n"),
+            KeywordBehavior::Split => values
+                .iter()
+                .flat_map(|value| value.split_whitespace())
+                .collect::<Vec<_>>()
+                .join(" "),
+        })
+    }
+
+    #[cfg(feature = "std")]
+    /// Returns `false` if `headline` is tagged with `noexport` or commented,
+    /// so that it's excluded from exports with its subtree.
+    pub(crate) fn is_exported(&self, headline: Headline) -> bool {
+        let title = headline.title(self);
+        !title.is_commented() && !title.tags.iter().any(|tag| tag == "noexport")
+    }
+
+    /// Returns link abbreviations defined by `#+LINK` keywords, keyed by
+    /// lowercased abbreviations. The first definition of an abbreviation wins.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse("#+LINK: gh https://github.com/%s\n[[gh:theiceshelf/orgize]]");
+    ///
+    /// assert_eq!(org.link_abbreviations()["gh"], "https://github.com/%s");
+    /// ```
+    pub fn link_abbreviations(&self) -> HashMap<String, String> {
+        let mut abbreviations = HashMap::new();
+        for keyword in self.keywords() {
+            if !keyword.key.eq_ignore_ascii_case("LINK") {
+                continue;
+            }
+            let mut parts = keyword.value.trim().splitn(2, char::is_whitespace);
+            if let (Some(key), Some(replacement)) = (parts.next(), parts.next()) {
+                abbreviations
+                    .entry(key.to_lowercase())
+                    .or_insert_with(|| replacement.trim().to_string());
+            }
+        }
+        abbreviations
+    }
+
+    /// Returns constants defined by `#+CONSTANTS` keywords for table
+    /// formulas. The first definition of a constant wins.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse("#+CONSTANTS: pi=3.14159 c=299792458\n#+CONSTANTS: pi=3\n");
+    ///
+    /// assert_eq!(org.constants()["pi"], "3.14159");
+    /// assert_eq!(org.constants()["c"], "299792458");
+    /// ```
+    pub fn constants(&self) -> HashMap<String, String> {
+        let mut constants = HashMap::new();
+        for keyword in self.keywords() {
+            if !keyword.key.eq_ignore_ascii_case("CONSTANTS") {
+                continue;
+            }
+            for constant in keyword.value.split_whitespace() {
+                if let Some((name, value)) = constant.split_once('=') {
+                    constants
+                        .entry(name.to_string())
+                        .or_insert_with(|| value.to_string());
+                }
+            }
+        }
+        constants
+    }
+
+    /// Replaces `$name` references to constants defined by `#+CONSTANTS`
+    /// in the table formula `formula` with their values in parentheses, like
+    /// org-table does before evaluating a formula.
+    ///
+    /// Field references like `$2` and unknown names are kept.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse("#+CONSTANTS: pi=3.14159 c=299792458\n");
+    ///
+    /// assert_eq!(
+    ///     org.expand_constants("$3=2*$pi*$2::$4=$c*$x"),
+    ///     "$3=2*(3.14159)*$2::$4=(299792458)*$x"
+    /// );
+    /// ```
+    pub fn expand_constants(&self, formula: &str) -> String {
+        let constants = self.constants();
+        let mut expanded = String::with_capacity(formula.len());
+        let mut rest = formula;
+
+        while let Some(i) = rest.find('$') {
+            expanded.push_str(&rest[..i]);
+            rest = &rest[i + 1..];
+
+            let len = rest
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .unwrap_or(rest.len());
+            let name = &rest[..len];
+            match constants.get(name) {
+                Some(value) if name.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+                    expanded.push('(');
+                    expanded.push_str(value);
+                    expanded.push(')');
+                    rest = &rest[len..];
+                }
+                _ => expanded.push('$'),
+            }
+        }
+        expanded.push_str(rest);
+
+        expanded
+    }
+
+    /// Updates expanded destinations of all links with `#+LINK` keywords and
+    /// attachment directories.
+    pub(crate) fn expand_links(&mut self) {
+        let abbreviations = self.link_abbreviations();
+        for node in self.arena.iter_mut().filter(|node| !node.is_removed()) {
+            if let Element::Link(link) = node.get_mut() {
+                link.expanded = if abbreviations.is_empty() {
+                    None
+                } else {
+                    expand_link(&link.path, &abbreviations).map(Cow::Owned)
+                };
+            }
+        }
+
+        let attachments: Vec<_> = self
+            .root
+            .descendants(&self.arena)
+            .filter_map(|node| Some((node, self.attachment_path(node)?)))
+            .collect();
+        for (node, path) in attachments {
+            if let Element::Link(link) = &mut self[node] {
+                link.expanded = Some(Cow::Owned(path));
+            }
+        }
+
+        let coderefs: Vec<_> = self
+            .root
+            .descendants(&self.arena)
+            .filter_map(|node| match &self[node] {
+                Element::Link(link) => {
+                    let label = link.coderef()?;
+                    self.resolve_coderef(label)?;
+                    Some((node, format!("#coderef-{}", label)))
+                }
+                _ => None,
+            })
+            .collect();
+        for (node, target) in coderefs {
+            if let Element::Link(link) = &mut self[node] {
+                link.expanded = Some(Cow::Owned(target));
+            }
+        }
+    }
+
+    /// Returns the plain text of all objects inside `node`, with markup stripped.
+    ///
+    /// Texts from different paragraphs and titles are separated by a newline,
+    /// while links are replaced with their description, or their path if they
+    /// have no description.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse("* Fix *urgent* [[https://example.com][bug]]\nfirst ~code~\n\nsecond");
+    ///
+    /// let h1 = org.headlines().next().unwrap();
+    ///
+    /// assert_eq!(org.text_content(h1.title_node()), "Fix urgent bug");
+    /// assert_eq!(
+    ///     org.text_content(h1.headline_node()),
+    ///     "Fix urgent bug\nfirst code\nsecond"
+    /// );
+    /// ```
+    pub fn text_content(&self, node: NodeId) -> String {
+        let mut text = String::new();
+
+        for edge in node.traverse(&self.arena) {
+            match edge {
+                NodeEdge::Start(node) => match &self[node] {
+                    Element::Text { value }
+                    | Element::Verbatim { value }
+                    | Element::Code { value } => text.push_str(value),
+                    Element::Link(link) => text.push_str(link.desc.as_ref().unwrap_or(&link.path)),
+                    _ => (),
+                },
+                NodeEdge::End(node) => {
+                    if let Element::Paragraph { .. } | Element::VerseLine { .. } | Element::Title(_) =
+                        &self[node]
+                    {
+                        if !text.is_empty() && !text.ends_with('\n') {
+                            text.push('\n');
+                        }
+                    }
+                }
+            }
+        }
+
+        if text.ends_with('\n') {
+            text.pop();
+        }
+
+        text
+    }
+
+    #[cfg(feature = "std")]
     /// Writes an `Org` struct as html format.
+    ///
+    /// Pending contents of a lazily parsed document are skipped, see
+    /// [`Org::parse_lazy`](#method.parse_lazy).
     pub fn write_html<W>(&self, writer: W) -> Result<(), Error>
     where
         W: Write,
@@ -91,6 +509,7 @@ impl<'a> Org<'a> {
         self.write_html_custom(writer, &mut DefaultHtmlHandler)
     }
 
+    #[cfg(feature = "std")]
     /// Writes an `Org` struct as html format with custom `HtmlHandler`.
     pub fn write_html_custom<W, H, E>(&self, mut writer: W, handler: &mut H) -> Result<(), E>
     where
@@ -108,7 +527,62 @@ impl<'a> Org<'a> {
         Ok(())
     }
 
+    #[cfg(feature = "std")]
+    /// Writes `node` and its descendants as html format with custom
+    /// `HtmlHandler`.
+    pub(crate) fn write_html_node<W, H, E>(
+        &self,
+        mut writer: W,
+        handler: &mut H,
+        node: NodeId,
+    ) -> Result<(), E>
+    where
+        W: Write,
+        E: From<Error>,
+        H: HtmlHandler<E>,
+    {
+        for edge in node.traverse(&self.arena) {
+            match edge {
+                NodeEdge::Start(node) => handler.start(&mut writer, &self[node])?,
+                NodeEdge::End(node) => handler.end(&mut writer, &self[node])?,
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "std")]
+    /// Writes `headline` and its subtree as html format with custom
+    /// `HtmlHandler`, skipping headlines excluded from exports.
+    pub(crate) fn write_html_headline<W, H, E>(
+        &self,
+        writer: &mut W,
+        handler: &mut H,
+        headline: Headline,
+    ) -> Result<(), E>
+    where
+        W: Write,
+        E: From<Error>,
+        H: HtmlHandler<E>,
+    {
+        handler.start(&mut *writer, &self[headline.headline_node()])?;
+        self.write_html_node(&mut *writer, handler, headline.title_node())?;
+        if let Some(section) = headline.section_node() {
+            self.write_html_node(&mut *writer, handler, section)?;
+        }
+        for child in headline.children(self).filter(|h| self.is_exported(*h)) {
+            self.write_html_headline(writer, handler, child)?;
+        }
+        handler.end(&mut *writer, &self[headline.headline_node()])?;
+
+        Ok(())
+    }
+
+    #[cfg(feature = "std")]
     /// Writes an `Org` struct as org format.
+    ///
+    /// Pending contents of a lazily parsed document are written as they are
+    /// in the source text.
     pub fn write_org<W>(&self, writer: W) -> Result<(), Error>
     where
         W: Write,
@@ -116,6 +590,7 @@ impl<'a> Org<'a> {
         self.write_org_custom(writer, &mut DefaultOrgHandler)
     }
 
+    #[cfg(feature = "std")]
     /// Writes an `Org` struct as org format with custom `OrgHandler`.
     pub fn write_org_custom<W, H, E>(&self, mut writer: W, handler: &mut H) -> Result<(), E>
     where
@@ -123,10 +598,16 @@ impl<'a> Org<'a> {
         E: From<Error>,
         H: OrgHandler<E>,
     {
-        for event in self.iter() {
-            match event {
-                Event::Start(element) => handler.start(&mut writer, element)?,
-                Event::End(element) => handler.end(&mut writer, element)?,
+        for edge in self.root.traverse(&self.arena) {
+            match edge {
+                NodeEdge::Start(node) => {
+                    handler.start(&mut writer, &self[node])?;
+                    // contents of lazily parsed nodes are written as they are
+                    if let Some(container) = self.pending.get(&node) {
+                        writer.write_all(container.content().as_bytes())?;
+                    }
+                }
+                NodeEdge::End(node) => handler.end(&mut writer, &self[node])?,
             }
         }
 
@@ -0,0 +1,72 @@
+use rayon::prelude::*;
+use std::borrow::Cow;
+
+use crate::{
+    config::{ParseConfig, DEFAULT_CONFIG},
+    elements::Element,
+    parsers::{blank_lines, parse_container, parse_container_content, Container, ElementArena},
+    Org,
+};
+
+impl<'a> Org<'a> {
+    /// Parses string `text` into `Org` struct, parsing the contents of
+    /// top-level sections and headlines in parallel.
+    ///
+    /// This produces the same result as [`Org::parse`], and is only faster
+    /// on large documents with many top-level headlines.
+    ///
+    /// [`Org::parse`]: #method.parse
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse_parallel("* title 1\nsection 1\n* title 2\nsection 2");
+    ///
+    /// assert_eq!(org.headlines().count(), 2);
+    /// ```
+    pub fn parse_parallel(text: &'a str) -> Org<'a> {
+        Org::parse_parallel_custom(text, &DEFAULT_CONFIG)
+    }
+
+    /// Parses string `text` into `Org` struct in parallel with custom `ParseConfig`.
+    ///
+    /// See [`Org::parse_parallel`](#method.parse_parallel) for more details.
+    pub fn parse_parallel_custom(text: &'a str, config: &ParseConfig) -> Org<'a> {
+        let (content, pre_blank) = blank_lines(text);
+        let mut org = Org::with_text(Cow::Borrowed(text), pre_blank);
+        let node = org.root;
+        org.set_span(node, text);
+
+        // splits the outline into top-level sections and headlines
+        let mut containers = Vec::new();
+        parse_container_content(
+            &mut org,
+            Container::Document { content, node },
+            &mut containers,
+            config,
+        );
+
+        let parsed: Vec<_> = containers
+            .into_par_iter()
+            .map(|container| {
+                let mut child = Org::with_text(Cow::Borrowed(text), 0);
+                let (node, container) = container.with_node(child.root);
+                parse_container(&mut child, container, config);
+                (node, child)
+            })
+            .collect();
+
+        for (node, mut child) in parsed {
+            if let Some(span) = child.contents_spans.remove(&child.root) {
+                org.contents_spans.insert(node, span);
+            }
+            org.graft(child, node, None, |e: Element<'a>| e);
+        }
+
+        org.expand_links();
+        org.handle_keywords(config);
+        org.debug_validate();
+
+        org
+    }
+}
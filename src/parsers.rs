@@ -1,20 +1,28 @@
-use std::borrow::Cow;
-use std::iter::once;
-use std::marker::PhantomData;
+use alloc::borrow::Cow;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::once;
+use core::marker::PhantomData;
 
 use indextree::{Arena, NodeId};
+#[cfg(feature = "std")]
 use jetscii::{bytes, BytesConst};
 use memchr::{memchr, memchr_iter};
 use nom::{bytes::complete::take_while1, combinator::verify, error::ParseError, IResult};
 
-use crate::config::ParseConfig;
+use crate::compat::HashMap;
+use crate::config::{ObjectKind, ParseConfig, Syntax};
 use crate::elements::{
-    block::parse_block_element, emphasis::parse_emphasis, keyword::parse_keyword,
-    radio_target::parse_radio_target, BabelCall, CenterBlock, Clock, Comment, CommentBlock, Cookie,
-    Drawer, DynBlock, Element, ExampleBlock, ExportBlock, FixedWidth, FnDef, FnRef, InlineCall,
+    block::{parse_block_element, unescape},
+    emphasis::{is_unicode_delimiter, parse_emphasis},
+    keyword::parse_keyword,
+    radio_target::parse_radio_target,
+    BabelCall, CenterBlock, Citation, Clock, Comment, CommentBlock, Cookie, Drawer, DynBlock,
+    Element, EncryptedSection, ExampleBlock, ExportBlock, FixedWidth, FnDef, FnRef, InlineCall,
     InlineSrc, Keyword, Link, List, ListItem, Macros, QuoteBlock, Rule, Snippet, SourceBlock,
     SpecialBlock, Table, TableCell, TableRow, Target, Timestamp, Title, VerseBlock,
 };
+use crate::org::Org;
 
 pub trait ElementArena<'a> {
     fn append<T>(&mut self, element: T, parent: NodeId) -> NodeId
@@ -26,6 +34,14 @@ pub trait ElementArena<'a> {
     fn set<T>(&mut self, node: NodeId, element: T)
     where
         T: Into<Element<'a>>;
+    /// Records `raw`, the source text covered by `node`.
+    ///
+    /// Arenas which don't keep track of spans just ignore it.
+    fn set_span(&mut self, _node: NodeId, _raw: &'a str) {}
+    /// Records `raw`, the source text covered by the contents of `node`.
+    ///
+    /// Arenas which don't keep track of spans just ignore it.
+    fn set_contents_span(&mut self, _node: NodeId, _raw: &'a str) {}
 }
 
 impl<'a> ElementArena<'a> for Arena<Element<'a>> {
@@ -59,6 +75,41 @@ impl<'a> ElementArena<'a> for Arena<Element<'a>> {
     }
 }
 
+impl<'a> ElementArena<'a> for Org<'a> {
+    fn append<T>(&mut self, element: T, parent: NodeId) -> NodeId
+    where
+        T: Into<Element<'a>>,
+    {
+        self.arena.append(element, parent)
+    }
+
+    fn insert_before_last_child<T>(&mut self, element: T, parent: NodeId) -> NodeId
+    where
+        T: Into<Element<'a>>,
+    {
+        self.arena.insert_before_last_child(element, parent)
+    }
+
+    fn set<T>(&mut self, node: NodeId, element: T)
+    where
+        T: Into<Element<'a>>,
+    {
+        self.arena.set(node, element)
+    }
+
+    fn set_span(&mut self, node: NodeId, raw: &'a str) {
+        if let Some(start) = self.offset_of(raw) {
+            self.spans.insert(node, start..start + raw.len());
+        }
+    }
+
+    fn set_contents_span(&mut self, node: NodeId, raw: &'a str) {
+        if let Some(start) = self.offset_of(raw) {
+            self.contents_spans.insert(node, start..start + raw.len());
+        }
+    }
+}
+
 pub struct OwnedArena<'a, 'b, 'c> {
     arena: &'b mut Arena<Element<'c>>,
     phantom: PhantomData<&'a ()>,
@@ -103,12 +154,44 @@ pub enum Container<'a> {
     Block { content: &'a str, node: NodeId },
     // Pargraph, Inline Markup
     Inline { content: &'a str, node: NodeId },
+    // Verse Block
+    Verse { content: &'a str, node: NodeId },
     // Headline
     Headline { content: &'a str, node: NodeId },
     // Document
     Document { content: &'a str, node: NodeId },
 }
 
+impl<'a> Container<'a> {
+    /// Returns the text this container holds.
+    #[cfg(feature = "std")]
+    pub fn content(&self) -> &'a str {
+        match self {
+            Container::Block { content, .. }
+            | Container::Inline { content, .. }
+            | Container::Verse { content, .. }
+            | Container::Headline { content, .. }
+            | Container::Document { content, .. } => content,
+        }
+    }
+
+    /// Returns the node of this container, and this container with its node
+    /// replaced by `node`.
+    pub fn with_node(self, node: NodeId) -> (NodeId, Container<'a>) {
+        match self {
+            Container::Block { content, node: old } => (old, Container::Block { content, node }),
+            Container::Inline { content, node: old } => (old, Container::Inline { content, node }),
+            Container::Verse { content, node: old } => (old, Container::Verse { content, node }),
+            Container::Headline { content, node: old } => {
+                (old, Container::Headline { content, node })
+            }
+            Container::Document { content, node: old } => {
+                (old, Container::Document { content, node })
+            }
+        }
+    }
+}
+
 pub fn parse_container<'a, T: ElementArena<'a>>(
     arena: &mut T,
     container: Container<'a>,
@@ -117,19 +200,62 @@ pub fn parse_container<'a, T: ElementArena<'a>>(
     let containers = &mut vec![container];
 
     while let Some(container) = containers.pop() {
-        match container {
-            Container::Document { content, node } => {
-                parse_section_and_headlines(arena, content, node, containers);
-            }
-            Container::Headline { content, node } => {
-                parse_headline_content(arena, content, node, containers, config);
-            }
-            Container::Block { content, node } => {
-                parse_blocks(arena, content, node, containers);
-            }
-            Container::Inline { content, node } => {
-                parse_inlines(arena, content, node, containers);
-            }
+        parse_container_content(arena, container, containers, config);
+    }
+}
+
+/// Parses the contents of `container`, pushing the new containers it found
+/// into `containers`.
+pub fn parse_container_content<'a, T: ElementArena<'a>>(
+    arena: &mut T,
+    container: Container<'a>,
+    containers: &mut Vec<Container<'a>>,
+    config: &ParseConfig,
+) {
+    match container {
+        Container::Document { content, node } => {
+            arena.set_contents_span(node, content);
+            parse_section_and_headlines(arena, content, node, containers, false);
+        }
+        Container::Headline { content, node } => {
+            parse_headline_content(arena, content, node, containers, config);
+        }
+        Container::Block { content, node } => {
+            arena.set_contents_span(node, content);
+            parse_blocks(arena, content, node, containers, config);
+        }
+        Container::Inline { content, node } => {
+            arena.set_contents_span(node, content);
+            parse_inlines(arena, content, node, config);
+        }
+        Container::Verse { content, node } => {
+            arena.set_contents_span(node, content);
+            parse_verse_lines(arena, content, node, containers);
+        }
+    }
+}
+
+/// Parses each line of a verse block into a verse line containing objects,
+/// so that line breaks and indentation are kept.
+pub fn parse_verse_lines<'a, T: ElementArena<'a>>(
+    arena: &mut T,
+    content: &'a str,
+    parent: NodeId,
+    containers: &mut Vec<Container<'a>>,
+) {
+    for line in content.split_inclusive('\n') {
+        let text = line.trim_start_matches([' ', '\t']);
+        let node = arena.append(
+            Element::VerseLine {
+                indent: line.len() - text.len(),
+            },
+            parent,
+        );
+        arena.set_span(node, line);
+
+        let text = text.trim_end_matches(|c: char| c.is_ascii_whitespace());
+        if !text.is_empty() {
+            containers.push(Container::Inline { content: text, node });
         }
     }
 }
@@ -141,10 +267,17 @@ pub fn parse_headline_content<'a, T: ElementArena<'a>>(
     containers: &mut Vec<Container<'a>>,
     config: &ParseConfig,
 ) {
-    let (tail, (title, content)) = Title::parse(content, config).unwrap();
+    let (tail, (title, raw)) = Title::parse(content, config).unwrap();
+    let crypt = title.tags.iter().any(|tag| tag == "crypt");
     let node = arena.append(title, parent);
+    arena.set_span(node, consumed(content, tail));
+    let content = raw;
     containers.push(Container::Inline { content, node });
-    parse_section_and_headlines(arena, tail, parent, containers);
+    if !tail.is_empty() {
+        // headline's contents begin after its title
+        arena.set_contents_span(parent, tail);
+    }
+    parse_section_and_headlines(arena, tail, parent, containers, crypt);
 }
 
 pub fn parse_section_and_headlines<'a, T: ElementArena<'a>>(
@@ -152,6 +285,7 @@ pub fn parse_section_and_headlines<'a, T: ElementArena<'a>>(
     content: &'a str,
     parent: NodeId,
     containers: &mut Vec<Container<'a>>,
+    crypt: bool,
 ) {
     let content = skip_empty_lines(content);
     if content.is_empty() {
@@ -162,12 +296,11 @@ pub fn parse_section_and_headlines<'a, T: ElementArena<'a>>(
     for i in memchr_iter(b'\n', content.as_bytes()).chain(once(content.len())) {
         if let Some((mut tail, (headline_content, level))) = parse_headline(&content[last_end..]) {
             if last_end != 0 {
-                let node = arena.append(Element::Section, parent);
-                let content = &content[0..last_end];
-                containers.push(Container::Block { content, node });
+                parse_section(arena, &content[0..last_end], parent, containers, crypt);
             }
 
             let node = arena.append(Element::Headline { level }, parent);
+            arena.set_span(node, headline_content);
             containers.push(Container::Headline {
                 content: headline_content,
                 node,
@@ -176,6 +309,7 @@ pub fn parse_section_and_headlines<'a, T: ElementArena<'a>>(
             while let Some((new_tail, (content, level))) = parse_headline(tail) {
                 debug_assert_ne!(tail, new_tail);
                 let node = arena.append(Element::Headline { level }, parent);
+                arena.set_span(node, content);
                 containers.push(Container::Headline { content, node });
                 tail = new_tail;
             }
@@ -184,7 +318,26 @@ pub fn parse_section_and_headlines<'a, T: ElementArena<'a>>(
         last_end = i + 1;
     }
 
+    parse_section(arena, content, parent, containers, crypt);
+}
+
+/// Appends a section, or an encrypted section if its headline is tagged with
+/// `:crypt:` and it contains nothing but a PGP message.
+fn parse_section<'a, T: ElementArena<'a>>(
+    arena: &mut T,
+    content: &'a str,
+    parent: NodeId,
+    containers: &mut Vec<Container<'a>>,
+    crypt: bool,
+) {
+    if let Some(section) = crypt.then(|| EncryptedSection::parse(content)).flatten() {
+        let node = arena.append(section, parent);
+        arena.set_span(node, content);
+        return;
+    }
+
     let node = arena.append(Element::Section, parent);
+    arena.set_span(node, content);
     containers.push(Container::Block { content, node });
 }
 
@@ -193,10 +346,11 @@ pub fn parse_blocks<'a, T: ElementArena<'a>>(
     content: &'a str,
     parent: NodeId,
     containers: &mut Vec<Container<'a>>,
+    config: &ParseConfig,
 ) {
     let mut tail = skip_empty_lines(content);
 
-    if let Some(new_tail) = parse_block(content, arena, parent, containers) {
+    if let Some(new_tail) = parse_block(content, arena, parent, containers, config) {
         tail = skip_empty_lines(new_tail);
     }
 
@@ -212,6 +366,12 @@ pub fn parse_blocks<'a, T: ElementArena<'a>>(
             debug_assert_ne!(tail, tail_);
             tail = tail_;
 
+            // e.g. a whitespace-only last line without line ending
+            if pos == 0 {
+                text = tail;
+                continue;
+            }
+
             let node = arena.append(
                 Element::Paragraph {
                     // including current line (&tail[0..i])
@@ -219,21 +379,23 @@ pub fn parse_blocks<'a, T: ElementArena<'a>>(
                 },
                 parent,
             );
+            arena.set_span(node, consumed(text, tail));
 
             containers.push(Container::Inline {
-                content: &text[0..pos].trim_end(),
+                content: text[0..pos].trim_end_matches(|c: char| c.is_ascii_whitespace()),
                 node,
             });
 
             pos = 0;
             text = tail;
-        } else if let Some(new_tail) = parse_block(tail, arena, parent, containers) {
+        } else if let Some(new_tail) = parse_block(tail, arena, parent, containers, config) {
             if pos != 0 {
                 let node =
                     arena.insert_before_last_child(Element::Paragraph { post_blank: 0 }, parent);
+                arena.set_span(node, &text[0..pos]);
 
                 containers.push(Container::Inline {
-                    content: &text[0..pos].trim_end(),
+                    content: text[0..pos].trim_end_matches(|c: char| c.is_ascii_whitespace()),
                     node,
                 });
 
@@ -251,9 +413,10 @@ pub fn parse_blocks<'a, T: ElementArena<'a>>(
 
     if !text.is_empty() {
         let node = arena.append(Element::Paragraph { post_blank: 0 }, parent);
+        arena.set_span(node, &text[0..pos]);
 
         containers.push(Container::Inline {
-            content: &text[0..pos].trim_end(),
+            content: text[0..pos].trim_end_matches(|c: char| c.is_ascii_whitespace()),
             node,
         });
     }
@@ -264,8 +427,9 @@ pub fn parse_block<'a, T: ElementArena<'a>>(
     arena: &mut T,
     parent: NodeId,
     containers: &mut Vec<Container<'a>>,
+    config: &ParseConfig,
 ) -> Option<&'a str> {
-    match contents
+    let (tail, node) = match contents
         .as_bytes()
         .iter()
         .find(|c| !c.is_ascii_whitespace())?
@@ -274,79 +438,80 @@ pub fn parse_block<'a, T: ElementArena<'a>>(
             let (tail, (fn_def, content)) = FnDef::parse(contents)?;
             let node = arena.append(fn_def, parent);
             containers.push(Container::Block { content, node });
-            Some(tail)
-        }
-        b'0'..=b'9' | b'*' => {
-            let tail = parse_list(arena, contents, parent, containers)?;
-            Some(tail)
+            (tail, node)
         }
+        b'0'..=b'9' | b'*' => parse_list(arena, contents, parent, containers)?,
         b'C' => {
             let (tail, clock) = Clock::parse(contents)?;
-            arena.append(clock, parent);
-            Some(tail)
+            (tail, arena.append(clock, parent))
         }
         b'\'' => {
             // TODO: LaTeX environment
-            None
+            return None;
         }
         b'-' => {
             if let Some((tail, rule)) = Rule::parse(contents) {
-                arena.append(rule, parent);
-                Some(tail)
+                (tail, arena.append(rule, parent))
             } else {
-                let tail = parse_list(arena, contents, parent, containers)?;
-                Some(tail)
+                parse_list(arena, contents, parent, containers)?
             }
         }
         b':' => {
-            if let Some((tail, (drawer, content))) = Drawer::parse(contents) {
+            if let Some((tail, (drawer, content))) = Drawer::parse(contents)
+                .filter(|(_, (drawer, _))| config.is_drawer_name(&drawer.name))
+            {
                 let node = arena.append(drawer, parent);
                 containers.push(Container::Block { content, node });
-                Some(tail)
+                (tail, node)
             } else {
                 let (tail, fixed_width) = FixedWidth::parse(contents)?;
-                arena.append(fixed_width, parent);
-                Some(tail)
+                (tail, arena.append(fixed_width, parent))
             }
         }
-        b'|' => {
-            let tail = parse_org_table(arena, contents, containers, parent);
-            Some(tail)
-        }
+        b'|' => parse_org_table(arena, contents, containers, parent),
         b'+' => {
             if let Some((tail, table)) = Table::parse_table_el(contents) {
-                arena.append(table, parent);
-                Some(tail)
+                (tail, arena.append(table, parent))
             } else {
-                let tail = parse_list(arena, contents, parent, containers)?;
-                Some(tail)
+                parse_list(arena, contents, parent, containers)?
             }
         }
         b'#' => {
             if let Some((tail, (name, args, content, blank))) = parse_block_element(contents) {
-                match_block(
-                    arena,
-                    parent,
-                    containers,
-                    name.into(),
-                    args.map(Into::into),
-                    content,
-                    blank,
-                );
-                Some(tail)
+                let node = if config.syntax == Syntax::Org8 && is_org8_export_block(name) {
+                    arena.append(
+                        ExportBlock {
+                            data: name.to_lowercase().into(),
+                            contents: content.into(),
+                            post_blank: blank,
+                        },
+                        parent,
+                    )
+                } else {
+                    match_block(
+                        arena,
+                        parent,
+                        containers,
+                        name.into(),
+                        args.map(Into::into),
+                        content,
+                        blank,
+                    )
+                };
+                (tail, node)
             } else if let Some((tail, (dyn_block, content))) = DynBlock::parse(contents) {
                 let node = arena.append(dyn_block, parent);
                 containers.push(Container::Block { content, node });
-                Some(tail)
+                (tail, node)
             } else if let Some((tail, (key, optional, value, blank))) = parse_keyword(contents) {
-                if (&*key).eq_ignore_ascii_case("CALL") {
+                let node = if (&*key).eq_ignore_ascii_case("CALL") {
                     arena.append(
                         BabelCall {
                             value: value.into(),
                             post_blank: blank,
                         },
                         parent,
-                    );
+                    )
                 } else {
                     arena.append(
                         Keyword {
@@ -356,17 +521,27 @@ pub fn parse_block<'a, T: ElementArena<'a>>(
                             post_blank: blank,
                         },
                         parent,
-                    );
-                }
-                Some(tail)
+                    )
+                };
+                (tail, node)
             } else {
                 let (tail, comment) = Comment::parse(contents)?;
-                arena.append(comment, parent);
-                Some(tail)
+                (tail, arena.append(comment, parent))
             }
         }
-        _ => None,
-    }
+        _ => return None,
+    };
+
+    arena.set_span(node, consumed(contents, tail));
+
+    Some(tail)
+}
+
+/// Returns `true` if `name` is a block name of Org 8.x export backends.
+fn is_org8_export_block(name: &str) -> bool {
+    ["ASCII", "BEAMER", "HTML", "LATEX", "MAN", "ODT", "TEXINFO"]
+        .iter()
+        .any(|backend| name.eq_ignore_ascii_case(backend))
 }
 
 pub fn match_block<'a, T: ElementArena<'a>>(
@@ -377,7 +552,7 @@ pub fn match_block<'a, T: ElementArena<'a>>(
     parameters: Option<Cow<'a, str>>,
     content: &'a str,
     post_blank: usize,
-) {
+) -> NodeId {
     match &*name.to_uppercase() {
         "CENTER" => {
             let (content, pre_blank) = blank_lines(content);
@@ -390,6 +565,7 @@ pub fn match_block<'a, T: ElementArena<'a>>(
                 parent,
             );
             containers.push(Container::Block { content, node });
+            node
         }
         "QUOTE" => {
             let (content, pre_blank) = blank_lines(content);
@@ -402,6 +578,7 @@ pub fn match_block<'a, T: ElementArena<'a>>(
                 parent,
             );
             containers.push(Container::Block { content, node });
+            node
         }
         "VERSE" => {
             let (content, pre_blank) = blank_lines(content);
@@ -413,38 +590,33 @@ pub fn match_block<'a, T: ElementArena<'a>>(
                 },
                 parent,
             );
-            containers.push(Container::Block { content, node });
-        }
-        "COMMENT" => {
-            arena.append(
-                CommentBlock {
-                    data: parameters,
-                    contents: content.into(),
-                    post_blank,
-                },
-                parent,
-            );
-        }
-        "EXAMPLE" => {
-            arena.append(
-                ExampleBlock {
-                    data: parameters,
-                    contents: content.into(),
-                    post_blank,
-                },
-                parent,
-            );
-        }
-        "EXPORT" => {
-            arena.append(
-                ExportBlock {
-                    data: parameters.unwrap_or_default(),
-                    contents: content.into(),
-                    post_blank,
-                },
-                parent,
-            );
+            containers.push(Container::Verse { content, node });
+            node
         }
+        "COMMENT" => arena.append(
+            CommentBlock {
+                data: parameters,
+                contents: content.into(),
+                post_blank,
+            },
+            parent,
+        ),
+        "EXAMPLE" => arena.append(
+            ExampleBlock {
+                data: parameters,
+                contents: unescape(content),
+                post_blank,
+            },
+            parent,
+        ),
+        "EXPORT" => arena.append(
+            ExportBlock {
+                data: parameters.unwrap_or_default(),
+                contents: unescape(content),
+                post_blank,
+            },
+            parent,
+        ),
         "SRC" => {
             let (language, arguments) = match &parameters {
                 Some(Cow::Borrowed(args)) => {
@@ -459,11 +631,11 @@ pub fn match_block<'a, T: ElementArena<'a>>(
                 SourceBlock {
                     arguments,
                     language,
-                    contents: content.into(),
+                    contents: unescape(content),
                     post_blank,
                 },
                 parent,
-            );
+            )
         }
         _ => {
             let (content, pre_blank) = blank_lines(content);
@@ -477,22 +649,100 @@ pub fn match_block<'a, T: ElementArena<'a>>(
                 parent,
             );
             containers.push(Container::Block { content, node });
+            node
         }
     }
 }
 
+/// Memoized forward search.
+///
+/// Remembers where the last search started and what it found, so a search
+/// starting between these two positions can reuse the result instead of
+/// scanning the same text again.
+#[derive(Default)]
+pub struct Memo(Option<(usize, Option<usize>)>);
+
+impl Memo {
+    /// Returns the result of `search` starting from `from`.
+    pub fn find(
+        &mut self,
+        from: usize,
+        search: impl FnOnce(usize) -> Option<usize>,
+    ) -> Option<usize> {
+        match self.0 {
+            Some((last, found)) if last <= from && found.map(|i| i >= from).unwrap_or(true) => {
+                found
+            }
+            _ => {
+                let found = search(from);
+                self.0 = Some((from, found));
+                found
+            }
+        }
+    }
+}
+
+fn find_byte(bytes: &[u8], from: usize, predicate: impl Fn(u8) -> bool) -> Option<usize> {
+    bytes
+        .get(from..)?
+        .iter()
+        .position(|&b| predicate(b))
+        .map(|i| i + from)
+}
+
+fn find_bytes(bytes: &[u8], from: usize, needle: &[u8]) -> Option<usize> {
+    memchr_iter(needle[0], bytes.get(from..)?)
+        .map(|i| i + from)
+        .find(|&i| bytes[i..].starts_with(needle))
+}
+
+/// Finds the first byte which can precede an object, including leading
+/// bytes of non-ascii whitespaces and punctuations.
+#[cfg(feature = "std")]
+fn find_pre_byte(bytes: &[u8]) -> Option<usize> {
+    lazy_static::lazy_static! {
+        static ref PRE_BYTES: BytesConst = bytes!(
+            b'@', b'<', b'[', b' ', b'(', b'{', b'\'', b'"', b'\n', 0xC2, 0xE1, 0xE2, 0xE3, 0xEF
+        );
+    }
+
+    PRE_BYTES.find(bytes)
+}
+
+#[cfg(not(feature = "std"))]
+fn find_pre_byte(bytes: &[u8]) -> Option<usize> {
+    bytes.iter().position(|b| {
+        matches!(
+            b,
+            b'@' | b'<'
+                | b'['
+                | b' '
+                | b'('
+                | b'{'
+                | b'\''
+                | b'"'
+                | b'\n'
+                | 0xC2
+                | 0xE1
+                | 0xE2
+                | 0xE3
+                | 0xEF
+        )
+    })
+}
+
 struct InlinePositions<'a> {
-    bytes: &'a [u8],
+    text: &'a str,
     pos: usize,
     next: Option<usize>,
 }
 
 impl InlinePositions<'_> {
-    fn new(bytes: &[u8]) -> InlinePositions {
+    fn new(text: &str, pos: usize) -> InlinePositions<'_> {
         InlinePositions {
-            bytes,
-            pos: 0,
-            next: Some(0),
+            text,
+            pos,
+            next: Some(pos),
         }
     }
 }
@@ -501,24 +751,25 @@ impl Iterator for InlinePositions<'_> {
     type Item = usize;
 
     fn next(&mut self) -> Option<Self::Item> {
-        lazy_static::lazy_static! {
-            static ref PRE_BYTES: BytesConst =
-                bytes!(b'@', b'<', b'[', b' ', b'(', b'{', b'\'', b'"', b'\n');
-        }
-
-        self.next.take().or_else(|| {
-            PRE_BYTES.find(&self.bytes[self.pos..]).map(|i| {
-                self.pos += i + 1;
-
-                match self.bytes[self.pos - 1] {
-                    b'{' => {
-                        self.next = Some(self.pos);
-                        self.pos - 1
+        self.next.take().or_else(|| loop {
+            let i = find_pre_byte(&self.text.as_bytes()[self.pos..])?;
+            self.pos += i + 1;
+
+            match self.text.as_bytes()[self.pos - 1] {
+                b'{' => {
+                    self.next = Some(self.pos);
+                    return Some(self.pos - 1);
+                }
+                b' ' | b'(' | b'\'' | b'"' | b'\n' => return Some(self.pos),
+                b'@' | b'<' | b'[' => return Some(self.pos - 1),
+                _ => {
+                    let c = self.text[self.pos - 1..].chars().next()?;
+                    self.pos += c.len_utf8() - 1;
+                    if is_unicode_delimiter(c) {
+                        return Some(self.pos);
                     }
-                    b' ' | b'(' | b'\'' | b'"' | b'\n' => self.pos,
-                    _ => self.pos - 1,
                 }
-            })
+            }
         })
     }
 }
@@ -527,134 +778,463 @@ pub fn parse_inlines<'a, T: ElementArena<'a>>(
     arena: &mut T,
     content: &'a str,
     parent: NodeId,
-    containers: &mut Vec<Container<'a>>,
+    config: &ParseConfig,
 ) {
-    let mut tail = content;
+    InlineScanner::new(content, config).scan(arena, parent);
+}
 
-    if let Some(tail_) = parse_inline(tail, arena, containers, parent) {
-        tail = tail_;
-    }
+/// Scanner of objects in the contents of an inline container.
+///
+/// Objects are parsed in a single forward pass: nested emphasis is parsed in
+/// place rather than rescanned as another container, and searches for
+/// closing delimiters are memoized, so that failed attempts never make later
+/// attempts scan the same text again.
+#[derive(Default)]
+struct InlineScanner<'a> {
+    text: &'a str,
+    /// Enabled emphasis markers
+    markers: Vec<u8>,
+    disabled: Vec<ObjectKind>,
+    syntax: Syntax,
+    /// Offsets of all newlines in `text`
+    newlines: Vec<usize>,
+    /// Closing markers of `*`, `+`, `/`, `_`, `=` and `~`
+    emphasis: [Memo; 6],
+    /// Brackets ending citations
+    citation: Memo,
+    /// `@@` ending snippets
+    snippet: Memo,
+    /// `}}}` and `)}}}` ending macros
+    macros: Memo,
+    macros_args: Memo,
+    /// Ends of links' paths and descriptions
+    link_path: Memo,
+    link_desc: Memo,
+    /// End of diary timestamps
+    diary: Memo,
+    /// Ends of inline source blocks' languages, options and bodies
+    src_lang: Memo,
+    src_options: Memo,
+    src_body: Memo,
+    /// Ends of inline babel calls' names, headers and arguments
+    call_name: Memo,
+    call_header: Memo,
+    call_args: Memo,
+    call_end_header: Memo,
+    /// First `]` closing an unopened bracket at or after each offset, built
+    /// on first use
+    brackets: Option<Vec<Option<usize>>>,
+}
 
-    while let Some((tail_, i)) = InlinePositions::new(tail.as_bytes())
-        .filter_map(|i| parse_inline(&tail[i..], arena, containers, parent).map(|tail| (tail, i)))
-        .next()
-    {
-        if i != 0 {
-            arena.insert_before_last_child(
-                Element::Text {
-                    value: tail[0..i].into(),
-                },
-                parent,
-            );
+impl<'a> InlineScanner<'a> {
+    fn new(text: &'a str, config: &ParseConfig) -> InlineScanner<'a> {
+        InlineScanner {
+            text,
+            markers: config.emphasis_markers.bytes().collect(),
+            disabled: config.disabled_objects.clone(),
+            syntax: config.syntax,
+            newlines: memchr_iter(b'\n', text.as_bytes()).collect(),
+            ..Default::default()
         }
-        tail = tail_;
     }
 
-    if !tail.is_empty() {
-        arena.append(Element::Text { value: tail.into() }, parent);
-    }
-}
+    fn scan<T: ElementArena<'a>>(&mut self, arena: &mut T, parent: NodeId) {
+        let text = self.text;
+
+        // open containers and the end of their contents
+        let mut stack = vec![(parent, text.len())];
+        let mut start = 0;
+        let mut positions = InlinePositions::new(text, 0);
+
+        while let Some(&(parent, end)) = stack.last() {
+            if let Some(pos) = positions.next() {
+                if let Some((tail, child)) = self.parse_inline(arena, parent, pos, end) {
+                    if pos != start {
+                        let value = &text[start..pos];
+                        let node = arena.insert_before_last_child(
+                            Element::Text {
+                                value: value.into(),
+                            },
+                            parent,
+                        );
+                        arena.set_span(node, value);
+                    }
 
-pub fn parse_inline<'a, T: ElementArena<'a>>(
-    contents: &'a str,
-    arena: &mut T,
-    containers: &mut Vec<Container<'a>>,
-    parent: NodeId,
-) -> Option<&'a str> {
-    if contents.len() < 3 {
-        return None;
-    }
-
-    match contents.as_bytes()[0] {
-        b'@' => {
-            let (tail, snippet) = Snippet::parse(contents)?;
-            arena.append(snippet, parent);
-            Some(tail)
-        }
-        b'{' => {
-            let (tail, macros) = Macros::parse(contents)?;
-            arena.append(macros, parent);
-            Some(tail)
-        }
-        b'<' => {
-            if let Some((tail, _content)) = parse_radio_target(contents) {
-                arena.append(Element::RadioTarget, parent);
-                Some(tail)
-            } else if let Some((tail, target)) = Target::parse(contents) {
-                arena.append(target, parent);
-                Some(tail)
-            } else if let Some((tail, timestamp)) = Timestamp::parse_active(contents) {
-                arena.append(timestamp, parent);
-                Some(tail)
-            } else {
-                let (tail, timestamp) = Timestamp::parse_diary(contents)?;
-                arena.append(timestamp, parent);
-                Some(tail)
-            }
-        }
-        b'[' => {
-            if let Some((tail, fn_ref)) = FnRef::parse(contents) {
-                arena.append(fn_ref, parent);
-                Some(tail)
-            } else if let Some((tail, link)) = Link::parse(contents) {
-                arena.append(link, parent);
-                Some(tail)
-            } else if let Some((tail, cookie)) = Cookie::parse(contents) {
-                arena.append(cookie, parent);
-                Some(tail)
+                    if let Some((child, end)) = child {
+                        arena.set_contents_span(child, &text[pos + 1..end]);
+                        stack.push((child, end));
+                        start = pos + 1;
+                        positions = InlinePositions::new(&text[0..end], start);
+                    } else {
+                        start = tail;
+                        positions = InlinePositions::new(&text[0..end], start);
+                    }
+                }
             } else {
-                let (tail, timestamp) = Timestamp::parse_inactive(contents)?;
-                arena.append(timestamp, parent);
-                Some(tail)
+                if start < end {
+                    let value = &text[start..end];
+                    let node = arena.append(
+                        Element::Text {
+                            value: value.into(),
+                        },
+                        parent,
+                    );
+                    arena.set_span(node, value);
+                }
+
+                stack.pop();
+
+                // skips the closing marker
+                if let Some(&(_, parent_end)) = stack.last() {
+                    start = end + 1;
+                    positions = InlinePositions::new(&text[0..parent_end], start);
+                }
             }
         }
-        b'*' => {
-            let (tail, content) = parse_emphasis(contents, b'*')?;
-            let node = arena.append(Element::Bold, parent);
-            containers.push(Container::Inline { content, node });
-            Some(tail)
+    }
+
+    /// Parses the object beginning at `pos`, in a container ending at `end`.
+    ///
+    /// Returns the end of this object, and the object itself and the end of
+    /// its contents if it's a container.
+    fn parse_inline<T: ElementArena<'a>>(
+        &mut self,
+        arena: &mut T,
+        parent: NodeId,
+        pos: usize,
+        end: usize,
+    ) -> Option<(usize, Option<(NodeId, usize)>)> {
+        let text = self.text;
+        let bytes = text.as_bytes();
+        let contents = &text[pos..end];
+
+        if contents.len() < 3 {
+            return None;
         }
-        b'+' => {
-            let (tail, content) = parse_emphasis(contents, b'+')?;
-            let node = arena.append(Element::Strike, parent);
-            containers.push(Container::Inline { content, node });
-            Some(tail)
+
+        let offset = |tail: &str| tail.as_ptr() as usize - text.as_ptr() as usize;
+
+        let (tail, node) = match bytes[pos] {
+            b'@' if self.enabled(ObjectKind::Snippet) => {
+                self.snippet
+                    .find(pos + 2, |from| find_bytes(bytes, from, b"@@"))
+                    .filter(|&i| i + 2 <= end)?;
+                let (tail, snippet) = Snippet::parse(contents)?;
+                (offset(tail), arena.append(snippet, parent))
+            }
+            b'{' if self.enabled(ObjectKind::Macros) => {
+                let args = self
+                    .macros_args
+                    .find(pos + 3, |from| find_bytes(bytes, from, b")}}}"))
+                    .map(|i| i + 4);
+                let name = self
+                    .macros
+                    .find(pos + 3, |from| find_bytes(bytes, from, b"}}}"))
+                    .map(|i| i + 3);
+                let limit = args.into_iter().chain(name).filter(|&i| i <= end).max()?;
+                let (tail, macros) = Macros::parse(&text[pos..limit])?;
+                (offset(tail), arena.append(macros, parent))
+            }
+            b'<' => {
+                if let Some((tail, _content)) =
+                    parse_radio_target(contents).filter(|_| self.enabled(ObjectKind::RadioTarget))
+                {
+                    (offset(tail), arena.append(Element::RadioTarget, parent))
+                } else if let Some((tail, target)) =
+                    Target::parse(contents).filter(|_| self.enabled(ObjectKind::Target))
+                {
+                    (offset(tail), arena.append(target, parent))
+                } else if !self.enabled(ObjectKind::Timestamp) {
+                    return None;
+                } else if let Some((tail, timestamp)) = Timestamp::parse_active(contents) {
+                    (offset(tail), arena.append(timestamp, parent))
+                } else {
+                    if !contents.starts_with("<%%(") {
+                        return None;
+                    }
+                    self.diary
+                        .find(pos + 4, |from| {
+                            find_byte(bytes, from, |b| b == b')' || b == b'>' || b == b'\n')
+                        })
+                        .filter(|&i| i + 2 <= end && &bytes[i..i + 2] == b")>")?;
+                    let (tail, timestamp) = Timestamp::parse_diary(contents)?;
+                    (offset(tail), arena.append(timestamp, parent))
+                }
+            }
+            b'[' => {
+                if let Some((tail, fn_ref)) = self.parse_fn_ref(pos, end) {
+                    (offset(tail), arena.append(fn_ref, parent))
+                } else if let Some((tail, citation)) = self.parse_citation(pos, end) {
+                    (offset(tail), arena.append(citation, parent))
+                } else if let Some((tail, link)) = self.parse_link(pos, end) {
+                    (offset(tail), arena.append(link, parent))
+                } else if let Some((tail, cookie)) =
+                    Cookie::parse(contents).filter(|_| self.enabled(ObjectKind::Cookie))
+                {
+                    (offset(tail), arena.append(cookie, parent))
+                } else {
+                    if !self.enabled(ObjectKind::Timestamp) {
+                        return None;
+                    }
+                    let (tail, timestamp) = Timestamp::parse_inactive(contents)?;
+                    (offset(tail), arena.append(timestamp, parent))
+                }
+            }
+            b'*' => {
+                let close = self.parse_emphasis(pos, end)?;
+                let node = arena.append(Element::Bold, parent);
+                arena.set_span(node, &text[pos..close + 1]);
+                return Some((close + 1, Some((node, close))));
+            }
+            b'+' => {
+                let close = self.parse_emphasis(pos, end)?;
+                let node = arena.append(Element::Strike, parent);
+                arena.set_span(node, &text[pos..close + 1]);
+                return Some((close + 1, Some((node, close))));
+            }
+            b'/' => {
+                let close = self.parse_emphasis(pos, end)?;
+                let node = arena.append(Element::Italic, parent);
+                arena.set_span(node, &text[pos..close + 1]);
+                return Some((close + 1, Some((node, close))));
+            }
+            b'_' => {
+                let close = self.parse_emphasis(pos, end)?;
+                let node = arena.append(Element::Underline, parent);
+                arena.set_span(node, &text[pos..close + 1]);
+                return Some((close + 1, Some((node, close))));
+            }
+            b'=' => {
+                let close = self.parse_emphasis(pos, end)?;
+                let value = text[pos + 1..close].into();
+                (close + 1, arena.append(Element::Verbatim { value }, parent))
+            }
+            b'~' => {
+                let close = self.parse_emphasis(pos, end)?;
+                let value = text[pos + 1..close].into();
+                (close + 1, arena.append(Element::Code { value }, parent))
+            }
+            b's' if self.enabled(ObjectKind::InlineSrc) => {
+                let (tail, inline_src) = self.parse_inline_src(pos, end)?;
+                (offset(tail), arena.append(inline_src, parent))
+            }
+            b'c' if self.enabled(ObjectKind::InlineCall) => {
+                let (tail, inline_call) = self.parse_inline_call(pos, end)?;
+                (offset(tail), arena.append(inline_call, parent))
+            }
+            _ => return None,
+        };
+
+        arena.set_span(node, &text[pos..tail]);
+
+        Some((tail, None))
+    }
+
+    fn enabled(&self, kind: ObjectKind) -> bool {
+        !self.disabled.contains(&kind)
+    }
+
+    fn parse_emphasis(&mut self, pos: usize, end: usize) -> Option<usize> {
+        let marker = self.text.as_bytes()[pos];
+
+        if !self.markers.contains(&marker) {
+            return None;
         }
-        b'/' => {
-            let (tail, content) = parse_emphasis(contents, b'/')?;
-            let node = arena.append(Element::Italic, parent);
-            containers.push(Container::Inline { content, node });
-            Some(tail)
+        let closing = match marker {
+            b'*' => &mut self.emphasis[0],
+            b'+' => &mut self.emphasis[1],
+            b'/' => &mut self.emphasis[2],
+            b'_' => &mut self.emphasis[3],
+            b'=' => &mut self.emphasis[4],
+            _ => &mut self.emphasis[5],
+        };
+
+        parse_emphasis(self.text, pos, end, marker, closing, &self.newlines)
+    }
+
+    fn parse_fn_ref(&mut self, pos: usize, end: usize) -> Option<(&'a str, FnRef<'a>)> {
+        let text = self.text;
+
+        if !self.enabled(ObjectKind::FnRef) || !text[pos..end].starts_with("[fn:") {
+            return None;
         }
-        b'_' => {
-            let (tail, content) = parse_emphasis(contents, b'_')?;
-            let node = arena.append(Element::Underline, parent);
-            containers.push(Container::Inline { content, node });
-            Some(tail)
+
+        // footnote reference ends with the first `]` that closes its opening bracket
+        let brackets = self.brackets.get_or_insert_with(|| {
+            let bytes = text.as_bytes();
+            let mut depths = Vec::with_capacity(bytes.len() + 1);
+            let mut depth = 0isize;
+            for &b in bytes {
+                depths.push(depth);
+                match b {
+                    b'[' => depth += 1,
+                    b']' => depth -= 1,
+                    _ => (),
+                }
+            }
+
+            let mut closing = HashMap::new();
+            let mut brackets = vec![None; bytes.len()];
+            for i in (0..bytes.len()).rev() {
+                if bytes[i] == b']' {
+                    closing.insert(depths[i], i);
+                }
+                brackets[i] = closing.get(&depths[i]).cloned();
+            }
+            brackets
+        });
+
+        brackets
+            .get(pos + 4)
+            .cloned()
+            .flatten()
+            .filter(|&i| i < end)?;
+
+        FnRef::parse(&text[pos..end])
+    }
+
+    fn parse_citation(&mut self, pos: usize, end: usize) -> Option<(&'a str, Citation<'a>)> {
+        let text = self.text;
+        let bytes = text.as_bytes();
+
+        if self.syntax != Syntax::Org9
+            || !self.enabled(ObjectKind::Citation)
+            || !text[pos..end].starts_with("[cite")
+        {
+            return None;
         }
-        b'=' => {
-            let (tail, value) = parse_emphasis(contents, b'=')?;
-            let value = value.into();
-            arena.append(Element::Verbatim { value }, parent);
-            Some(tail)
+
+        self.citation
+            .find(pos + 5, |from| {
+                find_byte(bytes, from, |b| b == b'[' || b == b']')
+            })
+            .filter(|&i| i < end && bytes[i] == b']')?;
+
+        Citation::parse(&text[pos..end])
+    }
+
+    fn parse_link(&mut self, pos: usize, end: usize) -> Option<(&'a str, Link<'a>)> {
+        let text = self.text;
+        let bytes = text.as_bytes();
+
+        if !self.enabled(ObjectKind::Link) || !text[pos..end].starts_with("[[") {
+            return None;
         }
-        b'~' => {
-            let (tail, value) = parse_emphasis(contents, b'~')?;
-            let value = value.into();
-            arena.append(Element::Code { value }, parent);
-            Some(tail)
+
+        let path = self
+            .link_path
+            .find(pos + 2, |from| {
+                find_byte(bytes, from, |b| {
+                    b == b'<' || b == b'>' || b == b'\n' || b == b']'
+                })
+            })
+            .filter(|&i| i + 1 < end && bytes[i] == b']')?;
+
+        match bytes[path + 1] {
+            b']' => (),
+            b'[' => {
+                self.link_desc
+                    .find(path + 2, |from| {
+                        find_byte(bytes, from, |b| b == b'[' || b == b']')
+                    })
+                    .filter(|&i| i + 1 < end && &bytes[i..i + 2] == b"]]")?;
+            }
+            _ => return None,
         }
-        b's' => {
-            let (tail, inline_src) = InlineSrc::parse(contents)?;
-            arena.append(inline_src, parent);
-            Some(tail)
+
+        Link::parse(&text[pos..end])
+    }
+
+    fn parse_inline_src(&mut self, pos: usize, end: usize) -> Option<(&'a str, InlineSrc<'a>)> {
+        let text = self.text;
+        let bytes = text.as_bytes();
+
+        if !text[pos..end].starts_with("src_") {
+            return None;
         }
-        b'c' => {
-            let (tail, inline_call) = InlineCall::parse(contents)?;
-            arena.append(inline_call, parent);
-            Some(tail)
+
+        let lang = self
+            .src_lang
+            .find(pos + 4, |from| {
+                find_byte(bytes, from, |b| {
+                    b.is_ascii_whitespace() || b == b'[' || b == b'{'
+                })
+            })
+            .filter(|&i| i > pos + 4 && i < end)?;
+
+        let body = match bytes[lang] {
+            b'{' => lang,
+            b'[' => {
+                self.src_options
+                    .find(lang + 1, |from| {
+                        find_byte(bytes, from, |b| b == b'\n' || b == b']')
+                    })
+                    .filter(|&i| i + 1 < end && bytes[i] == b']' && bytes[i + 1] == b'{')?
+                    + 1
+            }
+            _ => return None,
+        };
+
+        self.src_body
+            .find(body + 1, |from| {
+                find_byte(bytes, from, |b| b == b'\n' || b == b'}')
+            })
+            .filter(|&i| i < end && bytes[i] == b'}')?;
+
+        InlineSrc::parse(&text[pos..end])
+    }
+
+    fn parse_inline_call(&mut self, pos: usize, end: usize) -> Option<(&'a str, InlineCall<'a>)> {
+        let text = self.text;
+        let bytes = text.as_bytes();
+
+        if !text[pos..end].starts_with("call_") {
+            return None;
         }
-        _ => None,
+
+        let name = self
+            .call_name
+            .find(pos + 5, |from| {
+                find_byte(bytes, from, |b| {
+                    b == b'[' || b == b'\n' || b == b'(' || b == b')'
+                })
+            })
+            .filter(|&i| i < end)?;
+
+        let args = match bytes[name] {
+            b'(' => name,
+            b'[' => {
+                self.call_header
+                    .find(name + 1, |from| {
+                        find_byte(bytes, from, |b| b == b'\n' || b == b']')
+                    })
+                    .filter(|&i| i + 1 < end && bytes[i] == b']' && bytes[i + 1] == b'(')?
+                    + 1
+            }
+            _ => return None,
+        };
+
+        let close = self
+            .call_args
+            .find(args + 1, |from| {
+                find_byte(bytes, from, |b| b == b'\n' || b == b')')
+            })
+            .filter(|&i| i < end && bytes[i] == b')')?;
+
+        // stops before the end header if it's not closed
+        let limit = if bytes.get(close + 1) == Some(&b'[') {
+            self.call_end_header
+                .find(close + 2, |from| {
+                    find_byte(bytes, from, |b| b == b'\n' || b == b']')
+                })
+                .filter(|&i| i < end && bytes[i] == b']')
+                .map(|i| i + 1)
+                .unwrap_or(close + 1)
+        } else {
+            close + 1
+        };
+
+        InlineCall::parse(&text[pos..limit])
     }
 }
 
@@ -663,7 +1243,7 @@ pub fn parse_list<'a, T: ElementArena<'a>>(
     contents: &'a str,
     parent: NodeId,
     containers: &mut Vec<Container<'a>>,
-) -> Option<&'a str> {
+) -> Option<(&'a str, NodeId)> {
     let (mut tail, (first_item, content)) = ListItem::parse(contents)?;
     let first_item_indent = first_item.indent;
     let first_item_ordered = first_item.ordered;
@@ -671,11 +1251,13 @@ pub fn parse_list<'a, T: ElementArena<'a>>(
     let parent = arena.append(Element::Document { pre_blank: 0 }, parent); // placeholder
 
     let node = arena.append(first_item, parent);
+    arena.set_span(node, consumed(contents, tail));
     containers.push(Container::Block { content, node });
 
     while let Some((tail_, (item, content))) = ListItem::parse(tail) {
         if item.indent == first_item_indent {
             let node = arena.append(item, parent);
+            arena.set_span(node, consumed(tail, tail_));
             containers.push(Container::Block { content, node });
             debug_assert_ne!(tail, tail_);
             tail = tail_;
@@ -695,7 +1277,7 @@ pub fn parse_list<'a, T: ElementArena<'a>>(
         },
     );
 
-    Some(tail)
+    Some((tail, parent))
 }
 
 pub fn parse_org_table<'a, T: ElementArena<'a>>(
@@ -703,7 +1285,7 @@ pub fn parse_org_table<'a, T: ElementArena<'a>>(
     contents: &'a str,
     containers: &mut Vec<Container<'a>>,
     parent: NodeId,
-) -> &'a str {
+) -> (&'a str, NodeId) {
     let (tail, contents) = take_lines_while(|line| line.trim_start().starts_with('|'))(contents);
     let (tail, blank) = blank_lines(tail);
 
@@ -732,7 +1314,7 @@ pub fn parse_org_table<'a, T: ElementArena<'a>>(
         lines.push(line);
     }
 
-    let parent = arena.append(
+    let table = arena.append(
         Table::Org {
             tblfm: None,
             post_blank: blank,
@@ -744,25 +1326,31 @@ pub fn parse_org_table<'a, T: ElementArena<'a>>(
     for line in lines {
         if line.starts_with("|-") {
             if has_header {
-                arena.append(Element::TableRow(TableRow::HeaderRule), parent);
+                let node = arena.append(Element::TableRow(TableRow::HeaderRule), table);
+                arena.set_span(node, line);
                 has_header = false;
             } else {
-                arena.append(Element::TableRow(TableRow::BodyRule), parent);
+                let node = arena.append(Element::TableRow(TableRow::BodyRule), table);
+                arena.set_span(node, line);
             }
         } else {
             if has_header {
-                let parent = arena.append(Element::TableRow(TableRow::Header), parent);
+                let parent = arena.append(Element::TableRow(TableRow::Header), table);
+                arena.set_span(parent, line);
                 for content in line.split_terminator('|').skip(1) {
                     let node = arena.append(Element::TableCell(TableCell::Header), parent);
+                    arena.set_span(node, content);
                     containers.push(Container::Inline {
                         content: content.trim(),
                         node,
                     });
                 }
             } else {
-                let parent = arena.append(Element::TableRow(TableRow::Body), parent);
+                let parent = arena.append(Element::TableRow(TableRow::Body), table);
+                arena.set_span(parent, line);
                 for content in line.split_terminator('|').skip(1) {
                     let node = arena.append(Element::TableCell(TableCell::Body), parent);
+                    arena.set_span(node, content);
                     containers.push(Container::Inline {
                         content: content.trim(),
                         node,
@@ -772,7 +1360,7 @@ pub fn parse_org_table<'a, T: ElementArena<'a>>(
         }
     }
 
-    tail
+    (tail, table)
 }
 
 pub fn line<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&str, &str, E> {
@@ -818,6 +1406,12 @@ pub fn skip_empty_lines(input: &str) -> &str {
     take_lines_while(|line| line.as_bytes().iter().all(|c| c.is_ascii_whitespace()))(input).0
 }
 
+/// Returns the part of `input` before `tail`, which must be a suffix of `input`.
+pub fn consumed<'a>(input: &'a str, tail: &str) -> &'a str {
+    debug_assert!(input.ends_with(tail));
+    &input[0..input.len() - tail.len()]
+}
+
 pub fn parse_headline(input: &str) -> Option<(&str, (&str, usize))> {
     let (input_, level) = parse_headline_level(input)?;
     let (input_, content) = take_lines_while(move |line| {
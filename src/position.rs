@@ -0,0 +1,146 @@
+use core::iter::once;
+use core::ops::Range;
+use indextree::NodeId;
+use memchr::memchr_iter;
+
+use crate::Org;
+
+impl Org<'_> {
+    /// Returns the offset of `raw` in the source text, or `None` if `raw`
+    /// isn't a slice of it.
+    pub(crate) fn offset_of(&self, raw: &str) -> Option<usize> {
+        let base = self.text.as_ptr() as usize;
+        let start = raw.as_ptr() as usize;
+
+        if start >= base && start + raw.len() <= base + self.text.len() {
+            Some(start - base)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the byte range of `node` in the source text, including its
+    /// trailing blank lines.
+    ///
+    /// Returns `None` if this element wasn't parsed from the source text, e.g.
+    /// it was created or replaced using the mutable API.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let text = "* title\n#+BEGIN_QUOTE\n*quote*\n#+END_QUOTE\n\n";
+    /// let org = Org::parse(text);
+    ///
+    /// let h1 = org.headlines().next().unwrap();
+    /// let quote = h1.section_node().and_then(|n| org.arena()[n].first_child()).unwrap();
+    ///
+    /// assert_eq!(
+    ///     &text[org.span(quote).unwrap()],
+    ///     "#+BEGIN_QUOTE\n*quote*\n#+END_QUOTE\n\n"
+    /// );
+    /// ```
+    pub fn span(&self, node: NodeId) -> Option<Range<usize>> {
+        self.spans.get(&node).cloned()
+    }
+
+    /// Returns the byte range of the contents of `node` in the source text.
+    ///
+    /// Returns `None` if this element isn't a container, or it wasn't parsed
+    /// from the source text.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let text = "* title\n#+BEGIN_QUOTE\n*quote*\n#+END_QUOTE\n\n";
+    /// let org = Org::parse(text);
+    ///
+    /// let h1 = org.headlines().next().unwrap();
+    /// let quote = h1.section_node().and_then(|n| org.arena()[n].first_child()).unwrap();
+    ///
+    /// assert_eq!(&text[org.contents_span(quote).unwrap()], "*quote*\n");
+    /// assert_eq!(&text[org.contents_span(h1.title_node()).unwrap()], "title");
+    /// ```
+    pub fn contents_span(&self, node: NodeId) -> Option<Range<usize>> {
+        self.contents_spans.get(&node).cloned()
+    }
+
+    /// Returns the byte range between the first line and the `#+END:` line
+    /// of a dynamic block.
+    pub(crate) fn dyn_block_contents(&self, node: NodeId) -> Option<Range<usize>> {
+        let span = self.span(node)?;
+        let text = &self.text[span.clone()];
+        let start = text.find('\n')? + 1;
+        let mut offset = start;
+        for line in text[start..].split_inclusive('\n') {
+            if line.trim().eq_ignore_ascii_case("#+END:") {
+                return Some(span.start + start..span.start + offset);
+            }
+            offset += line.len();
+        }
+        None
+    }
+
+    pub(crate) fn line_starts(&self) -> &[usize] {
+        self.line_starts.get_or_init(|| {
+            once(0)
+                .chain(memchr_iter(b'\n', self.text.as_bytes()).map(|i| i + 1))
+                .collect()
+        })
+    }
+
+    /// Converts a byte offset in the source text into a `(line, column)` pair.
+    ///
+    /// Both line and column are zero-based, and column is counted in characters.
+    ///
+    /// Returns `None` if the offset is out of bounds or not on a character boundary.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse("* 标题\nsection");
+    ///
+    /// assert_eq!(org.position(0), Some((0, 0)));
+    /// assert_eq!(org.position(5), Some((0, 3)));
+    /// assert_eq!(org.position(9), Some((1, 0)));
+    /// assert_eq!(org.position(16), Some((1, 7)));
+    /// assert_eq!(org.position(3), None);
+    /// assert_eq!(org.position(17), None);
+    /// ```
+    pub fn position(&self, offset: usize) -> Option<(usize, usize)> {
+        if !self.text.is_char_boundary(offset) {
+            return None;
+        }
+
+        let line_starts = self.line_starts();
+        let line = match line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(line) => line - 1,
+        };
+        let column = self.text[line_starts[line]..offset].chars().count();
+
+        Some((line, column))
+    }
+
+    /// Returns the positions of the beginning and the end of `node` in the
+    /// source text, as returned by [`Org::position`].
+    ///
+    /// Returns `None` if this element wasn't parsed from the source text, e.g.
+    /// it was created or replaced using the mutable API.
+    ///
+    /// [`Org::position`]: #method.position
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse("* title\n** sub title\n#+TITLE: org\n");
+    ///
+    /// let sub = org.headlines().nth(1).unwrap();
+    ///
+    /// assert_eq!(org.range(sub.headline_node()), Some(((1, 0), (3, 0))));
+    /// ```
+    pub fn range(&self, node: NodeId) -> Option<((usize, usize), (usize, usize))> {
+        let span = self.spans.get(&node)?;
+
+        Some((self.position(span.start)?, self.position(span.end)?))
+    }
+}
@@ -0,0 +1,98 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use indextree::NodeId;
+
+use crate::elements::Element;
+use crate::{Headline, Org};
+
+impl Org<'_> {
+    /// Returns the value of property `name` of `node` with inheritance, like
+    /// `org-entry-get` with inheritance enabled.
+    ///
+    /// Property drawers of the headline containing `node` and its ancestors
+    /// are searched from the innermost one, and then `#+PROPERTY` keywords
+    /// of the document. Values of `NAME+` properties are appended to the
+    /// inherited value with a space, e.g. `:VAR+: y` after `:VAR: x` results
+    /// in `x y`. Property names are case-insensitive.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse(
+    ///     "#+PROPERTY: VAR x\n#+PROPERTY: VAR+ y\n\
+    ///      * project\n:PROPERTIES:\n:var+: z\n:END:\n\
+    ///      ** task\n:PROPERTIES:\n:OWNER: me\n:END:\ntext\n\
+    ///      * home\n:PROPERTIES:\n:VAR: a\n:VAR+: b\n:END:\n",
+    /// );
+    /// let headlines: Vec<_> = org.headlines().collect();
+    /// let task = headlines[1].section_node().unwrap();
+    ///
+    /// assert_eq!(org.inherited_property(task, "VAR").as_deref(), Some("x y z"));
+    /// assert_eq!(org.inherited_property(task, "owner").as_deref(), Some("me"));
+    /// assert_eq!(org.inherited_property(headlines[2].headline_node(), "VAR").as_deref(), Some("a b"));
+    /// assert_eq!(org.inherited_property(headlines[0].headline_node(), "OWNER"), None);
+    /// ```
+    pub fn inherited_property(&self, node: NodeId, name: &str) -> Option<String> {
+        let is_name = |key: &str| key.eq_ignore_ascii_case(name);
+        let is_accumulated = |key: &str| key.strip_suffix('+').is_some_and(is_name);
+
+        // values from the innermost headline, in reverse order
+        let mut values = Vec::new();
+        let headlines = node
+            .ancestors(&self.arena)
+            .filter_map(|node| match self[node] {
+                Element::Headline { level } => Some(Headline::from_node(node, level, self)),
+                _ => None,
+            });
+        for headline in headlines {
+            let properties = &headline.title(self).properties;
+            if let Some((_, value)) = properties.iter().find(|(key, _)| is_accumulated(key)) {
+                values.push(&**value);
+            }
+            if let Some((_, value)) = properties.iter().find(|(key, _)| is_name(key)) {
+                values.push(&**value);
+                return Some(join(values.into_iter().rev()));
+            }
+        }
+
+        let mut document = None;
+        for keyword in self.keywords() {
+            if !keyword.key.eq_ignore_ascii_case("PROPERTY") {
+                continue;
+            }
+            let (key, value) = keyword
+                .value
+                .split_once(char::is_whitespace)
+                .unwrap_or((&keyword.value, ""));
+            if is_name(key) {
+                document = Some(vec![value]);
+            } else if is_accumulated(key) {
+                document.get_or_insert_with(Vec::new).push(value);
+            }
+        }
+
+        if document.is_none() && values.is_empty() {
+            return None;
+        }
+        Some(join(
+            document
+                .into_iter()
+                .flatten()
+                .chain(values.into_iter().rev()),
+        ))
+    }
+}
+
+/// Joins non-empty values with spaces.
+fn join<'a>(values: impl Iterator<Item = &'a str>) -> String {
+    let mut s = String::new();
+    for value in values.map(str::trim).filter(|value| !value.is_empty()) {
+        if !s.is_empty() {
+            s.push(' ');
+        }
+        s.push_str(value);
+    }
+    s
+}
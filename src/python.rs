@@ -0,0 +1,110 @@
+//! Python bindings for querying org documents from Python.
+//!
+//! Build a `cdylib` crate depending on orgize with the `pyo3` feature, e.g.
+//! using [maturin], then:
+//!
+//! ```python
+//! import orgize
+//!
+//! org = orgize.parse("* TODO title :tag:\nsection")
+//! for headline in org.headlines():
+//!     print(headline.level, headline.keyword, headline.title, headline.tags)
+//! print(org.to_html())
+//! ```
+//!
+//! [maturin]: https://github.com/PyO3/maturin
+
+use pyo3::prelude::*;
+use std::collections::HashMap;
+
+use crate::{config::DEFAULT_CONFIG, Org};
+
+/// Parsed org document
+#[pyclass(name = "Org", module = "orgize")]
+pub struct PyOrg {
+    org: Org<'static>,
+}
+
+/// Snapshot of a headline
+#[pyclass(name = "Headline", module = "orgize", get_all)]
+#[derive(Clone)]
+pub struct PyHeadline {
+    pub level: usize,
+    pub title: String,
+    pub keyword: Option<String>,
+    pub priority: Option<char>,
+    pub tags: Vec<String>,
+    pub properties: HashMap<String, String>,
+    /// Zero-based line of the headline, `None` if it has no position
+    pub line: Option<usize>,
+}
+
+#[pymethods]
+impl PyOrg {
+    /// Returns all headlines in document order.
+    fn headlines(&self) -> Vec<PyHeadline> {
+        self.org
+            .headlines()
+            .map(|headline| {
+                let title = headline.title(&self.org);
+                PyHeadline {
+                    level: headline.level(),
+                    title: title.raw.to_string(),
+                    keyword: title.keyword.as_ref().map(|k| k.to_string()),
+                    priority: title.priority,
+                    tags: title.tags.iter().map(|t| t.to_string()).collect(),
+                    properties: title
+                        .properties
+                        .iter()
+                        .map(|(k, v)| (k.to_string(), v.to_string()))
+                        .collect(),
+                    line: self
+                        .org
+                        .range(headline.headline_node())
+                        .map(|((line, _), _)| line),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns values of all keywords named `key`, case-insensitively.
+    fn keywords(&self, key: &str) -> Vec<String> {
+        self.org
+            .keywords()
+            .filter(|keyword| keyword.key.eq_ignore_ascii_case(key))
+            .map(|keyword| keyword.value.to_string())
+            .collect()
+    }
+
+    /// Exports this document to HTML.
+    fn to_html(&self) -> String {
+        let mut writer = Vec::new();
+        self.org.write_html(&mut writer).unwrap();
+        String::from_utf8(writer).unwrap()
+    }
+}
+
+/// Parses `text` into an `Org` object.
+#[pyfunction]
+fn parse(text: String) -> PyOrg {
+    let mut org = Org::new();
+    org.reparse(text, &DEFAULT_CONFIG);
+    PyOrg { org }
+}
+
+/// Parses `text` and exports it to HTML.
+#[pyfunction]
+fn to_html(text: &str) -> String {
+    let mut writer = Vec::new();
+    Org::parse(text).write_html(&mut writer).unwrap();
+    String::from_utf8(writer).unwrap()
+}
+
+#[pymodule]
+fn orgize(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyOrg>()?;
+    m.add_class::<PyHeadline>()?;
+    m.add_function(wrap_pyfunction!(parse, m)?)?;
+    m.add_function(wrap_pyfunction!(to_html, m)?)?;
+    Ok(())
+}
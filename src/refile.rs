@@ -0,0 +1,127 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{Headline, Org};
+
+/// Configuration of refile targets, like `org-refile-targets`
+#[derive(Clone, Debug, Default)]
+pub struct RefileConfig {
+    /// Maximum level of target headlines, like `:maxlevel`
+    pub max_level: Option<usize>,
+    /// Only headlines with one of these tags are targets if it's not empty,
+    /// like `:tag`. As in org-mode, only tags written on the headline itself
+    /// are matched, not inherited ones or `#+FILETAGS`.
+    pub tags: Vec<String>,
+    /// Only headlines with one of these todo keywords are targets if it's
+    /// not empty, like `:todo`
+    pub todo_keywords: Vec<String>,
+    /// Whether documents themselves are targets, like `org-refile-use-outline-path`
+    /// set to `file`
+    pub include_documents: bool,
+}
+
+/// Refile target, i.e. a headline or a whole document
+#[derive(Debug)]
+pub struct RefileTarget<'b> {
+    /// Index of the document in the given slice
+    pub file: usize,
+    /// Target headline, or `None` if the target is the document itself
+    pub headline: Option<Headline>,
+    /// Raw titles of the headline's ancestors and itself
+    pub path: Vec<&'b str>,
+}
+
+impl RefileTarget<'_> {
+    /// Returns the outline path joined with `/`, like the completion list of
+    /// `org-refile`. Slashes inside titles are escaped as `\/`.
+    pub fn outline_path(&self) -> String {
+        let mut path = String::new();
+        for (i, title) in self.path.iter().enumerate() {
+            if i > 0 {
+                path.push('/');
+            }
+            path.push_str(&title.replace('/', "\\/"));
+        }
+        path
+    }
+}
+
+/// Lists candidate headlines for refiling from `orgs`, in document order.
+///
+/// ```rust
+/// # use orgize::{refile_targets, Org, RefileConfig};
+/// #
+/// let work = Org::parse("* projects\n** website\n*** design\n* notes :ref:\n");
+/// let home = Org::parse("* garden\n");
+///
+/// let config = RefileConfig {
+///     max_level: Some(2),
+///     ..Default::default()
+/// };
+/// let targets: Vec<_> = refile_targets(&[&work, &home], &config)
+///     .iter()
+///     .map(|target| (target.file, target.outline_path()))
+///     .collect();
+///
+/// assert_eq!(
+///     targets,
+///     vec![
+///         (0, "projects".to_string()),
+///         (0, "projects/website".to_string()),
+///         (0, "notes".to_string()),
+///         (1, "garden".to_string()),
+///     ]
+/// );
+/// ```
+pub fn refile_targets<'b>(orgs: &[&'b Org], config: &RefileConfig) -> Vec<RefileTarget<'b>> {
+    let mut targets = Vec::new();
+
+    for (file, org) in orgs.iter().enumerate() {
+        if config.include_documents {
+            targets.push(RefileTarget {
+                file,
+                headline: None,
+                path: Vec::new(),
+            });
+        }
+
+        for headline in org.headlines() {
+            let title = headline.title(org);
+            if config.max_level.is_some_and(|max| title.level > max) {
+                continue;
+            }
+            if !config.tags.is_empty()
+                && !title
+                    .tags
+                    .iter()
+                    .any(|tag| config.tags.iter().any(|t| t == tag))
+            {
+                continue;
+            }
+            if !config.todo_keywords.is_empty()
+                && !title
+                    .keyword
+                    .as_ref()
+                    .is_some_and(|keyword| config.todo_keywords.iter().any(|k| k == keyword))
+            {
+                continue;
+            }
+
+            let mut path = Vec::new();
+            let mut ancestor = Some(headline);
+            while let Some(hdl) = ancestor {
+                path.push(&*hdl.title(org).raw);
+                ancestor = hdl.parent(org);
+            }
+            path.reverse();
+
+            targets.push(RefileTarget {
+                file,
+                headline: Some(headline),
+                path,
+            });
+        }
+    }
+
+    targets
+}
@@ -0,0 +1,71 @@
+use regex::Regex;
+
+use crate::elements::Element;
+use crate::Org;
+
+impl Org<'_> {
+    /// Replaces all matches of `pattern` in text objects with `replacement`,
+    /// and reparses the document. Returns the number of replaced matches.
+    ///
+    /// Only plain texts are searched, so markup, links, code, timestamps and
+    /// blocks are never touched, and a match never spans several objects.
+    /// `replacement` may refer to capture groups like `${1}`, see
+    /// [`Regex::replace_all`](regex/struct.Regex.html#method.replace_all).
+    /// Pending contents of a lazily parsed document are expanded first.
+    ///
+    /// ```rust
+    /// # use orgize::regex::Regex;
+    /// # use orgize::Org;
+    /// #
+    /// let mut org = Org::parse("* colour\nThe colour of [[https://colour.org][colour]] is ~colour~.\n");
+    ///
+    /// let count = org.replace_text(&Regex::new("colou?r").unwrap(), "color");
+    ///
+    /// assert_eq!(count, 2);
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "* color\nThe color of [[https://colour.org][colour]] is ~colour~.\n"
+    /// );
+    /// ```
+    pub fn replace_text(&mut self, pattern: &Regex, replacement: &str) -> usize {
+        self.expand_all();
+
+        let mut count = 0;
+        let mut edits = Vec::new();
+
+        for node in self.root.descendants(&self.arena) {
+            let value = match &self[node] {
+                Element::Text { value } => value,
+                _ => continue,
+            };
+            // texts which don't come from the source verbatim are skipped
+            let span = match self.span(node) {
+                Some(span) if self.text[span.clone()] == **value => span,
+                _ => continue,
+            };
+
+            for captures in pattern.captures_iter(value) {
+                let matched = captures.get(0).unwrap();
+                if matched.as_str().is_empty() {
+                    continue;
+                }
+                let mut text = String::new();
+                captures.expand(replacement, &mut text);
+                count += 1;
+                if text != matched.as_str() {
+                    edits.push((
+                        span.start + matched.start()..span.start + matched.end(),
+                        text,
+                    ));
+                }
+            }
+        }
+
+        edits.sort_by_key(|(range, _)| range.start);
+        self.apply_edits(edits);
+
+        count
+    }
+}
@@ -0,0 +1,264 @@
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use indextree::NodeId;
+
+use crate::elements::Element;
+use crate::{Headline, Org};
+
+/// Weight of words in titles, relative to words in sections
+const TITLE_WEIGHT: usize = 3;
+
+/// Inverted index of words in headlines across documents, for full-text
+/// search
+///
+/// Each headline is indexed with the text of its title and its own section,
+/// excluding sub-headlines. Text before the first headline is indexed as
+/// well, with no headline.
+///
+/// ```rust
+/// # use orgize::{Org, SearchIndex};
+/// #
+/// let rust = Org::parse("* Ownership\nEach value has an owner.\n* Lifetimes\nOwners of references.\n");
+/// let notes = Org::parse("* Inbox\nRead about rust ownership.\n");
+///
+/// let mut index = SearchIndex::default();
+/// index.add("rust.org", &rust).add("notes.org", &notes);
+///
+/// let hits = index.query("owner");
+/// let titles: Vec<_> = hits
+///     .iter()
+///     .map(|hit| (hit.file, &*hit.headline.unwrap().title(&rust).raw))
+///     .take(2)
+///     .collect();
+/// assert_eq!(titles, vec![("rust.org", "Ownership"), ("rust.org", "Lifetimes")]);
+/// assert_eq!(hits[2].file, "notes.org");
+/// assert_eq!(hits[0].spans, vec![2..11, 30..35]);
+///
+/// assert_eq!(index.query("rust owner").len(), 1);
+/// assert!(index.query("borrow").is_empty());
+/// ```
+#[derive(Default)]
+pub struct SearchIndex {
+    files: Vec<Option<String>>,
+    terms: BTreeMap<String, Vec<Posting>>,
+}
+
+#[derive(Clone)]
+struct Posting {
+    file: usize,
+    headline: Option<Headline>,
+    span: Option<Range<usize>>,
+    title: bool,
+}
+
+/// Headline matching a search query
+#[derive(Debug, Clone)]
+pub struct SearchHit<'i> {
+    /// Name of the file containing the headline
+    pub file: &'i str,
+    /// Matching headline, or `None` for text before the first headline
+    pub headline: Option<Headline>,
+    /// Relevance of this hit, higher is better
+    pub score: usize,
+    /// Byte ranges of matched words in the source text, in document order
+    pub spans: Vec<Range<usize>>,
+}
+
+impl SearchIndex {
+    /// Adds a document with its file name to this index, replacing the
+    /// document previously added with the same name if any.
+    pub fn add(&mut self, file: &str, org: &Org) -> &mut Self {
+        let index = match self.position(file) {
+            Some(index) => {
+                self.remove_postings(index);
+                index
+            }
+            None => {
+                self.files.push(Some(file.to_string()));
+                self.files.len() - 1
+            }
+        };
+
+        let document = org.document();
+        self.add_section(org, index, None, document.section_node());
+        for headline in org.headlines() {
+            self.add_text(org, index, Some(headline), headline.title_node(), true);
+            self.add_section(org, index, Some(headline), headline.section_node());
+        }
+
+        self
+    }
+
+    /// Removes the document with this file name from this index. Returns
+    /// `false` if it wasn't added.
+    pub fn remove(&mut self, file: &str) -> bool {
+        match self.position(file) {
+            Some(index) => {
+                self.remove_postings(index);
+                self.files[index] = None;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns headlines containing every word of `query`, ranked by
+    /// relevance.
+    ///
+    /// Words are matched case-insensitively by prefix, so `own` matches
+    /// `Ownership`. Words in titles weigh more than words in sections, and
+    /// hits with the same score are kept in file and document order.
+    pub fn query(&self, query: &str) -> Vec<SearchHit<'_>> {
+        let words: Vec<String> = words(query).map(|(_, word)| word.to_lowercase()).collect();
+        if words.is_empty() {
+            return Vec::new();
+        }
+
+        // (file, headline) => (headline, score per word, spans)
+        let mut matches = BTreeMap::new();
+        for (i, word) in words.iter().enumerate() {
+            let postings = self
+                .terms
+                .range::<str, _>((
+                    core::ops::Bound::Included(word.as_str()),
+                    core::ops::Bound::Unbounded,
+                ))
+                .take_while(|(term, _)| term.starts_with(word.as_str()))
+                .flat_map(|(_, postings)| postings);
+
+            for posting in postings {
+                let key = (posting.file, posting.headline.map(|h| h.headline_node()));
+                let (_, scores, spans) = matches
+                    .entry(key)
+                    .or_insert_with(|| (posting.headline, vec![0; words.len()], Vec::new()));
+                scores[i] += if posting.title { TITLE_WEIGHT } else { 1 };
+                if let Some(span) = &posting.span {
+                    spans.push(span.clone());
+                }
+            }
+        }
+
+        let mut hits: Vec<_> = matches
+            .into_iter()
+            .filter(|(_, (_, scores, _))| scores.iter().all(|&score| score > 0))
+            .filter_map(|((file, _), (headline, scores, mut spans))| {
+                spans.sort_by_key(|span| (span.start, span.end));
+                spans.dedup();
+                Some(SearchHit {
+                    file: self.files[file].as_deref()?,
+                    headline,
+                    score: scores.iter().sum(),
+                    spans,
+                })
+            })
+            .collect();
+
+        let position = |hit: &SearchHit| {
+            let file = self.position(hit.file);
+            let start = hit.spans.first().map(|span| span.start);
+            (file, hit.headline.is_some(), start)
+        };
+        hits.sort_by(|a, b| b.score.cmp(&a.score).then(position(a).cmp(&position(b))));
+        hits
+    }
+
+    /// Returns the number of indexed words.
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Returns `true` if no words are indexed.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    fn position(&self, file: &str) -> Option<usize> {
+        self.files.iter().position(|f| f.as_deref() == Some(file))
+    }
+
+    fn remove_postings(&mut self, file: usize) {
+        self.terms.retain(|_, postings| {
+            postings.retain(|posting| posting.file != file);
+            !postings.is_empty()
+        });
+    }
+
+    fn add_section(
+        &mut self,
+        org: &Org,
+        file: usize,
+        headline: Option<Headline>,
+        section: Option<NodeId>,
+    ) {
+        if let Some(section) = section {
+            self.add_text(org, file, headline, section, false);
+        }
+    }
+
+    fn add_text(
+        &mut self,
+        org: &Org,
+        file: usize,
+        headline: Option<Headline>,
+        node: NodeId,
+        title: bool,
+    ) {
+        for node in node.descendants(&org.arena) {
+            let value = match &org[node] {
+                Element::Text { value } | Element::Verbatim { value } | Element::Code { value } => {
+                    &**value
+                }
+                Element::Link(link) => link.desc.as_deref().unwrap_or(&link.path),
+                _ => continue,
+            };
+
+            // offset of this value in the source text, since spans of
+            // objects like code and links include their markers
+            let offset = org.span(node).and_then(|span| {
+                org.text[span.clone()]
+                    .find(value)
+                    .map(|offset| span.start + offset)
+            });
+
+            for (start, word) in words(value) {
+                let span = offset.map(|offset| offset + start..offset + start + word.len());
+                self.terms
+                    .entry(word.to_lowercase())
+                    .or_default()
+                    .push(Posting {
+                        file,
+                        headline,
+                        span,
+                        title,
+                    });
+            }
+        }
+    }
+}
+
+/// Splits `text` into words of alphanumeric characters, with their byte
+/// offsets.
+fn words(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(move |word| (word.as_ptr() as usize - text.as_ptr() as usize, word))
+}
+
+#[test]
+fn split_words() {
+    let words: Vec<_> = words("Hello, Wörld! foo_bar 42").collect();
+    assert_eq!(
+        words,
+        vec![
+            (0, "Hello"),
+            (7, "Wörld"),
+            (15, "foo"),
+            (19, "bar"),
+            (23, "42")
+        ]
+    );
+}
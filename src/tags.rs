@@ -0,0 +1,102 @@
+use alloc::vec::Vec;
+
+use indextree::NodeId;
+
+use crate::elements::Element;
+use crate::{Headline, Org};
+
+impl Org<'_> {
+    /// Returns tags of the whole document from `#+FILETAGS` keywords, e.g.
+    /// `#+FILETAGS: :work:project:`, deduplicated in order.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse("#+FILETAGS: :work:project:\n#+FILETAGS: home work\n");
+    ///
+    /// assert_eq!(org.file_tags(), vec!["work", "project", "home"]);
+    /// ```
+    pub fn file_tags(&self) -> Vec<&str> {
+        let mut tags = Vec::new();
+        for tag in self
+            .keyword_values("FILETAGS")
+            .into_iter()
+            .flat_map(|value| value.split(|c: char| c == ':' || c.is_whitespace()))
+            .filter(|tag| !tag.is_empty())
+        {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        tags
+    }
+
+    /// Returns tags of `node` with inheritance, like `org-get-tags`: tags of
+    /// the document from `#+FILETAGS`, followed by tags of the headline
+    /// containing `node` and its ancestors from the outermost one,
+    /// deduplicated in order.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse("#+FILETAGS: :work:\n* project :code:\n** task :urgent:work:\ntext\n");
+    /// let task = org.headlines().nth(1).unwrap();
+    ///
+    /// assert_eq!(
+    ///     org.inherited_tags(task.section_node().unwrap()),
+    ///     vec!["work", "code", "urgent"]
+    /// );
+    /// ```
+    pub fn inherited_tags(&self, node: NodeId) -> Vec<&str> {
+        let mut headlines: Vec<_> = node
+            .ancestors(&self.arena)
+            .filter_map(|node| match self[node] {
+                Element::Headline { level } => Some(Headline::from_node(node, level, self)),
+                _ => None,
+            })
+            .collect();
+        headlines.reverse();
+
+        let mut tags = self.file_tags();
+        for tag in headlines
+            .iter()
+            .flat_map(|headline| headline.title(self).tags.iter().map(|tag| &**tag))
+        {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        tags
+    }
+
+    /// Returns headlines tagged with `tag`, including inherited tags and
+    /// tags of the document from `#+FILETAGS`.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse("* project :code:\n** task\n* home\n");
+    /// let titles: Vec<_> = org
+    ///     .headlines_with_tag("code")
+    ///     .map(|headline| headline.title(&org).raw.to_string())
+    ///     .collect();
+    ///
+    /// assert_eq!(titles, vec!["project", "task"]);
+    /// ```
+    pub fn headlines_with_tag<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = Headline> + 'a {
+        let file_tagged = self.file_tags().contains(&tag);
+        // levels of the current headline's ancestors tagged with `tag`
+        let mut tagged: Vec<usize> = Vec::new();
+
+        self.headlines().filter(move |headline| {
+            let level = headline.level();
+            while tagged.last().is_some_and(|last| *last >= level) {
+                tagged.pop();
+            }
+            if headline.title(self).tags.iter().any(|t| t == tag) {
+                tagged.push(level);
+            }
+            file_tagged || !tagged.is_empty()
+        })
+    }
+}
@@ -0,0 +1,338 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use indextree::NodeId;
+
+use crate::elements::{block::escape, Element};
+use crate::{HeaderArgs, Headline, Org};
+
+/// File extracted from source blocks by tangling
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TangledFile {
+    /// Target path, which is relative to the directory of the org file
+    /// unless it's absolute or starts with `~`
+    pub path: String,
+    pub contents: String,
+}
+
+impl Org<'_> {
+    /// Extracts source blocks into files, like `org-babel-tangle`. `file` is
+    /// the path of this document, used for `:tangle yes` and comment links.
+    ///
+    /// Blocks are grouped by their `:tangle` header argument in document
+    /// order. Noweb references are expanded if `:noweb` is `yes`, `tangle`,
+    /// `no-export` or `strip-export`, and link comments are added if
+    /// `:comments` is `link`, `yes`, `both` or `noweb`. `:shebang` and
+    /// `:padline` are also supported. Blocks in commented subtrees are
+    /// skipped.
+    ///
+    /// ```rust
+    /// # use orgize::{Org, TangledFile};
+    /// #
+    /// let org = Org::parse(
+    ///     "#+PROPERTY: header-args:sh :tangle setup.sh\n\
+    ///      * install\n\
+    ///      #+BEGIN_SRC sh :shebang #!/bin/sh\napt install git\n#+END_SRC\n\
+    ///      * configure\n\
+    ///      #+BEGIN_SRC sh :comments link\ngit config --global pull.rebase true\n#+END_SRC\n\
+    ///      #+BEGIN_SRC emacs-lisp :tangle yes\n(setq x 1)\n#+END_SRC\n",
+    /// );
+    ///
+    /// assert_eq!(
+    ///     org.tangle("dotfiles/README.org"),
+    ///     vec![
+    ///         TangledFile {
+    ///             path: "dotfiles/setup.sh".into(),
+    ///             contents: "#!/bin/sh\napt install git\n\n\
+    ///                 ## [[file:README.org::*configure][configure:1]]\n\
+    ///                 git config --global pull.rebase true\n# configure:1 ends here\n"
+    ///                 .into(),
+    ///         },
+    ///         TangledFile {
+    ///             path: "dotfiles/README.el".into(),
+    ///             contents: "(setq x 1)\n".into(),
+    ///         },
+    ///     ]
+    /// );
+    /// ```
+    pub fn tangle(&self, file: &str) -> Vec<TangledFile> {
+        let mut files: Vec<TangledFile> = Vec::new();
+        let name = file.rsplit(['/', '\\']).next().unwrap_or(file);
+
+        for tangled in self.tangled_blocks(file) {
+            let args = &tangled.args;
+            let body = match args.get("noweb") {
+                Some("yes") | Some("tangle") | Some("no-export") | Some("strip-export") => {
+                    self.expand_noweb(tangled.node)
+                }
+                _ => self.src_body(tangled.node),
+            };
+            let mut body = body.unwrap_or_default();
+            if !body.is_empty() && !body.ends_with('\n') {
+                body.push('\n');
+            }
+
+            if let Some(label) = &tangled.label {
+                let comment = comment_start(tangled.language);
+                let link = match tangled.headline {
+                    Some(headline) => format!("{}::*{}", name, headline.title(self).raw),
+                    None => name.to_string(),
+                };
+                body = format!(
+                    "{} [[file:{}][{}]]\n{}{} {} ends here\n",
+                    comment, link, label, body, comment, label
+                );
+            }
+
+            match files.iter_mut().find(|f| f.path == tangled.path) {
+                Some(file) => {
+                    if args.get("padline") != Some("no") {
+                        file.contents.push('\n');
+                    }
+                    file.contents.push_str(&body);
+                }
+                None => {
+                    let mut contents = String::new();
+                    if let Some(shebang) = args.get("shebang") {
+                        contents.push_str(shebang.trim_matches('"'));
+                        contents.push('\n');
+                    }
+                    contents.push_str(&body);
+                    files.push(TangledFile {
+                        path: tangled.path,
+                        contents,
+                    });
+                }
+            }
+        }
+
+        files
+    }
+
+    /// Tangles this document like [`tangle`], and calls `sink` with the path
+    /// and contents of each file, stopping at the first error.
+    ///
+    /// [`tangle`]: #method.tangle
+    pub fn tangle_with<E, F>(&self, file: &str, mut sink: F) -> Result<(), E>
+    where
+        F: FnMut(&str, &str) -> Result<(), E>,
+    {
+        for tangled in self.tangle(file) {
+            sink(&tangled.path, &tangled.contents)?;
+        }
+        Ok(())
+    }
+
+    /// Updates source blocks from the contents of a tangled file, like
+    /// `org-babel-detangle`, and returns the number of updated blocks.
+    ///
+    /// Blocks are located by the labels of link comments added by [`tangle`],
+    /// like `Title:2` for the second block under headlines titled `Title`,
+    /// so only blocks tangled with `:comments link` can be updated. Blocks
+    /// whose noweb references were expanded are skipped.
+    ///
+    /// [`tangle`]: #method.tangle
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let mut org = Org::parse(
+    ///     "* setup\n#+BEGIN_SRC sh :tangle setup.sh :comments link\necho hi\n#+END_SRC\n",
+    /// );
+    /// let tangled = org.tangle("README.org").remove(0).contents;
+    ///
+    /// assert_eq!(org.detangle(&tangled.replace("echo hi", "echo bye")), 1);
+    /// assert_eq!(org.tangle("README.org")[0].contents, tangled.replace("hi", "bye"));
+    /// ```
+    pub fn detangle(&mut self, contents: &str) -> usize {
+        let mut bodies: Vec<(&str, String)> = Vec::new();
+        let mut current: Option<(&str, String)> = None;
+        for line in contents.split_inclusive('\n') {
+            let trimmed = line.trim();
+            current = match current.take() {
+                Some((label, body)) => {
+                    if trimmed
+                        .strip_suffix(" ends here")
+                        .is_some_and(|rest| rest.ends_with(label))
+                    {
+                        bodies.push((label, body));
+                        None
+                    } else {
+                        Some((label, body + line))
+                    }
+                }
+                None => trimmed
+                    .find("[[file:")
+                    .and_then(|i| trimmed[i..].strip_suffix("]]"))
+                    .and_then(|link| Some(&link[link.rfind("][")? + 2..]))
+                    .map(|label| (label, String::new())),
+            };
+        }
+
+        let mut edits = Vec::new();
+        for tangled in self.tangled_blocks("") {
+            let label = match &tangled.label {
+                Some(label) => label,
+                None => continue,
+            };
+            if let Some("yes") | Some("tangle") | Some("no-export") | Some("strip-export") =
+                tangled.args.get("noweb")
+            {
+                continue;
+            }
+            let body = match bodies.iter().find(|(l, _)| l == label) {
+                Some((_, body)) => body,
+                None => continue,
+            };
+            let (range, indent) = match self.src_contents(tangled.node) {
+                Some(contents) => contents,
+                None => continue,
+            };
+            let text: String = escape(body)
+                .split_inclusive('\n')
+                .map(|line| {
+                    if line.trim().is_empty() {
+                        line.trim_start_matches([' ', '\t']).to_string()
+                    } else {
+                        format!("{}{}", " ".repeat(indent), line)
+                    }
+                })
+                .collect();
+            if self.text[range.clone()] != *text {
+                edits.push((range, text));
+            }
+        }
+
+        let count = edits.len();
+        for (range, text) in edits.into_iter().rev() {
+            self.edit(range, &text);
+        }
+        count
+    }
+
+    /// Returns blocks to be tangled in document order.
+    fn tangled_blocks(&self, file: &str) -> Vec<TangledBlock<'_>> {
+        let mut blocks = Vec::new();
+        // counted by title, so that labels of same-title headlines don't collide
+        let mut counters: Vec<(&str, usize)> = Vec::new();
+
+        for node in self.src_blocks() {
+            let block = match &self[node] {
+                Element::SourceBlock(block) => block,
+                _ => continue,
+            };
+            let headline = self.enclosing_headline(node);
+            let mut ancestor = headline;
+            let mut commented = false;
+            while let Some(hdl) = ancestor {
+                commented |= hdl.title(self).is_commented();
+                ancestor = hdl.parent(self);
+            }
+            if commented {
+                continue;
+            }
+
+            let args = self.header_args(node);
+            let target = match args.get("tangle") {
+                None | Some("") | Some("no") => continue,
+                Some("yes") => {
+                    let name = file.rsplit(['/', '\\']).next().unwrap_or(file);
+                    let stem = match name.rfind('.') {
+                        Some(i) if i > 0 => &name[..i],
+                        _ => name,
+                    };
+                    format!("{}.{}", stem, extension(&block.language))
+                }
+                Some(target) => target.trim_matches('"').to_string(),
+            };
+            let path = if target.starts_with(['/', '~']) {
+                target
+            } else {
+                match file.rfind(['/', '\\']) {
+                    Some(i) => format!("{}{}", &file[..=i], target),
+                    None => target,
+                }
+            };
+
+            let label = match args.get("comments") {
+                Some("link") | Some("yes") | Some("both") | Some("noweb") => {
+                    let title = headline.map_or("No heading", |headline| &headline.title(self).raw);
+                    let count = match counters.iter_mut().find(|(t, _)| *t == title) {
+                        Some((_, count)) => {
+                            *count += 1;
+                            *count
+                        }
+                        None => {
+                            counters.push((title, 1));
+                            1
+                        }
+                    };
+                    Some(format!("{}:{}", title, count))
+                }
+                _ => None,
+            };
+
+            blocks.push(TangledBlock {
+                node,
+                language: &block.language,
+                headline,
+                path,
+                args,
+                label,
+            });
+        }
+
+        blocks
+    }
+}
+
+struct TangledBlock<'a> {
+    node: NodeId,
+    language: &'a str,
+    headline: Option<Headline>,
+    path: String,
+    args: HeaderArgs,
+    /// Label of link comments, e.g. `Title:1`
+    label: Option<String>,
+}
+
+/// Returns the file extension for `:tangle yes` of this language.
+fn extension(language: &str) -> &str {
+    match language {
+        "emacs-lisp" | "elisp" => "el",
+        "shell" | "sh" | "bash" | "zsh" => "sh",
+        "python" => "py",
+        "ruby" => "rb",
+        "perl" => "pl",
+        "rust" => "rs",
+        "javascript" | "js" => "js",
+        "typescript" => "ts",
+        "haskell" => "hs",
+        "clojure" => "clj",
+        "scheme" => "scm",
+        "lisp" => "lisp",
+        "markdown" => "md",
+        "latex" => "tex",
+        "fish" => "fish",
+        "yaml" => "yaml",
+        "ocaml" => "ml",
+        "go" => "go",
+        "c" => "c",
+        "C" => "c",
+        "cpp" | "C++" => "cpp",
+        language => language,
+    }
+}
+
+/// Returns the line comment syntax of this language.
+fn comment_start(language: &str) -> &str {
+    match language {
+        "emacs-lisp" | "elisp" | "lisp" | "scheme" | "clojure" | "racket" => ";;",
+        "rust" | "c" | "C" | "cpp" | "C++" | "java" | "javascript" | "js" | "typescript" | "go"
+        | "swift" | "kotlin" | "scala" => "//",
+        "haskell" | "lua" | "sql" | "elm" => "--",
+        "latex" | "tex" | "matlab" | "octave" => "%",
+        "vim" => "\"",
+        _ => "#",
+    }
+}
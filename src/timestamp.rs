@@ -0,0 +1,244 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use indextree::NodeId;
+
+use crate::elements::{Element, Timestamp};
+use crate::{Headline, Org};
+
+impl Org<'_> {
+    /// Replaces the timestamp `node` with `timestamp` in the source text and
+    /// reparses it.
+    ///
+    /// Returns `false` if `node` isn't a timestamp parsed from the source
+    /// text. Timestamps in planning lines aren't nodes, use
+    /// [`Headline::set_scheduled`] and [`Headline::set_deadline`] instead.
+    ///
+    /// [`Headline::set_scheduled`]: struct.Headline.html#method.set_scheduled
+    /// [`Headline::set_deadline`]: struct.Headline.html#method.set_deadline
+    pub fn set_timestamp(&mut self, node: NodeId, timestamp: &Timestamp) -> bool {
+        let span = match (&self[node], self.span(node)) {
+            (Element::Timestamp(_), Some(span)) => span,
+            _ => return false,
+        };
+        self.edit(span, &timestamp.to_string());
+        true
+    }
+
+    /// Turns the timestamp `node` inactive if it's active, or active
+    /// otherwise, in the source text.
+    ///
+    /// Returns `false` if `node` isn't a timestamp or it's a diary timestamp.
+    ///
+    /// ```rust
+    /// # use orgize::{Element, Org};
+    /// #
+    /// let mut org = Org::parse("meeting <2023-01-02 Mon 10:00>\n");
+    /// let find = |org: &Org| {
+    ///     org.arena()
+    ///         .iter()
+    ///         .filter(|node| !node.is_removed())
+    ///         .filter_map(|node| org.arena().get_node_id(node))
+    ///         .find(|node| matches!(org[*node], Element::Timestamp(_)))
+    ///         .unwrap()
+    /// };
+    ///
+    /// let node = find(&org);
+    /// assert!(org.toggle_timestamp(node));
+    /// let node = find(&org);
+    /// assert!(org.shift_timestamp(node, 7));
+    /// let node = find(&org);
+    /// assert!(org.set_timestamp_time(node, Some((9, 30))));
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "meeting [2023-01-09 Mon 09:30]\n"
+    /// );
+    /// ```
+    pub fn toggle_timestamp(&mut self, node: NodeId) -> bool {
+        self.rewrite_timestamp(node, |timestamp| match timestamp {
+            Timestamp::Diary { .. } => None,
+            _ if timestamp.is_active() => Some(timestamp.to_inactive()),
+            _ => Some(timestamp.to_active()),
+        })
+    }
+
+    /// Shifts the timestamp `node` by `days` in the source text, see
+    /// [`Timestamp::shift`](elements/enum.Timestamp.html#method.shift).
+    ///
+    /// Returns `false` if `node` isn't a timestamp or it can't be shifted.
+    pub fn shift_timestamp(&mut self, node: NodeId, days: i64) -> bool {
+        self.rewrite_timestamp(node, |timestamp| timestamp.shift(days))
+    }
+
+    /// Sets the time of the timestamp `node` in the source text, see
+    /// [`Timestamp::with_time`](elements/enum.Timestamp.html#method.with_time).
+    ///
+    /// Returns `false` if `node` isn't a timestamp or the time can't be set.
+    pub fn set_timestamp_time(&mut self, node: NodeId, time: Option<(u8, u8)>) -> bool {
+        self.rewrite_timestamp(node, |timestamp| timestamp.with_time(time))
+    }
+
+    fn rewrite_timestamp(
+        &mut self,
+        node: NodeId,
+        f: impl FnOnce(&Timestamp) -> Option<Timestamp<'static>>,
+    ) -> bool {
+        let timestamp = match &self[node] {
+            Element::Timestamp(timestamp) => f(timestamp),
+            _ => None,
+        };
+        match timestamp {
+            Some(timestamp) => self.set_timestamp(node, &timestamp),
+            None => false,
+        }
+    }
+}
+
+impl Headline {
+    /// Changes the `SCHEDULED` timestamp in the planning line of this
+    /// headline in the source text, or removes it if `timestamp` is `None`.
+    /// The planning line is added or removed if needed.
+    ///
+    /// Returns `false` if nothing is changed or this headline isn't parsed
+    /// from the source text. The document is reparsed after changing, so
+    /// previously returned headlines and nodes are invalid.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let mut org = Org::parse("* TODO task\nDEADLINE: <2023-01-06 Fri>\ntext\n");
+    /// let task = org.headlines().next().unwrap();
+    /// let deadline = task.title(&org).deadline().unwrap().shift(-3).unwrap();
+    ///
+    /// assert!(task.set_scheduled(Some(&deadline), &mut org));
+    /// let task = org.headlines().next().unwrap();
+    /// assert!(task.shift_planning(7, &mut org));
+    /// let task = org.headlines().next().unwrap();
+    /// assert!(task.set_deadline(None, &mut org));
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "* TODO task\nSCHEDULED: <2023-01-10 Tue>\ntext\n"
+    /// );
+    /// ```
+    pub fn set_scheduled(self, timestamp: Option<&Timestamp>, org: &mut Org) -> bool {
+        self.set_planning("SCHEDULED", timestamp, org)
+    }
+
+    /// Changes the `DEADLINE` timestamp of this headline, like
+    /// [`Headline::set_scheduled`](#method.set_scheduled).
+    pub fn set_deadline(self, timestamp: Option<&Timestamp>, org: &mut Org) -> bool {
+        self.set_planning("DEADLINE", timestamp, org)
+    }
+
+    /// Shifts the `SCHEDULED` and `DEADLINE` timestamps of this headline by
+    /// `days` in the source text, see
+    /// [`Timestamp::shift`](elements/enum.Timestamp.html#method.shift).
+    ///
+    /// Returns `false` if there's neither, one of them can't be shifted, or
+    /// this headline isn't parsed from the source text.
+    pub fn shift_planning(self, days: i64, org: &mut Org) -> bool {
+        let title = self.title(org);
+        let mut edits = Vec::new();
+        for (keyword, timestamp) in [
+            ("SCHEDULED", title.scheduled()),
+            ("DEADLINE", title.deadline()),
+        ] {
+            if let Some(timestamp) = timestamp {
+                let edit = timestamp
+                    .shift(days)
+                    .and_then(|shifted| self.planning_edit(keyword, Some(&shifted), org));
+                match edit {
+                    Some(edit) => edits.push(edit),
+                    None => return false,
+                }
+            }
+        }
+        if edits.is_empty() {
+            return false;
+        }
+        edits.sort_by_key(|(range, _)| range.start);
+        org.apply_edits(edits);
+        true
+    }
+
+    fn set_planning(self, keyword: &str, timestamp: Option<&Timestamp>, org: &mut Org) -> bool {
+        match self.planning_edit(keyword, timestamp, org) {
+            Some((range, text)) => {
+                org.edit(range, &text);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the edit setting the timestamp of `keyword` in the planning
+    /// line, or removing it if `timestamp` is `None`.
+    fn planning_edit(
+        self,
+        keyword: &str,
+        timestamp: Option<&Timestamp>,
+        org: &Org,
+    ) -> Option<(Range<usize>, String)> {
+        let span = org.span(self.title_node())?;
+        let text = &org.text[span.clone()];
+        let line_end = span.start + text.find('\n').map_or(text.len(), |i| i + 1);
+        let planning = self.title(org).planning.as_ref().map(|_| {
+            let text = &org.text[line_end..span.end];
+            line_end..line_end + text.find('\n').map_or(text.len(), |i| i + 1)
+        });
+
+        let label = format!("{}:", keyword);
+        let existing = planning.clone().and_then(|planning| {
+            let start = planning.start + org.text[planning.clone()].find(&label)?;
+            let rest = &org.text[start + label.len()..planning.end];
+            let rest = rest.trim_start_matches([' ', '\t']);
+            let (tail, _) = Timestamp::parse_active(rest)
+                .or_else(|| Timestamp::parse_inactive(rest))
+                .or_else(|| Timestamp::parse_diary(rest))?;
+            Some(start..planning.end - tail.len())
+        });
+
+        match (timestamp, existing, planning) {
+            (Some(timestamp), Some(existing), _) => {
+                let text = format!("{} {}", label, timestamp);
+                if org.text[existing.clone()] == *text {
+                    return None;
+                }
+                Some((existing, text))
+            }
+            (Some(timestamp), None, Some(planning)) => {
+                let end = planning.start + org.text[planning].trim_end().len();
+                Some((end..end, format!(" {} {}", label, timestamp)))
+            }
+            (Some(timestamp), None, None) => {
+                let newline = if org.text[..line_end].ends_with('\n') {
+                    ""
+                } else {
+                    "\n"
+                };
+                let text = format!("{}{} {}\n", newline, label, timestamp);
+                Some((line_end..line_end, text))
+            }
+            (None, Some(existing), Some(planning)) => {
+                let before = &org.text[planning.start..existing.start];
+                let after = &org.text[existing.end..planning.end];
+                if before.trim().is_empty() && after.trim().is_empty() {
+                    Some((planning, String::new()))
+                } else {
+                    let end =
+                        existing.end + after.len() - after.trim_start_matches([' ', '\t']).len();
+                    Some((existing.start..end, String::new()))
+                }
+            }
+            (None, _, _) => None,
+        }
+    }
+}
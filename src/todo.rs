@@ -0,0 +1,301 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::clock::format_datetime;
+use crate::config::ParseConfig;
+use crate::elements::Datetime;
+use crate::{Headline, Org};
+
+/// Settings of logging todo state changes
+///
+/// They're read from `#+STARTUP` and `#+TODO` keywords by
+/// [`Org::todo_log`](struct.Org.html#method.todo_log).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TodoLog {
+    /// What to log when switching to a done state, like `org-log-done`
+    pub done: LogDone,
+    /// Puts state change notes into the `LOGBOOK` drawer, like
+    /// `org-log-into-drawer`
+    pub into_drawer: bool,
+    /// Keywords whose entering is logged with a state change note, like
+    /// `WAIT(w!)` or `WAIT(w@)`
+    pub log_enter: Vec<String>,
+    /// Keywords whose leaving is logged with a state change note if the new
+    /// state isn't logged, like `WAIT(w/!)`
+    pub log_leave: Vec<String>,
+}
+
+/// What to log when switching to a done state
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogDone {
+    /// Logs nothing, like `#+STARTUP: nologdone`
+    #[default]
+    Nothing,
+    /// Adds a `CLOSED:` timestamp, like `#+STARTUP: logdone`
+    Time,
+    /// Adds a `CLOSED:` timestamp and a `CLOSING NOTE`, like
+    /// `#+STARTUP: lognotedone`
+    Note,
+}
+
+impl Org<'_> {
+    /// Returns the settings of logging todo state changes of this document.
+    ///
+    /// `logdone`, `lognotedone`, `nologdone`, `logdrawer` and `nologdrawer`
+    /// options of `#+STARTUP` keywords are recognized, as well as logging
+    /// flags of keywords in `#+TODO`, `#+SEQ_TODO` and `#+TYP_TODO`, e.g.
+    /// `WAIT(w@/!)`.
+    ///
+    /// ```rust
+    /// # use orgize::{LogDone, Org};
+    /// #
+    /// let org = Org::parse("#+STARTUP: logdone logdrawer\n#+TODO: TODO WAIT(w@/!) | DONE(d!)\n");
+    /// let log = org.todo_log();
+    ///
+    /// assert_eq!(log.done, LogDone::Time);
+    /// assert!(log.into_drawer);
+    /// assert_eq!(log.log_enter, vec!["WAIT", "DONE"]);
+    /// assert_eq!(log.log_leave, vec!["WAIT"]);
+    /// ```
+    pub fn todo_log(&self) -> TodoLog {
+        let mut log = TodoLog::default();
+
+        for value in self.keyword_values("STARTUP") {
+            for option in value.split_whitespace() {
+                match &*option.to_ascii_lowercase() {
+                    "logdone" => log.done = LogDone::Time,
+                    "lognotedone" => log.done = LogDone::Note,
+                    "nologdone" => log.done = LogDone::Nothing,
+                    "logdrawer" => log.into_drawer = true,
+                    "nologdrawer" => log.into_drawer = false,
+                    _ => (),
+                }
+            }
+        }
+
+        for keyword in self.keywords().filter(|keyword| {
+            ["TODO", "SEQ_TODO", "TYP_TODO"]
+                .iter()
+                .any(|key| keyword.key.eq_ignore_ascii_case(key))
+        }) {
+            for word in keyword.value.split_whitespace() {
+                let (name, flags) = match word.split_once('(') {
+                    Some((name, flags)) => (name, flags.trim_end_matches(')')),
+                    None => continue,
+                };
+                let (enter, leave) = flags.split_once('/').unwrap_or((flags, ""));
+                if enter.ends_with(['!', '@']) {
+                    log.log_enter.push(name.to_string());
+                }
+                if leave.starts_with(['!', '@']) {
+                    log.log_leave.push(name.to_string());
+                }
+            }
+        }
+
+        log
+    }
+}
+
+impl Headline {
+    /// Changes the todo keyword of this headline to `state` in the source
+    /// text, or removes it if `state` is `None`, logging the change like
+    /// `org-todo` according to [`Org::todo_log`](struct.Org.html#method.todo_log).
+    ///
+    /// Switching to a done state adds a `CLOSED:` timestamp to the planning
+    /// line if `logdone` is set, and switching back to a todo state removes
+    /// it. State change notes like
+    /// `- State "DONE"       from "TODO"       [2023-01-02 Mon 10:00]` are
+    /// added before other notes, or at the top of the `LOGBOOK` drawer if
+    /// `logdrawer` is set. Notes that require text in org-mode are written
+    /// without text.
+    ///
+    /// Returns `false` if `state` isn't a keyword of `config`, or it's the
+    /// current keyword, or this headline isn't parsed from the source text.
+    /// The document is reparsed after changing, so previously returned
+    /// headlines and nodes are invalid.
+    ///
+    /// ```rust
+    /// # use orgize::elements::Datetime;
+    /// # use orgize::{Org, ParseConfig};
+    /// #
+    /// let mut org = Org::parse(
+    ///     "#+STARTUP: logdone\n#+TODO: TODO(t!) | DONE(d!)\n\
+    ///      * TODO task\nSCHEDULED: <2023-01-02 Mon>\n",
+    /// );
+    /// let now = Datetime {
+    ///     year: 2023,
+    ///     month: 1,
+    ///     day: 2,
+    ///     dayname: "Mon".into(),
+    ///     hour: Some(10),
+    ///     minute: Some(0),
+    /// };
+    ///
+    /// let task = org.headlines().next().unwrap();
+    /// assert!(task.set_todo_with_log(Some("DONE"), &now, &ParseConfig::default(), &mut org));
+    ///
+    /// let task = org.headlines().next().unwrap();
+    /// assert_eq!(task.title(&org).keyword.as_deref(), Some("DONE"));
+    /// assert!(task.title(&org).closed().is_some());
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert!(String::from_utf8(writer).unwrap().ends_with(
+    ///     "- State \"DONE\"       from \"TODO\"       [2023-01-02 Mon 10:00]\n"
+    /// ));
+    /// ```
+    pub fn set_todo_with_log(
+        self,
+        state: Option<&str>,
+        now: &Datetime,
+        config: &ParseConfig,
+        org: &mut Org,
+    ) -> bool {
+        let log = org.todo_log();
+        self.set_todo_with_log_custom(state, now, config, &log, org)
+    }
+
+    /// Like [`Headline::set_todo_with_log`](#method.set_todo_with_log), but
+    /// logs with custom `TodoLog` instead of the settings of the document.
+    pub fn set_todo_with_log_custom(
+        self,
+        state: Option<&str>,
+        now: &Datetime,
+        config: &ParseConfig,
+        log: &TodoLog,
+        org: &mut Org,
+    ) -> bool {
+        match self.todo_edits(state, now, config, log, org) {
+            Some(edits) => {
+                org.apply_edits_custom(edits, config);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn todo_edits(
+        self,
+        state: Option<&str>,
+        now: &Datetime,
+        config: &ParseConfig,
+        log: &TodoLog,
+        org: &Org,
+    ) -> Option<Vec<(Range<usize>, String)>> {
+        let (todo, done) = &config.todo_keywords;
+        let is_done = |keyword: Option<&str>| keyword.is_some_and(|k| done.iter().any(|d| d == k));
+        let is_todo = |keyword: Option<&str>| keyword.is_some_and(|k| todo.iter().any(|t| t == k));
+
+        let title = self.title(org);
+        let old = title.keyword.as_deref();
+        if old == state || (state.is_some() && !is_todo(state) && !is_done(state)) {
+            return None;
+        }
+
+        let span = org.span(self.title_node())?;
+        let text = &org.text[span.clone()];
+        let line_end = span.start + text.find('\n').map_or(text.len(), |i| i + 1);
+        let mut edits = Vec::new();
+
+        // todo keyword right after the stars
+        let stars = span.start + title.level;
+        let after_stars = &org.text[stars..line_end];
+        let keyword_start =
+            stars + after_stars.len() - after_stars.trim_start_matches([' ', '\t']).len();
+        match (old, state) {
+            (Some(old), Some(state)) => {
+                edits.push((keyword_start..keyword_start + old.len(), state.to_string()))
+            }
+            (Some(old), None) => {
+                let rest = &org.text[keyword_start + old.len()..line_end];
+                let end = line_end - rest.trim_start_matches([' ', '\t']).len();
+                edits.push((keyword_start..end, String::new()));
+            }
+            (None, Some(state)) => {
+                edits.push((keyword_start..keyword_start, format!("{} ", state)))
+            }
+            (None, None) => (),
+        }
+
+        // closed timestamp in the planning line
+        let timestamp = format!("[{}]", format_datetime(now));
+        let now_done = is_done(state) && !is_done(old);
+        let planning = title.planning.as_ref().map(|_| {
+            let text = &org.text[line_end..span.end];
+            line_end..line_end + text.find('\n').map_or(text.len(), |i| i + 1)
+        });
+        let closed = planning.clone().and_then(|planning| {
+            let line = &org.text[planning.clone()];
+            let start = planning.start + line.find("CLOSED:")?;
+            let end = start + org.text[start..planning.end].find(']')? + 1;
+            let rest = &org.text[end..planning.end];
+            Some(start..end + rest.len() - rest.trim_start_matches([' ', '\t']).len())
+        });
+        if now_done && log.done != LogDone::Nothing {
+            let closed_text = format!("CLOSED: {}", timestamp);
+            match (&planning, &closed) {
+                (_, Some(closed)) => {
+                    let end = closed.start + org.text[closed.clone()].trim_end().len();
+                    edits.push((closed.start..end, closed_text));
+                }
+                (Some(planning), None) => {
+                    let line = &org.text[planning.clone()];
+                    let start = planning.start + line.len() - line.trim_start().len();
+                    edits.push((start..start, format!("{} ", closed_text)));
+                }
+                (None, None) => edits.push((line_end..line_end, format!("{}\n", closed_text))),
+            }
+        } else if state.is_none() || (is_todo(state) && !is_todo(old)) {
+            if let (Some(planning), Some(closed)) = (planning, closed) {
+                let before = &org.text[planning.start..closed.start];
+                let after = &org.text[closed.end..planning.end];
+                if before.trim().is_empty() && after.trim().is_empty() {
+                    edits.push((planning, String::new()));
+                } else {
+                    edits.push((closed, String::new()));
+                }
+            }
+        }
+
+        // state change notes, the newest first
+        let mut notes = String::new();
+        let log_state = state.is_some()
+            && (state.is_some_and(|state| log.log_enter.iter().any(|k| k == state))
+                || old.is_some_and(|old| log.log_leave.iter().any(|k| k == old)));
+        if log_state {
+            notes = format!(
+                "- State {:<12} from {:<12} {}\n",
+                quoted(state),
+                quoted(old),
+                timestamp
+            );
+        } else if now_done && log.done == LogDone::Note {
+            notes = format!("- CLOSING NOTE {}\n", timestamp);
+        }
+        if !notes.is_empty() {
+            if log.into_drawer {
+                edits.push(org.logbook_edit(self, &notes)?);
+            } else {
+                let end = span.end;
+                let newline = if org.text[..end].ends_with('\n') {
+                    ""
+                } else {
+                    "\n"
+                };
+                edits.push((end..end, format!("{}{}", newline, notes)));
+            }
+        }
+
+        edits.sort_by_key(|(range, _)| range.start);
+        Some(edits)
+    }
+}
+
+fn quoted(keyword: Option<&str>) -> String {
+    keyword
+        .map(|keyword| format!("\"{}\"", keyword))
+        .unwrap_or_default()
+}
@@ -1,5 +1,6 @@
+use alloc::vec::Vec;
 use indextree::NodeId;
-use std::ops::RangeInclusive;
+use core::ops::RangeInclusive;
 
 use crate::elements::{Element, Table, TableCell, TableRow};
 use crate::Org;
@@ -70,6 +71,11 @@ impl Org<'_> {
         }
 
         for node_id in self.root.descendants(&self.arena) {
+            // contents of pending containers haven't been parsed yet
+            if self.pending.contains_key(&node_id) {
+                continue;
+            }
+
             let node = &self.arena[node_id];
             match node.get() {
                 Element::Document { .. } => {
@@ -101,8 +107,10 @@ impl Org<'_> {
                     if let Some(child) = children.next() {
                         expect_element!(
                             child,
-                            "Headline|Section",
-                            Element::Headline { .. } | Element::Section
+                            "Headline|Section|EncryptedSection",
+                            Element::Headline { .. }
+                                | Element::Section
+                                | Element::EncryptedSection(_)
                         );
                     }
 
@@ -161,14 +169,15 @@ impl Org<'_> {
                 | Element::Macros(_)
                 | Element::RadioTarget
                 | Element::Snippet(_)
+                | Element::Citation(_)
                 | Element::Target(_)
                 | Element::Text { .. }
                 | Element::Timestamp(_)
                 | Element::Verbatim { .. }
-                | Element::FnDef(_)
                 | Element::Clock(_)
                 | Element::Comment { .. }
                 | Element::FixedWidth { .. }
+                | Element::EncryptedSection(_)
                 | Element::Keyword(_)
                 | Element::Rule(_)
                 | Element::Cookie(_)
@@ -178,21 +187,26 @@ impl Org<'_> {
                         errors.push(ValidationError::UnexpectedChildren { at: node_id });
                     }
                 }
-                Element::SpecialBlock(_)
-                | Element::QuoteBlock(_)
-                | Element::CenterBlock(_)
-                | Element::VerseBlock(_)
-                | Element::Paragraph { .. }
+                Element::Paragraph { .. }
                 | Element::Section
                 | Element::Bold
                 | Element::Italic
                 | Element::Underline
-                | Element::Strike
-                | Element::DynBlock(_)
-                | Element::ListItem(_) => {
+                | Element::Strike => {
                     expect_children!(node_id);
                 }
-                Element::Drawer(_) | Element::TableCell(_) | Element::Table(_) => (),
+                // blocks, list items and verse lines can be empty
+                Element::SpecialBlock(_)
+                | Element::QuoteBlock(_)
+                | Element::CenterBlock(_)
+                | Element::VerseBlock(_)
+                | Element::VerseLine { .. }
+                | Element::DynBlock(_)
+                | Element::ListItem(_)
+                | Element::FnDef(_)
+                | Element::Drawer(_)
+                | Element::TableCell(_)
+                | Element::Table(_) => (),
             }
         }
         errors
@@ -202,9 +216,12 @@ impl Org<'_> {
         if cfg!(debug_assertions) {
             let errors = self.validate();
             if !errors.is_empty() {
-                eprintln!("Org validation failed. {} error(s) found:", errors.len());
-                for err in errors {
-                    eprintln!("{:?} at {:?}", err, err.element(self));
+                #[cfg(feature = "std")]
+                {
+                    eprintln!("Org validation failed. {} error(s) found:", errors.len());
+                    for err in errors {
+                        eprintln!("{:?} at {:?}", err, err.element(self));
+                    }
                 }
                 panic!(
                     "Looks like there's a bug in orgize! Please report it with your org-mode content at https://github.com/PoiScript/orgize/issues."
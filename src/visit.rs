@@ -0,0 +1,185 @@
+use alloc::borrow::Cow;
+use indextree::NodeId;
+
+use crate::elements::*;
+use crate::Org;
+
+macro_rules! by_ref {
+    ($($tt:tt)*) => { &$($tt)* };
+}
+
+macro_rules! by_mut {
+    ($($tt:tt)*) => { &mut $($tt)* };
+}
+
+macro_rules! visitor {
+    (
+        $(#[$attr:meta])*
+        $trait:ident, $ref:ident;
+        $($ele0:ident => $visit0:ident),*;
+        $($ele1:ident => $visit1:ident),*;
+        $($ele2:ident => $visit2:ident),*
+    ) => {
+        $(#[$attr])*
+        pub trait $trait<'a> {
+            /// Visits the whole document.
+            fn visit_org(&mut self, org: $ref!(Org<'a>)) {
+                let root = org.root;
+                self.visit(org, root);
+            }
+
+            /// Visits `node` and then its children.
+            ///
+            /// Overrides this method to change the traversal, e.g. skipping
+            /// some subtrees.
+            fn visit(&mut self, org: $ref!(Org<'a>), node: NodeId) {
+                match $ref!(org[node]) {
+                    $(Element::$ele0(e) => self.$visit0(e),)*
+                    $(Element::$ele1(e) => self.$visit1(e),)*
+                    $(Element::$ele2 => self.$visit2(),)*
+                    Element::Document { pre_blank } => self.visit_document(pre_blank),
+                    Element::Headline { level } => self.visit_headline(level),
+                    Element::Paragraph { post_blank } => self.visit_paragraph(post_blank),
+                    Element::VerseLine { indent } => self.visit_verse_line(indent),
+                    Element::Text { value } => self.visit_text(value),
+                    Element::Verbatim { value } => self.visit_verbatim(value),
+                    Element::Code { value } => self.visit_code(value),
+                }
+
+                self.visit_children(org, node);
+            }
+
+            /// Visits each child of `node` in order.
+            fn visit_children(&mut self, org: $ref!(Org<'a>), node: NodeId) {
+                let mut child = org.arena[node].first_child();
+                while let Some(node) = child {
+                    self.visit(org, node);
+                    child = org.arena[node].next_sibling();
+                }
+            }
+
+            $(fn $visit0(&mut self, _: $ref!($ele0<'a>)) {})*
+            $(fn $visit1(&mut self, _: $ref!($ele1)) {})*
+            $(fn $visit2(&mut self) {})*
+            fn visit_document(&mut self, _pre_blank: $ref!(usize)) {}
+            fn visit_headline(&mut self, _level: $ref!(usize)) {}
+            fn visit_paragraph(&mut self, _post_blank: $ref!(usize)) {}
+            fn visit_verse_line(&mut self, _indent: $ref!(usize)) {}
+            #[allow(clippy::ptr_arg)]
+            fn visit_text(&mut self, _value: $ref!(Cow<'a, str>)) {}
+            #[allow(clippy::ptr_arg)]
+            fn visit_verbatim(&mut self, _value: $ref!(Cow<'a, str>)) {}
+            #[allow(clippy::ptr_arg)]
+            fn visit_code(&mut self, _value: $ref!(Cow<'a, str>)) {}
+        }
+    };
+}
+
+macro_rules! visitors {
+    ($($tt:tt)*) => {
+        visitor! {
+            /// Walks an `Org` struct, calling the method for each element it visits.
+            ///
+            /// Every method does nothing by default, so only the elements of
+            /// interest need to be handled.
+            ///
+            /// ```rust
+            /// # use orgize::{elements::Timestamp, Org, Visitor};
+            /// #
+            /// struct Timestamps(usize);
+            ///
+            /// impl Visitor<'_> for Timestamps {
+            ///     fn visit_timestamp(&mut self, _: &Timestamp) {
+            ///         self.0 += 1;
+            ///     }
+            /// }
+            ///
+            /// let org = Org::parse("* <2003-09-16 Tue>\nfrom <2003-09-16 Tue>--<2003-09-17 Wed>");
+            ///
+            /// let mut visitor = Timestamps(0);
+            /// visitor.visit_org(&org);
+            ///
+            /// assert_eq!(visitor.0, 2);
+            /// ```
+            Visitor, by_ref;
+            $($tt)*
+        }
+
+        visitor! {
+            /// Walks an `Org` struct like [`Visitor`], but visits mutable
+            /// references of elements.
+            ///
+            /// [`Visitor`]: trait.Visitor.html
+            ///
+            /// ```rust
+            /// # use orgize::{elements::Link, Org, VisitorMut};
+            /// #
+            /// struct Https;
+            ///
+            /// impl VisitorMut<'_> for Https {
+            ///     fn visit_link(&mut self, link: &mut Link) {
+            ///         if link.path.starts_with("http:") {
+            ///             link.path = link.path.replacen("http:", "https:", 1).into();
+            ///         }
+            ///     }
+            /// }
+            ///
+            /// let mut org = Org::parse("[[http://example.com][example]]");
+            /// Https.visit_org(&mut org);
+            ///
+            /// let mut writer = Vec::new();
+            /// org.write_org(&mut writer).unwrap();
+            ///
+            /// assert_eq!(
+            ///     String::from_utf8(writer).unwrap(),
+            ///     "[[https://example.com][example]]\n"
+            /// );
+            /// ```
+            VisitorMut, by_mut;
+            $($tt)*
+        }
+    };
+}
+
+visitors! {
+    SpecialBlock => visit_special_block,
+    QuoteBlock => visit_quote_block,
+    CenterBlock => visit_center_block,
+    VerseBlock => visit_verse_block,
+    CommentBlock => visit_comment_block,
+    ExampleBlock => visit_example_block,
+    ExportBlock => visit_export_block,
+    SourceBlock => visit_source_block,
+    BabelCall => visit_babel_call,
+    Clock => visit_clock,
+    Cookie => visit_cookie,
+    Drawer => visit_drawer,
+    DynBlock => visit_dyn_block,
+    EncryptedSection => visit_encrypted_section,
+    FnDef => visit_fn_def,
+    FnRef => visit_fn_ref,
+    InlineCall => visit_inline_call,
+    InlineSrc => visit_inline_src,
+    Keyword => visit_keyword,
+    Link => visit_link,
+    ListItem => visit_list_item,
+    Macros => visit_macros,
+    Snippet => visit_snippet,
+    Citation => visit_citation,
+    Timestamp => visit_timestamp,
+    Target => visit_target,
+    Comment => visit_comment,
+    FixedWidth => visit_fixed_width,
+    Title => visit_title,
+    Table => visit_table;
+    List => visit_list,
+    Rule => visit_rule,
+    TableRow => visit_table_row,
+    TableCell => visit_table_cell;
+    Section => visit_section,
+    RadioTarget => visit_radio_target,
+    Bold => visit_bold,
+    Strike => visit_strike,
+    Italic => visit_italic,
+    Underline => visit_underline
+}
@@ -0,0 +1,29 @@
+//! WebAssembly bindings for using orgize from JavaScript.
+//!
+//! ```js
+//! import { parse, to_html } from "orgize";
+//!
+//! const tree = parse("* title\n*bold*");
+//! const html = to_html("* title\n*bold*");
+//! ```
+
+use wasm_bindgen::prelude::*;
+
+use crate::Org;
+
+/// Parses `text` and returns the syntax tree as a JavaScript object, in
+/// the same shape as the JSON serialized by the `ser` feature.
+#[wasm_bindgen]
+pub fn parse(text: &str) -> Result<JsValue, JsValue> {
+    let json = serde_json::to_string(&Org::parse(text))
+        .map_err(|err| JsValue::from_str(&err.to_string()))?;
+    js_sys::JSON::parse(&json)
+}
+
+/// Parses `text` and exports it to HTML.
+#[wasm_bindgen]
+pub fn to_html(text: &str) -> String {
+    let mut writer = Vec::new();
+    Org::parse(text).write_html(&mut writer).unwrap();
+    String::from_utf8(writer).unwrap()
+}
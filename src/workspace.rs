@@ -0,0 +1,341 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use indextree::NodeId;
+
+use crate::category::file_category;
+use crate::config::ParseConfig;
+use crate::elements::Element;
+use crate::{Headline, IdIndex, Org, SearchIndex};
+
+/// Collection of parsed org files, identified by their paths
+///
+/// ```rust
+/// # use orgize::Workspace;
+/// #
+/// let mut workspace = Workspace::default();
+/// workspace.insert("inbox.org", "* TODO read :book:\n[[file:notes.org][notes]]\n");
+/// workspace.insert("notes.org", "* DONE write :blog:book:\n");
+///
+/// let todos: Vec<_> = workspace
+///     .todos()
+///     .iter()
+///     .map(|(path, headline)| (*path, &*headline.title(&workspace[*path]).raw))
+///     .collect();
+/// assert_eq!(todos, vec![("inbox.org", "read")]);
+/// assert_eq!(workspace.tags(), vec!["blog", "book"]);
+/// assert_eq!(workspace.links()[0].target, "notes.org");
+///
+/// workspace.insert("inbox.org", "* TODO reload\n");
+/// assert!(workspace.links().is_empty());
+/// ```
+#[derive(Default)]
+pub struct Workspace {
+    files: Vec<(String, Org<'static>)>,
+    pub(crate) config: ParseConfig,
+}
+
+/// Link from one file to another file in the workspace
+#[derive(Debug, Clone, Copy)]
+pub struct WorkspaceLink<'w> {
+    /// Path of the file containing the link
+    pub source: &'w str,
+    /// Headline containing the link, if any
+    pub source_headline: Option<Headline>,
+    /// Path of the linked file
+    pub target: &'w str,
+    /// Linked headline, from `id:` links, `#custom-id` links or file links
+    /// with a search option
+    pub target_headline: Option<Headline>,
+}
+
+impl Workspace {
+    /// Creates an empty workspace parsing files with custom `ParseConfig`.
+    pub fn with_config(config: ParseConfig) -> Workspace {
+        Workspace {
+            files: Vec::new(),
+            config,
+        }
+    }
+
+    /// Parses `text` as the file at `path`, replacing the previous file at
+    /// the same path if any, and returns the parsed document.
+    pub fn insert(&mut self, path: impl Into<String>, text: impl Into<String>) -> &Org<'static> {
+        let path = path.into();
+        let mut org = Org::new();
+        org.reparse(text.into(), &self.config);
+
+        let index = match self.files.iter().position(|(p, _)| *p == path) {
+            Some(index) => {
+                self.files[index].1 = org;
+                index
+            }
+            None => {
+                self.files.push((path, org));
+                self.files.len() - 1
+            }
+        };
+        &self.files[index].1
+    }
+
+    /// Removes the file at `path`, and returns its document.
+    pub fn remove(&mut self, path: &str) -> Option<Org<'static>> {
+        let index = self.files.iter().position(|(p, _)| p == path)?;
+        Some(self.files.remove(index).1)
+    }
+
+    /// Returns the document at `path`.
+    pub fn get(&self, path: &str) -> Option<&Org<'static>> {
+        self.files
+            .iter()
+            .find(|(p, _)| p == path)
+            .map(|(_, org)| org)
+    }
+
+    /// Returns the document at `path` for editing.
+    pub fn get_mut(&mut self, path: &str) -> Option<&mut Org<'static>> {
+        self.files
+            .iter_mut()
+            .find(|(p, _)| p == path)
+            .map(|(_, org)| org)
+    }
+
+    /// Returns paths and documents of all files in insertion order.
+    pub fn files(&self) -> impl Iterator<Item = (&str, &Org<'static>)> {
+        self.files.iter().map(|(path, org)| (path.as_str(), org))
+    }
+
+    /// Returns headlines with an unfinished todo keyword of all files.
+    pub fn todos(&self) -> Vec<(&str, Headline)> {
+        let todo_keywords = &self.config.todo_keywords.0;
+        self.files()
+            .flat_map(|(path, org)| {
+                org.headlines()
+                    .filter(move |headline| {
+                        headline
+                            .title(org)
+                            .keyword
+                            .as_ref()
+                            .is_some_and(|keyword| todo_keywords.iter().any(|k| k == keyword))
+                    })
+                    .map(move |headline| (path, headline))
+            })
+            .collect()
+    }
+
+    /// Returns headlines with a running clock of all files.
+    pub fn running_clocks(&self) -> Vec<(&str, Headline)> {
+        self.files()
+            .flat_map(|(path, org)| {
+                org.running_clocks()
+                    .into_iter()
+                    .map(move |headline| (path, headline))
+            })
+            .collect()
+    }
+
+    /// Returns the category of `node` in the file at `path`, falling back to
+    /// the file name without extension, see [`Org::category`]. Returns
+    /// `None` if there's no file at this path.
+    ///
+    /// [`Org::category`]: struct.Org.html#method.category
+    pub fn category(&self, path: &str, node: NodeId) -> Option<&str> {
+        let (path, org) = self.files().find(|(p, _)| *p == path)?;
+        Some(org.category(node).unwrap_or_else(|| file_category(path)))
+    }
+
+    /// Returns all tags used by headlines and `#+FILETAGS` keywords of all
+    /// files, sorted and deduplicated.
+    pub fn tags(&self) -> Vec<&str> {
+        let mut tags: Vec<&str> = self
+            .files()
+            .flat_map(|(_, org)| {
+                org.headlines()
+                    .flat_map(move |headline| headline.title(org).tags.iter().map(|tag| &**tag))
+                    .chain(org.file_tags())
+            })
+            .collect();
+        tags.sort_unstable();
+        tags.dedup();
+        tags
+    }
+
+    /// Returns an index of headline IDs of all files.
+    pub fn id_index(&self) -> IdIndex<'static, '_> {
+        let mut index = IdIndex::default();
+        for (path, org) in self.files() {
+            index.add(path, org);
+        }
+        index
+    }
+
+    /// Returns a full-text search index of all files.
+    pub fn search_index(&self) -> SearchIndex {
+        let mut index = SearchIndex::default();
+        for (path, org) in self.files() {
+            index.add(path, org);
+        }
+        index
+    }
+
+    /// Returns links between files in the workspace, in file and document
+    /// order.
+    ///
+    /// File links are resolved relative to the directory of the file
+    /// containing them, with `::*title` and `::#custom-id` search options.
+    /// `id:` links are resolved by headline IDs, and `#custom-id` links by
+    /// `CUSTOM_ID` properties in the same file. Links to files outside of
+    /// the workspace are ignored.
+    pub fn links(&self) -> Vec<WorkspaceLink<'_>> {
+        let ids = self.id_index();
+        let mut links = Vec::new();
+
+        for (source, org) in self.files() {
+            for node in org.root.descendants(&org.arena) {
+                let link = match &org[node] {
+                    Element::Link(link) => link,
+                    _ => continue,
+                };
+
+                let (target, target_headline) = if let Some(target) = ids.resolve(link) {
+                    (target.file, Some(target.headline))
+                } else if link.target().starts_with('#') {
+                    match find_headline(org, link.target()) {
+                        Some(headline) => (source, Some(headline)),
+                        None => continue,
+                    }
+                } else if let Some(path) = file_path(link.target()) {
+                    let (path, search) = match path.split_once("::") {
+                        Some((path, search)) => (path, Some(search)),
+                        None => (path, None),
+                    };
+                    let path = resolve_path(source, path);
+                    let (target, target_org) = match self.files().find(|(p, _)| *p == path) {
+                        Some(file) => file,
+                        None => continue,
+                    };
+                    (
+                        target,
+                        search.and_then(|search| find_headline(target_org, search)),
+                    )
+                } else {
+                    continue;
+                };
+
+                links.push(WorkspaceLink {
+                    source,
+                    source_headline: org.enclosing_headline(node),
+                    target,
+                    target_headline,
+                });
+            }
+        }
+
+        links
+    }
+
+    /// Returns links to the file at `path`, or to `headline` of it if it's
+    /// given, from all files in the workspace including itself.
+    ///
+    /// ```rust
+    /// # use orgize::Workspace;
+    /// #
+    /// let mut workspace = Workspace::default();
+    /// workspace.insert(
+    ///     "rust.org",
+    ///     "* ownership\n:PROPERTIES:\n:CUSTOM_ID: own\n:END:\n* lifetimes\nSee [[#own]].\n",
+    /// );
+    /// workspace.insert("index.org", "* rust\n[[file:rust.org::#own]] [[file:rust.org]]\n");
+    ///
+    /// let ownership = workspace["rust.org"].headlines().next().unwrap();
+    /// let sources: Vec<_> = workspace
+    ///     .backlinks("rust.org", Some(ownership))
+    ///     .iter()
+    ///     .map(|link| link.source)
+    ///     .collect();
+    ///
+    /// assert_eq!(sources, vec!["rust.org", "index.org"]);
+    /// assert_eq!(workspace.backlinks("rust.org", None).len(), 3);
+    /// ```
+    pub fn backlinks(&self, path: &str, headline: Option<Headline>) -> Vec<WorkspaceLink<'_>> {
+        self.links()
+            .into_iter()
+            .filter(|link| link.target == path)
+            .filter(|link| match headline {
+                Some(headline) => link
+                    .target_headline
+                    .is_some_and(|target| target.headline_node() == headline.headline_node()),
+                None => true,
+            })
+            .collect()
+    }
+}
+
+/// Finds the headline matching a search option of a file link, i.e.
+/// `*title` or `#custom-id`.
+fn find_headline(org: &Org, search: &str) -> Option<Headline> {
+    if let Some(title) = search.strip_prefix('*') {
+        org.headlines()
+            .find(|headline| headline.title(org).raw == title)
+    } else if let Some(custom_id) = search.strip_prefix('#') {
+        org.headlines().find(|headline| {
+            headline
+                .title(org)
+                .properties
+                .iter()
+                .any(|(key, value)| key.eq_ignore_ascii_case("CUSTOM_ID") && value == custom_id)
+        })
+    } else {
+        None
+    }
+}
+
+impl core::ops::Index<&str> for Workspace {
+    type Output = Org<'static>;
+
+    /// Panics if there's no file at this path.
+    fn index(&self, path: &str) -> &Org<'static> {
+        self.get(path).expect("no file at this path in workspace")
+    }
+}
+
+/// Returns the path of a file link, e.g. `file:notes.org` or `./notes.org`.
+fn file_path(target: &str) -> Option<&str> {
+    if let Some(path) = target.strip_prefix("file:") {
+        Some(path)
+    } else if target.starts_with(['/', '.', '~']) {
+        Some(target)
+    } else {
+        None
+    }
+}
+
+/// Resolves `path` relative to the directory of `base`, removing `.` and
+/// `..` components. Absolute paths and paths starting with `~` are kept.
+fn resolve_path(base: &str, path: &str) -> String {
+    if path.starts_with(['/', '~']) {
+        return path.to_string();
+    }
+
+    let mut components: Vec<&str> = base.split('/').collect();
+    components.pop();
+    for component in path.split('/') {
+        match component {
+            "" | "." => (),
+            ".." if components.last().is_some_and(|last| *last != "..") => {
+                components.pop();
+            }
+            component => components.push(component),
+        }
+    }
+    components.join("/")
+}
+
+#[test]
+fn parse() {
+    assert_eq!(resolve_path("a.org", "b.org"), "b.org");
+    assert_eq!(resolve_path("dir/a.org", "./b.org"), "dir/b.org");
+    assert_eq!(resolve_path("dir/sub/a.org", "../b.org"), "dir/b.org");
+    assert_eq!(resolve_path("a.org", "../b.org"), "../b.org");
+    assert_eq!(resolve_path("dir/a.org", "/abs/b.org"), "/abs/b.org");
+}
@@ -0,0 +1,165 @@
+use orgize::elements::Datetime;
+use orgize::{Agenda, AgendaKind, Org};
+
+fn date(day: u8) -> Datetime<'static> {
+    Datetime {
+        year: 2023,
+        month: 3,
+        day,
+        dayname: "".into(),
+        hour: None,
+        minute: None,
+    }
+}
+
+#[test]
+fn agenda() {
+    let org = Org::parse(
+        r#"#+CATEGORY: tasks
+* TODO [#C] low
+SCHEDULED: <2023-03-02 Thu>
+* DONE finished
+DEADLINE: <2023-02-20 Mon>
+* TODO [#A] high
+SCHEDULED: <2023-03-02 Thu>
+* trip
+:PROPERTIES:
+:CATEGORY: travel
+:END:
+<2023-03-01 Wed 08:00>--<2023-03-03 Fri 18:00>
+** COMMENT hidden
+*** child <2023-03-02 Thu>
+* TODO late
+DEADLINE: <2023-02-28 Tue 12:00>
+"#,
+    );
+
+    let mut agenda = Agenda::default();
+    agenda.add("notes/inbox.org", &org);
+
+    let entries: Vec<_> = agenda
+        .entries(&date(1), &date(3), &date(2))
+        .into_iter()
+        .map(|entry| {
+            (
+                entry.date.format(&Default::default()),
+                entry.kind,
+                entry.title.raw.to_string(),
+                entry.category,
+                entry.time,
+                entry.overdue,
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        entries,
+        vec![
+            (
+                "2023-03-01 Wed".into(),
+                AgendaKind::Timestamp,
+                "trip".into(),
+                "travel",
+                Some((8, 0)),
+                None
+            ),
+            (
+                "2023-03-02 Thu".into(),
+                AgendaKind::Scheduled,
+                "high".into(),
+                "tasks",
+                None,
+                None
+            ),
+            (
+                "2023-03-02 Thu".into(),
+                AgendaKind::Deadline,
+                "late".into(),
+                "tasks",
+                None,
+                Some(2)
+            ),
+            (
+                "2023-03-02 Thu".into(),
+                AgendaKind::Timestamp,
+                "trip".into(),
+                "travel",
+                None,
+                None
+            ),
+            (
+                "2023-03-02 Thu".into(),
+                AgendaKind::Scheduled,
+                "low".into(),
+                "tasks",
+                None,
+                None
+            ),
+            (
+                "2023-03-03 Fri".into(),
+                AgendaKind::Timestamp,
+                "trip".into(),
+                "travel",
+                None,
+                None
+            ),
+        ]
+    );
+
+    let org = Org::parse("* meeting <2023-03-02 Thu>\n");
+    let mut agenda = Agenda::default();
+    agenda.add("notes/inbox.org", &org);
+    let entries = agenda.entries(&date(1), &date(3), &date(2));
+    assert_eq!(entries[0].category, "inbox");
+}
+
+#[test]
+fn repeaters() {
+    let org = Org::parse(
+        "* TODO water\nSCHEDULED: <2023-01-02 Mon +1d>\n\
+         * meeting\n<2023-01-02 Mon 10:00 +1w>\n\
+         * TODO rent\nDEADLINE: <2022-11-05 Sat +1m>\n\
+         * trip\n<2022-12-30 Fri>--<2023-01-03 Tue +1y>\n",
+    );
+    let mut agenda = Agenda::default();
+    agenda.add("notes/inbox.org", &org);
+
+    let date = |day| Datetime {
+        year: 2023,
+        month: 1,
+        day,
+        dayname: "".into(),
+        hour: None,
+        minute: None,
+    };
+    let entries: Vec<_> = agenda
+        .entries(&date(2), &date(12), &date(4))
+        .into_iter()
+        .map(|entry| {
+            (
+                entry.date.day,
+                entry.title.raw.to_string(),
+                entry.time,
+                entry.overdue,
+            )
+        })
+        .collect();
+
+    let mut expected = vec![
+        (2, "meeting".to_string(), Some((10, 0)), None),
+        (2, "water".into(), None, None),
+        (2, "trip".into(), None, None),
+        (3, "water".into(), None, None),
+        (3, "trip".into(), None, None),
+        (4, "rent".into(), None, Some(60)),
+        (4, "water".into(), None, Some(2)),
+        (5, "rent".into(), None, None),
+    ];
+    for day in 5..=12 {
+        if day == 9 {
+            expected.push((9, "meeting".into(), Some((10, 0)), None));
+        }
+        expected.push((day, "water".into(), None, None));
+    }
+    assert_eq!(entries, expected);
+}
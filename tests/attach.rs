@@ -0,0 +1,41 @@
+use orgize::{Element, Event, Org};
+use pretty_assertions::assert_eq;
+
+const TEXT: &str = "* trip\n\
+                    :PROPERTIES:\n\
+                    :ID: 9a3f21c0\n\
+                    :END:\n\
+                    [[attachment:beach.png]]\n\
+                    ** day one\n\
+                    See [[attachment:notes.txt][notes]].\n";
+
+fn targets(org: &Org) -> Vec<String> {
+    org.iter()
+        .filter_map(|event| match event {
+            Event::Start(Element::Link(link)) => Some(link.target().to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn attachment_links() {
+    let mut org = Org::parse(TEXT);
+    assert_eq!(
+        targets(&org),
+        vec!["data/9a/3f21c0/beach.png", "data/9a/3f21c0/notes.txt"]
+    );
+
+    let mut html = Vec::new();
+    org.write_html(&mut html).unwrap();
+    let html = String::from_utf8(html).unwrap();
+    assert!(html.contains(r#"<img src="data/9a/3f21c0/beach.png" alt="beach.png">"#));
+    assert!(html.contains(r#"<a href="data/9a/3f21c0/notes.txt">notes</a>"#));
+
+    let end = TEXT.find(":END:").unwrap();
+    org.edit(end..end, ":DIR: files/\n");
+    assert_eq!(targets(&org), vec!["files/beach.png", "files/notes.txt"]);
+
+    let org = Org::parse("[[attachment:orphan.png]]");
+    assert_eq!(targets(&org), vec!["attachment:orphan.png"]);
+}
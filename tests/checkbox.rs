@@ -0,0 +1,82 @@
+mod common;
+
+use common::write;
+use orgize::elements::Checkbox;
+use orgize::{Element, Org};
+use pretty_assertions::assert_eq;
+
+fn items(org: &Org) -> Vec<indextree::NodeId> {
+    org.arena()
+        .iter()
+        .filter(|node| !node.is_removed() && matches!(node.get(), Element::ListItem(_)))
+        .filter_map(|node| org.arena().get_node_id(node))
+        .collect()
+}
+
+fn toggle(org: &mut Org, text: &str) {
+    let item = items(org)
+        .into_iter()
+        .find(|item| {
+            org.text_content(*item)
+                .lines()
+                .next()
+                .unwrap()
+                .contains(text)
+        })
+        .unwrap();
+    assert!(org.toggle_checkbox(item));
+}
+
+#[test]
+fn toggle_checkbox() {
+    let mut org = Org::parse(
+        "* trip [1/3]\n\
+         - [X] tickets\n\
+         - [ ] packing [0/2]\n\
+         \x20 1. [@3] [ ] socks\n\
+         \x20 2. [ ] shirts\n\
+         - [ ] hotel\n\
+         - plain\n",
+    );
+
+    toggle(&mut org, "socks");
+    assert_eq!(
+        write(&org),
+        "* trip [1/3]\n\
+         - [X] tickets\n\
+         - [-] packing [1/2]\n\
+         \x20 1. [@3] [X] socks\n\
+         \x20 2. [ ] shirts\n\
+         - [ ] hotel\n\
+         - plain\n"
+    );
+
+    toggle(&mut org, "shirts");
+    assert!(write(&org).starts_with("* trip [2/3]\n- [X] tickets\n- [X] packing [2/2]\n"));
+
+    // sub-items follow their parent
+    toggle(&mut org, "packing");
+    assert_eq!(
+        write(&org),
+        "* trip [1/3]\n\
+         - [X] tickets\n\
+         - [ ] packing [0/2]\n\
+         \x20 1. [@3] [ ] socks\n\
+         \x20 2. [ ] shirts\n\
+         - [ ] hotel\n\
+         - plain\n"
+    );
+
+    let states: Vec<_> = items(&org)
+        .into_iter()
+        .map(|item| org.checkbox(item))
+        .collect();
+    assert!(states.contains(&Some(Checkbox::On)));
+    assert!(states.contains(&None));
+
+    let plain = items(&org)
+        .into_iter()
+        .find(|item| org.text_content(*item) == "plain")
+        .unwrap();
+    assert!(!org.toggle_checkbox(plain));
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "cli")]
+
+use pretty_assertions::assert_eq;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn orgize(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_orgize"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // the process may exit before reading its input
+    let _ = child.stdin.take().unwrap().write_all(input.as_bytes());
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(args: &[&str], input: &str) -> String {
+    let output = orgize(args, input);
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn formats() {
+    let input = "* NEXT Title\n*bold* [[https://example.com][link]]\n";
+
+    assert_eq!(
+        stdout(&[], input),
+        "<main><h1>NEXT Title</h1><section><p><b>bold</b> \
+         <a href=\"https://example.com\">link</a></p></section></main>\n"
+    );
+    assert_eq!(
+        stdout(&["--to", "markdown", "--todo", "NEXT"], input),
+        "# Title\n\n**bold** [link](https://example.com)\n"
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_str(&stdout(&["-t", "json", "--todo", "NEXT", "-"], input)).unwrap();
+    assert_eq!(json["children"][0]["children"][0]["keyword"], "NEXT");
+
+    let page = stdout(&["--standalone", "--no-css"], "#+TITLE: Notes\n");
+    assert!(page.starts_with("<!DOCTYPE html>\n"));
+    assert!(page.contains("<title>Notes</title>"));
+    assert!(!page.contains("<style>"));
+}
+
+#[test]
+fn invalid_arguments() {
+    for args in [
+        &["--to", "pdf"][..],
+        &["--to"],
+        &["--unknown"],
+        &["-t", "json", "--standalone"],
+        &["a.org", "b.org"],
+    ] {
+        let output = orgize(args, "");
+        assert_eq!(output.status.code(), Some(2));
+        assert!(String::from_utf8(output.stderr).unwrap().contains("Usage:"));
+    }
+
+    let output = orgize(&["missing.org"], "");
+    assert_eq!(output.status.code(), Some(1));
+}
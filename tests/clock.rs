@@ -0,0 +1,78 @@
+mod common;
+
+use common::write;
+use orgize::elements::{Clock, Datetime};
+use orgize::{Element, Event, Org, Workspace};
+use pretty_assertions::assert_eq;
+
+fn datetime(day: u8, hour: u8, minute: u8) -> Datetime<'static> {
+    Datetime {
+        year: 2023,
+        month: 1,
+        day,
+        dayname: "".into(),
+        hour: Some(hour),
+        minute: Some(minute),
+    }
+}
+
+#[test]
+fn clock() {
+    let mut org = Org::parse(
+        "* project\n** task\n:PROPERTIES:\n:ID: 1\n:END:\n  :LOGBOOK:\n  - note\n  :END:\ntext\n",
+    );
+
+    let task = org.headlines().nth(1).unwrap();
+    assert!(org.clock_in(task, &datetime(2, 23, 30)));
+    let task = org.headlines().nth(1).unwrap();
+    assert!(!org.clock_in(task, &datetime(3, 0, 0)));
+    assert_eq!(org.running_clocks().len(), 1);
+    assert_eq!(org.running_clocks()[0].title(&org).raw, "task");
+
+    // clocks of sub-headlines don't count
+    let project = org.headlines().next().unwrap();
+    assert_eq!(org.clock_out(project, &datetime(3, 0, 0)), None);
+
+    let task = org.headlines().nth(1).unwrap();
+    assert_eq!(org.clock_out(task, &datetime(2, 0, 0)), None);
+    let duration = org.clock_out(task, &datetime(3, 1, 5)).unwrap();
+    assert_eq!(duration.minutes(), 95);
+    assert!(org.running_clocks().is_empty());
+
+    assert_eq!(
+        write(&org),
+        "* project\n** task\n:PROPERTIES:\n:ID: 1\n:END:\n:LOGBOOK:\n\
+         CLOCK: [2023-01-02 Mon 23:30]--[2023-01-03 Tue 01:05] => 1:35\n  - note\n\
+         :END:\ntext\n"
+    );
+
+    let task = org.headlines().nth(1).unwrap();
+    let clocks: Vec<_> = org
+        .iter()
+        .filter_map(|event| match event {
+            Event::Start(Element::Clock(Clock::Closed { duration, .. })) => {
+                Some(duration.to_string())
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(clocks, vec!["1:35"]);
+    assert!(org.clock_in(task, &datetime(4, 9, 0)));
+}
+
+#[test]
+fn running_clocks() {
+    let mut workspace = Workspace::default();
+    workspace.insert("a.org", "* idle\n* busy\nCLOCK: [2023-01-02 Mon 09:00]\n");
+    workspace.insert(
+        "b.org",
+        "* done\nCLOCK: [2023-01-02 Mon 09:00]--[2023-01-02 Mon 10:00] =>  1:00\n",
+    );
+
+    let running: Vec<_> = workspace
+        .running_clocks()
+        .iter()
+        .map(|(path, headline)| (*path, headline.title(&workspace[*path]).raw.to_string()))
+        .collect();
+    assert_eq!(running, vec![("a.org", "busy".to_string())]);
+}
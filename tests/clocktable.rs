@@ -0,0 +1,91 @@
+use orgize::elements::Datetime;
+use orgize::Org;
+use pretty_assertions::assert_eq;
+
+const TEXT: &str = r#"* project
+:LOGBOOK:
+CLOCK: [2023-01-02 Mon 23:00]--[2023-01-03 Tue 01:00] =>  2:00
+CLOCK: [2023-01-04 Wed 09:00]
+:END:
+#+BEGIN: clocktable :scope subtree :tstart "<2023-01-03 Tue>"
+old
+#+END:
+** task
+CLOCK: [2023-01-03 Tue 09:00]--[2023-01-03 Tue 11:30] =>  2:30
+*** subtask
+CLOCK: [2023-01-05 Thu 09:00]--[2023-01-05 Thu 09:15] =>  0:15
+** idle
+* other
+"#;
+
+#[test]
+fn clock_report() {
+    let org = Org::parse(TEXT);
+
+    let report = org.clock_report(None, None);
+    let rows: Vec<_> = report
+        .rows
+        .iter()
+        .map(|row| {
+            (
+                row.title.raw.as_ref(),
+                row.level,
+                row.minutes,
+                row.own_minutes,
+            )
+        })
+        .collect();
+    assert_eq!(report.total, 285);
+    assert_eq!(
+        rows,
+        vec![
+            ("project", 1, 285, 120),
+            ("task", 2, 165, 150),
+            ("subtask", 3, 15, 15)
+        ]
+    );
+
+    let date = |day| Datetime {
+        year: 2023,
+        month: 1,
+        day,
+        dayname: "".into(),
+        hour: None,
+        minute: None,
+    };
+    let report = org.clock_report(Some(&date(3)), Some(&date(5)));
+    assert_eq!(report.total, 210);
+    assert_eq!(report.rows.len(), 2);
+
+    assert_eq!(
+        report.to_table(Some(1)),
+        "| Headline     | Time   |\n\
+         |--------------+--------|\n\
+         | *Total time* | *3:30* |\n\
+         |--------------+--------|\n\
+         | project      | 3:30   |\n"
+    );
+}
+
+#[test]
+fn update_clocktables() {
+    let mut org = Org::parse(TEXT);
+    org.update_clocktables();
+
+    let expected = TEXT.replace(
+        "old\n",
+        "| Headline       | Time   |      |      |\n\
+         |----------------+--------+------+------|\n\
+         | *Total time*   | *3:45* |      |      |\n\
+         |----------------+--------+------+------|\n\
+         | project        | 3:45   |      |      |\n\
+         | \\_  task       |        | 2:45 |      |\n\
+         | \\_    subtask |        |      | 0:15 |\n",
+    );
+    let expected = Org::parse(&expected);
+
+    assert_eq!(
+        serde_json::to_string_pretty(&org).unwrap(),
+        serde_json::to_string_pretty(&expected).unwrap()
+    );
+}
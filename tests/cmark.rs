@@ -0,0 +1,50 @@
+#![cfg(feature = "pulldown-cmark")]
+
+use orgize::pulldown_cmark::html::push_html;
+use orgize::Org;
+
+fn render(s: &str) -> String {
+    let org = Org::parse(s);
+    let mut html = String::new();
+    push_html(&mut html, org.cmark_events());
+    html
+}
+
+#[test]
+fn blocks() {
+    assert_eq!(
+        render("#+BEGIN_SRC rust\nfn main() {}\n#+END_SRC\n\n#+BEGIN_QUOTE\nquote\n#+END_QUOTE\n-----\n"),
+        "<pre><code class=\"language-rust\">fn main() {}\n</code></pre>\n\
+         <blockquote>\n<p>quote</p>\n</blockquote>\n<hr />\n"
+    );
+}
+
+#[test]
+fn lists() {
+    assert_eq!(
+        render("3. three\n4. four\n"),
+        "<ol start=\"3\">\n<li>\n<p>three</p>\n</li>\n<li>\n<p>four</p>\n</li>\n</ol>\n"
+    );
+}
+
+#[test]
+fn tables() {
+    assert_eq!(
+        render("| a | b |\n|---+---|\n| 1 | 2 |\n"),
+        "<table><thead><tr><th>a</th><th>b</th></tr></thead><tbody>\n\
+         <tr><td>1</td><td>2</td></tr>\n</tbody></table>\n"
+    );
+    assert_eq!(
+        render("| 1 | 2 |\n| 3 | 4 |\n"),
+        "<table><thead><tr></tr></thead><tbody>\n\
+         <tr><td>1</td><td>2</td></tr>\n<tr><td>3</td><td>4</td></tr>\n</tbody></table>\n"
+    );
+}
+
+#[test]
+fn skipped() {
+    assert_eq!(
+        render("#+TITLE: title\n:DRAWER:\nhidden\n:END:\n# comment\ntext ~code~ +strike+\n"),
+        "<p>text <code>code</code> <del>strike</del></p>\n"
+    );
+}
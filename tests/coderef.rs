@@ -0,0 +1,53 @@
+use orgize::{Element, Event, Org};
+use pretty_assertions::assert_eq;
+
+const TEXT: &str = r#"#+BEGIN_SRC emacs-lisp -n -r
+(save-excursion                 (ref:sc)
+   (goto-char (point-min)))     (ref:jump)
+#+END_SRC
+In line [[(sc)]] we remember the current position. [[(jump)][Line (jump)]]
+jumps to point-min, [[(missing)]] doesn't exist.
+
+#+BEGIN_EXAMPLE -l "<%s>"
+foo <bar>
+#+END_EXAMPLE
+"#;
+
+#[test]
+fn coderefs() {
+    let org = Org::parse(TEXT);
+
+    let sc = org.resolve_coderef("sc").unwrap();
+    assert_eq!(sc.line, 1);
+    assert_eq!(org.resolve_coderef("jump").unwrap().line, 2);
+    let bar = org.resolve_coderef("bar").unwrap();
+    assert_eq!(bar.line, 1);
+    assert!(matches!(org[bar.block], Element::ExampleBlock(_)));
+
+    let targets: Vec<_> = org
+        .iter()
+        .filter_map(|event| match event {
+            Event::Start(Element::Link(link)) => Some(link.target().to_string()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(targets, vec!["#coderef-sc", "#coderef-jump", "(missing)"]);
+
+    let mut html = Vec::new();
+    org.write_html(&mut html).unwrap();
+    assert_eq!(
+        String::from_utf8(html).unwrap(),
+        "<main><section>\
+         <div class=\"org-src-container\"><pre class=\"src src-emacs-lisp\">\
+         <span id=\"coderef-sc\" class=\"coderef-off\">(save-excursion</span>\n\
+         <span id=\"coderef-jump\" class=\"coderef-off\">   (goto-char (point-min)))</span>\n\
+         </pre></div>\
+         <p>In line <a href=\"#coderef-sc\">sc</a> we remember the current position. \
+         <a href=\"#coderef-jump\">Line (jump)</a>\n\
+         jumps to point-min, <a href=\"(missing)\">missing</a> doesn&apos;t exist.</p>\
+         <pre class=\"example\">\
+         <span id=\"coderef-bar\" class=\"coderef-off\">foo &lt;bar&gt;</span>\n\
+         </pre>\
+         </section></main>"
+    );
+}
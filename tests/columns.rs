@@ -0,0 +1,93 @@
+use orgize::{Element, Event, Org};
+use pretty_assertions::assert_eq;
+
+const TEXT: &str = r#"#+COLUMNS: %ITEM %Effort{:} %Cost{+} %Done{X/}
+* TODO project :work:
+:PROPERTIES:
+:COLUMNS: %ITEM %TODO %Cost{+} %CLOCKSUM
+:END:
+#+BEGIN: columnview :id local
+#+END:
+** design
+:PROPERTIES:
+:Effort: 1:30
+:Cost: 10
+:Done: [X]
+:END:
+CLOCK: [2023-01-02 Mon 09:00]--[2023-01-02 Mon 10:00] =>  1:00
+** build
+:PROPERTIES:
+:Effort: 2:15
+:Cost: 2.5
+:Done: [ ]
+:END:
+*** deploy
+:PROPERTIES:
+:cost: 4
+:END:
+* other
+:PROPERTIES:
+:Effort: 0:30
+:END:
+"#;
+
+#[test]
+fn column_view() {
+    let org = Org::parse(TEXT);
+
+    let view = org.column_view(None);
+    let rows: Vec<_> = view
+        .rows
+        .iter()
+        .map(|row| {
+            row.values
+                .iter()
+                .map(|value| value.as_deref().unwrap_or(""))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    assert_eq!(
+        rows,
+        vec![
+            vec!["project", "3:45", "14", "[1/2]"],
+            vec!["design", "1:30", "10", "[X]"],
+            vec!["build", "2:15", "4", "[ ]"],
+            vec!["deploy", "", "4", ""],
+            vec!["other", "0:30", "", ""],
+        ]
+    );
+
+    let project = org.headlines().next().unwrap();
+    let view = org.column_view(Some(project));
+    assert_eq!(
+        view.to_table(Some(2)),
+        "| ITEM      | TODO | Cost | CLOCKSUM |\n\
+         |-----------+------+------+----------|\n\
+         | * project | TODO | 14   | 1:00     |\n\
+         | ** design |      | 10   | 1:00     |\n\
+         | ** build  |      | 4    |          |\n"
+    );
+}
+
+#[test]
+fn update_columnviews() {
+    let mut org = Org::parse(TEXT);
+    org.update_columnviews();
+
+    let rows = org
+        .iter()
+        .filter(|event| matches!(event, Event::Start(Element::TableRow(_))))
+        .count();
+    assert_eq!(rows, 6);
+
+    let mut org =
+        Org::parse("#+BEGIN: columnview :id global :maxlevel 1\nold\n#+END:\n* a\n** b\n");
+    org.update_columnviews();
+
+    let mut html = Vec::new();
+    org.write_html(&mut html).unwrap();
+    let html = String::from_utf8(html).unwrap();
+    assert!(!html.contains("old"));
+    assert!(html.contains("* a"));
+    assert!(!html.contains("** b"));
+}
@@ -0,0 +1,22 @@
+// helpers shared by integration tests, each of which uses only some of them
+#![allow(dead_code)]
+
+use orgize::Org;
+use std::ops::Range;
+
+pub type Spans = Vec<(Option<Range<usize>>, Option<Range<usize>>)>;
+
+/// Returns spans and contents spans of all nodes in document order.
+pub fn spans(org: &Org) -> Spans {
+    let root = org.cursor().node();
+    root.descendants(org.arena())
+        .map(|node| (org.span(node), org.contents_span(node)))
+        .collect()
+}
+
+/// Writes `org` as org format.
+pub fn write(org: &Org) -> String {
+    let mut writer = Vec::new();
+    org.write_org(&mut writer).unwrap();
+    String::from_utf8(writer).unwrap()
+}
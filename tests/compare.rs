@@ -0,0 +1,27 @@
+use orgize::Org;
+
+#[test]
+fn structural_equality() {
+    let a = Org::parse("#+TITLE: notes\n\n* TODO task :tag:\n- [ ] item\n");
+    let b = Org::parse("#+TITLE: notes\n\n* TODO task :tag:\n- [ ] item\n");
+    let c = Org::parse("#+TITLE: notes\n\n* TODO task :tag:\n- [X] item\n");
+    assert!(a == b);
+    assert!(a != c);
+
+    // same subtree at different offsets of different documents
+    let d = Org::parse("* intro\n* TODO task :tag:\n- [ ] item\n");
+    let task_a = a.headlines().next().unwrap();
+    let task_d = d.headlines().nth(1).unwrap();
+    assert_ne!(
+        a.span(task_a.headline_node()),
+        d.span(task_d.headline_node())
+    );
+    assert!(a.subtree_eq_with(task_a.headline_node(), &d, task_d.headline_node()));
+    assert!(!a.subtree_eq_with(task_a.title_node(), &d, task_d.headline_node()));
+
+    // pending contents of lazily parsed documents are compared as texts
+    let lazy = Org::parse_lazy("* TODO task :tag:\n- [ ] item\n");
+    let other = Org::parse_lazy("* TODO task :tag:\n- [X] item\n");
+    assert!(lazy == Org::parse_lazy("* TODO task :tag:\n- [ ] item\n"));
+    assert!(lazy != other);
+}
@@ -0,0 +1,69 @@
+use orgize::{elements::Element, CoverageError, Org};
+
+const TEXT: &str = r#"
+#+TITLE: coverage
+
+* TODO title 1 :tag:
+  SCHEDULED: <2019-01-01 Tue>
+  :PROPERTIES:
+  :ID: a
+  :END:
+section *bold /italic/* [[https://example.com][link]]
+
+  - item 1
+    more
+  - [X] item 2
+
+#+BEGIN_QUOTE
+quote
+#+END_QUOTE
+| a | b |
+|---+---|
+| c | d |
+
+[fn:1] definition
+:LOGBOOK:
+CLOCK: [2019-01-01 Tue 10:00]
+:END:
+#+BEGIN: clocktable
+table
+#+END:
+** title 2
+-----
+# comment
+: fixed
+"#;
+
+#[test]
+fn parse() {
+    assert_eq!(Org::parse(TEXT).verify_coverage(), vec![]);
+}
+
+#[test]
+fn lazy() {
+    let mut org = Org::parse_lazy(TEXT);
+    assert_eq!(org.verify_coverage(), vec![]);
+
+    org.expand_all();
+    assert_eq!(org.verify_coverage(), vec![]);
+}
+
+#[test]
+fn edit() {
+    let mut org = Org::parse(TEXT);
+    org.edit(30..35, "one *two*");
+    assert_eq!(org.verify_coverage(), vec![]);
+}
+
+#[test]
+fn missing_span() {
+    let mut org = Org::parse("* title");
+    let root = org.cursor().node();
+    let section = org.arena_mut().new_node(Element::Section);
+    root.append(section, org.arena_mut());
+
+    assert_eq!(
+        org.verify_coverage(),
+        vec![CoverageError::MissingSpan { at: section }]
+    );
+}
@@ -0,0 +1,103 @@
+use orgize::{Element, Org};
+use pretty_assertions::assert_eq;
+
+const TEXT: &str = "* secrets :crypt:\n\
+                    :PROPERTIES:\n\
+                    :ID: 4e1c\n\
+                    :END:\n\
+                    -----BEGIN PGP MESSAGE-----\n\
+                    \n\
+                    hQEMA1\n\
+                    -----END PGP MESSAGE-----\n\
+                    \n\
+                    * plain :crypt:\n\
+                    not encrypted yet\n\
+                    * keys :crypt:\n\
+                    -----BEGIN PGP MESSAGE-----\n\
+                    hQEMA2\n\
+                    -----END PGP MESSAGE-----\n";
+
+fn titles(org: &Org, headlines: Vec<orgize::Headline>) -> Vec<String> {
+    headlines
+        .into_iter()
+        .map(|headline| headline.title(org).raw.to_string())
+        .collect()
+}
+
+#[test]
+fn encrypted_sections() {
+    let org = Org::parse(TEXT);
+    assert_eq!(
+        titles(&org, org.encrypted_headlines()),
+        vec!["secrets", "keys"]
+    );
+    assert!(org.validate().is_empty());
+
+    let section = org.headlines().next().unwrap().headline_node();
+    let data: Vec<_> = section
+        .children(org.arena())
+        .filter_map(|node| match &org[node] {
+            Element::EncryptedSection(section) => Some((&*section.data, section.post_blank)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        data,
+        vec![(
+            "-----BEGIN PGP MESSAGE-----\n\nhQEMA1\n-----END PGP MESSAGE-----\n",
+            1
+        )]
+    );
+
+    let mut writer = Vec::new();
+    org.write_org(&mut writer).unwrap();
+    assert_eq!(String::from_utf8(writer).unwrap(), TEXT);
+
+    // text under an untagged headline is never treated as encrypted
+    let org = Org::parse("* keys\n-----BEGIN PGP MESSAGE-----\n-----END PGP MESSAGE-----\n");
+    assert!(org.encrypted_headlines().is_empty());
+}
+
+#[test]
+fn decrypt_entries() {
+    let mut org = Org::parse(TEXT);
+    let decrypted = org
+        .decrypt_entries(|message| {
+            Ok::<_, ()>(if message.contains("hQEMA1") {
+                "** bank\n1234".into()
+            } else {
+                "ssh-ed25519\n".into()
+            })
+        })
+        .unwrap();
+    assert_eq!(decrypted, 2);
+    assert!(org.encrypted_headlines().is_empty());
+
+    let mut writer = Vec::new();
+    org.write_org(&mut writer).unwrap();
+    assert_eq!(
+        String::from_utf8(writer).unwrap(),
+        "* secrets :crypt:\n\
+         :PROPERTIES:\n\
+         :ID: 4e1c\n\
+         :END:\n\
+         ** bank\n\
+         1234\n\
+         \n\
+         * plain :crypt:\n\
+         not encrypted yet\n\
+         * keys :crypt:\n\
+         ssh-ed25519\n"
+    );
+
+    let secrets = org.headlines().next().unwrap();
+    assert_eq!(titles(&org, secrets.children(&org).collect()), vec!["bank"]);
+
+    // decryption errors leave the document untouched
+    let mut org = Org::parse(TEXT);
+    assert_eq!(
+        org.decrypt_entries(|_| Err("bad passphrase")),
+        Err("bad passphrase")
+    );
+    assert_eq!(org.encrypted_headlines().len(), 2);
+}
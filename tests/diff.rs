@@ -0,0 +1,85 @@
+use orgize::{ChangeKind, Element, Org};
+use pretty_assertions::assert_eq;
+
+fn changes(old: &Org, new: &Org) -> Vec<(ChangeKind, String)> {
+    old.diff(new)
+        .iter()
+        .map(|change| (change.kind, change.outline_path()))
+        .collect()
+}
+
+#[test]
+fn unchanged() {
+    let text = "intro\n* a\n** b\ntext\n| table |\n* c\n";
+    assert!(Org::parse(text).diff(&Org::parse(text)).is_empty());
+}
+
+#[test]
+fn elements() {
+    let old = Org::parse("intro\n\n- item\n* a\none\n\ntwo\n\nthree\n");
+    // blank lines after elements are part of them
+    let new = Org::parse(
+        "intro\n\n- item\n- new\n* a\nzero\n\none\n\n#+BEGIN_QUOTE\nquote\n#+END_QUOTE\n\nthree\n",
+    );
+
+    let diff = old.diff(&new);
+    assert_eq!(
+        diff.iter()
+            .map(|change| (change.kind, change.outline_path()))
+            .collect::<Vec<_>>(),
+        vec![
+            (ChangeKind::Modified, "".to_string()),
+            (ChangeKind::Added, "a".to_string()),
+            (ChangeKind::Added, "a".to_string()),
+            (ChangeKind::Removed, "a".to_string()),
+        ]
+    );
+    assert!(matches!(new[diff[0].new.unwrap()], Element::List(_)));
+    assert!(matches!(
+        new[diff[1].new.unwrap()],
+        Element::Paragraph { .. }
+    ));
+    assert!(matches!(new[diff[2].new.unwrap()], Element::QuoteBlock(_)));
+    assert!(matches!(
+        old[diff[3].old.unwrap()],
+        Element::Paragraph { .. }
+    ));
+}
+
+#[test]
+fn headlines() {
+    let old = Org::parse(
+        "* projects\n** website\n*** design\n** garden\n\
+         * notes\n:PROPERTIES:\n:ID: n1\n:END:\n",
+    );
+    let new = Org::parse(
+        "* projects\n** DONE website\n** shop\n*** stock\n\
+         * archive\n** notes\n:PROPERTIES:\n:ID: n1\n:END:\n",
+    );
+
+    assert_eq!(
+        changes(&old, &new),
+        vec![
+            (ChangeKind::Modified, "projects/website".to_string()),
+            (ChangeKind::Added, "projects/shop".to_string()),
+            (ChangeKind::Added, "archive".to_string()),
+            (ChangeKind::Moved, "archive/notes".to_string()),
+            (ChangeKind::Removed, "projects/website/design".to_string()),
+            (ChangeKind::Removed, "projects/garden".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn duplicated_titles() {
+    let old = Org::parse("* note\none\n* note\ntwo\n");
+    let new = Org::parse("* note\none\n* note\ntwo\n* note\nthree\n");
+
+    let diff = old.diff(&new);
+    assert_eq!(diff.len(), 1);
+    assert_eq!(diff[0].kind, ChangeKind::Added);
+    assert_eq!(
+        new.headlines().nth(2).unwrap().headline_node(),
+        diff[0].new.unwrap()
+    );
+}
@@ -0,0 +1,52 @@
+use orgize::export::DotConfig;
+use orgize::Workspace;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn dot() {
+    let mut workspace = Workspace::default();
+    workspace.insert(
+        "notes/rust.org",
+        "See [[file:../index.org]].\n\
+         * Ownership :core:\n\
+         :PROPERTIES:\n:CUSTOM_ID: own\n:END:\n\
+         ** DONE Borrowing \"rules\"\n\
+         Back to [[#own]], twice: [[#own]].\n",
+    );
+    workspace.insert(
+        "index.org",
+        "* TODO Learn\n[[file:notes/rust.org::#own]] [[file:missing.org]]\n",
+    );
+
+    let config = DotConfig {
+        tag_colors: vec![("core".into(), "lightblue".into())],
+        ..Default::default()
+    };
+    let mut writer = Vec::new();
+    workspace.write_dot(&mut writer, &config).unwrap();
+    assert_eq!(
+        String::from_utf8(writer).unwrap(),
+        "digraph org {\n    \
+         node [shape=box];\n    \
+         \"f0\" [label=\"notes/rust.org\", shape=folder];\n    \
+         \"f0h0\" [label=\"Ownership\", style=filled, fillcolor=\"lightblue\"];\n    \
+         \"f0h1\" [label=\"DONE Borrowing \\\"rules\\\"\", color=\"forestgreen\"];\n    \
+         \"f1\" [label=\"index.org\", shape=folder];\n    \
+         \"f1h0\" [label=\"TODO Learn\", color=\"red\"];\n    \
+         \"f0\" -> \"f0h0\" [color=\"gray\", arrowhead=none];\n    \
+         \"f0h0\" -> \"f0h1\" [color=\"gray\", arrowhead=none];\n    \
+         \"f1\" -> \"f1h0\" [color=\"gray\", arrowhead=none];\n    \
+         \"f0\" -> \"f1\" [color=\"blue\"];\n    \
+         \"f0h1\" -> \"f0h0\";\n    \
+         \"f1h0\" -> \"f0h0\" [color=\"blue\"];\n\
+         }\n"
+    );
+
+    let config = DotConfig {
+        structure: false,
+        ..Default::default()
+    };
+    let mut writer = Vec::new();
+    workspace.write_dot(&mut writer, &config).unwrap();
+    assert!(!String::from_utf8(writer).unwrap().contains("arrowhead"));
+}
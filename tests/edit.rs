@@ -0,0 +1,65 @@
+use orgize::Org;
+use pretty_assertions::assert_eq;
+
+macro_rules! test_suite {
+    ($name:ident, $content:expr, $range:expr, $text:expr) => {
+        #[test]
+        fn $name() {
+            let mut org = Org::parse($content);
+            org.edit($range, $text);
+
+            let mut content = String::from($content);
+            content.replace_range($range, $text);
+            let expected = Org::parse(&content);
+
+            assert_eq!(
+                serde_json::to_string_pretty(&org).unwrap(),
+                serde_json::to_string_pretty(&expected).unwrap()
+            );
+
+            let spans = |org: &Org| {
+                let root = org.cursor().node();
+                root.descendants(org.arena())
+                    .map(|node| (org.span(node), org.contents_span(node)))
+                    .collect::<Vec<_>>()
+            };
+
+            assert_eq!(spans(&org), spans(&expected));
+        }
+    };
+}
+
+const TEXT: &str = r#"
+#+TITLE: edit
+
+* title 1
+section 1
+** title 2
+section 2
+* title 3
+section 3
+
+* title 4
+section 4
+"#;
+
+test_suite!(title, TEXT, 18..23, "one");
+test_suite!(section, TEXT, 26..35, "paragraph\n\n*bold*");
+test_suite!(insert_before_headline, TEXT, 57..57, "more\n");
+test_suite!(insert_headline, TEXT, 57..57, "* title 2.5\n");
+test_suite!(remove_headline, TEXT, 36..57, "");
+test_suite!(remove_stars, TEXT, 36..39, "");
+test_suite!(lower_level, TEXT, 57..58, "***");
+test_suite!(raise_level, TEXT, 36..38, "*");
+test_suite!(across_headlines, TEXT, 30..70, "1\n** title");
+test_suite!(preface, TEXT, 1..14, "#+AUTHOR: me");
+test_suite!(leading_blank_lines, TEXT, 0..0, "\n\n");
+test_suite!(trailing_blank_lines, TEXT, 98..98, "\n\n");
+test_suite!(remove_newline, TEXT, 76..77, "");
+test_suite!(remove_blank_line, TEXT, 77..78, "");
+test_suite!(append, TEXT, 98..98, "** title 5\n");
+test_suite!(unicode, TEXT, 67..74, "节");
+test_suite!(whole, TEXT, 0..98, "");
+test_suite!(past_last_span, "* h\n*\r\nabc", 5..10, "");
+test_suite!(past_trailing_text, "*\n*\r\n[|+\n\r", 1..8, "|c#");
+test_suite!(before_trailing_text, ":\n* >\n*\r\n", 0..3, "");
@@ -0,0 +1,51 @@
+use orgize::{Duration, Org};
+
+#[test]
+fn effort_rollup() {
+    let org = Org::parse(
+        r#"* project
+:PROPERTIES:
+:Effort:   10:00
+:END:
+** design
+:PROPERTIES:
+:EFFORT:   1:30
+:END:
+CLOCK: [2023-01-02 Mon 09:00]--[2023-01-02 Mon 10:00] =>  1:00
+*** review
+:PROPERTIES:
+:Effort:   0:45
+:END:
+CLOCK: [2023-01-03 Tue 09:00]--[2023-01-03 Tue 09:20] =>  0:20
+** build
+:PROPERTIES:
+:Effort:   oops
+:END:
+* notes
+"#,
+    );
+
+    let summaries: Vec<_> = org
+        .effort_rollup()
+        .into_iter()
+        .map(|summary| {
+            (
+                summary.title.raw.to_string(),
+                summary.effort.map(Duration::minutes),
+                summary.total_effort.minutes(),
+                summary.clocked.minutes(),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        summaries,
+        vec![
+            ("project".into(), Some(600), 45, 80),
+            ("design".into(), Some(90), 45, 80),
+            ("review".into(), Some(45), 45, 20),
+            ("build".into(), None, 0, 0),
+            ("notes".into(), None, 0, 0),
+        ]
+    );
+}
@@ -0,0 +1,109 @@
+use orgize::{Element, Event, Executor, HeaderArgs, Org, ResultCollection};
+use pretty_assertions::assert_eq;
+
+fn results(org: &Org) -> Vec<String> {
+    org.iter()
+        .filter_map(|event| match event {
+            Event::Start(Element::Keyword(keyword)) => {
+                Some(format!("{} {}", keyword.key, keyword.value))
+            }
+            Event::Start(Element::FixedWidth(fixed_width)) => Some(fixed_width.value.to_string()),
+            Event::Start(Element::Drawer(drawer)) => Some(drawer.name.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[derive(Default)]
+struct Calc {
+    collections: Vec<ResultCollection>,
+}
+
+impl Executor for Calc {
+    type Error = String;
+
+    fn execute(
+        &mut self,
+        language: &str,
+        code: &str,
+        collection: ResultCollection,
+        _: &HeaderArgs,
+    ) -> Result<String, String> {
+        assert_eq!(language, "calc");
+        self.collections.push(collection);
+        let mut numbers = code.split('+').map(|n| n.trim().parse::<i32>());
+        match (numbers.next(), numbers.next()) {
+            (Some(Ok(a)), Some(Ok(b))) => Ok((a + b).to_string()),
+            _ => Err(code.to_string()),
+        }
+    }
+}
+
+#[test]
+fn execute() {
+    let mut org = Org::parse(
+        "#+NAME: one\n#+BEGIN_SRC calc\n1 + 1\n#+END_SRC\n\n\
+         #+RESULTS: one\n: old\n: lines\n\n\
+         * two\n\
+         #+BEGIN_SRC calc :results output drawer\n2 + 2\n#+END_SRC\n\
+         #+BEGIN_SRC calc :results silent\n3 + 3\n#+END_SRC\n\
+         #+BEGIN_SRC calc :eval no\n4 + 4\n#+END_SRC",
+    );
+
+    let mut calc = Calc::default();
+    assert_eq!(org.execute_src_blocks(&mut calc), Ok(3));
+    assert_eq!(
+        calc.collections,
+        vec![
+            ResultCollection::Value,
+            ResultCollection::Output,
+            ResultCollection::Value
+        ]
+    );
+    assert_eq!(
+        results(&org),
+        vec!["NAME one", "RESULTS one", ": 2\n", "RESULTS ", "results"]
+    );
+
+    // executing again replaces existing results
+    assert_eq!(org.execute_src_blocks(&mut calc), Ok(3));
+    assert_eq!(
+        results(&org),
+        vec!["NAME one", "RESULTS one", ": 2\n", "RESULTS ", "results"]
+    );
+
+    let mut org = Org::parse("#+BEGIN_SRC calc\n1 +\n#+END_SRC\n");
+    assert_eq!(
+        org.execute_src_blocks(&mut calc),
+        Err(String::from("1 +\n"))
+    );
+}
+
+struct Echo;
+
+impl Executor for Echo {
+    type Error = String;
+
+    fn execute(
+        &mut self,
+        _: &str,
+        code: &str,
+        _: ResultCollection,
+        _: &HeaderArgs,
+    ) -> Result<String, String> {
+        Ok(code.to_string())
+    }
+}
+
+#[test]
+fn escaped_results() {
+    let mut org = Org::parse("#+BEGIN_SRC org :wrap example\n,* a\n  ,#+b\n#+END_SRC\n");
+    assert_eq!(org.execute_src_blocks(&mut Echo), Ok(1));
+
+    let mut text = Vec::new();
+    org.write_org(&mut text).unwrap();
+    assert!(String::from_utf8(text)
+        .unwrap()
+        .ends_with("#+BEGIN_EXAMPLE\n,* a\n  ,#+b\n#+END_EXAMPLE\n"));
+    assert_eq!(org.headlines().count(), 0);
+}
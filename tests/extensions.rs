@@ -0,0 +1,69 @@
+use orgize::{Org, ParseConfig};
+
+#[derive(Debug, PartialEq)]
+struct Section(String);
+
+#[derive(Debug, PartialEq, Default)]
+struct Aliases(Vec<String>);
+
+fn config() -> ParseConfig {
+    let mut config = ParseConfig::default();
+    config
+        .keyword_handlers
+        .register("HUGO_SECTION", |keyword, extensions| {
+            extensions.insert(Section(keyword.value.to_string()));
+        })
+        .register("ROAM_ALIAS", |keyword, extensions| {
+            extensions
+                .get_or_insert_with(Aliases::default)
+                .0
+                .push(keyword.value.to_string());
+        });
+    config
+}
+
+#[test]
+fn keyword_handlers() {
+    let config = config();
+    let mut org = Org::parse_custom(
+        "#+HUGO_SECTION: posts\n#+ROAM_ALIAS: foo\n* title\n#+roam_alias: bar\n",
+        &config,
+    );
+
+    assert_eq!(
+        org.extensions().get::<Section>(),
+        Some(&Section("posts".into()))
+    );
+    assert_eq!(
+        org.extensions().get::<Aliases>(),
+        Some(&Aliases(vec!["foo".into(), "bar".into()]))
+    );
+
+    org.edit_custom(0.."#+HUGO_SECTION: posts\n".len(), "", &config);
+    assert_eq!(org.extensions().get::<Section>(), None);
+    assert_eq!(org.extensions().get::<Aliases>().unwrap().0.len(), 2);
+
+    org.edit_custom(0..0, "#+HUGO_SECTION: notes\n", &config);
+    assert_eq!(
+        org.extensions().get::<Section>(),
+        Some(&Section("notes".into()))
+    );
+
+    let org = Org::parse("#+HUGO_SECTION: posts\n");
+    assert!(org.extensions().is_empty());
+}
+
+#[test]
+fn keyword_handlers_lazy() {
+    let config = config();
+    let mut org = Org::parse_lazy_custom(
+        "* a\n#+ROAM_ALIAS: foo\n* b\n#+BEGIN_QUOTE\n#+ROAM_ALIAS: bar\n#+END_QUOTE\n",
+        &config,
+    );
+    org.expand_all();
+
+    assert_eq!(
+        org.extensions().get::<Aliases>(),
+        Some(&Aliases(vec!["foo".into(), "bar".into()]))
+    );
+}
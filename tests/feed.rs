@@ -0,0 +1,97 @@
+use orgize::export::Feed;
+use orgize::{Org, Workspace};
+use pretty_assertions::assert_eq;
+
+fn is_post(org: &Org, headline: orgize::Headline) -> bool {
+    headline.level() == 1 && !headline.title(org).tags.iter().any(|tag| tag == "draft")
+}
+
+#[test]
+fn rss() {
+    let mut workspace = Workspace::default();
+    workspace.insert(
+        "2023.org",
+        "* DONE Older post\n\
+         CLOSED: [2023-01-02 Mon 08:30]\n\
+         :PROPERTIES:\n:RSS_PERMALINK: posts/older.html\n:END:\n\
+         Some <html> & text.\n\
+         ** Details\n\
+         More.\n\
+         ** Notes :noexport:\n\
+         Private.\n\
+         * Draft <2023-05-01 Mon> :draft:\n",
+    );
+    workspace.insert(
+        "2024.org",
+        "* Newer post <2024-02-29 Thu>\n\
+         :PROPERTIES:\n:ID: 42\n:END:\n\
+         * Undated\n",
+    );
+
+    let feed = Feed {
+        title: "Blog".into(),
+        link: "https://example.com".into(),
+        description: "Notes & posts".into(),
+        entries: workspace.feed_entries(is_post),
+        ..Default::default()
+    };
+
+    let mut writer = Vec::new();
+    feed.write_rss(&mut writer).unwrap();
+    assert_eq!(
+        String::from_utf8(writer).unwrap(),
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+         <rss version=\"2.0\">\n\
+         <channel>\n\
+         <title>Blog</title>\n\
+         <link>https://example.com</link>\n\
+         <description>Notes &amp; posts</description>\n\
+         <lastBuildDate>Thu, 29 Feb 2024 00:00:00 +0000</lastBuildDate>\n\
+         <item>\n\
+         <title>Newer post &lt;2024-02-29 Thu&gt;</title>\n\
+         <link>https://example.com#42</link>\n\
+         <guid>https://example.com#42</guid>\n\
+         <pubDate>Thu, 29 Feb 2024 00:00:00 +0000</pubDate>\n\
+         <description></description>\n\
+         </item>\n\
+         <item>\n\
+         <title>Older post</title>\n\
+         <link>https://example.com/posts/older.html</link>\n\
+         <guid>https://example.com/posts/older.html</guid>\n\
+         <pubDate>Mon, 02 Jan 2023 08:30:00 +0000</pubDate>\n\
+         <description>&lt;section&gt;&lt;p&gt;Some &amp;lt;html&amp;gt; &amp;amp; text.&lt;/p&gt;&lt;/section&gt;\
+         &lt;h2&gt;Details&lt;/h2&gt;&lt;section&gt;&lt;p&gt;More.&lt;/p&gt;&lt;/section&gt;</description>\n\
+         </item>\n\
+         </channel>\n\
+         </rss>\n"
+    );
+}
+
+#[test]
+fn fallback_links() {
+    let org = Org::parse(
+        "* Weekly notes\n:PROPERTIES:\n:PUBDATE: <2024-03-01 Fri>\n:END:\n\
+         * Weekly notes\n:PROPERTIES:\n:PUBDATE: <2024-03-08 Fri>\n:END:\n",
+    );
+    let feed = Feed {
+        link: "https://example.com/".into(),
+        entries: org.feed_entries(is_post),
+        ..Default::default()
+    };
+
+    let mut writer = Vec::new();
+    feed.write_atom(&mut writer).unwrap();
+    let atom = String::from_utf8(writer).unwrap();
+    let ids: Vec<_> = atom
+        .lines()
+        .filter(|line| line.starts_with("<id>"))
+        .skip(1)
+        .collect();
+    assert_eq!(
+        ids,
+        [
+            "<id>https://example.com/#weekly-notes-2024-03-08</id>",
+            "<id>https://example.com/#weekly-notes-2024-03-01</id>",
+        ]
+    );
+}
@@ -0,0 +1,73 @@
+use orgize::{Element, Event, IterFilter, Org};
+use pretty_assertions::assert_eq;
+
+const TEXT: &str = "* intro\n\
+                    :NOTES:\n\
+                    hidden\n\
+                    :END:\n\
+                    visible\n\
+                    * old :ARCHIVE:\n\
+                    ** older\n\
+                    * COMMENT draft\n\
+                    * private :secret:\n\
+                    * draft :noexport:\n\
+                    * outro\n";
+
+fn visible(org: &Org, filter: IterFilter) -> Vec<String> {
+    org.iter_filtered(filter)
+        .filter_map(|event| match event {
+            Event::Start(Element::Title(title)) => Some(title.raw.to_string()),
+            Event::Start(Element::Text { value }) => Some(value.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn iter_filtered() {
+    let org = Org::parse(TEXT);
+
+    assert_eq!(
+        visible(&org, IterFilter::default()),
+        vec!["intro", "intro", "visible", "private", "private", "outro", "outro"]
+    );
+
+    assert_eq!(
+        visible(
+            &org,
+            IterFilter {
+                skip_commented: false,
+                skip_archived: false,
+                skip_drawers: false,
+                exclude_tags: vec!["secret".into()],
+            }
+        ),
+        vec![
+            "intro",
+            "intro",
+            "hidden",
+            "visible",
+            "old",
+            "old",
+            "older",
+            "older",
+            "COMMENT draft",
+            "COMMENT draft",
+            "draft",
+            "draft",
+            "outro",
+            "outro"
+        ]
+    );
+
+    // events stay balanced
+    let mut depth = 0;
+    for event in org.iter_filtered(IterFilter::default()) {
+        match event {
+            Event::Start(_) => depth += 1,
+            Event::End(_) => depth -= 1,
+        }
+        assert!(depth >= 0);
+    }
+    assert_eq!(depth, 0);
+}
@@ -0,0 +1,58 @@
+use orgize::{FlashcardKind, Org};
+use pretty_assertions::assert_eq;
+
+const TEXT: &str = r#"* Vocabulary
+** Hund :drill:
+SCHEDULED: <2023-03-05 Sun>
+:PROPERTIES:
+:DRILL_CARD_TYPE: twosided
+:DRILL_LAST_INTERVAL: 4.0
+:DRILL_FAILURE_COUNT: 1
+:DRILL_LAST_REVIEWED: [2023-03-01 Wed 09:15]
+:END:
+What does /Hund/ mean?
+*** Answer
+dog
+** {{Bern}@0} is the capital of {{Switzerland}{country}@1} :fc:
+:PROPERTIES:
+:FC_TYPE: cloze
+:END:
+:REVIEW_DATA:
+| position | ease | box | interval | due                  |
+|----------+------+-----+----------+----------------------|
+|        0 | 2.50 |   2 |     1.00 | 2023-03-02T10:30:00Z |
+|        1 | 2.80 |   5 |    12.00 | 2023-03-14T10:30:00Z |
+:END:
+** Plain note
+"#;
+
+#[test]
+fn flashcards() {
+    let org = Org::parse(TEXT);
+    let cards = org.flashcards();
+    assert_eq!(cards.len(), 2);
+
+    let drill = &cards[0];
+    assert_eq!(drill.kind, FlashcardKind::Drill);
+    assert_eq!(drill.card_type.as_deref(), Some("twosided"));
+    assert_eq!(drill.question, "What does /Hund/ mean?");
+    assert_eq!(drill.answer, "dog");
+    assert_eq!(drill.scheduling.interval, Some(4.0));
+    assert_eq!(drill.scheduling.failures, Some(1));
+    assert_eq!(drill.scheduling.repeats, None);
+    let due = drill.scheduling.due.as_ref().unwrap();
+    assert_eq!((due.month, due.day), (3, 5));
+    let reviewed = drill.scheduling.last_reviewed.as_ref().unwrap();
+    assert_eq!((reviewed.day, reviewed.hour), (1, Some(9)));
+
+    let fc = &cards[1];
+    assert_eq!(fc.kind, FlashcardKind::Fc);
+    assert_eq!(fc.card_type.as_deref(), Some("cloze"));
+    assert_eq!(fc.question, "[...] is the capital of [country...]");
+    assert_eq!(fc.answer, "Bern is the capital of Switzerland");
+    assert_eq!(fc.scheduling.ease, Some(2.5));
+    assert_eq!(fc.scheduling.repeats, Some(2));
+    assert_eq!(fc.scheduling.interval, Some(1.0));
+    let due = fc.scheduling.due.as_ref().unwrap();
+    assert_eq!((due.day, due.hour, due.minute), (2, Some(10), Some(30)));
+}
@@ -0,0 +1,75 @@
+use orgize::{Element, FootnoteDefinition, Org};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn footnotes() {
+    let org = Org::parse(
+        "* Title[fn:b]\n\
+         First[fn:a], inline[fn:c:Inline [[https://example.com][link]].], \
+         missing[fn:d] and again[fn:a][fn:d:Late.].\n\
+         * Footnotes\n\
+         [fn:a] Defines a, citing[fn:nested].\n\n\
+         [fn:b] Defines b.\n\n\
+         [fn:nested] Nested.\n\n\
+         [fn:unused] Unused.\n",
+    );
+
+    let footnotes = org.footnotes();
+    let summary: Vec<_> = footnotes
+        .iter()
+        .map(|footnote| {
+            (
+                footnote.number,
+                footnote.label,
+                footnote.references.len(),
+                match footnote.definition {
+                    Some(FootnoteDefinition::Node(node)) => match &org[node] {
+                        Element::FnDef(fn_def) => format!("node {}", fn_def.label),
+                        _ => unreachable!(),
+                    },
+                    Some(FootnoteDefinition::Inline(text)) => format!("inline {}", text),
+                    None => "none".to_string(),
+                },
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        summary,
+        vec![
+            (1, "b", 1, "node b".to_string()),
+            (2, "a", 2, "node a".to_string()),
+            (3, "nested", 1, "node nested".to_string()),
+            (
+                4,
+                "c",
+                1,
+                "inline Inline [[https://example.com][link]].".to_string()
+            ),
+            (5, "d", 2, "inline Late.".to_string()),
+        ]
+    );
+
+    // references are kept in document order
+    let a = &footnotes[1].references;
+    assert!(org.span(a[0]).unwrap().start < org.span(a[1]).unwrap().start);
+}
+
+#[test]
+fn anonymous_footnotes() {
+    let org = Org::parse("a[fn::one] b[fn::two]\n\n[fn:1] Unrelated.\n");
+
+    let footnotes = org.footnotes();
+    assert_eq!(footnotes.len(), 2);
+    assert_eq!(
+        footnotes[0].definition,
+        Some(FootnoteDefinition::Inline("one"))
+    );
+    assert_eq!(
+        footnotes[1].definition,
+        Some(FootnoteDefinition::Inline("two"))
+    );
+    assert_eq!(footnotes[1].number, 2);
+
+    assert!(Org::parse("no footnotes").footnotes().is_empty());
+}
@@ -0,0 +1,116 @@
+use orgize::{Element, Event, Org};
+use pretty_assertions::assert_eq;
+
+const TEXT: &str = "|name|qty|\n\
+                    |-\n\
+                    |apple|3|\n\
+                    \n\
+                    * fruits\n\
+                    \x20\x20| kiwi | 12 |\n\
+                    \x20\x20|--+-|\n\
+                    \x20\x20|x|\n\
+                    #+TBLFM: $2=1\n";
+
+/// Returns spans of rows of each table.
+fn rows(org: &Org) -> Vec<Vec<std::ops::Range<usize>>> {
+    let mut tables = Vec::new();
+    for node in org.document().section_node().into_iter().chain(
+        org.headlines()
+            .filter_map(|headline| headline.section_node()),
+    ) {
+        for table in node.children(org.arena()) {
+            if let Element::Table(_) = org[table] {
+                tables.push(
+                    table
+                        .children(org.arena())
+                        .map(|row| org.span(row).unwrap())
+                        .collect(),
+                );
+            }
+        }
+    }
+    tables
+}
+
+fn cells(org: &Org) -> Vec<String> {
+    org.iter()
+        .filter_map(|event| match event {
+            Event::Start(Element::Text { value }) => Some(value.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn format() {
+    let mut org = Org::parse(TEXT);
+    let before = cells(&org);
+    org.format();
+
+    // | name  | qty |
+    // |-------+-----|
+    // | apple |   3 |
+    //
+    // * fruits
+    //   | kiwi | 12 |
+    //   |------+----|
+    //   | x    |    |
+    // #+TBLFM: $2=1
+    assert_eq!(
+        rows(&org),
+        vec![vec![0..15, 16..31, 32..47], vec![60..73, 76..89, 92..105]]
+    );
+    assert_eq!(cells(&org), before);
+
+    // already aligned tables are left untouched
+    let table = org.document().section_node().unwrap();
+    let table = table.children(org.arena()).next().unwrap();
+    org.format();
+    assert_eq!(
+        org.document()
+            .section_node()
+            .unwrap()
+            .children(org.arena())
+            .next(),
+        Some(table)
+    );
+
+    let mut org = Org::parse("text\n");
+    let paragraph = org.document().section_node().unwrap();
+    let paragraph = paragraph.children(org.arena()).next().unwrap();
+    assert!(!org.realign_table(paragraph));
+}
+
+#[test]
+fn renumber_lists() {
+    let mut org = Org::parse(
+        "* steps\n\
+         1. first\n\
+         \x20\x20 3. nested\n\
+         \x20\x20 3. nested\n\
+         3. third\n\
+         \x20\x20 - unordered\n\
+         \x20\x20 - unordered\n\
+         9. [@7] seventh\n\
+         2. eighth\n\
+         \n\
+         |a|\n",
+    );
+    org.format();
+
+    let mut writer = Vec::new();
+    org.write_org(&mut writer).unwrap();
+    assert!(String::from_utf8(writer).unwrap().starts_with(
+        "* steps\n\
+         1. first\n\
+         \x20\x20 1. nested\n\
+         \x20\x20 2. nested\n\
+         2. third\n\
+         \x20\x20 - unordered\n\
+         \x20\x20 - unordered\n\
+         7. [@7] seventh\n\
+         8. eighth\n"
+    ));
+    // "| a |"
+    assert_eq!(rows(&org), vec![vec![109..114]]);
+}
@@ -0,0 +1,63 @@
+use orgize::elements::Datetime;
+use orgize::{HabitState, Org};
+
+fn date(day: u8) -> Datetime<'static> {
+    Datetime {
+        year: 2023,
+        month: 1,
+        day,
+        dayname: "".into(),
+        hour: None,
+        minute: None,
+    }
+}
+
+#[test]
+fn habit() {
+    let org = Org::parse(
+        r#"* TODO water plants
+SCHEDULED: <2023-01-12 Thu .+2d/3d>
+:PROPERTIES:
+:STYLE:    habit
+:END:
+- State "DONE"       from "TODO"       [2023-01-10 Tue 09:00]
+- State "CANCELED"   from "TODO"       [2023-01-09 Mon 09:00]
+- State "DONE"       from "TODO"       [2023-01-05 Thu 09:00]
+- State "DONE"       from "TODO"       [2023-01-03 Tue 09:00]
+* TODO not a habit
+SCHEDULED: <2023-01-12 Thu .+2d>
+"#,
+    );
+
+    let mut headlines = org.headlines();
+    let habit = headlines.next().unwrap().habit(&org, &["DONE"]).unwrap();
+    assert!(headlines.next().unwrap().habit(&org, &["DONE"]).is_none());
+
+    let completions: Vec<_> = habit.completions.iter().map(|date| date.day).collect();
+    assert_eq!(completions, vec![3, 5, 10]);
+
+    // 5 -> 10 breaks the streak
+    assert_eq!(habit.streak(&date(12)), 1);
+    assert_eq!(habit.streak(&date(14)), 0);
+
+    let days: Vec<_> = habit
+        .days(&date(3), &date(10))
+        .into_iter()
+        .map(|day| (day.date.day, day.done, day.state))
+        .collect();
+    assert_eq!(
+        days,
+        vec![
+            (3, true, HabitState::Early),
+            (4, false, HabitState::Early),
+            (5, true, HabitState::Due),
+            (6, false, HabitState::Early),
+            (7, false, HabitState::Due),
+            (8, false, HabitState::Due),
+            (9, false, HabitState::Overdue),
+            (10, true, HabitState::Overdue),
+        ]
+    );
+
+    assert_eq!(habit.consistency(&date(3), &date(10)), 0.75);
+}
@@ -0,0 +1,96 @@
+use orgize::{Element, Event, IdIndex, Org};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn resolve_id_links() {
+    let a = Org::parse(
+        "* a\n:PROPERTIES:\n:ID: 1\n:END:\n[[id:2]] [[id:3::*c]] [[id:4]] [[file:b.org]]\n",
+    );
+    let b = Org::parse(
+        "* b\n:PROPERTIES:\n:ID: 2\n:END:\n** c\n:PROPERTIES:\n:id: 3\n:END:\n* dup\n:PROPERTIES:\n:ID: 1\n:END:\n",
+    );
+
+    let mut index = IdIndex::default();
+    index.add("a.org", &a).add("b.org", &b);
+    assert_eq!(index.len(), 3);
+
+    let resolved: Vec<_> = a
+        .iter()
+        .filter_map(|event| match event {
+            Event::Start(Element::Link(link)) => Some(index.resolve(link).map(|target| {
+                (
+                    target.file,
+                    target.headline.title(target.org).raw.to_string(),
+                )
+            })),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        resolved,
+        vec![
+            Some(("b.org", "b".to_string())),
+            Some(("b.org", "c".to_string())),
+            None,
+            None
+        ]
+    );
+
+    let first = index.get("1").unwrap();
+    assert_eq!(first.file, "a.org");
+
+    let mut ids: Vec<_> = index.ids().collect();
+    ids.sort_unstable();
+    assert_eq!(ids, vec!["1", "2", "3"]);
+}
+
+#[test]
+fn ensure_id() {
+    let mut org = Org::parse("text\n* a\n:PROPERTIES:\n:id:\n:END:\n** b\n");
+    let nodes = |org: &Org| -> Vec<_> { org.headlines().map(|h| h.headline_node()).collect() };
+
+    let section = org.document().section_node().unwrap();
+    assert_eq!(org.ensure_id(section), None);
+
+    assert_eq!(
+        org.ensure_id_with(nodes(&org)[0], || "a-id".into())
+            .unwrap(),
+        "a-id"
+    );
+    let b = org.ensure_id(nodes(&org)[1]).unwrap();
+    assert_ne!(b, "a-id");
+
+    let a = org.headlines().next().unwrap();
+    assert_eq!(a.title(&org).properties.len(), 1);
+    assert_eq!(a.title(&org).properties["id"], "a-id");
+
+    let mut index = IdIndex::default();
+    index.add("a.org", &org);
+    assert_eq!(index.get(&b).unwrap().headline.title(&org).raw, "b");
+    assert_eq!(index.get("a-id").unwrap().headline.title(&org).raw, "a");
+}
+
+#[test]
+fn ensure_id_then_edit() {
+    let mut org =
+        Org::parse("* a\n  :PROPERTIES:\n  :KEY: v\n  :END:\n* b\nSCHEDULED: <2023-01-02 Mon>\n");
+    let nodes = |org: &Org| -> Vec<_> { org.headlines().map(|h| h.headline_node()).collect() };
+
+    org.ensure_id_with(nodes(&org)[0], || "a-id".into());
+    org.ensure_id_with(nodes(&org)[1], || "b-id".into());
+    org.edit(0..0, "more\n");
+
+    let mut index = IdIndex::default();
+    index.add("a.org", &org);
+    assert_eq!(index.get("a-id").unwrap().headline.title(&org).raw, "a");
+    assert_eq!(index.get("b-id").unwrap().headline.title(&org).raw, "b");
+
+    let a = org.headlines().next().unwrap();
+    assert_eq!(a.title(&org).properties["KEY"], "v");
+
+    let mut writer = Vec::new();
+    org.write_org(&mut writer).unwrap();
+    assert!(String::from_utf8(writer)
+        .unwrap()
+        .ends_with("* b\nSCHEDULED: <2023-01-02 Mon>\n:PROPERTIES:\n:ID: b-id\n:END:\n"));
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use common::{spans, write};
+use orgize::Org;
+use pretty_assertions::assert_eq;
+
+const TEXT: &str = r#"
+#+TITLE: lazy
+
+preface *bold*
+
+* TODO title 1 :tag:
+  SCHEDULED: <2019-10-28 Mon>
+  :PROPERTIES:
+  :ID: title-1
+  :END:
+section 1
+- item 1
+- item 2
+** title 2 [[https://example.com][link]]
+#+BEGIN_QUOTE
+quote
+#+END_QUOTE
+* title 3
+| a | b |
+|---+---|
+| c | d |
+"#;
+
+#[test]
+fn expand_all() {
+    let mut org = Org::parse_lazy(TEXT);
+    org.expand_all();
+
+    let expected = Org::parse(TEXT);
+
+    assert_eq!(
+        serde_json::to_string_pretty(&org).unwrap(),
+        serde_json::to_string_pretty(&expected).unwrap()
+    );
+    assert_eq!(spans(&org), spans(&expected));
+}
+
+#[test]
+fn expand() {
+    let mut org = Org::parse_lazy(TEXT);
+
+    let h2 = org.headlines().nth(1).unwrap();
+    assert!(org.is_pending(h2.title_node()));
+    assert!(org.is_pending(h2.section_node().unwrap()));
+
+    org.expand(h2.headline_node());
+    assert!(!org.is_pending(h2.title_node()));
+    assert!(!org.is_pending(h2.section_node().unwrap()));
+    assert_eq!(org.text_content(h2.title_node()), "title 2 link");
+
+    let h1 = org.headlines().next().unwrap();
+    assert!(org.is_pending(h1.section_node().unwrap()));
+    assert!(org.validate().is_empty());
+}
+
+#[test]
+fn edit() {
+    let mut org = Org::parse_lazy(TEXT);
+    org.edit(0..1, "");
+
+    let expected = Org::parse(&TEXT[1..]);
+
+    assert_eq!(
+        serde_json::to_string_pretty(&org).unwrap(),
+        serde_json::to_string_pretty(&expected).unwrap()
+    );
+    assert_eq!(spans(&org), spans(&expected));
+}
+
+#[test]
+fn write_org() {
+    // the org writer doesn't write table rules
+    let text = &TEXT[..TEXT.find("* title 3").unwrap()];
+
+    assert_eq!(write(&Org::parse_lazy(text)), write(&Org::parse(text)));
+}
+
+#[test]
+fn subtree_eq() {
+    let mut org = Org::parse_lazy(TEXT);
+    let expected = Org::parse(TEXT);
+
+    assert!(org == expected);
+
+    let h2 = org.headlines().nth(1).unwrap();
+    org.expand(h2.headline_node());
+    assert!(org == expected);
+    assert!(org != Org::parse(&TEXT.replace("section 1", "section 2")));
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn replace_text() {
+    let mut org = Org::parse_lazy(TEXT);
+
+    let count = org.replace_text(&orgize::regex::Regex::new("section").unwrap(), "body");
+
+    assert_eq!(count, 1);
+    let section = org.headlines().next().unwrap().section_node().unwrap();
+    assert!(org.text_content(section).starts_with("body 1"));
+}
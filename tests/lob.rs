@@ -0,0 +1,46 @@
+use orgize::{Element, Org};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn resolve_call() {
+    let library = Org::parse(
+        "#+NAME: greet\n#+BEGIN_SRC sh :var name=\"world\" :results output\necho $name\n#+END_SRC\n\n\
+         #+NAME: local\n#+BEGIN_SRC sh\necho library\n#+END_SRC\n",
+    );
+    let org = Org::parse(
+        "#+PROPERTY: header-args :exports both\n\
+         #+NAME: local\n#+BEGIN_SRC sh\necho local\n#+END_SRC\n\n\
+         Say call_greet[:results value](\"orgize\")[:results raw] and call_local().\n\n\
+         #+CALL: missing()\n",
+    );
+
+    let calls = org.babel_calls();
+    assert_eq!(calls.len(), 3);
+    assert!(matches!(org[calls[2]], Element::BabelCall(_)));
+
+    let greet = org.resolve_call(calls[0], &[&library]).unwrap();
+    assert_eq!(greet.org.element_name(greet.block), Some("greet"));
+    assert_eq!(
+        greet.args.iter().collect::<Vec<_>>(),
+        vec![
+            ("var", "name=\"world\""),
+            ("results", "output"),
+            ("results", "value"),
+            ("var", "name=\"orgize\""),
+            ("results", "raw"),
+        ]
+    );
+    assert_eq!(
+        greet.org.expand_noweb(greet.block).as_deref(),
+        Some("echo $name\n")
+    );
+
+    let local = org.resolve_call(calls[1], &[&library]).unwrap();
+    assert_eq!(
+        local.org.expand_noweb(local.block).as_deref(),
+        Some("echo local\n")
+    );
+    assert_eq!(local.args.get("exports"), Some("both"));
+
+    assert!(org.resolve_call(calls[2], &[&library]).is_none());
+}
@@ -0,0 +1,55 @@
+use orgize::{FoldingKind, Org, Position};
+
+#[test]
+fn document_symbols() {
+    let org = Org::parse("#+TITLE: lsp\n\n* 标题 :a:b:\n** DONE sub\n*** deep\n* last\n");
+    let symbols = org.document_symbols();
+
+    assert_eq!(symbols.len(), 2);
+    assert_eq!(symbols[0].name, "标题");
+    assert_eq!(symbols[0].detail.as_deref(), Some(":a:b:"));
+    assert_eq!(
+        symbols[0].selection_range,
+        Position {
+            line: 2,
+            character: 2
+        }..Position {
+            line: 2,
+            character: 4
+        }
+    );
+    assert_eq!(
+        symbols[0].range,
+        Position {
+            line: 2,
+            character: 0
+        }..Position {
+            line: 4,
+            character: 8
+        }
+    );
+    assert_eq!(symbols[0].children[0].detail.as_deref(), Some("DONE"));
+    assert_eq!(symbols[0].children[0].children[0].name, "deep");
+    assert!(symbols[1].children.is_empty());
+}
+
+#[test]
+fn folding_ranges() {
+    let org = Org::parse(
+        "* title\n  :PROPERTIES:\n  :ID: a\n  :END:\n#+BEGIN_QUOTE\nquote\n#+END_QUOTE\n* single\n",
+    );
+    let ranges: Vec<_> = org
+        .folding_ranges()
+        .into_iter()
+        .map(|range| (range.start_line, range.end_line, range.kind))
+        .collect();
+
+    assert_eq!(
+        ranges,
+        vec![
+            (0, 6, FoldingKind::Headline),
+            (1, 3, FoldingKind::Drawer),
+            (4, 6, FoldingKind::Block),
+        ]
+    );
+}
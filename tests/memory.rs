@@ -0,0 +1,57 @@
+mod common;
+
+use common::spans;
+use orgize::Org;
+use pretty_assertions::assert_eq;
+
+const TEXT: &str = r#"
+#+TITLE: memory
+
+* title 1 *bold*
+section 1
+** title 2
+- item 1
+- item 2
+* title 3
+section 3
+"#;
+
+#[test]
+fn compact() {
+    let mut org = Org::parse(TEXT);
+    org.edit(19..25, "/italic/");
+    org.edit(40..48, "");
+
+    let usage = org.memory_usage();
+    assert!(usage.removed_nodes > 0);
+
+    let json = serde_json::to_string(&org).unwrap();
+    let spans_before = spans(&org);
+
+    org.compact();
+
+    let usage = org.memory_usage();
+    assert_eq!(usage.removed_nodes, 0);
+    assert_eq!(
+        usage.nodes,
+        org.cursor().node().descendants(org.arena()).count()
+    );
+    assert_eq!(serde_json::to_string(&org).unwrap(), json);
+    assert_eq!(spans(&org), spans_before);
+    assert!(org.validate().is_empty());
+}
+
+#[test]
+fn compact_lazy() {
+    let mut org = Org::parse_lazy(TEXT);
+    org.compact();
+    org.expand_all();
+
+    let expected = Org::parse(TEXT);
+
+    assert_eq!(
+        serde_json::to_string(&org).unwrap(),
+        serde_json::to_string(&expected).unwrap()
+    );
+    assert_eq!(spans(&org), spans(&expected));
+}
@@ -0,0 +1,38 @@
+mod common;
+
+use common::write;
+use orgize::Org;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn merge_by_id() {
+    let mut target = Org::parse(
+        "intro\n\
+         * project\n:PROPERTIES:\n:ID: p\n:END:\n\
+         ** TODO task\n:PROPERTIES:\n:ID: t\n:END:\nlocal\n\
+         *** local child\n\
+         ** same\n:PROPERTIES:\n:ID: s\n:END:\nsame\n\
+         * no id\nlocal\n",
+    );
+    let source = Org::parse(
+        "* renamed project\n:PROPERTIES:\n:ID: p\n:END:\nnew section\n\
+         * same\n:PROPERTIES:\n:ID: s\n:END:\nsame\n\
+         * no id\nremote\n\
+         * missing\n:PROPERTIES:\n:ID: m\n:END:\n\
+         * DONE task\nCLOSED: [2023-03-01 Wed]\n:PROPERTIES:\n:ID: t\n:END:\nremote",
+    );
+
+    assert_eq!(target.merge_by_id(&source), 2);
+    assert_eq!(
+        write(&target),
+        "intro\n\
+         * renamed project\n:PROPERTIES:\n:ID: p\n:END:\nnew section\n\
+         ** DONE task\nCLOSED: [2023-03-01 Wed]\n:PROPERTIES:\n:ID: t\n:END:\nremote\n\
+         *** local child\n\
+         ** same\n:PROPERTIES:\n:ID: s\n:END:\nsame\n\
+         * no id\nlocal\n"
+    );
+    assert!(target.validate().is_empty());
+
+    assert_eq!(target.merge_by_id(&source), 0);
+}
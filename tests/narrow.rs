@@ -0,0 +1,46 @@
+use orgize::{Org, ParseConfig};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn narrow() {
+    let org = Org::parse("#+TITLE: doc\n* a\n** TODO b\ntext\n*** c\n* d\n");
+    let b = org.headlines().nth(1).unwrap();
+    let start = org.span(b.headline_node()).unwrap().start;
+
+    let narrowed = org.narrow(b.headline_node()).unwrap();
+    assert_eq!(narrowed.keyword("TITLE"), None);
+
+    let mut html = Vec::new();
+    narrowed.write_html(&mut html).unwrap();
+    assert_eq!(
+        String::from_utf8(html).unwrap(),
+        "<main><h2>b</h2><section><p>text</p></section><h3>c</h3></main>"
+    );
+
+    // offsets rebase onto the original document
+    let c = narrowed.headlines().nth(1).unwrap();
+    let span = narrowed.span(c.headline_node()).unwrap();
+    let original = org.headlines().nth(2).unwrap();
+    assert_eq!(
+        (span.start + start)..(span.end + start),
+        org.span(original.headline_node()).unwrap()
+    );
+}
+
+#[test]
+fn narrow_custom() {
+    let org = Org::parse("* a\n** WAIT b\n");
+    let b = org.headlines().nth(1).unwrap();
+
+    let narrowed = org.narrow(b.headline_node()).unwrap();
+    let title = narrowed.headlines().next().unwrap().title(&narrowed);
+    assert_eq!(title.keyword, None);
+
+    let config = ParseConfig {
+        todo_keywords: (vec!["WAIT".into()], vec![]),
+        ..Default::default()
+    };
+    let narrowed = org.narrow_custom(b.headline_node(), &config).unwrap();
+    let title = narrowed.headlines().next().unwrap().title(&narrowed);
+    assert_eq!(title.keyword.as_deref(), Some("WAIT"));
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "rayon")]
+
+mod common;
+
+use common::spans;
+use orgize::Org;
+use pretty_assertions::assert_eq;
+
+const TEXT: &str = r#"
+#+TITLE: parallel
+
+preface *bold*
+
+* TODO title 1 :tag:
+  SCHEDULED: <2019-10-28 Mon>
+section 1
+- item 1
+- item 2
+** title 2 [[https://example.com][link]]
+#+BEGIN_QUOTE
+quote
+#+END_QUOTE
+* title 3
+| a | b |
+|---+---|
+| c | d |
+*** title 4
+"#;
+
+#[test]
+fn parse_parallel() {
+    let org = Org::parse_parallel(TEXT);
+    let expected = Org::parse(TEXT);
+
+    assert_eq!(
+        serde_json::to_string_pretty(&org).unwrap(),
+        serde_json::to_string_pretty(&expected).unwrap()
+    );
+    assert_eq!(spans(&org), spans(&expected));
+}
@@ -1,6 +1,10 @@
-use orgize::Org;
+use orgize::{Element, Event, Org};
 use pretty_assertions::assert_eq;
 
+mod common;
+
+use common::write;
+
 macro_rules! test_suite {
     ($name:ident, $content:expr, $expected:expr) => {
         #[test]
@@ -170,3 +174,326 @@ test_suite!(
      <tbody><tr></tr></tbody>\
      </table></section></main>"
 );
+
+test_suite!(
+    nested_emphasis,
+    "*bold /italic _underline_/* and +strike*+",
+    "<main><section><p><b>bold <i>italic <u>underline</u></i></b> and \
+     <s>strike*</s></p></section></main>"
+);
+
+test_suite!(
+    title_objects,
+    "* TODO [#A] Fix *urgent* [[bug][link]] :tag:\nbody",
+    "<main><h1>Fix <b>urgent</b> <a href=\"bug\">link</a></h1>\
+     <section><p>body</p></section></main>"
+);
+
+test_suite!(
+    verse_block,
+    "#+BEGIN_VERSE\nGreat clouds\n   overhead, *bold*\n\nTiny birds\n#+END_VERSE\n",
+    "<main><section><p class=\"verse\">Great clouds<br/>\
+     &#xa0;&#xa0;&#xa0;overhead, <b>bold</b><br/><br/>Tiny birds<br/></p></section></main>"
+);
+
+#[test]
+fn verse_lines() {
+    let text = "#+BEGIN_VERSE\n  Great clouds\n\n  overhead /now/\n#+END_VERSE\n";
+
+    for org in [Org::parse(text), {
+        let mut org = Org::parse_lazy(text);
+        org.expand_all();
+        org
+    }] {
+        let lines: Vec<_> = org
+            .iter()
+            .filter_map(|event| match event {
+                Event::Start(Element::VerseLine { indent }) => Some(*indent),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(lines, vec![2, 0, 2]);
+        assert!(org.validate().is_empty());
+
+        let mut writer = Vec::new();
+        org.write_org(&mut writer).unwrap();
+        assert_eq!(String::from_utf8(writer).unwrap(), text);
+    }
+}
+
+#[test]
+fn escaped_commas() {
+    let text = "#+BEGIN_SRC org\n,* Headline\n  ,#+TITLE: x\n,,* kept\n#+END_SRC\n\
+                #+BEGIN_EXAMPLE\n,#+END_EXAMPLE\n#+END_EXAMPLE\n";
+    let org = Org::parse(text);
+
+    let contents: Vec<_> = org
+        .iter()
+        .filter_map(|event| match event {
+            Event::Start(Element::SourceBlock(block)) => Some(block.contents.to_string()),
+            Event::Start(Element::ExampleBlock(block)) => Some(block.contents.to_string()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        contents,
+        vec!["* Headline\n  #+TITLE: x\n,* kept\n", "#+END_EXAMPLE\n"]
+    );
+
+    let mut writer = Vec::new();
+    org.write_org(&mut writer).unwrap();
+    assert_eq!(String::from_utf8(writer).unwrap(), text);
+}
+
+fn nodes(org: &Org) -> usize {
+    org.arena().iter().filter(|node| !node.is_removed()).count()
+}
+
+#[test]
+fn pathological() {
+    let text = "*a /b [[c [fn:: {{{d( @@e src_f{ call_g( ".repeat(10000);
+    let org = Org::parse(&text);
+    assert_eq!(nodes(&org), 4);
+
+    let text = format!("{}x{}", "*/".repeat(10000), "/*".repeat(10000));
+    let org = Org::parse(&text);
+    assert_eq!(nodes(&org), 20004);
+}
+
+#[test]
+fn parse_on_construct() {
+    // `Org::parse` returns a fully parsed document, which is iterated through
+    // shared references
+    let org = Org::parse("* title\ntext");
+    let shared = &org;
+    assert_eq!(shared.iter().count(), org.iter().count());
+    assert_eq!(org.headlines().count(), 1);
+
+    assert!(Org::new() == Org::parse(""));
+    assert_eq!(Org::new().iter().count(), 2);
+}
+
+#[test]
+fn from_reader() {
+    let text = "\n#+TITLE: reader\n* title *bold*\n  :PROPERTIES:\n  :ID: a\n  :END:\n- item\n";
+    let org = Org::from_reader(text.as_bytes()).unwrap();
+    let expected = Org::parse(text);
+
+    assert_eq!(
+        serde_json::to_string(&org).unwrap(),
+        serde_json::to_string(&expected).unwrap()
+    );
+    assert_eq!(org.span(org.cursor().node()), Some(0..text.len()));
+    assert!(org.validate().is_empty());
+}
+
+test_suite!(
+    empty_elements,
+    "#+BEGIN_QUOTE\n#+END_QUOTE\n\n- \n\n**) +a+\n\u{a0}\n\n\r",
+    "<main><section><blockquote></blockquote>\
+     <ul><li></li></ul>\
+     <p>**) <s>a</s>\n\u{a0}</p></section></main>"
+);
+
+test_suite!(
+    repeater,
+    "<2023-01-02 Mon 09:00 +1w -2d> [2023-01-02 Mon .+1d/3d]",
+    "<main><section><p><span class=\"timestamp-wrapper\"><span class=\"timestamp\">\
+     &lt;2023-01-02 Mon 09:00 +1w -2d&gt;</span></span> \
+     <span class=\"timestamp-wrapper\"><span class=\"timestamp\">\
+     [2023-01-02 Mon .+1d/3d]</span></span></p></section></main>"
+);
+
+#[test]
+fn lossy() {
+    let org = Org::new_lossy(b"* \xFF\n\xC0 *bold*");
+    let mut writer = Vec::new();
+    org.write_html(&mut writer).unwrap();
+
+    assert_eq!(
+        String::from_utf8(writer).unwrap(),
+        "<main><h1>\u{FFFD}</h1><section><p>\u{FFFD} <b>bold</b></p></section></main>"
+    );
+    assert!(org.validate().is_empty());
+}
+
+test_suite!(
+    unicode_emphasis,
+    "「*粗体*」和（/斜体/）, “_u_” and 中文*粗体*中文",
+    "<main><section><p>「<b>粗体</b>」和（<i>斜体</i>）, “<u>u</u>” \
+     and 中文*粗体*中文</p></section></main>"
+);
+
+#[test]
+fn org8_syntax() {
+    use orgize::{ParseConfig, Syntax};
+
+    let text = "#+BEGIN_HTML\n<br/>\n#+END_HTML\n";
+    let html = |org: Org| {
+        let mut writer = Vec::new();
+        org.write_html(&mut writer).unwrap();
+        String::from_utf8(writer).unwrap()
+    };
+
+    assert_eq!(
+        html(Org::parse(text)),
+        "<main><section><p>&lt;br/&gt;</p></section></main>"
+    );
+
+    let config = ParseConfig {
+        syntax: Syntax::Org8,
+        ..Default::default()
+    };
+    assert_eq!(
+        html(Org::parse_custom(text, &config)),
+        "<main><section><br/>\n</section></main>"
+    );
+}
+
+#[test]
+fn drawer_names() {
+    use orgize::{ParseConfig, Syntax};
+
+    let text = ":NOTES2:\nnote\n:END:\n:CUSTOM:\ncustom\n:END:\n";
+    let drawers = |config: ParseConfig| {
+        let org = Org::parse_custom(text, &config);
+        org.iter()
+            .filter_map(|event| match event {
+                orgize::Event::Start(orgize::Element::Drawer(drawer)) => {
+                    Some(drawer.name.to_string())
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert!(!drawers(ParseConfig::default()).contains(&"NOTES2".to_string()));
+    assert_eq!(
+        drawers(ParseConfig {
+            drawer_names: vec!["notes2".into()],
+            ..Default::default()
+        }),
+        vec!["NOTES2", "CUSTOM"]
+    );
+    assert_eq!(
+        drawers(ParseConfig {
+            syntax: Syntax::Org8,
+            drawer_names: vec!["NOTES2".into()],
+            ..Default::default()
+        }),
+        vec!["NOTES2"]
+    );
+}
+
+#[test]
+fn citations() {
+    use orgize::{ParseConfig, Syntax};
+
+    let text = "As [cite/t:see @doe p. 12; @roe] said.";
+    let html = |org: &Org| {
+        let mut writer = Vec::new();
+        org.write_html(&mut writer).unwrap();
+        String::from_utf8(writer).unwrap()
+    };
+
+    let org9 = Org::parse(text);
+    assert_eq!(
+        html(&org9),
+        "<main><section><p>As <cite>doe; roe</cite> said.</p></section></main>"
+    );
+    assert_eq!(write(&org9), "As [cite/t:see @doe p. 12; @roe] said.\n");
+
+    let org8 = Org::parse_custom(
+        text,
+        &ParseConfig {
+            syntax: Syntax::Org8,
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        html(&org8),
+        "<main><section><p>As [cite/t:see @doe p. 12; @roe] said.</p></section></main>"
+    );
+}
+
+#[test]
+fn org8_planning() {
+    use orgize::{ParseConfig, Syntax};
+
+    let text = "* TODO a\n:PROPERTIES:\n:ID: a\n:END:\nSCHEDULED: <2020-01-01 Wed>\n";
+    let scheduled = |org: &Org| {
+        let headline = org.headlines().next().unwrap();
+        let title = headline.title(org);
+        (
+            title.properties.len(),
+            title
+                .planning
+                .as_ref()
+                .and_then(|planning| planning.scheduled.as_ref())
+                .is_some(),
+        )
+    };
+
+    assert_eq!(scheduled(&Org::parse(text)), (1, false));
+    assert_eq!(
+        scheduled(&Org::parse_custom(
+            text,
+            &ParseConfig {
+                syntax: Syntax::Org8,
+                ..Default::default()
+            },
+        )),
+        (1, true)
+    );
+}
+
+#[test]
+fn link_abbreviations() {
+    let links = |org: &Org| {
+        org.iter()
+            .filter_map(|event| match event {
+                orgize::Event::Start(orgize::Element::Link(link)) => Some((
+                    link.path.to_string(),
+                    link.expanded.as_ref().map(|e| e.to_string()),
+                )),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut org = Org::parse(
+        "#+LINK: gh https://github.com/%s\n\n[[gh:theiceshelf/orgize][orgize]] [[https://example.com]]\n",
+    );
+    assert_eq!(
+        links(&org),
+        vec![
+            (
+                "gh:theiceshelf/orgize".to_string(),
+                Some("https://github.com/theiceshelf/orgize".to_string())
+            ),
+            ("https://example.com".to_string(), None),
+        ]
+    );
+
+    let mut writer = Vec::new();
+    org.write_html(&mut writer).unwrap();
+    assert!(String::from_utf8(writer)
+        .unwrap()
+        .contains("<a href=\"https://github.com/theiceshelf/orgize\">orgize</a>"));
+
+    let mut writer = Vec::new();
+    org.write_org(&mut writer).unwrap();
+    assert!(String::from_utf8(writer)
+        .unwrap()
+        .contains("[[gh:theiceshelf/orgize][orgize]]"));
+
+    org.edit(0..0, "#+LINK: gh https://gitlab.com/\n");
+    assert_eq!(
+        links(&org)[0].1.as_deref(),
+        Some("https://gitlab.com/theiceshelf/orgize")
+    );
+
+    org.edit(0.."#+LINK: gh https://gitlab.com/\n".len(), "");
+    org.edit(0.."#+LINK: gh https://github.com/%s\n".len(), "");
+    assert_eq!(links(&org)[0].1, None);
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use common::write;
+use orgize::{Direction, Org, ParseConfig};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn cycle_priority() {
+    let mut org = Org::parse("* TODO task :tag:\n* [#Z] out of range\n");
+    let config = ParseConfig {
+        priorities: ('A', 'E', 'C'),
+        ..Default::default()
+    };
+
+    let priorities: Vec<_> = (0..6)
+        .map(|_| {
+            let task = org.headlines().next().unwrap();
+            task.cycle_priority(Direction::Down, &config, &mut org)
+        })
+        .collect();
+    assert_eq!(
+        priorities,
+        vec![Some('C'), Some('D'), Some('E'), None, Some('C'), Some('D')]
+    );
+    assert_eq!(write(&org), "* TODO [#D] task :tag:\n* [#Z] out of range\n");
+
+    let task = org.headlines().next().unwrap();
+    task.set_priority(None, &mut org);
+    let task = org.headlines().next().unwrap();
+    assert_eq!(task.title(&org).priority, None);
+    assert_eq!(
+        task.cycle_priority(Direction::Up, &config, &mut org),
+        Some('C')
+    );
+
+    let out_of_range = org.headlines().nth(1).unwrap();
+    assert_eq!(
+        out_of_range.cycle_priority(Direction::Up, &config, &mut org),
+        None
+    );
+    assert_eq!(write(&org), "* TODO [#C] task :tag:\n* out of range\n");
+}
+
+#[test]
+fn set_priority_then_edit() {
+    let mut org = Org::parse("* [#B] a\n* TODO c\n*  b\n");
+
+    let headlines: Vec<_> = org.headlines().collect();
+    headlines[0].set_priority(Some('A'), &mut org);
+    let headlines: Vec<_> = org.headlines().collect();
+    headlines[1].set_priority(Some('C'), &mut org);
+    let headlines: Vec<_> = org.headlines().collect();
+    headlines[2].set_priority(Some('A'), &mut org);
+    org.edit(0..0, "text\n");
+
+    let priorities: Vec<_> = org
+        .headlines()
+        .map(|headline| headline.title(&org).priority)
+        .collect();
+    assert_eq!(priorities, vec![Some('A'), Some('C'), Some('A')]);
+    assert_eq!(write(&org), "text\n* [#A] a\n* TODO [#C] c\n* [#A] b\n");
+}
@@ -0,0 +1,49 @@
+use orgize::Org;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn inherited_property() {
+    let org = Org::parse(
+        "#+PROPERTY: header-args :tangle no\n#+PROPERTY: LIBS a\n#+PROPERTY: LIBS b\n\
+         * project\n:PROPERTIES:\n:LIBS+: c\n:header-args+: :noweb yes\n:END:\n\
+         ** reset\n:PROPERTIES:\n:LIBS: d\n:END:\n*** task\n:PROPERTIES:\n:LIBS+: e\n:END:\ntext\n",
+    );
+    let headlines: Vec<_> = org.headlines().collect();
+
+    assert_eq!(
+        org.inherited_property(headlines[0].headline_node(), "libs")
+            .as_deref(),
+        Some("b c")
+    );
+    assert_eq!(
+        org.inherited_property(headlines[0].headline_node(), "HEADER-ARGS")
+            .as_deref(),
+        Some(":tangle no :noweb yes")
+    );
+    let task = headlines[2].section_node().unwrap();
+    assert_eq!(org.inherited_property(task, "LIBS").as_deref(), Some("d e"));
+    assert_eq!(org.inherited_property(task, "OWNER"), None);
+
+    let mut writer = Vec::new();
+    org.write_org(&mut writer).unwrap();
+    assert!(String::from_utf8(writer).unwrap().contains(":LIBS+: e\n"));
+}
+
+#[test]
+fn header_args() {
+    // properties are unordered, so parse a few times to catch the order
+    for _ in 0..50 {
+        let org = Org::parse(
+            "#+PROPERTY: header-args :noweb yes\n\
+             * config\n:PROPERTIES:\n:header-args: :tangle no\n:header-args+: :tangle yes\n\
+             :header-args:sh+: :results silent\n:END:\n\
+             #+BEGIN_SRC sh :exports none\necho hi\n#+END_SRC\n",
+        );
+        let args = org.header_args(org.src_blocks()[0]);
+
+        assert_eq!(args.get("tangle"), Some("yes"));
+        assert_eq!(args.get("noweb"), None);
+        assert_eq!(args.get("results"), Some("silent"));
+        assert_eq!(args.get("exports"), Some("none"));
+    }
+}
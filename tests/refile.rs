@@ -0,0 +1,57 @@
+use orgize::{refile_targets, Org, ParseConfig, RefileConfig};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn refile_targets_filter() {
+    let config = ParseConfig {
+        todo_keywords: (vec!["TODO".into(), "NEXT".into()], vec![]),
+        ..Default::default()
+    };
+    let work = Org::parse_custom(
+        "* TODO projects :work:\n** NEXT a/b\n*** deep\n* notes\n",
+        &config,
+    );
+    let home = Org::parse_custom("* NEXT garden :work:\n", &config);
+    let orgs = [&work, &home];
+
+    let paths = |config: &RefileConfig| {
+        refile_targets(&orgs, config)
+            .iter()
+            .map(|target| (target.file, target.outline_path()))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        paths(&RefileConfig::default()),
+        vec![
+            (0, "projects".to_string()),
+            (0, "projects/a\\/b".to_string()),
+            (0, "projects/a\\/b/deep".to_string()),
+            (0, "notes".to_string()),
+            (1, "garden".to_string()),
+        ]
+    );
+
+    assert_eq!(
+        paths(&RefileConfig {
+            tags: vec!["work".into()],
+            include_documents: true,
+            ..Default::default()
+        }),
+        vec![
+            (0, "".to_string()),
+            (0, "projects".to_string()),
+            (1, "".to_string()),
+            (1, "garden".to_string()),
+        ]
+    );
+
+    assert_eq!(
+        paths(&RefileConfig {
+            todo_keywords: vec!["NEXT".into()],
+            max_level: Some(1),
+            ..Default::default()
+        }),
+        vec![(1, "garden".to_string())]
+    );
+}
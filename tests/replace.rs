@@ -0,0 +1,58 @@
+#![cfg(feature = "regex")]
+
+use orgize::regex::Regex;
+use orgize::{Element, Event, Org};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn replace_text() {
+    let mut org = Org::parse(
+        "* TODO Meet bob :bob:\n\
+         SCHEDULED: <2023-03-01 Wed>\n\
+         Ask *bob* about =bob= and src_sh{echo bob}.\n\
+         #+BEGIN_SRC sh\n\
+         echo bob\n\
+         #+END_SRC\n\
+         | bob | bobby |\n",
+    );
+
+    let count = org.replace_text(&Regex::new(r"\bbob\b").unwrap(), "Alice");
+    assert_eq!(count, 3);
+
+    let texts: Vec<_> = org
+        .iter()
+        .filter_map(|event| match event {
+            Event::Start(Element::Text { value }) => Some(value.to_string()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        texts,
+        vec![
+            "Meet Alice",
+            "Ask ",
+            "Alice",
+            " about ",
+            " and ",
+            ".",
+            "Alice",
+            "bobby"
+        ]
+    );
+    let headline = org.headlines().next().unwrap();
+    assert_eq!(headline.title(&org).tags, vec!["bob"]);
+    assert!(headline.title(&org).scheduled().is_some());
+    assert!(org.validate().is_empty());
+
+    // capture groups and reparsed markup
+    let count = org.replace_text(&Regex::new(r"(Alice)").unwrap(), "_${1}_");
+    assert_eq!(count, 3);
+    assert_eq!(
+        org.iter()
+            .filter(|event| matches!(event, Event::Start(Element::Underline)))
+            .count(),
+        3
+    );
+
+    assert_eq!(org.replace_text(&Regex::new("x*").unwrap(), ""), 0);
+}
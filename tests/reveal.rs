@@ -0,0 +1,67 @@
+use orgize::Org;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn reveal() {
+    let org = Org::parse(
+        "#+TITLE: Org & slides\n\
+         #+AUTHOR: Alice\n\
+         #+REVEAL_ROOT: ./reveal.js/\n\
+         #+REVEAL_TRANS: fade\n\
+         #+REVEAL_INIT_OPTIONS: slideNumber: true\n\
+         #+REVEAL_EXTRA_CSS: extra.css\n\
+         #+REVEAL_EXTRA_CSS: more.css\n\
+         \n\
+         Welcome!\n\
+         * Intro\n\
+         Why org?\n\
+         ** Plain text\n\
+         *** Deeper\n\
+         Still on this slide.\n\
+         *** Hidden :noexport:\n\
+         ** COMMENT Draft\n\
+         * Outro :noexport:\n\
+         ** Gone\n\
+         * Questions\n",
+    );
+
+    let mut writer = Vec::new();
+    org.write_reveal(&mut writer).unwrap();
+    assert_eq!(
+        String::from_utf8(writer).unwrap(),
+        "<!DOCTYPE html>\n\
+         <html>\n\
+         <head>\n\
+         <meta charset=\"utf-8\">\n\
+         <title>Org &amp; slides</title>\n\
+         <link rel=\"stylesheet\" href=\"./reveal.js/dist/reveal.css\">\n\
+         <link rel=\"stylesheet\" href=\"./reveal.js/dist/theme/black.css\">\n\
+         <link rel=\"stylesheet\" href=\"extra.css\">\n\
+         <link rel=\"stylesheet\" href=\"more.css\">\n\
+         </head>\n\
+         <body>\n\
+         <div class=\"reveal\">\n\
+         <div class=\"slides\">\n\
+         <section id=\"title-slide\"><h1 class=\"title\">Org &amp; slides</h1>\
+         <p class=\"author\">Alice</p><section><p>Welcome!</p></section></section>\n\
+         <section><section><h1>Intro</h1><section><p>Why org?</p></section></section>\
+         <section><h2>Plain text</h2><h3>Deeper</h3><section><p>Still on this slide.</p></section>\
+         </section></section>\n\
+         <section><h1>Questions</h1></section>\n\
+         </div>\n\
+         </div>\n\
+         <script src=\"./reveal.js/dist/reveal.js\"></script>\n\
+         <script>\n\
+         Reveal.initialize({transition: 'fade', slideNumber: true});\n\
+         </script>\n\
+         </body>\n\
+         </html>\n"
+    );
+
+    let org = Org::parse("#+REVEAL_TITLE_SLIDE: nil\n* Only\n");
+    let mut writer = Vec::new();
+    org.write_reveal(&mut writer).unwrap();
+    let html = String::from_utf8(writer).unwrap();
+    assert!(!html.contains("title-slide"));
+    assert!(html.contains("<section><h1>Only</h1></section>\n"));
+}
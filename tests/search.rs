@@ -0,0 +1,45 @@
+use orgize::{SearchIndex, Workspace};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn search() {
+    let mut workspace = Workspace::default();
+    let intro = "Search ~engine~ intro.\n* Engines :tag:\nSee [[https://example.com][Search docs]].\n** Sub\nsearch\n";
+    workspace.insert("a.org", intro);
+    workspace.insert("b.org", "* SEARCH\n");
+
+    let mut index = workspace.search_index();
+    let hits: Vec<_> = index
+        .query("search engine")
+        .into_iter()
+        .map(|hit| {
+            let spans: Vec<_> = hit.spans.iter().map(|span| &intro[span.clone()]).collect();
+            (hit.file, hit.headline.is_some(), hit.score, spans)
+        })
+        .collect();
+    assert_eq!(
+        hits,
+        vec![
+            ("a.org", true, 4, vec!["Engines", "Search"]),
+            ("a.org", false, 2, vec!["Search", "engine"]),
+        ]
+    );
+
+    // sub-headlines are indexed separately
+    let hits = index.query("SEARCH");
+    assert_eq!(hits.len(), 4);
+    assert_eq!((hits[0].file, hits[0].score), ("b.org", 3));
+    assert_eq!(hits[3].headline.unwrap().level(), 2);
+
+    let org = workspace.insert("b.org", "* Nothing here\n");
+    index.add("b.org", org);
+    assert_eq!(index.query("search").len(), 3);
+    assert_eq!(index.query("nothing")[0].file, "b.org");
+
+    assert!(index.remove("a.org"));
+    assert!(!index.remove("a.org"));
+    assert!(index.query("search").is_empty());
+    assert!(index.query("  ").is_empty());
+
+    assert!(SearchIndex::default().is_empty());
+}
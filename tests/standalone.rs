@@ -0,0 +1,51 @@
+use orgize::export::{StandaloneConfig, DEFAULT_CSS};
+use orgize::Org;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn standalone() {
+    let dir = std::env::temp_dir().join("orgize-standalone");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("dot.png"), b"foo").unwrap();
+
+    let org = Org::parse(
+        "#+TITLE: A <b> note\n\
+         #+TITLE: continued\n\
+         #+LANGUAGE: de\n\
+         #+DESCRIPTION: \"quoted\"\n\
+         \n\
+         [[file:dot.png]] [[./missing.png]] [[https://example.com/logo.png]]\n",
+    );
+    let config = StandaloneConfig {
+        inline_images: true,
+        base_dir: dir,
+        ..Default::default()
+    };
+
+    let mut writer = Vec::new();
+    org.write_html_standalone(&mut writer, &config).unwrap();
+    assert_eq!(
+        String::from_utf8(writer).unwrap(),
+        format!(
+            "<!DOCTYPE html>\n\
+             <html lang=\"de\">\n\
+             <head>\n\
+             <meta charset=\"utf-8\">\n\
+             <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+             <title>A &lt;b&gt; note continued</title>\n\
+             <meta name=\"description\" content=\"&quot;quoted&quot;\">\n\
+             <style>{}</style>\n\
+             </head>\n\
+             <body>\n\
+             <h1 class=\"title\">A &lt;b&gt; note continued</h1>\n\
+             <main><section><p>\
+             <img src=\"data:image/png;base64,Zm9v\" alt=\"dot.png\"> \
+             <img src=\"./missing.png\" alt=\"missing.png\"> \
+             <img src=\"https://example.com/logo.png\" alt=\"logo.png\">\
+             </p></section></main>\n\
+             </body>\n\
+             </html>\n",
+            DEFAULT_CSS
+        )
+    );
+}
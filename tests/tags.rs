@@ -0,0 +1,48 @@
+use orgize::{Org, Workspace};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn file_tags() {
+    let org = Org::parse(
+        "#+TITLE: notes\n#+filetags: :work:\n\
+         * project :code:\n** task :work:review:\n*** step\n* home\n",
+    );
+
+    assert_eq!(org.file_tags(), vec!["work"]);
+
+    let headlines: Vec<_> = org.headlines().collect();
+    assert_eq!(
+        org.inherited_tags(headlines[2].headline_node()),
+        vec!["work", "code", "review"]
+    );
+    assert_eq!(
+        org.inherited_tags(headlines[3].headline_node()),
+        vec!["work"]
+    );
+
+    let titles = |tag| {
+        org.headlines_with_tag(tag)
+            .map(|headline| headline.title(&org).raw.to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(titles("work"), vec!["project", "task", "step", "home"]);
+    assert_eq!(titles("review"), vec!["task", "step"]);
+    assert!(titles("none").is_empty());
+
+    let org = Org::parse("* a :x:\n*** deep\n** b\n* c\n** d :x:\n* e\n");
+    let titles: Vec<_> = org
+        .headlines_with_tag("x")
+        .map(|headline| headline.title(&org).raw.to_string())
+        .collect();
+    assert_eq!(titles, vec!["a", "deep", "b", "d"]);
+    assert!(org.file_tags().is_empty());
+}
+
+#[test]
+fn workspace_tags() {
+    let mut workspace = Workspace::default();
+    workspace.insert("a.org", "#+FILETAGS: :work:project:\n* task :urgent:\n");
+    workspace.insert("b.org", "* home :work:\n");
+
+    assert_eq!(workspace.tags(), vec!["project", "urgent", "work"]);
+}
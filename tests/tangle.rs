@@ -0,0 +1,153 @@
+use orgize::{Org, TangledFile};
+use pretty_assertions::assert_eq;
+
+const TEXT: &str = r#"#+PROPERTY: header-args :tangle init.el
+* packages
+:PROPERTIES:
+:header-args: :tangle init.el :noweb yes
+:END:
+  #+BEGIN_SRC emacs-lisp
+    (progn
+      <<install>>)
+  #+END_SRC
+
+#+BEGIN_SRC emacs-lisp :noweb-ref install :tangle no
+(install 'magit)
+#+END_SRC
+
+#+BEGIN_SRC emacs-lisp :noweb-ref install :tangle no
+(install 'evil)
+#+END_SRC
+* COMMENT disabled
+#+BEGIN_SRC emacs-lisp
+(disabled)
+#+END_SRC
+* keys
+#+BEGIN_SRC emacs-lisp :padline no
+(bind "C-c")
+#+END_SRC
+#+BEGIN_SRC emacs-lisp :tangle /tmp/other.el
+<<install>>
+#+END_SRC
+"#;
+
+#[test]
+fn tangle() {
+    let org = Org::parse(TEXT);
+
+    assert_eq!(
+        org.tangle("config.org"),
+        vec![
+            TangledFile {
+                path: "init.el".into(),
+                contents: "(progn\n  (install 'magit)\n  (install 'evil))\n(bind \"C-c\")\n".into(),
+            },
+            TangledFile {
+                path: "/tmp/other.el".into(),
+                contents: "<<install>>\n".into(),
+            },
+        ]
+    );
+
+    let mut paths = Vec::new();
+    let result: Result<(), &str> = org.tangle_with("dir/config.org", |path, _| {
+        paths.push(path.to_string());
+        if path.ends_with("other.el") {
+            Err("failed")
+        } else {
+            Ok(())
+        }
+    });
+    assert_eq!(result, Err("failed"));
+    assert_eq!(paths, vec!["dir/init.el", "/tmp/other.el"]);
+}
+
+#[test]
+fn detangle() {
+    let mut org = Org::parse(
+        "#+BEGIN_SRC sh :tangle a.sh :comments link\necho top\n#+END_SRC\n\
+         * setup\n\
+         \x20 #+BEGIN_SRC sh :tangle a.sh :comments link\n    echo one\n\n    echo two\n  #+END_SRC\n\
+         #+BEGIN_SRC sh :tangle a.sh :comments link\necho three\n#+END_SRC\n",
+    );
+    let tangled = org.tangle("a.org").remove(0).contents;
+    assert_eq!(
+        tangled,
+        "# [[file:a.org][No heading:1]]\necho top\n# No heading:1 ends here\n\n\
+         # [[file:a.org::*setup][setup:1]]\necho one\n\necho two\n# setup:1 ends here\n\n\
+         # [[file:a.org::*setup][setup:2]]\necho three\n# setup:2 ends here\n"
+    );
+
+    let edited = tangled
+        .replace("echo two", "echo 2\necho 2.5")
+        .replace("echo three", "echo 3");
+    assert_eq!(org.detangle(&edited), 2);
+    assert_eq!(org.detangle(&edited), 0);
+    assert_eq!(org.tangle("a.org").remove(0).contents, edited);
+
+    let mut text = Vec::new();
+    org.write_org(&mut text).unwrap();
+    assert!(String::from_utf8(text)
+        .unwrap()
+        .contains("    echo one\n\n    echo 2\n    echo 2.5\n"));
+}
+
+#[test]
+fn detangle_escaped() {
+    let mut org = Org::parse(
+        "* notes\n#+BEGIN_SRC org :tangle a.txt :comments link\n,* heading\n  ,#+TITLE: t\n#+END_SRC\n",
+    );
+    let tangled = org.tangle("a.org").remove(0).contents;
+    assert!(tangled.contains("\n* heading\n  #+TITLE: t\n"));
+
+    let edited = tangled.replace("* heading", "* heading\n** sub");
+    assert_eq!(org.detangle(&edited), 1);
+    assert_eq!(org.headlines().count(), 1);
+
+    let mut text = Vec::new();
+    org.write_org(&mut text).unwrap();
+    assert!(String::from_utf8(text)
+        .unwrap()
+        .contains("\n,* heading\n,** sub\n  ,#+TITLE: t\n#+END_SRC\n"));
+}
+
+#[test]
+fn detangle_same_title() {
+    let mut org = Org::parse(
+        "* setup\n#+BEGIN_SRC sh :tangle a.sh :comments link\necho one\n#+END_SRC\n\
+         * setup\n#+BEGIN_SRC sh :tangle a.sh :comments link\necho two\n#+END_SRC\n",
+    );
+    let tangled = org.tangle("a.org").remove(0).contents;
+    assert_eq!(
+        tangled,
+        "# [[file:a.org::*setup][setup:1]]\necho one\n# setup:1 ends here\n\n\
+         # [[file:a.org::*setup][setup:2]]\necho two\n# setup:2 ends here\n"
+    );
+
+    let edited = tangled.replace("echo two", "echo 2");
+    assert_eq!(org.detangle(&edited), 1);
+    assert_eq!(org.tangle("a.org").remove(0).contents, edited);
+}
+
+#[test]
+fn noweb_references() {
+    let org = Org::parse(
+        "#+NAME: a\n#+BEGIN_SRC sh\nA\n#+END_SRC\n\
+         #+NAME: b\n#+BEGIN_SRC sh\nB1\nB2\n#+END_SRC\n\
+         #+BEGIN_SRC sh :noweb yes :tangle out.sh\n  <<a>> <<b>> <<c>>;\n#+END_SRC\n",
+    );
+    assert_eq!(org.tangle("a.org")[0].contents, "A B1\n B2 <<c>>;\n");
+}
+
+#[test]
+fn noweb_circular() {
+    let org = Org::parse(
+        "#+NAME: a\n#+BEGIN_SRC sh :noweb yes :tangle out.sh\n<<a>>\n<<a>>\n#+END_SRC\n\
+         #+NAME: b\n#+BEGIN_SRC sh :noweb yes :tangle out.sh\n<<c>> <<c>>\n#+END_SRC\n\
+         #+NAME: c\n#+BEGIN_SRC sh\n<<b>> c\n#+END_SRC\n",
+    );
+    assert_eq!(
+        org.tangle("a.org")[0].contents,
+        "<<a>>\n<<a>>\n\n<<b>> c <<b>> c\n"
+    );
+}
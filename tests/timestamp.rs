@@ -0,0 +1,135 @@
+mod common;
+
+use common::write;
+use orgize::indextree::NodeId;
+use orgize::{Element, Org};
+use pretty_assertions::assert_eq;
+
+fn timestamps(org: &Org) -> Vec<NodeId> {
+    let mut nodes: Vec<_> = org
+        .arena()
+        .iter()
+        .filter(|node| !node.is_removed())
+        .filter_map(|node| org.arena().get_node_id(node))
+        .filter(|node| matches!(org[*node], Element::Timestamp(_)))
+        .collect();
+    nodes.sort_by_key(|node| org.span(*node).unwrap().start);
+    nodes
+}
+
+#[test]
+fn rewrite() {
+    let mut org = Org::parse(
+        "* task\n<2023-01-31 Di 10:00 +1m>--<2023-02-01 Mi> [2023-01-02] <%%(diary-float t 4 2)>\n",
+    );
+
+    let nodes = timestamps(&org);
+    assert!(org.shift_timestamp(nodes[0], 1));
+    let nodes = timestamps(&org);
+    assert!(org.toggle_timestamp(nodes[0]));
+    let nodes = timestamps(&org);
+    assert!(org.toggle_timestamp(nodes[1]));
+    let nodes = timestamps(&org);
+    assert!(org.set_timestamp_time(nodes[1], Some((8, 5))));
+    let nodes = timestamps(&org);
+    assert!(!org.set_timestamp_time(nodes[1], Some((24, 0))));
+    assert!(!org.toggle_timestamp(nodes[2]));
+    assert!(!org.shift_timestamp(nodes[2], 1));
+
+    let headline = org.headlines().next().unwrap().headline_node();
+    assert!(!org.shift_timestamp(headline, 1));
+
+    assert_eq!(
+        write(&org),
+        "* task\n[2023-02-01 Mi 10:00 +1m]--[2023-02-02 Do] <2023-01-02 Mon 08:05> \
+         <%%(diary-float t 4 2)>\n"
+    );
+
+    let nodes = timestamps(&org);
+    assert!(org.set_timestamp_time(nodes[1], None));
+    assert_eq!(
+        write(&org),
+        "* task\n[2023-02-01 Mi 10:00 +1m]--[2023-02-02 Do] <2023-01-02 Mon> \
+         <%%(diary-float t 4 2)>\n"
+    );
+}
+
+#[test]
+fn planning() {
+    let mut org = Org::parse(
+        "* DONE task\nCLOSED: [2023-01-01 Sun] SCHEDULED: <2023-01-02 Mo 09:00 +1w> DEADLINE: <2023-01-03 Di>\n* idle",
+    );
+
+    let task = org.headlines().next().unwrap();
+    assert!(task.shift_planning(7, &mut org));
+    assert_eq!(
+        write(&org),
+        "* DONE task\nSCHEDULED: <2023-01-09 Mo 09:00 +1w> DEADLINE: <2023-01-10 Di> CLOSED: [2023-01-01 Sun]\n* idle\n"
+    );
+
+    let task = org.headlines().next().unwrap();
+    assert!(task.set_scheduled(None, &mut org));
+    let task = org.headlines().next().unwrap();
+    assert!(!task.set_scheduled(None, &mut org));
+    let deadline = task.title(&org).deadline().unwrap().clone().into_owned();
+    assert!(!task.set_deadline(Some(&deadline), &mut org));
+
+    let idle = org.headlines().nth(1).unwrap();
+    assert!(!idle.shift_planning(1, &mut org));
+    assert!(idle.set_deadline(Some(&deadline), &mut org));
+
+    let headlines: Vec<_> = org.headlines().collect();
+    assert_eq!(
+        headlines[0].title(&org).deadline(),
+        headlines[1].title(&org).deadline()
+    );
+    assert!(headlines[0].title(&org).scheduled().is_none());
+    assert!(headlines[0].title(&org).closed().is_some());
+}
+
+#[test]
+fn with_time() {
+    let org = Org::parse("<2023-01-02 Mon 10:00>--<2023-01-02 Mon 12:00>");
+    let timestamp = timestamps(&org)
+        .into_iter()
+        .find_map(|node| match &org[node] {
+            Element::Timestamp(timestamp) => Some(timestamp),
+            _ => None,
+        })
+        .unwrap();
+
+    assert_eq!(
+        timestamp.with_time(Some((11, 0))).unwrap().to_string(),
+        "<2023-01-02 Mon 11:00>--<2023-01-02 Mon 12:00>"
+    );
+    assert_eq!(timestamp.with_time(Some((13, 0))), None);
+}
+
+#[test]
+fn shift_out_of_range() {
+    let text = "* task\nDEADLINE: <2023-01-31 Tue 10:00>\n<2023-01-31 Tue>--<2023-02-01 Wed>\n";
+    let mut org = Org::parse(text);
+
+    let deadline = org
+        .headlines()
+        .next()
+        .unwrap()
+        .title(&org)
+        .deadline()
+        .unwrap()
+        .clone();
+    for days in [i64::MAX, i64::MIN, -800_000, 3_000_000] {
+        assert_eq!(deadline.shift(days), None);
+    }
+    assert_eq!(
+        deadline.shift(-738_000).unwrap().to_string(),
+        "<0002-07-05 Fri 10:00>"
+    );
+
+    let nodes = timestamps(&org);
+    assert!(!org.shift_timestamp(nodes[0], i64::MAX));
+    assert!(!org.shift_timestamp(nodes[0], -800_000));
+    let task = org.headlines().next().unwrap();
+    assert!(!task.shift_planning(i64::MIN, &mut org));
+    assert_eq!(write(&org), text);
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use common::write;
+use orgize::elements::Datetime;
+use orgize::{LogDone, Org, ParseConfig, TodoLog};
+use pretty_assertions::assert_eq;
+
+fn now() -> Datetime<'static> {
+    Datetime {
+        year: 2023,
+        month: 1,
+        day: 2,
+        dayname: "Mon".into(),
+        hour: Some(10),
+        minute: Some(0),
+    }
+}
+
+fn config() -> ParseConfig {
+    ParseConfig {
+        todo_keywords: (
+            vec!["TODO".into(), "WAIT".into()],
+            vec!["DONE".into(), "CANCELED".into()],
+        ),
+        ..Default::default()
+    }
+}
+
+fn set(org: &mut Org, state: Option<&str>) -> bool {
+    let config = config();
+    let headline = org.headlines().next().unwrap();
+    headline.set_todo_with_log(state, &now(), &config, org)
+}
+
+fn set_custom(org: &mut Org, state: Option<&str>, log: &TodoLog) -> bool {
+    let config = config();
+    let headline = org.headlines().next().unwrap();
+    headline.set_todo_with_log_custom(state, &now(), &config, log, org)
+}
+
+#[test]
+fn log_into_drawer() {
+    let mut org = Org::parse_custom(
+        "#+STARTUP: logdone logdrawer\n#+TODO: TODO WAIT(w@/!) | DONE(d!) CANCELED\n\
+         * TODO [#A] task\n:PROPERTIES:\n:ID: 1\n:END:\n:LOGBOOK:\n- old note\n:END:\nbody\n",
+        &config(),
+    );
+
+    assert!(set(&mut org, Some("WAIT")));
+    assert!(set(&mut org, Some("CANCELED")));
+    assert_eq!(
+        write(&org),
+        "#+STARTUP: logdone logdrawer\n#+TODO: TODO WAIT(w@/!) | DONE(d!) CANCELED\n\
+         * CANCELED [#A] task\n\
+         CLOSED: [2023-01-02 Mon 10:00]\n\
+         :PROPERTIES:\n:ID: 1\n:END:\n\
+         :LOGBOOK:\n\
+         - State \"CANCELED\"   from \"WAIT\"       [2023-01-02 Mon 10:00]\n\
+         - State \"WAIT\"       from \"TODO\"       [2023-01-02 Mon 10:00]\n\
+         - old note\n\
+         :END:\n\
+         body\n"
+    );
+
+    // reopening removes the closed timestamp
+    assert!(set(&mut org, Some("TODO")));
+    assert_eq!(org.headlines().next().unwrap().title(&org).planning, None);
+
+    assert!(!set(&mut org, Some("TODO")));
+    assert!(!set(&mut org, Some("UNKNOWN")));
+}
+
+#[test]
+fn log_done() {
+    let log = TodoLog {
+        done: LogDone::Note,
+        ..Default::default()
+    };
+    let mut org = Org::parse("* TODO task\nDEADLINE: <2023-01-05 Thu>\ntext\n");
+    assert!(set_custom(&mut org, Some("DONE"), &log));
+    assert_eq!(
+        write(&org),
+        "* DONE task\n\
+         DEADLINE: <2023-01-05 Thu> CLOSED: [2023-01-02 Mon 10:00]\n\
+         - CLOSING NOTE [2023-01-02 Mon 10:00]\n\
+         text\n"
+    );
+
+    // removing the keyword removes the closed timestamp only
+    assert!(set_custom(&mut org, None, &log));
+    assert_eq!(
+        write(&org),
+        "* task\nDEADLINE: <2023-01-05 Thu>\n- CLOSING NOTE [2023-01-02 Mon 10:00]\ntext\n"
+    );
+
+    // nothing is logged by default
+    let mut org = Org::parse("* task\n");
+    assert!(set(&mut org, Some("DONE")));
+    assert_eq!(write(&org), "* DONE task\n");
+}
@@ -0,0 +1,144 @@
+use orgize::{ParseConfig, Workspace};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn workspace() {
+    let mut workspace = Workspace::with_config(ParseConfig {
+        todo_keywords: (vec!["TODO".into(), "NEXT".into()], vec!["DONE".into()]),
+        ..Default::default()
+    });
+    workspace.insert(
+        "notes/index.org",
+        "* NEXT plan :work:\n\
+         [[file:rust.org::*ownership][ownership]] [[id:42]] [[../todo.org]] [[file:missing.org]]\n\
+         * DONE ship :work:\n",
+    );
+    workspace.insert(
+        "notes/rust.org",
+        "* ownership :rust:\n:PROPERTIES:\n:ID: 42\n:END:\n* TODO borrowing\n",
+    );
+    workspace.insert("todo.org", "Back to [[./notes/index.org]].\n");
+
+    let todos: Vec<_> = workspace
+        .todos()
+        .iter()
+        .map(|(path, headline)| (*path, headline.title(&workspace[*path]).raw.to_string()))
+        .collect();
+    assert_eq!(
+        todos,
+        vec![
+            ("notes/index.org", "plan".to_string()),
+            ("notes/rust.org", "borrowing".to_string())
+        ]
+    );
+    assert_eq!(workspace.tags(), vec!["rust", "work"]);
+
+    let links: Vec<_> = workspace
+        .links()
+        .iter()
+        .map(|link| {
+            (
+                link.source,
+                link.source_headline.is_some(),
+                link.target,
+                link.target_headline
+                    .map(|headline| headline.title(&workspace[link.target]).raw.to_string()),
+            )
+        })
+        .collect();
+    assert_eq!(
+        links,
+        vec![
+            (
+                "notes/index.org",
+                true,
+                "notes/rust.org",
+                Some("ownership".to_string())
+            ),
+            (
+                "notes/index.org",
+                true,
+                "notes/rust.org",
+                Some("ownership".to_string())
+            ),
+            ("notes/index.org", true, "todo.org", None),
+            ("todo.org", false, "notes/index.org", None),
+        ]
+    );
+
+    workspace.insert("notes/index.org", "* TODO reloaded\n");
+    assert_eq!(workspace.links().len(), 1);
+    assert_eq!(workspace.todos().len(), 2);
+
+    assert!(workspace.remove("todo.org").is_some());
+    assert!(workspace.get("todo.org").is_none());
+    assert_eq!(workspace.files().count(), 2);
+}
+
+#[test]
+fn backlinks() {
+    let mut workspace = Workspace::default();
+    workspace.insert(
+        "zettel/a.org",
+        "* a\n:PROPERTIES:\n:ID: a-id\n:CUSTOM_ID: a\n:END:\n** child\n",
+    );
+    workspace.insert(
+        "zettel/b.org",
+        "* b\n[[id:a-id][a]] [[file:a.org::#a]] [[file:a.org::*child]]\n",
+    );
+    workspace.insert("c.org", "[[file:zettel/a.org]] [[id:a-id]]\n");
+
+    let a = &workspace["zettel/a.org"];
+    let mut headlines = a.headlines();
+    let (first, child) = (headlines.next().unwrap(), headlines.next().unwrap());
+
+    let sources = |headline| {
+        workspace
+            .backlinks("zettel/a.org", headline)
+            .iter()
+            .map(|link| link.source)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        sources(Some(first)),
+        vec!["zettel/b.org", "zettel/b.org", "c.org"]
+    );
+    assert_eq!(sources(Some(child)), vec!["zettel/b.org"]);
+    assert_eq!(sources(None).len(), 5);
+    assert!(workspace.backlinks("c.org", None).is_empty());
+}
+
+#[test]
+fn category() {
+    let mut workspace = Workspace::default();
+    workspace.insert(
+        "notes/inbox.org",
+        "* task\n* project\n:PROPERTIES:\n:category: work\n:END:\n** sub\n",
+    );
+    workspace.insert(
+        "journal.org",
+        "#+CATEGORY: diary\n* entry\n#+CATEGORY: log\n",
+    );
+
+    let inbox = &workspace["notes/inbox.org"];
+    let categories: Vec<_> = inbox
+        .headlines()
+        .map(|headline| {
+            workspace
+                .category("notes/inbox.org", headline.headline_node())
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(categories, vec!["inbox", "work", "work"]);
+
+    let journal = &workspace["journal.org"];
+    let entry = journal.headlines().next().unwrap();
+    assert_eq!(
+        workspace.category("journal.org", entry.headline_node()),
+        Some("log")
+    );
+    assert_eq!(
+        workspace.category("missing.org", entry.headline_node()),
+        None
+    );
+}